@@ -243,11 +243,15 @@ impl Annotations {
             if let Some(model) = model.downcast_ref::<TypedModel>() {
                 for node_id in 0..model.nodes().len() {
                     let inputs = model.node_input_facts(node_id)?;
-                    let cost = model.node(node_id).op.cost(&*inputs)?;
+                    let mut cost = model.node(node_id).op.cost(&*inputs)?;
+                    if !cost.iter().any(|(k, _)| k.is_memory_traffic()) {
+                        cost.extend(memory_traffic(model, node_id, &cost)?);
+                    }
                     annotations.node_mut(NodeQId(prefix.into(), node_id)).cost = cost
                         .into_iter()
                         .map(|(k, v)| {
-                            (k, if k.is_compute() { v.maybe_mul(&multiplier).unwrap() } else { v })
+                            let per_eval = k.is_compute() || k.is_memory_traffic();
+                            (k, if per_eval { v.maybe_mul(&multiplier).unwrap() } else { v })
                         })
                         .collect();
 
@@ -271,3 +275,59 @@ impl Annotations {
         extract_costs_rec(self, model, &[], 1.into())
     }
 }
+
+/// Default memory traffic of a node: every input, plus the parameters embedded in the op, is
+/// read once and every output is written once.
+fn memory_traffic(
+    model: &TypedModel,
+    node_id: usize,
+    cost: &[(Cost, TDim)],
+) -> CliResult<TVec<(Cost, TDim)>> {
+    let node = model.node(node_id);
+    if node.op_is::<tract_core::ops::source::TypedSource>()
+        || node.op_is::<tract_core::ops::konst::Const>()
+    {
+        return Ok(tvec!());
+    }
+    fn bytes(fact: &TypedFact) -> CliResult<TDim> {
+        let len: TDim = fact.shape.iter().maybe_product()?;
+        Ok(len * fact.datum_type.size_of())
+    }
+    let mut read = 0.to_dim();
+    for input in model.node_input_facts(node_id)? {
+        read = read + bytes(input)?;
+    }
+    for (k, v) in cost {
+        if let Cost::Params(dt) = k {
+            read = read + v.clone() * dt.size_of();
+        }
+    }
+    let mut written = 0.to_dim();
+    for output in &node.outputs {
+        written = written + bytes(&output.fact)?;
+    }
+    Ok(tvec!((Cost::ReadBytes, read), (Cost::WrittenBytes, written)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn memory_traffic_of_unary_op() -> CliResult<()> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?)?;
+        let op = tract_core::ops::math::add::unary(rctensor2(&[[1f32, 2.0, 3.0]]));
+        let y = model.wire_node("y", op, &[x])?[0];
+        model.set_output_outlets(&[y])?;
+        let cost = model.node(y.node).op.cost(&*model.node_input_facts(y.node)?)?;
+        // 6 floats from x and 3 parameters are read, 6 floats are written
+        assert_eq!(
+            memory_traffic(&model, y.node, &cost)?,
+            tvec!((Cost::ReadBytes, TDim::from(36)), (Cost::WrittenBytes, TDim::from(24)))
+        );
+        assert_eq!(memory_traffic(&model, x.node, &[])?, tvec!());
+        Ok(())
+    }
+}
@@ -7,14 +7,139 @@ pub fn parse_costs(spec: &str) -> CliResult<Vec<(Cost, usize)>> {
             let mut toks = spec.split("=");
             let name = toks.next().unwrap();
             let n = toks.next().unwrap().parse::<usize>().unwrap();
-            let c = match name {
-                "FMA(F32)" => Cost::FMA(f32::datum_type()),
-                "Div(F32)" => Cost::Div(f32::datum_type()),
-                "Buffer(F32)" => Cost::Buffer(f32::datum_type()),
-                "Params(F32)" => Cost::Params(f32::datum_type()),
-                _ => bail!("Unknown cost specifier {}", name),
-            };
-            Ok((c, n))
+            Ok((parse_cost(name)?, n))
         })
         .collect()
 }
+
+/// Checks the costs listed in `spec` against `total`. Unless `exact`, cost kinds absent from
+/// `spec` are ignored.
+pub fn assert_costs(spec: &str, total: &[(Cost, TDim)], exact: bool) -> CliResult<()> {
+    let expected: HashMap<Cost, TDim> =
+        parse_costs(spec)?.into_iter().map(|(c, n)| (c, n.to_dim())).collect();
+    let total = total.iter().cloned().collect::<HashMap<_, _>>();
+    let found = if exact {
+        total
+    } else {
+        expected.keys().map(|c| (*c, total.get(c).cloned().unwrap_or(0.to_dim()))).collect()
+    };
+    if expected != found {
+        bail!("Cost assertion not met: expected {:?} got {:?}", expected, found);
+    }
+    Ok(())
+}
+
+fn parse_cost(name: &str) -> CliResult<Cost> {
+    let (kind, dt) = if let Some(open) = name.find('(') {
+        if !name.ends_with(')') {
+            bail!("Unknown cost specifier {}", name)
+        }
+        (&name[..open], Some(name[open + 1..name.len() - 1].parse::<DatumType>()?))
+    } else {
+        (name, None)
+    };
+    let c = match (kind, dt) {
+        ("FMA", Some(dt)) => Cost::FMA(dt),
+        ("Div", Some(dt)) => Cost::Div(dt),
+        ("ElementWise", Some(dt)) => Cost::ElementWise(dt),
+        ("Comparison", Some(dt)) => Cost::Comparison(dt),
+        ("Buffer", Some(dt)) => Cost::Buffer(dt),
+        ("Params", Some(dt)) => Cost::Params(dt),
+        ("ReadBytes", None) => Cost::ReadBytes,
+        ("WrittenBytes", None) => Cost::WrittenBytes,
+        _ => bail!("Unknown cost specifier {}", name),
+    };
+    Ok(c)
+}
+
+/// Peak throughput figures of a machine.
+#[derive(Debug, Clone)]
+pub struct Roofline {
+    /// Arithmetic operations per second.
+    pub flops: f64,
+    /// Bytes per second to and from memory.
+    pub bandwidth: f64,
+}
+
+impl Roofline {
+    pub fn from_args(matches: &clap::ArgMatches) -> CliResult<Option<Roofline>> {
+        if let (Some(gflops), Some(bandwidth)) =
+            (matches.value_of("peak-gflops"), matches.value_of("peak-bandwidth"))
+        {
+            Ok(Some(Roofline {
+                flops: gflops.parse::<f64>()? * 1e9,
+                bandwidth: bandwidth.parse::<f64>()? * 1e9,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Lower bounds, in seconds, for the compute-bound and the memory-bound execution of the
+    /// given costs.
+    pub fn estimate(&self, costs: &[(Cost, TDim)]) -> CliResult<(f64, f64)> {
+        let mut ops = 0f64;
+        let mut bytes = 0f64;
+        for (cost, n) in costs {
+            let n = n.to_usize().with_context(|| {
+                format!("Roofline estimate needs a concrete cost, got {:?}: {}", cost, n)
+            })? as f64;
+            ops += n * cost.flops() as f64;
+            if cost.is_memory_traffic() {
+                bytes += n;
+            }
+        }
+        Ok((ops / self.flops, bytes / self.bandwidth))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn costs() -> Vec<(Cost, TDim)> {
+        vec![
+            (Cost::FMA(f32::datum_type()), 1000.to_dim()),
+            (Cost::ElementWise(f32::datum_type()), 500.to_dim()),
+            (Cost::ReadBytes, 4000.to_dim()),
+            (Cost::WrittenBytes, 2000.to_dim()),
+        ]
+    }
+
+    #[test]
+    fn parse_new_kinds() -> CliResult<()> {
+        assert_eq!(
+            parse_costs("ElementWise(F32)=3,Comparison(I8)=2,ReadBytes=12,WrittenBytes=4")?,
+            vec![
+                (Cost::ElementWise(f32::datum_type()), 3),
+                (Cost::Comparison(i8::datum_type()), 2),
+                (Cost::ReadBytes, 12),
+                (Cost::WrittenBytes, 4)
+            ]
+        );
+        assert!(parse_cost("ReadBytes(F32)").is_err());
+        assert!(parse_cost("ElementWise").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn assert_exact_and_subset() -> CliResult<()> {
+        let spec = "FMA(F32)=1000,ElementWise(F32)=500,ReadBytes=4000,WrittenBytes=2000";
+        assert_costs(spec, &costs(), true)?;
+        assert!(assert_costs("FMA(F32)=1000", &costs(), true).is_err());
+        assert_costs("FMA(F32)=1000", &costs(), false)?;
+        assert_costs("FMA(F32)=1000,Div(F32)=0", &costs(), false)?;
+        assert!(assert_costs("FMA(F32)=999", &costs(), false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn roofline() -> CliResult<()> {
+        let roofline = Roofline { flops: 1e6, bandwidth: 1e6 };
+        // 2 * 1000 FMA + 500 element-wise ops, 6000 bytes
+        let (compute, memory) = roofline.estimate(&costs())?;
+        assert!((compute - 2.5e-3).abs() < 1e-9);
+        assert!((memory - 6e-3).abs() < 1e-9);
+        Ok(())
+    }
+}
@@ -67,15 +67,11 @@ pub fn handle(
 
     if options.cost {
         let total = annotations.tags.values().sum::<NodeTags>();
-        let assert =
-            sub_matches.value_of("assert-cost").map(|a| crate::cost::parse_costs(a)).transpose()?;
-        if let Some(assert) = assert {
-            let assert: HashMap<Cost, TDim> =
-                assert.iter().map(|(c, n)| (*c, n.to_dim())).collect();
-            let total = total.cost.iter().cloned().collect::<HashMap<_, _>>();
-            if assert != total {
-                bail!("Cost assertion not met: expected {:?} got {:?}", assert, total);
-            }
+        if let Some(spec) = sub_matches.value_of("assert-cost") {
+            crate::cost::assert_costs(spec, &total.cost, true)?;
+        }
+        if let Some(spec) = sub_matches.value_of("assert-listed-costs") {
+            crate::cost::assert_costs(spec, &total.cost, false)?;
         }
     }
 
@@ -85,6 +81,11 @@ pub fn handle(
     } else {
         terminal::render(model, &annotations, options)?;
         terminal::render_summaries(model, &annotations, options)?;
+        if options.cost {
+            if let Some(roofline) = crate::cost::Roofline::from_args(sub_matches)? {
+                terminal::render_roofline(model, &annotations, &roofline)?;
+            }
+        }
     }
 
     Ok(())
@@ -67,6 +67,7 @@ pub const STAGES: &'static [&'static str] = &[
     "pulse",
     "pulse-to-type",
     "pulse-declutter",
+    "prepack",
     "nnef-cycle",
     "nnef-cycle-declutter",
     "before-optimize",
@@ -92,7 +93,7 @@ fn main() {
     (@arg model: +takes_value "Sets the model to use")
 
     (@arg format: -f +takes_value
-     "Hint the model format ('kaldi', 'onnx' or 'tf') instead of guess from extension.")
+     "Hint the model format ('kaldi', 'nnef', 'onnx', 'tf' or 'tflite') instead of guess from extension.")
 
     (@arg input: -i --input +takes_value +multiple number_of_values(1)
      "Set input shape and type (@file.pb or @file.npz:thing.npy or 3x4xi32).")
@@ -140,6 +141,7 @@ fn main() {
 
     (@arg pass: --pass +takes_value possible_values(STAGES) "Pass to stop preprocessing after.")
     (@arg nnef_cycle: --("nnef-cycle") "Perform NNEF dump and reload before optimizing")
+    (@arg prepack: --prepack "Pack constant weights ahead of time (dumped to NNEF with tract-core extension)")
     (@arg nnef_tract_core: --("nnef-tract-core") "Allow usage of tract-core extension in NNEF dump and load")
     (@arg nnef_tract_onnx: --("nnef-tract-onnx") "Allow usage of tract-onnx extension in NNEF dump and load")
     (@arg nnef_tract_pulse: --("nnef-tract-pulse") "Allow usage of tract-pulse extension in NNEF dump and load")
@@ -221,7 +223,27 @@ fn main() {
             Arg::with_name("assert-cost")
             .takes_value(true)
             .long("assert-cost")
-            .help("Checks computed against the provided value (form: \"FMA(F32)=2060448 DIV(F32)=24576\")")
+            .help("Checks computed against the provided value, every computed kind must be listed (form: \"FMA(F32)=2060448,Div(F32)=24576,ReadBytes=81920\")")
+            )
+        .arg(
+            Arg::with_name("assert-listed-costs")
+            .takes_value(true)
+            .long("assert-listed-costs")
+            .help("Checks computed against the provided value, ignoring the kinds not listed (form: \"FMA(F32)=2060448,Div(F32)=24576\")")
+            )
+        .arg(
+            Arg::with_name("peak-gflops")
+            .takes_value(true)
+            .long("peak-gflops")
+            .requires("peak-bandwidth")
+            .help("Peak arithmetic throughput of the target machine (GFLOP/s), for a roofline estimate of --cost"),
+            )
+        .arg(
+            Arg::with_name("peak-bandwidth")
+            .takes_value(true)
+            .long("peak-bandwidth")
+            .requires("peak-gflops")
+            .help("Peak memory bandwidth of the target machine (GB/s), for a roofline estimate of --cost"),
             )
         .arg(
             Arg::with_name("nnef-dir")
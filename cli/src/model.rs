@@ -64,6 +64,12 @@ pub trait Model: downcast_rs::Downcast + std::fmt::Debug + dyn_clone::DynClone +
             self.node_op(id).downcast_ref::<tract_hir::ops::scan::InferenceScan>()
         {
             vec![("loop".into(), &hir.body)]
+        } else if let Some(mir) = self.node_op(id).downcast_ref::<tract_core::ops::scan::While>() {
+            vec![("loop".into(), &mir.body)]
+        } else if let Some(hir) =
+            self.node_op(id).downcast_ref::<tract_hir::ops::scan::InferenceWhile>()
+        {
+            vec![("loop".into(), &hir.body)]
         } else {
             vec![]
         }
@@ -80,6 +86,9 @@ pub trait Model: downcast_rs::Downcast + std::fmt::Debug + dyn_clone::DynClone +
         {
             // if we have typefact, we hopefully have type ops
             unreachable!();
+        } else if let Some(_) = self.node_op(id).downcast_ref::<tract_core::ops::scan::While>() {
+            // iteration count depends on the data
+            vec![None]
         } else {
             vec![]
         }
@@ -104,6 +104,8 @@ impl Parameters {
         let format = matches.value_of("format").unwrap_or(
             if filename.extension().map(|s| s == "onnx").unwrap_or(false) {
                 "onnx"
+            } else if filename.extension().map(|s| s == "tflite").unwrap_or(false) {
+                "tflite"
             } else if filename.extension().map(|s| s == "raw" || s == "txt").unwrap_or(false) {
                 "kaldi"
             } else if filename.is_dir()
@@ -179,6 +181,15 @@ impl Parameters {
                     (SomeGraphDef::NoGraphDef, Box::new(parsed.model), Option::<TfExt>::None)
                 }
             }
+            #[cfg(feature = "tflite")]
+            "tflite" => {
+                let tflite = tract_tflite::tflite();
+                info_usage("loaded framework (tflite)", probe);
+                let proto = tflite.proto_model_for_path(&filename)?;
+                info_usage("proto model loaded", probe);
+                let model = tflite.model_for_proto_model(&proto)?;
+                (SomeGraphDef::NoGraphDef, Box::new(model), Option::<TfExt>::None)
+            }
             #[cfg(feature = "tf")]
             "tf" => {
                 let tf = tract_tensorflow::tensorflow();
@@ -460,6 +471,7 @@ impl Parameters {
         });
 
         let nnef_cycle = matches.is_present("nnef_cycle");
+        let prepack = matches.is_present("prepack");
 
         info!("Will stop at {}", stop_at);
 
@@ -541,6 +553,9 @@ impl Parameters {
                 stage!("pulse-declutter", typed_model -> typed_model, |m:TypedModel| Ok(m.declutter()?));
             }
         }
+        if prepack {
+            stage!("prepack", typed_model -> typed_model, |m:TypedModel| Ok(m.prepack()?));
+        }
         if nnef_cycle {
             stage!("nnef-cycle", typed_model -> typed_model, |m:TypedModel| {
                 let nnef = super::nnef(&matches);
@@ -6,49 +6,11 @@ use crate::model::Model;
 use crate::CliResult;
 use tract_hir::internal::*;
 
+/// Parses a fact spec, like `1x3x224x224xf32` or `Sx40xf32`. Symbols with the
+/// same name are the same across calls, and `S` is the streaming dimension
+/// when pulsing is enabled.
 pub fn parse_spec(size: &str) -> CliResult<InferenceFact> {
-    if size.len() == 0 {
-        return Ok(InferenceFact::default());
-    }
-    let splits = size.split("x").collect::<Vec<_>>();
-
-    if splits.len() < 1 {
-        bail!("The <size> argument should be formatted as {size}x{...}x{type}.");
-    }
-
-    let last = splits.last().unwrap();
-    let (datum_type, shape) = if last.ends_with("S") || last.parse::<i32>().is_ok() {
-        (None, &*splits)
-    } else {
-        let datum_type = match splits.last().unwrap().to_lowercase().as_str() {
-            "f64" => DatumType::F64,
-            "f32" => DatumType::F32,
-            "i32" => DatumType::I32,
-            "i8" => DatumType::I8,
-            "u8" => DatumType::U8,
-            _ => bail!("Type of the input should be f64, f32, i32, i8 or u8."),
-        };
-        (Some(datum_type), &splits[0..splits.len() - 1])
-    };
-
-    let shape = ShapeFactoid::closed(
-        shape
-            .iter()
-            .map(|&s| {
-                Ok(if s == "_" {
-                    GenericFactoid::Any
-                } else {
-                    GenericFactoid::Only(parse_dim_stream(s)?)
-                })
-            })
-            .collect::<CliResult<TVec<DimFact>>>()?,
-    );
-
-    if let Some(dt) = datum_type {
-        Ok(InferenceFact::dt_shape(dt, shape))
-    } else {
-        Ok(InferenceFact::shape(shape))
-    }
+    Ok(InferenceFact::parse(&mut SYMBOLS.lock().unwrap(), size)?)
 }
 
 fn parse_values<'a, T: Datum + FromStr>(shape: &[usize], it: Vec<&'a str>) -> CliResult<Tensor> {
@@ -177,23 +139,18 @@ pub fn for_string(value: &str) -> CliResult<(Option<String>, InferenceFact)> {
     }
 }
 
+lazy_static::lazy_static! {
+    static ref SYMBOLS: std::sync::Mutex<SymbolTable> = std::sync::Mutex::new(symbols());
+}
+
 #[cfg(feature = "pulse")]
-fn parse_dim_stream(s: &str) -> CliResult<TDim> {
-    use tract_pulse::internal::stream_dim;
-    if s == "S" {
-        Ok(stream_dim())
-    } else if s.ends_with("S") {
-        let number: String = s.chars().take_while(|c| c.is_digit(10)).collect();
-        let number: i64 = number.parse::<i64>().map(|i| i.into())?;
-        Ok(stream_dim() * number)
-    } else {
-        Ok(s.parse::<i64>().map(|i| i.into())?)
-    }
+fn symbols() -> SymbolTable {
+    SymbolTable::default().with(tract_pulse::internal::stream_symbol())
 }
 
 #[cfg(not(feature = "pulse"))]
-fn parse_dim_stream(s: &str) -> CliResult<TDim> {
-    Ok(s.parse::<i64>().map(|i| i.into())?)
+fn symbols() -> SymbolTable {
+    SymbolTable::default()
 }
 
 pub fn make_inputs(values: &[impl std::borrow::Borrow<TypedFact>]) -> CliResult<TVec<Tensor>> {
@@ -387,6 +387,38 @@ pub fn render_summaries(
     Ok(())
 }
 
+pub fn render_roofline(
+    model: &dyn Model,
+    annotations: &Annotations,
+    roofline: &crate::cost::Roofline,
+) -> CliResult<()> {
+    let total = annotations.tags.values().sum::<NodeTags>();
+    let (compute, memory) = roofline.estimate(&total.cost)?;
+    println!("{}", White.bold().paint("Roofline estimate"));
+    println!(" * compute bound: {:.3} ms/i", compute * 1e3);
+    println!(" * memory bound: {:.3} ms/i", memory * 1e3);
+    let mut nodes = vec![];
+    for (qid, tags) in &annotations.tags {
+        let (compute, memory) = roofline.estimate(&tags.cost)?;
+        if compute > 0.0 || memory > 0.0 {
+            nodes.push((qid, compute, memory));
+        }
+    }
+    let sum = nodes.iter().map(|(_, c, m)| c.max(*m)).sum::<f64>();
+    println!(" * sum of per-node bounds: {}", White.bold().paint(format!("{:.3} ms/i", sum * 1e3)));
+    println!("{}", White.bold().paint("Heaviest nodes by roofline estimate"));
+    nodes.sort_by(|a, b| b.1.max(b.2).partial_cmp(&a.1.max(a.2)).unwrap());
+    for (qid, compute, memory) in nodes.into_iter().take(10) {
+        println!(
+            " * {} {:.3} ms/i ({} bound)",
+            Blue.bold().paint(format!("{:40}", qid.model(model).unwrap().node_name(qid.1))),
+            compute.max(memory) * 1e3,
+            if compute >= memory { "compute" } else { "memory" }
+        );
+    }
+    Ok(())
+}
+
 /// Format a rusage::Duration showing avgtime in ms.
 pub fn dur_avg(measure: Duration) -> String {
     White.bold().paint(format!("{:.3} ms/i", measure.as_secs_f64() * 1e3)).to_string()
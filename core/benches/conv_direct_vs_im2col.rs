@@ -58,11 +58,13 @@ impl Problem {
             group: 1,
             bias: None,
             q_params: None,
+            prepacked: None,
         };
 
         let mut model = TypedModel::default();
         let input = model.add_source("input", self.image_type()).unwrap();
-        let output = unsafe { conv.wire_as_im2col_pair(&mut model, "", input, direct).unwrap() };
+        let (output, _) =
+            unsafe { conv.wire_as_im2col_pair(&mut model, "", input, direct).unwrap() };
         model.set_output_outlets(&[output]).unwrap();
         SimplePlan::new(model).unwrap()
     }
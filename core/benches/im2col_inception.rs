@@ -39,6 +39,7 @@ fn b(
         group: 1,
         bias: None,
         q_params: None,
+        prepacked: None,
     };
 
     let mut m = TypedModel::default();
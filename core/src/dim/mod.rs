@@ -2,9 +2,11 @@
 use std::fmt;
 use std::ops;
 
+mod parse;
 mod tree;
 
-pub use self::tree::{Symbol, SymbolValues, TDim};
+pub use self::parse::parse_tdim;
+pub use self::tree::{Symbol, SymbolTable, SymbolValues, TDim};
 use crate::{TractError, TractResult};
 
 /// A super-trait for value acting as tensor dimensions in tract.
@@ -82,15 +84,27 @@ impl DimLike for TDim {
             }
             (_, Ok(q)) => Some((self.clone() / q, 1)),
             (_, _) => {
-                if self.symbols().len() == 1 && other.symbols().len() == 1 {
+                let (p_coef, p_factors) = self.factors();
+                let (q_coef, q_factors) = other.factors();
+                let mut rest = p_factors.clone();
+                let monomial = q_factors.len() > 0
+                    && q_factors.iter().all(|f| {
+                        rest.iter().position(|r| r == f).map(|ix| rest.remove(ix)).is_some()
+                    });
+                if monomial {
+                    let (p, q) = tree::reduce_ratio(p_coef, q_coef);
+                    Some((TDim::Prod(rest).reduce() * p, q))
+                } else if self.symbols().len() == 1 && other.symbols().len() == 1 {
                     let sym = self.symbols().into_iter().nth(0).unwrap();
-                    let slope_p = self.slope(sym);
-                    let slope_q = other.slope(sym);
-                    let (p, q) = tree::reduce_ratio(
-                        slope_p.0 * slope_q.1 as i64,
-                        slope_q.0 * slope_p.1 as i64,
-                    );
-                    Some((p.into(), q))
+                    self.slope(sym).and_then(|slope_p| {
+                        other.slope(sym).map(|slope_q| {
+                            let (p, q) = tree::reduce_ratio(
+                                slope_p.0 * slope_q.1 as i64,
+                                slope_q.0 * slope_p.1 as i64,
+                            );
+                            (p.into(), q)
+                        })
+                    })
                 } else {
                     None
                 }
@@ -101,7 +115,7 @@ impl DimLike for TDim {
                 return Ok(quotient);
             }
         }
-        crate::internal::bail!("Quotient is a non linear expression ({} / {})", self, other)
+        crate::internal::bail!("Quotient is not a simple expression ({} / {})", self, other)
     }
 
     fn maybe_mul(&self, other: &Self) -> TractResult<Self> {
@@ -110,10 +124,14 @@ impl DimLike for TDim {
         } else if let Ok(a) = self.to_i64() {
             Ok(other.clone() * a)
         } else {
-            crate::internal::bail!("product with too many symbols")
+            Ok(TDim::Prod(vec![self.clone(), other.clone()]).reduce())
         }
     }
 
+    fn div_ceil(&self, other: usize) -> Self {
+        TDim::div_ceil(self.clone(), other as u64)
+    }
+
     fn to_i64(&self) -> TractResult<i64> {
         TDim::to_i64(self)
     }
@@ -223,4 +241,22 @@ mod tests {
     fn div_sym_sym_rem() {
         assert!((s() + 1).maybe_div(&(s() * 4)).is_err());
     }
+
+    #[test]
+    fn div_affine_affine() {
+        assert_eq!((s() * 2 + 2).maybe_div(&(s() + 1)).unwrap(), (2.into(), 1));
+    }
+
+    #[test]
+    fn mul_sym_sym() {
+        let b: TDim = Symbol::new('B').into();
+        assert_eq!(s().maybe_mul(&b).unwrap(), TDim::Prod(vec![b, s()]).reduce());
+    }
+
+    #[test]
+    fn div_prod_sym() {
+        let b: TDim = Symbol::new('B').into();
+        let bs = (b.maybe_mul(&s()).unwrap()) * 6;
+        assert_eq!(bs.maybe_div(&(s() * 4)).unwrap(), (b * 3, 2));
+    }
 }
@@ -0,0 +1,221 @@
+//! Parsing of TDim expressions, as printed by their Display implementation.
+use super::{SymbolTable, TDim};
+use crate::internal::*;
+
+/// Parse a dimension expression like `2.S+1`, `B*S`, `div_ceil(S,2)` or
+/// `min(S,4)`. Symbols are single letters, looked up (or created) in `table`.
+pub fn parse_tdim(table: &mut SymbolTable, s: &str) -> TractResult<TDim> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut parser = Parser { table, chars: &chars, pos: 0 };
+    let dim = parser.expr()?;
+    if parser.pos != chars.len() {
+        bail!(
+            "Unexpected {:?} at {} in dimension expression {:?}",
+            chars[parser.pos],
+            parser.pos,
+            s
+        )
+    }
+    Ok(dim.reduce())
+}
+
+struct Parser<'a> {
+    table: &'a mut SymbolTable,
+    chars: &'a [char],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> TractResult<()> {
+        if !self.eat(c) {
+            bail!("Expected {:?} at {} in dimension expression", c, self.pos)
+        }
+        Ok(())
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> TractResult<TDim> {
+        let mut terms = vec![self.term()?];
+        loop {
+            if self.eat('+') {
+                terms.push(self.term()?);
+            } else if self.eat('-') {
+                terms.push(TDim::Mul(-1, Box::new(self.term()?)));
+            } else {
+                break;
+            }
+        }
+        Ok(if terms.len() == 1 { terms.remove(0) } else { TDim::Add(terms) })
+    }
+
+    // term := unary ('*' unary | '/' int | '%' int)*, or int '.' term, or int unary
+    fn term(&mut self) -> TractResult<TDim> {
+        let mut term = self.unary()?;
+        loop {
+            if self.eat('*') {
+                term = TDim::Prod(vec![term, self.unary()?]);
+            } else if self.eat('/') {
+                term = TDim::Div(Box::new(term), self.divisor()?);
+            } else if self.eat('%') {
+                term = TDim::Rem(Box::new(term), self.divisor()?);
+            } else if let TDim::Val(p) = term {
+                match self.peek() {
+                    Some('.') => {
+                        self.pos += 1;
+                        term = TDim::Mul(p, Box::new(self.term()?));
+                    }
+                    // implicit product, like in 2S
+                    Some(c) if c.is_alphabetic() || c == '(' => {
+                        term = TDim::Prod(vec![term, self.unary()?]);
+                    }
+                    _ => return Ok(term),
+                }
+            } else {
+                return Ok(term);
+            }
+        }
+    }
+
+    // unary := '-' int | '-' unary | atom
+    fn unary(&mut self) -> TractResult<TDim> {
+        if self.eat('-') {
+            if self.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) {
+                Ok(TDim::Val(-self.integer()?))
+            } else {
+                Ok(TDim::Mul(-1, Box::new(self.unary()?)))
+            }
+        } else {
+            self.atom()
+        }
+    }
+
+    // atom := int | symbol | '(' expr ')' | ('min' | 'max') '(' expr (',' expr)* ')'
+    //       | 'div_ceil' '(' expr ',' int ')'
+    fn atom(&mut self) -> TractResult<TDim> {
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => Ok(TDim::Val(self.integer()?)),
+            Some('(') => {
+                self.pos += 1;
+                let e = self.expr()?;
+                self.expect(')')?;
+                Ok(e)
+            }
+            Some(c) if c.is_alphabetic() => {
+                let ident: String = self.chars[self.pos..]
+                    .iter()
+                    .take_while(|c| c.is_alphanumeric() || **c == '_')
+                    .collect();
+                self.pos += ident.chars().count();
+                match &*ident {
+                    "min" | "max" => {
+                        self.expect('(')?;
+                        let mut terms = vec![self.expr()?];
+                        while self.eat(',') {
+                            terms.push(self.expr()?);
+                        }
+                        self.expect(')')?;
+                        Ok(if ident == "min" { TDim::Min(terms) } else { TDim::Max(terms) })
+                    }
+                    "div_ceil" => {
+                        self.expect('(')?;
+                        let num = self.expr()?;
+                        self.expect(',')?;
+                        let q = self.divisor()?;
+                        self.expect(')')?;
+                        Ok(TDim::DivCeil(Box::new(num), q))
+                    }
+                    _ if ident.chars().count() == 1 => Ok(self.table.sym(c).into()),
+                    _ => bail!("Unknown identifier {:?} in dimension expression", ident),
+                }
+            }
+            Some(c) => bail!("Unexpected {:?} at {} in dimension expression", c, self.pos),
+            None => bail!("Unexpected end of dimension expression"),
+        }
+    }
+
+    fn integer(&mut self) -> TractResult<i64> {
+        let digits: String =
+            self.chars[self.pos..].iter().take_while(|c| c.is_ascii_digit()).collect();
+        if digits.len() == 0 {
+            bail!("Expected an integer at {} in dimension expression", self.pos)
+        }
+        self.pos += digits.len();
+        Ok(digits.parse()?)
+    }
+
+    fn divisor(&mut self) -> TractResult<u64> {
+        let pos = self.pos;
+        let q = self.integer()?;
+        if q == 0 {
+            bail!("Division by zero at {} in dimension expression", pos)
+        }
+        Ok(q as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(s: &str) -> TractResult<()> {
+        let mut table = SymbolTable::default();
+        let dim = parse_tdim(&mut table, s)?;
+        assert_eq!(dim.to_string(), s);
+        assert_eq!(parse_tdim(&mut table, &dim.to_string())?, dim);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_linear() -> TractResult<()> {
+        round_trip("S")?;
+        round_trip("12")?;
+        round_trip("S+-2")?;
+        round_trip("-1.S")?;
+        round_trip("1+2.S")?;
+        round_trip("(S+1)/2")
+    }
+
+    #[test]
+    fn parse_non_linear() -> TractResult<()> {
+        round_trip("B*S")?;
+        round_trip("3.B*S")?;
+        round_trip("div_ceil(B*S,2)")?;
+        round_trip("min(S,4)")?;
+        round_trip("max(B,S)")?;
+        round_trip("(B*S)%3")
+    }
+
+    #[test]
+    fn parse_same_symbol() -> TractResult<()> {
+        let mut table = SymbolTable::default();
+        let a = parse_tdim(&mut table, "S")?;
+        let b = parse_tdim(&mut table, "S + 0")?;
+        assert_eq!(a, b);
+        assert_eq!(parse_tdim(&mut table, "2S")?, parse_tdim(&mut table, "2.S")?);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        let mut table = SymbolTable::default();
+        assert!(parse_tdim(&mut table, "S/B").is_err());
+        assert!(parse_tdim(&mut table, "foo").is_err());
+        assert!(parse_tdim(&mut table, "(S").is_err());
+        assert!(parse_tdim(&mut table, "S/0").is_err());
+        assert!(parse_tdim(&mut table, "S%0").is_err());
+        assert!(parse_tdim(&mut table, "div_ceil(S,0)").is_err());
+    }
+}
@@ -48,6 +48,35 @@ impl std::ops::IndexMut<Symbol> for SymbolValues {
     }
 }
 
+/// Symbols by name, so that parsing the same name twice gives the same symbol.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolTable(HashMap<char, Symbol>);
+
+impl SymbolTable {
+    /// Use `s` for its name instead of creating a new symbol.
+    pub fn with(mut self, s: Symbol) -> Self {
+        self.0.insert(s.0, s);
+        self
+    }
+
+    pub fn sym(&mut self, name: char) -> Symbol {
+        *self.0.entry(name).or_insert_with(|| Symbol::new(name))
+    }
+
+    /// A new symbol, named `hint` if the table has no symbol of that name yet,
+    /// or after the first free letter otherwise.
+    pub fn new_symbol(&mut self, hint: char) -> Symbol {
+        let name = std::iter::once(hint)
+            .chain('a'..='z')
+            .chain('A'..='Z')
+            .find(|c| !self.0.contains_key(c))
+            .unwrap_or(hint);
+        let symbol = Symbol::new(name);
+        self.0.entry(name).or_insert(symbol);
+        symbol
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Debug)]
 pub enum TDim {
     Sym(Symbol),
@@ -55,6 +84,12 @@ pub enum TDim {
     Add(Vec<TDim>),
     Mul(i64, Box<TDim>),
     Div(Box<TDim>, u64),
+    /// Product of non constant factors.
+    Prod(Vec<TDim>),
+    DivCeil(Box<TDim>, u64),
+    Rem(Box<TDim>, u64),
+    Min(Vec<TDim>),
+    Max(Vec<TDim>),
 }
 
 use TDim::*;
@@ -67,6 +102,20 @@ impl fmt::Display for TDim {
             Add(it) => write!(fmt, "{}", it.iter().map(|x| format!("{}", x)).join("+")),
             Mul(a, b) => write!(fmt, "{}.{}", a, b),
             Div(a, b) => write!(fmt, "({})/{}", a, b),
+            Prod(it) => write!(
+                fmt,
+                "{}",
+                it.iter()
+                    .map(|x| match x {
+                        Sym(_) | DivCeil(..) | Min(_) | Max(_) => format!("{}", x),
+                        _ => format!("({})", x),
+                    })
+                    .join("*")
+            ),
+            DivCeil(a, b) => write!(fmt, "div_ceil({},{})", a, b),
+            Rem(a, b) => write!(fmt, "({})%{}", a, b),
+            Min(it) => write!(fmt, "min({})", it.iter().map(|x| format!("{}", x)).join(",")),
+            Max(it) => write!(fmt, "max({})", it.iter().map(|x| format!("{}", x)).join(",")),
         }
     }
 }
@@ -91,6 +140,21 @@ impl TDim {
             Add(terms) => terms.iter().fold(Val(0), |acc, it| -> TDim { acc + it.eval(values) }),
             Div(a, q) => a.eval(values) / *q as i64,
             Mul(p, a) => a.eval(values) * *p,
+            Prod(factors) => Prod(factors.iter().map(|f| f.eval(values)).collect()).reduce(),
+            DivCeil(a, q) => DivCeil(b!(a.eval(values)), *q).reduce(),
+            Rem(a, q) => Rem(b!(a.eval(values)), *q).reduce(),
+            Min(terms) => Min(terms.iter().map(|t| t.eval(values)).collect()).reduce(),
+            Max(terms) => Max(terms.iter().map(|t| t.eval(values)).collect()).reduce(),
+        }
+    }
+
+    /// True for sums of symbols with rational coefficients, rounded down.
+    pub fn is_linear(&self) -> bool {
+        match self {
+            Sym(_) | Val(_) => true,
+            Add(terms) => terms.iter().all(|t| t.is_linear()),
+            Mul(_, a) | Div(a, _) => a.is_linear(),
+            Prod(_) | DivCeil(..) | Rem(..) | Min(_) | Max(_) => false,
         }
     }
 
@@ -110,15 +174,20 @@ impl TDim {
         match self {
             Sym(_) | Val(_) => 1,
             Add(terms) => 2 * terms.iter().map(TDim::cost).sum::<usize>(),
-            Div(a, _) => 3 * a.cost(),
+            Div(a, _) | DivCeil(a, _) | Rem(a, _) => 3 * a.cost(),
             Mul(_, a) => 2 * a.cost(),
+            Prod(terms) | Min(terms) | Max(terms) => {
+                2 * terms.iter().map(TDim::cost).sum::<usize>()
+            }
         }
     }
 
     fn wiggle(&self) -> Vec<TDim> {
         use self::TDim::*;
         match self {
-            Sym(_) | Val(_) => vec![self.clone()],
+            Sym(_) | Val(_) | Prod(_) | DivCeil(..) | Rem(..) | Min(_) | Max(_) => {
+                vec![self.clone()]
+            }
             Add(terms) => {
                 let mut forms = vec![];
                 let sub_wiggle = terms.iter().map(|e| e.wiggle()).multi_cartesian_product();
@@ -243,6 +312,12 @@ impl TDim {
                 let a = a.simplify();
                 if let Val(a) = a {
                     Val(a / q as i64)
+                } else if let Rem(_, r) = &a {
+                    if *r <= q {
+                        Val(0)
+                    } else {
+                        Div(b!(a), q)
+                    }
                 } else if let Mul(-1, a) = a {
                     Mul(-1, b!(Div(a, q)))
                 } else if let Add(mut terms) = a {
@@ -305,6 +380,124 @@ impl TDim {
                     Div(b!(a), q)
                 }
             }
+            Prod(factors) => {
+                let mut coef = 1i64;
+                let mut flat = vec![];
+                let mut todo = factors;
+                while let Some(factor) = todo.pop() {
+                    match factor.simplify() {
+                        Val(v) => coef *= v,
+                        Mul(p, a) => {
+                            coef *= p;
+                            todo.push(*a)
+                        }
+                        Prod(factors) => todo.extend(factors.into_iter()),
+                        f => flat.push(f),
+                    }
+                }
+                if coef == 0 {
+                    return Val(0);
+                }
+                // distribute over sums, so products of sums have a single form
+                if let Some(pos) =
+                    flat.iter().position(|f| if let Add(_) = f { true } else { false })
+                {
+                    if let Add(terms) = flat.remove(pos) {
+                        return Add(terms
+                            .into_iter()
+                            .map(|t| {
+                                let mut factors = flat.clone();
+                                factors.push(t);
+                                Mul(coef, b!(Prod(factors)))
+                            })
+                            .collect())
+                        .simplify();
+                    }
+                }
+                flat.sort();
+                let prod = match flat.len() {
+                    0 => return Val(coef),
+                    1 => flat.remove(0),
+                    _ => Prod(flat),
+                };
+                if coef == 1 {
+                    prod
+                } else {
+                    Mul(coef, b!(prod))
+                }
+            }
+            DivCeil(a, q) => {
+                if q == 1 {
+                    return a.simplify();
+                }
+                let a = a.simplify();
+                if let Val(v) = a {
+                    Val(v.div_ceil(&(q as i64)))
+                } else if a.is_linear() {
+                    // linear expressions are easier on the rest of tract in their floor form
+                    Div(b!(Add(vec![a, Val(q as i64 - 1)])), q).simplify()
+                } else if let DivCeil(a, q2) = a {
+                    DivCeil(a, q * q2).simplify()
+                } else if a.gcd() % q == 0 {
+                    a.div(q)
+                } else if let Add(terms) = a {
+                    let (integer, rest): (Vec<_>, Vec<_>) =
+                        terms.into_iter().partition(|t| t.gcd() % q == 0);
+                    if integer.len() > 0 {
+                        let mut terms: Vec<TDim> = integer.iter().map(|t| t.div(q)).collect();
+                        terms.push(DivCeil(b!(Add(rest)), q));
+                        Add(terms).simplify()
+                    } else {
+                        DivCeil(b!(Add(rest)), q)
+                    }
+                } else {
+                    DivCeil(b!(a), q)
+                }
+            }
+            Rem(a, q) => {
+                if q == 1 {
+                    return Val(0);
+                }
+                let a = a.simplify();
+                if let Val(v) = a {
+                    Val(v.rem_euclid(q as i64))
+                } else if a.gcd() % q == 0 {
+                    Val(0)
+                } else if let Rem(a, q2) = a {
+                    if q2 % q == 0 {
+                        Rem(a, q).simplify()
+                    } else if q % q2 == 0 {
+                        Rem(a, q2)
+                    } else {
+                        Rem(b!(Rem(a, q2)), q)
+                    }
+                } else if let Add(terms) = a {
+                    let mut changed = false;
+                    let terms: Vec<TDim> = terms
+                        .into_iter()
+                        .filter_map(|t| match t {
+                            Val(v) if v.rem_euclid(q as i64) != v => {
+                                changed = true;
+                                Some(Val(v.rem_euclid(q as i64)))
+                            }
+                            t if t.gcd() % q == 0 => {
+                                changed = true;
+                                None
+                            }
+                            t => Some(t),
+                        })
+                        .collect();
+                    if changed {
+                        Rem(b!(Add(terms)), q).simplify()
+                    } else {
+                        Rem(b!(Add(terms)), q)
+                    }
+                } else {
+                    Rem(b!(a), q)
+                }
+            }
+            Min(terms) => simplify_min_max(terms, false),
+            Max(terms) => simplify_min_max(terms, true),
             _ => self,
         }
     }
@@ -315,10 +508,8 @@ impl TDim {
         match self {
             Val(v) => v.abs() as u64,
             Sym(_) => 1,
-            Add(terms) => {
-                let (head, tail) = terms.split_first().unwrap();
-                tail.iter().fold(head.gcd(), |a, b| a.gcd(&b.gcd()))
-            }
+            // gcd(0, x) == x, and an empty sum is 0
+            Add(terms) => terms.iter().fold(0, |a, b| a.gcd(&b.gcd())),
             Mul(p, a) => a.gcd() * p.abs() as u64,
             Div(a, q) => {
                 if a.gcd() % *q == 0 {
@@ -327,9 +518,14 @@ impl TDim {
                     1
                 }
             }
+            Prod(factors) => factors.iter().map(|f| f.gcd()).product(),
+            DivCeil(..) | Rem(..) => 1,
+            Min(terms) | Max(terms) => terms.iter().fold(0, |a, b| a.gcd(&b.gcd())),
         }
     }
 
+    /// Exact division by `d`. `d` must divide `self.gcd()`: the quotient of
+    /// each term of a sum is computed separately.
     fn div(&self, d: u64) -> TDim {
         use self::TDim::*;
         use num_integer::Integer;
@@ -338,8 +534,23 @@ impl TDim {
         }
         match self {
             Val(v) => Val(v / d as i64),
-            Sym(_) => panic!(),
+            Sym(_) | DivCeil(..) | Rem(..) => Div(b!(self.clone()), d),
             Add(terms) => Add(terms.iter().map(|t| t.div(d)).collect()),
+            Min(terms) => Min(terms.iter().map(|t| t.div(d)).collect()),
+            Max(terms) => Max(terms.iter().map(|t| t.div(d)).collect()),
+            Prod(factors) => {
+                let mut d = d;
+                Prod(
+                    factors
+                        .iter()
+                        .map(|f| {
+                            let gcd = f.gcd().gcd(&d);
+                            d /= gcd;
+                            f.div(gcd)
+                        })
+                        .collect(),
+                )
+            }
             Mul(p, a) => {
                 if *p == d as i64 {
                     (**a).clone()
@@ -353,30 +564,46 @@ impl TDim {
     }
 
     pub fn div_ceil(self, rhs: u64) -> TDim {
-        TDim::Div(Box::new(Add(vec![self, Val(rhs as i64 - 1)])), rhs).reduce()
+        TDim::DivCeil(Box::new(self), rhs).reduce()
+    }
+
+    /// Constant coefficient and non constant factors of a monomial.
+    pub(super) fn factors(&self) -> (i64, Vec<TDim>) {
+        match self {
+            Val(v) => (*v, vec![]),
+            Mul(p, a) => {
+                let (p2, factors) = a.factors();
+                (p * p2, factors)
+            }
+            Prod(factors) => (1, factors.clone()),
+            _ => (1, vec![self.clone()]),
+        }
     }
 
-    pub fn slope(&self, sym: Symbol) -> (i64, u64) {
-        fn slope_rec(d: &TDim, sym: Symbol) -> (i64, i64) {
+    /// Slope of a linear expression with respect to `sym`, or None if the
+    /// expression is not linear.
+    pub fn slope(&self, sym: Symbol) -> Option<(i64, u64)> {
+        fn slope_rec(d: &TDim, sym: Symbol) -> Option<(i64, i64)> {
             match d {
-                Val(_) => (0, 1),
-                Sym(s) => ((sym == *s) as i64, 1),
-                Add(terms) => terms
-                    .iter()
-                    .map(|d| slope_rec(d, sym))
-                    .fold((1, 1), |a, b| ((a.0 * b.1 + a.1 * b.0), (b.1 * a.1))),
+                Val(_) => Some((0, 1)),
+                Sym(s) => Some(((sym == *s) as i64, 1)),
+                Add(terms) => terms.iter().try_fold((0, 1), |a, d| {
+                    let b = slope_rec(d, sym)?;
+                    Some(((a.0 * b.1 + a.1 * b.0), (b.1 * a.1)))
+                }),
                 Mul(p, a) => {
-                    let (n, d) = slope_rec(a, sym);
-                    (p * n, d)
+                    let (n, d) = slope_rec(a, sym)?;
+                    Some((p * n, d))
                 }
                 Div(a, q) => {
-                    let (n, d) = slope_rec(a, sym);
-                    (n, d * *q as i64)
+                    let (n, d) = slope_rec(a, sym)?;
+                    Some((n, d * *q as i64))
                 }
+                _ => None,
             }
         }
-        let (p, q) = slope_rec(self, sym);
-        reduce_ratio(p, q)
+        let (p, q) = slope_rec(self, sym)?;
+        Some(reduce_ratio(p, q))
     }
 
     pub fn symbols(&self) -> std::collections::HashSet<Symbol> {
@@ -387,10 +614,49 @@ impl TDim {
                 set.extend(v.symbols().into_iter());
                 set
             }),
-            Mul(_, a) => a.symbols(),
-            Div(a, _) => a.symbols(),
+            Mul(_, a) | Div(a, _) | DivCeil(a, _) | Rem(a, _) => a.symbols(),
+            Prod(terms) | Min(terms) | Max(terms) => terms.iter().fold(hashset!(), |mut set, v| {
+                set.extend(v.symbols().into_iter());
+                set
+            }),
+        }
+    }
+}
+
+/// Flattens nested mins (or maxs), folds the constant terms, and drops the
+/// terms another one bounds by a constant offset.
+fn simplify_min_max(terms: Vec<TDim>, max: bool) -> TDim {
+    let mut flat: Vec<TDim> = vec![];
+    let mut todo = terms;
+    while let Some(term) = todo.pop() {
+        match (term.simplify(), max) {
+            (Min(terms), false) | (Max(terms), true) => todo.extend(terms.into_iter()),
+            (t, _) => flat.push(t),
         }
     }
+    let mut kept: Vec<TDim> = vec![];
+    for term in flat.into_iter().sorted().unique() {
+        let mut dominated = false;
+        kept.retain(|k| match (term.clone() - k).to_i64() {
+            Ok(diff) if (diff >= 0) == max => false,
+            Ok(_) => {
+                dominated = true;
+                true
+            }
+            Err(_) => true,
+        });
+        if !dominated {
+            kept.push(term);
+        }
+    }
+    kept.sort();
+    if kept.len() == 1 {
+        kept.remove(0)
+    } else if max {
+        Max(kept)
+    } else {
+        Min(kept)
+    }
 }
 
 pub(super) fn reduce_ratio(mut p: i64, mut q: i64) -> (i64, u64) {
@@ -574,7 +840,7 @@ impl<I: AsPrimitive<u64>> ops::Div<I> for TDim {
 
 impl<I: AsPrimitive<u64>> ops::RemAssign<I> for TDim {
     fn rem_assign(&mut self, rhs: I) {
-        *self += -(self.clone() / rhs.as_() * rhs.as_());
+        *self = TDim::Rem(Box::new(std::mem::take(self)), rhs.as_()).reduce()
     }
 }
 
@@ -601,6 +867,7 @@ mod tests {
 
     lazy_static::lazy_static! {
         static ref S: Symbol = crate::dim::Symbol::new('S');
+        static ref T: Symbol = crate::dim::Symbol::new('T');
     }
 
     fn s() -> TDim {
@@ -797,4 +1064,133 @@ mod tests {
         let e = (s() - 3 + 1).div_ceil(1);
         assert_eq!(e, s() + -2);
     }
+
+    fn t() -> TDim {
+        (*T).into()
+    }
+
+    fn prod(a: &TDim, b: &TDim) -> TDim {
+        TDim::Prod(vec![a.clone(), b.clone()])
+    }
+
+    #[test]
+    fn reduce_prod_commutes() {
+        assert_eq!(prod(&s(), &t()).reduce(), prod(&t(), &s()).reduce())
+    }
+
+    #[test]
+    fn reduce_prod_coefs() {
+        assert_eq!(prod(&mul(2, &s()), &mul(3, &t())).reduce(), mul(6, &prod(&s(), &t())))
+    }
+
+    #[test]
+    fn reduce_prod_distributes() {
+        let e = prod(&add(&s(), &Val(1)), &t()).reduce();
+        assert_eq!(e, add(&prod(&s(), &t()), &t()).reduce());
+    }
+
+    #[test]
+    fn reduce_prod_sub() {
+        let st = prod(&s(), &t()).reduce();
+        assert_eq!(st.clone() * 3 - st * 3, Val(0));
+    }
+
+    #[test]
+    fn div_ceil_linear() {
+        assert_eq!(s().div_ceil(2), div(&add(&s(), &Val(1)), 2));
+    }
+
+    #[test]
+    fn div_ceil_prod() {
+        let st = prod(&s(), &t()).reduce();
+        assert_eq!(st.clone().div_ceil(2), DivCeil(b!(st.clone()), 2));
+        assert_eq!((st.clone() * 2 + 3).div_ceil(2), st + 2);
+    }
+
+    #[test]
+    fn div_ceil_nested() {
+        let st = prod(&s(), &t()).reduce();
+        assert_eq!(st.clone().div_ceil(2).div_ceil(3), st.div_ceil(6));
+    }
+
+    #[test]
+    fn reduce_rem_prod() {
+        let st = prod(&s(), &t()).reduce();
+        assert_eq!((st.clone() * 4 + 5) % 2, Val(1));
+        assert_eq!((st.clone() + 5) % 2, Rem(b!(st + 1), 2));
+    }
+
+    #[test]
+    fn reduce_rem_rem() {
+        assert_eq!(s() % 6 % 3, s() % 3);
+        assert_eq!(s() % 3 % 6, s() % 3);
+    }
+
+    #[test]
+    fn reduce_max_offset() {
+        assert_eq!(TDim::Max(vec![s(), s() + 2, Val(1)]).reduce(), Max(vec![Val(1), s() + 2]));
+        assert_eq!(TDim::Min(vec![s(), s() + 2]).reduce(), s());
+    }
+
+    #[test]
+    fn reduce_min_nested() {
+        let e = TDim::Min(vec![s(), TDim::Min(vec![Val(4), t(), Val(3)])]).reduce();
+        assert_eq!(e, Min(vec![s(), t(), Val(3)]));
+    }
+
+    #[test]
+    fn eval_non_linear() {
+        let mut values = SymbolValues::default().with(*S, 3);
+        values[*T] = Some(5);
+        let st = prod(&s(), &t()).reduce();
+        assert_eq!(st.eval(&values), Val(15));
+        assert_eq!(st.clone().div_ceil(2).eval(&values), Val(8));
+        assert_eq!((st.clone() % 4).eval(&values), Val(3));
+        assert_eq!(TDim::Min(vec![st.clone(), s() * 4]).eval(&values), Val(12));
+        assert_eq!(TDim::Max(vec![st, s() * 4]).eval(&values), Val(15));
+    }
+
+    #[test]
+    fn display_non_linear() {
+        let st = prod(&s(), &t()).reduce();
+        assert_eq!(st.to_string(), "S*T");
+        assert_eq!((st.clone() * 2).to_string(), "2.S*T");
+        assert_eq!(
+            prod(&add(&s(), &Val(1)), &div(&t(), 2)).reduce().to_string(),
+            "(T)/2+S*((T)/2)"
+        );
+        assert_eq!(st.clone().div_ceil(2).to_string(), "div_ceil(S*T,2)");
+        assert_eq!((st % 3).to_string(), "(S*T)%3");
+    }
+
+    #[test]
+    fn slope_linear() {
+        assert_eq!(add(&mul(2, &s()), &Val(3)).slope(*S), Some((2, 1)));
+        assert_eq!(div(&add(&s(), &Val(1)), 2).slope(*S), Some((1, 2)));
+        assert_eq!(add(&t(), &Val(1)).slope(*S), Some((0, 1)));
+    }
+
+    #[test]
+    fn slope_non_linear() {
+        assert_eq!(prod(&s(), &t()).slope(*S), None);
+        assert_eq!(DivCeil(b!(s()), 2).slope(*S), None);
+    }
+
+    #[test]
+    fn gcd_empty() {
+        assert_eq!(Add(vec![]).gcd(), 0);
+        assert_eq!(Min(vec![]).gcd(), 0);
+    }
+
+    #[test]
+    fn new_symbol_is_fresh() {
+        let mut table = SymbolTable::default();
+        let n = table.sym('n');
+        let fresh = table.new_symbol('n');
+        assert_ne!(n, fresh);
+        let name = TDim::from(fresh).to_string();
+        assert_ne!(name, "n");
+        assert_eq!(table.sym(name.chars().next().unwrap()), fresh);
+        assert_ne!(table.new_symbol('n'), fresh);
+    }
 }
@@ -94,7 +94,7 @@ pub type TractResult<T> = Result<T, anyhow::Error>;
 /// This prelude is meant for code using tract.
 pub mod prelude {
     pub use crate::datum::{Blob, Datum, DatumType};
-    pub use crate::dim::{Symbol, SymbolValues, TDim};
+    pub use crate::dim::{Symbol, SymbolTable, SymbolValues, TDim};
     pub use crate::framework::Framework;
     pub use crate::model::*;
     pub use crate::plan::{SimplePlan, SimpleState};
@@ -112,7 +112,7 @@ pub mod prelude {
 
 /// This prelude is meant for code extending tract (like implementing new ops).
 pub mod internal {
-    pub use crate::dim::{DimLike, MaybeProduct, TDim, ToDim};
+    pub use crate::dim::{parse_tdim, DimLike, MaybeProduct, TDim, ToDim};
     pub use crate::model::*;
     pub use crate::ops::change_axes::*;
     pub use crate::ops::element_wise::ElementWiseMiniOp;
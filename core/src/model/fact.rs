@@ -14,6 +14,12 @@ pub trait Fact: std::fmt::Debug + Downcast + dyn_clone::DynClone + Send + Sync +
         self.to_typed_fact()?.matches(t)
     }
 
+    /// Like `matches`, with the symbols in the fact taking the values resolved
+    /// so far in a session.
+    fn matches_resolved(&self, t: &Tensor, values: &SymbolValues) -> TractResult<bool> {
+        self.to_typed_fact()?.matches_resolved(t, values)
+    }
+
     fn same_as(&self, _other: &dyn Fact) -> bool;
 }
 
@@ -191,6 +197,12 @@ impl Fact for TypedFact {
         Ok(self.datum_type == t.datum_type() && self.shape == t.shape())
     }
 
+    fn matches_resolved(&self, t: &Tensor, values: &SymbolValues) -> TractResult<bool> {
+        Ok(self.datum_type == t.datum_type()
+            && self.shape.rank() == t.rank()
+            && self.shape.iter().zip(t.shape()).all(|(d, t)| d.eval(values) == t.to_dim()))
+    }
+
     fn same_as(&self, other: &dyn Fact) -> bool {
         if cfg!(debug_assertions) {
             self.consistent().unwrap()
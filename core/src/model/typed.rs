@@ -192,6 +192,20 @@ impl TypedModel {
         self.optimize_passes(&mut crate::optim::codegen())
     }
 
+    /// Pack the constant weights of the network ahead of time.
+    ///
+    /// `optimize` reuses the packed weights instead of packing them again, as
+    /// long as it selects the same kernels.
+    pub fn prepack(&self) -> TractResult<TypedModel> {
+        let mut model = self.clone();
+        for id in self.eval_order()? {
+            if let Some(op) = self.node(id).op.prepack(self, self.node(id))? {
+                model.node_mut(id).op = op;
+            }
+        }
+        Ok(model)
+    }
+
     pub fn invariants(&self) -> TractResult<invariants::Invariants> {
         invariants::for_model(self)
     }
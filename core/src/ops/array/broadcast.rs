@@ -1,6 +1,6 @@
 use crate::internal::*;
 
-#[derive(Debug, Clone, new, Default, Hash)]
+#[derive(Debug, Clone, new, Default, Hash, PartialEq)]
 pub struct MultiBroadcastTo {
     pub shape: TVec<TDim>,
 }
@@ -21,6 +21,7 @@ impl Op for MultiBroadcastTo {
 
     op_core_mir!();
     op_as_typed_op!();
+    impl_op_same_as!();
 }
 
 impl EvalOp for MultiBroadcastTo {
@@ -1,7 +1,7 @@
 use crate::internal::*;
 
 /// ConcatSlice: fully decluttered Concat equivalent
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, PartialEq)]
 pub enum ConcatSlice {
     Const(Arc<Tensor>),
     Var,
@@ -23,7 +23,7 @@ impl ConcatSlice {
     }
 }
 
-#[derive(new, Debug, Clone, Hash)]
+#[derive(new, Debug, Clone, Hash, PartialEq)]
 pub struct TypedConcat {
     pub axis: usize,
     pub slices: TVec<ConcatSlice>,
@@ -60,6 +60,7 @@ impl Op for TypedConcat {
 
     op_core_lir_mir!();
     op_as_typed_op!();
+    impl_op_same_as!();
 }
 
 impl TypedOp for TypedConcat {
@@ -0,0 +1,171 @@
+use crate::internal::*;
+
+/// Layout of the channel axis, as seen by DepthToSpace: depth-column-row or
+/// column-row-depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DepthToSpaceMode {
+    Dcr,
+    Crd,
+}
+
+/// Move blocks of channel data into spatial blocks, on NCHW inputs.
+#[derive(Debug, Clone, new, Hash)]
+pub struct DepthToSpace {
+    pub block_size: usize,
+    pub mode: DepthToSpaceMode,
+}
+tract_linalg::impl_dyn_hash!(DepthToSpace);
+
+impl DepthToSpace {
+    pub fn compute_shape<D: DimLike>(&self, input: &[D]) -> TractResult<TVec<D>> {
+        if input.len() != 4 {
+            bail!("DepthToSpace expects a NCHW input, got {:?}", input)
+        }
+        let b = self.block_size;
+        Ok(tvec!(
+            input[0].clone(),
+            input[1].clone() / (b * b),
+            input[2].clone() * b,
+            input[3].clone() * b
+        ))
+    }
+}
+
+impl Op for DepthToSpace {
+    fn name(&self) -> Cow<str> {
+        "DepthToSpace".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("block size: {} mode: {:?}", self.block_size, self.mode)])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for DepthToSpace {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        let output_shape = self.compute_shape(input.shape())?;
+        let (n, c, h, w) = (input.shape()[0], input.shape()[1], input.shape()[2], input.shape()[3]);
+        let b = self.block_size;
+        if c % (b * b) != 0 {
+            bail!("{} channels can not be split in blocks of {}x{}", c, b, b)
+        }
+        let (split, permutation) = match self.mode {
+            DepthToSpaceMode::Dcr => ([n, b, b, c / (b * b), h, w], [0, 3, 4, 1, 5, 2]),
+            DepthToSpaceMode::Crd => ([n, c / (b * b), b, b, h, w], [0, 1, 4, 2, 5, 3]),
+        };
+        let output = input
+            .into_tensor()
+            .into_shape(&split)?
+            .permute_axes(&permutation)?
+            .into_shape(&output_shape)?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for DepthToSpace {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = self.compute_shape(&*inputs[0].shape.to_tvec())?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    fn invariants(&self, _model: &TypedModel, _node: &TypedNode) -> TractResult<Invariants> {
+        Ok(tvec!(AxisInfo::simple(0)).into())
+    }
+}
+
+/// Move spatial blocks into the channel axis, on NCHW inputs.
+#[derive(Debug, Clone, new, Hash)]
+pub struct SpaceToDepth {
+    pub block_size: usize,
+}
+tract_linalg::impl_dyn_hash!(SpaceToDepth);
+
+impl SpaceToDepth {
+    pub fn compute_shape<D: DimLike>(&self, input: &[D]) -> TractResult<TVec<D>> {
+        if input.len() != 4 {
+            bail!("SpaceToDepth expects a NCHW input, got {:?}", input)
+        }
+        let b = self.block_size;
+        Ok(tvec!(
+            input[0].clone(),
+            input[1].clone() * (b * b),
+            input[2].clone() / b,
+            input[3].clone() / b
+        ))
+    }
+}
+
+impl Op for SpaceToDepth {
+    fn name(&self) -> Cow<str> {
+        "SpaceToDepth".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("block size: {}", self.block_size)])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for SpaceToDepth {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        let output_shape = self.compute_shape(input.shape())?;
+        let (n, c, h, w) = (input.shape()[0], input.shape()[1], input.shape()[2], input.shape()[3]);
+        let b = self.block_size;
+        if h % b != 0 || w % b != 0 {
+            bail!("{}x{} can not be split in blocks of {}x{}", h, w, b, b)
+        }
+        let output = input
+            .into_tensor()
+            .into_shape(&[n, c, h / b, b, w / b, b])?
+            .permute_axes(&[0, 3, 5, 1, 2, 4])?
+            .into_shape(&output_shape)?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for SpaceToDepth {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = self.compute_shape(&*inputs[0].shape.to_tvec())?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    fn invariants(&self, _model: &TypedModel, _node: &TypedNode) -> TractResult<Invariants> {
+        Ok(tvec!(AxisInfo::simple(0)).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn space_to_depth_and_back() {
+        let input = Tensor::from(
+            tract_ndarray::Array::from_shape_vec((1, 2, 2, 4), (0..16).map(|i| i as f32).collect())
+                .unwrap(),
+        );
+        let s2d = SpaceToDepth::new(2).eval(tvec!(input.clone().into_arc_tensor())).unwrap();
+        assert_eq!(s2d[0].shape(), &[1, 8, 1, 2]);
+        let d2s = DepthToSpace::new(2, DepthToSpaceMode::Dcr).eval(s2d).unwrap();
+        assert_eq!(*d2s[0], input);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::internal::*;
 use ndarray::*;
 
-#[derive(Debug, Clone, new, Hash)]
+#[derive(Debug, Clone, new, Hash, PartialEq)]
 pub struct Gather {
     pub axis: usize,
 }
@@ -14,6 +14,7 @@ impl Op for Gather {
 
     op_core_mir!();
     op_as_typed_op!();
+    impl_op_same_as!();
 }
 
 impl Gather {
@@ -80,6 +81,16 @@ impl TypedOp for Gather {
                 .compute_output_shape(&*inputs[0].shape.to_tvec(), &*inputs[1].shape.to_tvec())?
         )?))
     }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        // only the gathered slices of data are touched, not the whole tensor
+        let output = &self.output_facts(inputs)?[0];
+        let gathered: TDim = output.shape.iter().maybe_product()?;
+        let gathered = gathered * output.datum_type.size_of();
+        let indices: TDim = inputs[1].shape.iter().maybe_product()?;
+        let indices = indices * inputs[1].datum_type.size_of();
+        Ok(tvec!((Cost::ReadBytes, gathered.clone() + indices), (Cost::WrittenBytes, gathered)))
+    }
 }
 
 impl EvalOp for Gather {
@@ -0,0 +1,73 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Pick elements of data along `axis` at the positions given by indices. The
+/// output has the shape of the indices.
+#[derive(Debug, Clone, new, Hash)]
+pub struct GatherElements {
+    pub axis: usize,
+}
+tract_linalg::impl_dyn_hash!(GatherElements);
+
+impl Op for GatherElements {
+    fn name(&self) -> Cow<str> {
+        "GatherElements".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {}", self.axis)])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl GatherElements {
+    unsafe fn eval_t<T: Datum>(
+        &self,
+        data: &Tensor,
+        indices: &ArrayViewD<i64>,
+    ) -> TractResult<Tensor> {
+        let data_view = data.to_array_view_unchecked::<T>();
+        let output = ArrayD::from_shape_fn(indices.shape(), |mut coords| {
+            let index = indices[&coords];
+            coords[self.axis] = if index < 0 {
+                (index + data_view.shape()[self.axis] as i64) as usize
+            } else {
+                index as usize
+            };
+            data_view[&coords].clone()
+        });
+        let mut output = output.into_tensor();
+        output.set_datum_type(data.datum_type());
+        Ok(output)
+    }
+}
+
+impl EvalOp for GatherElements {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, indices) = args_2!(inputs);
+        let indices = indices.cast_to::<i64>()?;
+        let indices = indices.to_array_view::<i64>()?;
+        let len = data.shape()[self.axis] as i64;
+        if let Some(index) = indices.iter().find(|&&i| i < -len || i >= len) {
+            bail!("Index {} out of bounds for axis {} of {:?}", index, self.axis, data.shape())
+        }
+        let output = unsafe {
+            dispatch_datum_by_size!(Self::eval_t(data.datum_type())(self, &data, &indices))?
+        };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for GatherElements {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[1].shape.clone())?))
+    }
+}
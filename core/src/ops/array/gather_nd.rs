@@ -0,0 +1,122 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Gather slices of data, addressed by the tuples along the last axis of
+/// indices. The first `batch_dims` axes are shared by data and indices.
+#[derive(Debug, Clone, new, Hash)]
+pub struct GatherNd {
+    pub batch_dims: usize,
+}
+tract_linalg::impl_dyn_hash!(GatherNd);
+
+impl Op for GatherNd {
+    fn name(&self) -> Cow<str> {
+        "GatherNd".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("batch dims: {}", self.batch_dims)])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl GatherNd {
+    pub fn compute_shape<D: DimLike>(
+        &self,
+        data_shape: &[D],
+        indices_shape: &[D],
+    ) -> TractResult<TVec<D>> {
+        let n = indices_shape.last().map(|d| d.to_usize()).transpose()?.unwrap_or(0);
+        if indices_shape.len() <= self.batch_dims || self.batch_dims + n > data_shape.len() {
+            bail!(
+                "Inconsistent GatherNd shapes: data {:?}, indices {:?}, batch_dims {}",
+                data_shape,
+                indices_shape,
+                self.batch_dims
+            )
+        }
+        let mut shape: TVec<D> = indices_shape[..indices_shape.len() - 1].into();
+        shape.extend(data_shape[self.batch_dims + n..].iter().cloned());
+        Ok(shape)
+    }
+
+    unsafe fn eval_t<T: Datum>(
+        &self,
+        output: &mut Tensor,
+        data: &Tensor,
+        indices: &Tensor,
+    ) -> TractResult<()> {
+        let batch_dims = self.batch_dims;
+        let n = *indices.shape().last().unwrap();
+        let batch: usize = data.shape()[..batch_dims].iter().product();
+        let mut data_shape: TVec<usize> = tvec!(batch);
+        data_shape.extend(data.shape()[batch_dims..].iter().cloned());
+        let data = data.to_array_view_unchecked::<T>().into_shape(&*data_shape)?;
+        let outer: usize = indices.shape()[..indices.rank() - 1].iter().product();
+        let per_batch = outer / batch.max(1);
+        let mut output_shape: TVec<usize> = tvec!(outer);
+        output_shape.extend(data.shape()[1 + n..].iter().cloned());
+        let mut output = output.to_array_view_mut_unchecked::<T>().into_shape(&*output_shape)?;
+        let indices = indices.as_slice::<i64>()?;
+        for i in 0..outer {
+            let mut slice = data.index_axis(Axis(0), i / per_batch);
+            for &index in &indices[i * n..][..n] {
+                let len = slice.shape()[0] as i64;
+                if index < -len || index >= len {
+                    bail!("Index {} out of bounds for axis of length {}", index, len)
+                }
+                let index = if index < 0 { index + len } else { index };
+                slice = slice.index_axis_move(Axis(0), index as usize);
+            }
+            output.index_axis_mut(Axis(0), i).assign(&slice);
+        }
+        Ok(())
+    }
+}
+
+impl EvalOp for GatherNd {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, indices) = args_2!(inputs);
+        let indices = indices.cast_to::<i64>()?;
+        let shape = self.compute_shape(data.shape(), indices.shape())?;
+        unsafe {
+            let mut output = Tensor::uninitialized_dt(data.datum_type(), &*shape)?;
+            dispatch_datum_by_size!(Self::eval_t(data.datum_type())(
+                self,
+                &mut output,
+                &data,
+                &indices
+            ))?;
+            Ok(tvec!(output.into_arc_tensor()))
+        }
+    }
+}
+
+impl TypedOp for GatherNd {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = self.compute_shape(&*inputs[0].shape.to_tvec(), &*inputs[1].shape.to_tvec())?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batched_gather() {
+        let op = GatherNd::new(1);
+        let data = rctensor3(&[[[0i32, 1], [2, 3]], [[4, 5], [6, 7]]]);
+        let indices = rctensor2(&[[1i64], [0]]);
+        let output = op.eval(tvec!(data, indices)).unwrap();
+        assert_eq!(*output[0], tensor2(&[[2i32, 3], [4, 5]]));
+    }
+}
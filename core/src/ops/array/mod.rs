@@ -2,17 +2,37 @@
 mod broadcast;
 pub(crate) mod concat;
 mod constant_of_shape;
+mod depth_to_space;
 mod gather;
+mod gather_elements;
+mod gather_nd;
 mod pad;
+mod range;
 mod reshape;
+mod resize;
+mod reverse_sequence;
+mod scatter_elements;
+mod scatter_nd;
 mod slice;
 mod tile;
+mod topk;
+mod trilu;
 
 pub use self::broadcast::MultiBroadcastTo;
 pub use self::concat::{ConcatSlice, TypedConcat};
 pub use self::constant_of_shape::ConstantOfShape;
+pub use self::depth_to_space::{DepthToSpace, DepthToSpaceMode, SpaceToDepth};
 pub use self::gather::Gather;
+pub use self::gather_elements::GatherElements;
+pub use self::gather_nd::GatherNd;
 pub use self::pad::{Pad, PadMode};
+pub use self::range::Range;
 pub use self::reshape::FiniteReshape;
+pub use self::resize::{CoordTransformer, Interpolator, Nearest, Resize, ResizeAxis};
+pub use self::reverse_sequence::ReverseSequence;
+pub use self::scatter_elements::ScatterElements;
+pub use self::scatter_nd::ScatterNd;
 pub use self::slice::Slice;
 pub use self::tile::Tile;
+pub use self::topk::TopK;
+pub use self::trilu::Trilu;
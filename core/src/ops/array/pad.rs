@@ -5,6 +5,8 @@ use ndarray::*;
 pub enum PadMode {
     Constant(Arc<Tensor>),
     Reflect,
+    /// Reflect, including the edge frame.
+    Symmetric,
     Edge,
 }
 
@@ -45,7 +47,10 @@ impl Pad {
             .collect();
         let slice_info = SliceInfo::<_, IxDyn>::new(slice_spec).unwrap();
         output.slice_mut(slice_info.as_ref()).assign(&input);
-        if self.mode == PadMode::Reflect || self.mode == PadMode::Edge {
+        if self.mode == PadMode::Reflect
+            || self.mode == PadMode::Symmetric
+            || self.mode == PadMode::Edge
+        {
             for (ax, &(bef, aft)) in self.pads.iter().enumerate() {
                 let axis = Axis(ax);
                 let dim = output.shape()[ax];
@@ -56,6 +61,7 @@ impl Pad {
                         let source_slice = match self.mode {
                             PadMode::Edge => 0,
                             PadMode::Reflect => bef - i,
+                            PadMode::Symmetric => bef - 1 - i,
                             _ => panic!(),
                         };
                         let source =
@@ -70,6 +76,7 @@ impl Pad {
                         let source_slice = match self.mode {
                             PadMode::Edge => dim - aft - 1,
                             PadMode::Reflect => dim - aft - 2 - i,
+                            PadMode::Symmetric => dim - aft - 1 - i,
                             _ => panic!(),
                         };
                         let source =
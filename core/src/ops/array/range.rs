@@ -0,0 +1,176 @@
+use crate::internal::*;
+use std::ops::Add;
+
+/// Generate a 1D sequence from three scalar inputs: start, limit and delta.
+///
+/// The length of the output depends on the input values, so they must be
+/// known at typing time. The limit can be a symbolic TDim (typically derived
+/// from an input shape): the output length is then symbolic too, and the
+/// sequence is computed once the symbols are resolved in the session.
+#[derive(Debug, Clone, Default, Hash)]
+pub struct Range;
+tract_linalg::impl_dyn_hash!(Range);
+
+impl Op for Range {
+    fn name(&self) -> Cow<str> {
+        "Range".into()
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl Range {
+    pub fn len_for(start: &Tensor, limit: &Tensor, delta: &Tensor) -> TractResult<usize> {
+        let start = start.cast_to_scalar::<f64>()?;
+        let limit = limit.cast_to_scalar::<f64>()?;
+        let delta = delta.cast_to_scalar::<f64>()?;
+        if delta == 0.0 {
+            bail!("Range delta can not be zero")
+        }
+        Ok(((limit - start) / delta).ceil().max(0.0) as usize)
+    }
+
+    /// Output length, as `div_ceil(limit - start, delta)` if the bounds are
+    /// symbolic. Only integer deltas are supported in that case, and the
+    /// length is assumed not to be negative.
+    pub fn len_dim(start: &Tensor, limit: &Tensor, delta: &Tensor) -> TractResult<TDim> {
+        match Self::len_for(start, limit, delta) {
+            Ok(len) => return Ok(len.to_dim()),
+            Err(e) if limit.datum_type() != TDim::datum_type() => return Err(e),
+            _ => (),
+        }
+        let start = start.cast_to::<TDim>()?.to_scalar::<TDim>()?.clone();
+        let limit = limit.cast_to::<TDim>()?.to_scalar::<TDim>()?.clone();
+        let delta = delta.cast_to::<TDim>()?.to_scalar::<TDim>()?.to_i64()?;
+        if delta > 0 {
+            Ok((limit - start).div_ceil(delta as u64))
+        } else if delta < 0 {
+            Ok((start - limit).div_ceil(-delta as u64))
+        } else {
+            bail!("Range delta can not be zero")
+        }
+    }
+
+    fn eval_t<T: Datum + Add<Output = T>>(
+        start: &Tensor,
+        limit: &Tensor,
+        delta: &Tensor,
+    ) -> TractResult<Arc<Tensor>> {
+        let len = Self::len_for(start, limit, delta)?;
+        let delta = delta.to_scalar::<T>()?;
+        let mut value = start.to_scalar::<T>()?.clone();
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            values.push(value.clone());
+            value = value + delta.clone();
+        }
+        Ok(rctensor1(&values))
+    }
+
+    fn eval_concrete(
+        start: &Tensor,
+        limit: &Tensor,
+        delta: &Tensor,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        if start.datum_type() == TDim::datum_type() {
+            Ok(tvec!(Self::eval_t::<TDim>(start, limit, delta)?))
+        } else {
+            Ok(tvec!(dispatch_numbers!(Self::eval_t(start.datum_type())(start, limit, delta))?))
+        }
+    }
+}
+
+impl EvalOp for Range {
+    fn is_stateless(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (start, limit, delta) = args_3!(inputs);
+        Self::eval_concrete(&start, &limit, &delta)
+    }
+
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(RangeState)))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RangeState;
+
+impl OpState for RangeState {
+    fn eval(
+        &mut self,
+        session: &mut SessionState,
+        _op: &dyn Op,
+        inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let mut inputs: TVec<Arc<Tensor>> = inputs
+            .into_iter()
+            .map(|t| {
+                if let Ok(d) = t.to_scalar::<TDim>() {
+                    rctensor0(d.eval(&session.resolved_symbols))
+                } else {
+                    t
+                }
+            })
+            .collect();
+        let (start, limit, delta) = args_3!(inputs);
+        Range::eval_concrete(&start, &limit, &delta)
+    }
+}
+
+impl TypedOp for Range {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if let (Some(start), Some(limit), Some(delta)) =
+            (&inputs[0].konst, &inputs[1].konst, &inputs[2].konst)
+        {
+            let len = Self::len_dim(start, limit, delta)?;
+            if len.to_usize().is_ok() {
+                let mut output = Self::eval_concrete(start, limit, delta)?;
+                Ok(tvec!(TypedFact::from(output.remove(0))))
+            } else {
+                Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*tvec!(len))?))
+            }
+        } else {
+            bail!("Range requires start, limit and delta to be known, got {:?}", inputs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_delta() {
+        let output =
+            Range.eval(tvec!(rctensor0(10i32), rctensor0(4i32), rctensor0(-3i32))).unwrap();
+        assert_eq!(*output[0], tensor1(&[10i32, 7]));
+    }
+
+    #[test]
+    fn symbolic_limit() -> TractResult<()> {
+        let mut table = SymbolTable::default();
+        let s: TDim = table.sym('S').into();
+        let mut model = TypedModel::default();
+        let start = model.add_const("start", tensor0(TDim::from(1)))?;
+        let limit = model.add_const("limit", tensor0(s.clone()))?;
+        let delta = model.add_const("delta", tensor0(TDim::from(2)))?;
+        let range = model.wire_node("range", Range, &[start, limit, delta])?;
+        model.set_output_outlets(&range)?;
+        assert_eq!(model.outlet_fact(range[0])?.shape[0], (s - 1).div_ceil(2));
+        let mut state = SimpleState::new(SimplePlan::new(&model)?)?;
+        state.session_state.resolved_symbols = SymbolValues::default().with(table.sym('S'), 6);
+        let output = state.run(tvec!())?;
+        assert_eq!(*output[0], tensor1(&[TDim::from(1), 3.into(), 5.into()]));
+        Ok(())
+    }
+}
@@ -0,0 +1,416 @@
+use crate::internal::*;
+use ndarray::*;
+use num_traits::Float;
+
+/// How output coordinates are mapped back to the input space.
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+pub enum CoordTransformer {
+    HalfPixel,
+    PytorchHalfPixel,
+    AlignCorners,
+    Asymmetric,
+    TfHalfPixelForNn,
+    TfCropAndResize,
+}
+
+impl CoordTransformer {
+    /// Input coordinate of the `x_out`-th output value. As in ONNX, the
+    /// output length is taken unrounded (`len_in * scale`), except by
+    /// `TfCropAndResize` which also uses `roi` as a (start, end) fraction of the
+    /// input axis.
+    pub fn transform(
+        &self,
+        x_out: usize,
+        scale: f32,
+        len_in: usize,
+        len_out: usize,
+        roi: (f32, f32),
+    ) -> f32 {
+        let x_out = x_out as f32;
+        match self {
+            CoordTransformer::HalfPixel => (x_out + 0.5) / scale - 0.5,
+            CoordTransformer::PytorchHalfPixel => {
+                if len_in as f32 * scale > 1.0 {
+                    (x_out + 0.5) / scale - 0.5
+                } else {
+                    0.0
+                }
+            }
+            CoordTransformer::AlignCorners => {
+                let len_out = len_in as f32 * scale;
+                if len_out > 1.0 {
+                    x_out * (len_in as f32 - 1.0) / (len_out - 1.0)
+                } else {
+                    0.0
+                }
+            }
+            CoordTransformer::Asymmetric => x_out / scale,
+            CoordTransformer::TfHalfPixelForNn => (x_out + 0.5) / scale,
+            CoordTransformer::TfCropAndResize => {
+                let (start, end) = roi;
+                let len_in = len_in as f32 - 1.0;
+                if len_out > 1 {
+                    start * len_in + x_out * (end - start) * len_in / (len_out as f32 - 1.0)
+                } else {
+                    0.5 * (start + end) * len_in
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+pub enum Interpolator {
+    Nearest,
+    Linear,
+    Cubic,
+}
+
+/// Rounding of input coordinates in `Interpolator::Nearest` mode.
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+pub enum Nearest {
+    Floor,
+    Ceil,
+    RoundPreferFloor,
+    RoundPreferCeil,
+}
+
+impl Nearest {
+    pub fn round(&self, x: f32) -> f32 {
+        match self {
+            Nearest::Floor => x.floor(),
+            Nearest::Ceil => x.ceil(),
+            Nearest::RoundPreferFloor if x.fract().abs() == 0.5 => x.floor(),
+            Nearest::RoundPreferCeil if x.fract().abs() == 0.5 => x.ceil(),
+            _ => x.round(),
+        }
+    }
+}
+
+/// Resizing of one axis.
+#[derive(Clone, Debug, new, Educe)]
+#[educe(Hash)]
+pub struct ResizeAxis {
+    pub axis: usize,
+    pub len: usize,
+    /// Output to input length ratio, as used by the coordinate transformation.
+    #[educe(Hash(method = "hash_f32"))]
+    pub scale: f32,
+    #[educe(Hash(method = "hash_f32"))]
+    pub roi_start: f32,
+    #[educe(Hash(method = "hash_f32"))]
+    pub roi_end: f32,
+}
+
+/// N-dimensional resampling, following ONNX Resize semantics.
+///
+/// Axes are processed one after the other, which is exact for all the
+/// supported interpolators. No antialiasing is performed when downsampling.
+#[derive(Clone, Debug, Educe)]
+#[educe(Hash)]
+pub struct Resize {
+    pub axes: TVec<ResizeAxis>,
+    pub coord_transformer: CoordTransformer,
+    pub interpolator: Interpolator,
+    pub nearest: Nearest,
+    #[educe(Hash(method = "hash_f32"))]
+    pub cubic_coeff_a: f32,
+    pub exclude_outside: bool,
+    #[educe(Hash(method = "hash_f32"))]
+    pub extrapolation_value: f32,
+}
+
+tract_linalg::impl_dyn_hash!(Resize);
+
+impl Default for Resize {
+    fn default() -> Resize {
+        Resize {
+            axes: tvec!(),
+            coord_transformer: CoordTransformer::HalfPixel,
+            interpolator: Interpolator::Nearest,
+            nearest: Nearest::RoundPreferFloor,
+            cubic_coeff_a: -0.75,
+            exclude_outside: false,
+            extrapolation_value: 0.0,
+        }
+    }
+}
+
+impl Resize {
+    /// Input indices and weights contributing to each output position of
+    /// `axis`, or None for positions to be filled with the extrapolation value.
+    fn taps(&self, axis: &ResizeAxis, len_in: usize) -> TVec<Option<TVec<(usize, f32)>>> {
+        let clamp = |x: isize| x.max(0).min(len_in as isize - 1) as usize;
+        (0..axis.len)
+            .map(|x_out| {
+                let x = self.coord_transformer.transform(
+                    x_out,
+                    axis.scale,
+                    len_in,
+                    axis.len,
+                    (axis.roi_start, axis.roi_end),
+                );
+                if self.coord_transformer == CoordTransformer::TfCropAndResize
+                    && (x < 0.0 || x > len_in as f32 - 1.0)
+                {
+                    return None;
+                }
+                let x0 = x.floor();
+                let t = x - x0;
+                let x0 = x0 as isize;
+                let taps: TVec<(isize, f32)> = match self.interpolator {
+                    Interpolator::Nearest => tvec!((self.nearest.round(x) as isize, 1.0)),
+                    Interpolator::Linear => tvec!((x0, 1.0 - t), (x0 + 1, t)),
+                    Interpolator::Cubic => {
+                        let a = self.cubic_coeff_a;
+                        let far = |d: f32| ((a * d - 5.0 * a) * d + 8.0 * a) * d - 4.0 * a;
+                        let near = |d: f32| ((a + 2.0) * d - (a + 3.0)) * d * d + 1.0;
+                        tvec!(
+                            (x0 - 1, far(t + 1.0)),
+                            (x0, near(t)),
+                            (x0 + 1, near(1.0 - t)),
+                            (x0 + 2, far(2.0 - t))
+                        )
+                    }
+                };
+                let mut taps = taps;
+                if self.exclude_outside && self.interpolator != Interpolator::Nearest {
+                    taps.retain(|(x, _)| *x >= 0 && *x < len_in as isize);
+                    let sum = taps.iter().map(|(_, w)| w).sum::<f32>();
+                    taps.iter_mut().for_each(|(_, w)| *w /= sum);
+                }
+                Some(taps.into_iter().map(|(x, w)| (clamp(x), w)).collect())
+            })
+            .collect()
+    }
+
+    fn eval_nearest<T: Datum>(&self, input: &Tensor) -> TractResult<Tensor> {
+        let mut data = input.to_array_view::<T>()?.to_owned();
+        let extrapolation = if self.coord_transformer == CoordTransformer::TfCropAndResize {
+            Some(tensor0(self.extrapolation_value).cast_to::<T>()?.to_scalar::<T>()?.clone())
+        } else {
+            None
+        };
+        for axis in &self.axes {
+            let mut shape: TVec<usize> = data.shape().into();
+            shape[axis.axis] = axis.len;
+            let mut output = ArrayD::<T>::default(&*shape);
+            for (x_out, taps) in self.taps(axis, data.shape()[axis.axis]).iter().enumerate() {
+                let mut slice = output.index_axis_mut(Axis(axis.axis), x_out);
+                match taps {
+                    Some(taps) => slice.assign(&data.index_axis(Axis(axis.axis), taps[0].0)),
+                    None => slice.fill(extrapolation.clone().unwrap()),
+                }
+            }
+            data = output;
+        }
+        Ok(data.into_tensor())
+    }
+
+    fn eval_interpolated<T: Datum + Float + LinalgScalar + ScalarOperand>(
+        &self,
+        input: &Tensor,
+    ) -> TractResult<Tensor> {
+        let mut data = input.to_array_view::<T>()?.to_owned();
+        let extrapolation = T::from(self.extrapolation_value).unwrap();
+        for axis in &self.axes {
+            let mut shape: TVec<usize> = data.shape().into();
+            shape[axis.axis] = axis.len;
+            let mut output = ArrayD::<T>::zeros(&*shape);
+            for (x_out, taps) in self.taps(axis, data.shape()[axis.axis]).iter().enumerate() {
+                let mut slice = output.index_axis_mut(Axis(axis.axis), x_out);
+                match taps {
+                    Some(taps) => {
+                        for &(x_in, w) in taps {
+                            let w = T::from(w).unwrap();
+                            slice.scaled_add(w, &data.index_axis(Axis(axis.axis), x_in));
+                        }
+                    }
+                    None => slice.fill(extrapolation),
+                }
+            }
+            data = output;
+        }
+        Ok(data.into_tensor())
+    }
+}
+
+impl Op for Resize {
+    fn name(&self) -> Cow<str> {
+        "Resize".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        let mut info = vec![format!(
+            "{:?} interpolation, {:?} coordinates",
+            self.interpolator, self.coord_transformer
+        )];
+        for axis in &self.axes {
+            info.push(format!("axis {} to {} (scale: {})", axis.axis, axis.len, axis.scale));
+        }
+        Ok(info)
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for Resize {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = &inputs[0];
+        let output = if self.interpolator == Interpolator::Nearest {
+            dispatch_datum!(Self::eval_nearest(input.datum_type())(self, input))?
+        } else {
+            match input.datum_type() {
+                DatumType::F32 => self.eval_interpolated::<f32>(input)?,
+                DatumType::F64 => self.eval_interpolated::<f64>(input)?,
+                dt => bail!("{:?} interpolation is not supported for {:?}", self.interpolator, dt),
+            }
+        };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for Resize {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let mut shape = inputs[0].shape.to_tvec();
+        for axis in &self.axes {
+            if axis.axis >= shape.len() {
+                bail!("Can not resize axis {} of a rank {} input", axis.axis, shape.len())
+            }
+            shape[axis.axis] = axis.len.to_dim();
+        }
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
+        let input = model.outlet_fact(node.inputs[0])?;
+        let axes = (0..input.rank())
+            .filter(|ax| !self.axes.iter().any(|axis| axis.axis == *ax))
+            .map(AxisInfo::simple)
+            .collect::<TVec<_>>();
+        Ok(axes.into())
+    }
+
+    fn change_axes(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+        _io: InOut,
+        change: &AxisOp,
+    ) -> TractResult<Option<AxisChangeConsequence>> {
+        let mut axes = tvec!();
+        for axis in &self.axes {
+            if let Some(ax) = change.transform_axis(axis.axis) {
+                axes.push(ResizeAxis { axis: ax, ..axis.clone() });
+            } else {
+                return Ok(None);
+            }
+        }
+        let op = Some(Box::new(Resize { axes, ..self.clone() }) as _);
+        Ok(Some(AxisChangeConsequence::new(model, node, op, change)))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if self.axes.is_empty() {
+            return Ok(Some(TypedModelPatch::shunt_one_op(model, node)?));
+        }
+        Ok(None)
+    }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let taps = match self.interpolator {
+            Interpolator::Nearest => return Ok(tvec!()),
+            Interpolator::Linear => 2,
+            Interpolator::Cubic => 4,
+        };
+        let mut shape = inputs[0].shape.to_tvec();
+        let mut fma = 0.to_dim();
+        for axis in &self.axes {
+            shape[axis.axis] = axis.len.to_dim();
+            let len: TDim = shape.iter().maybe_product()?;
+            fma += len * taps;
+        }
+        Ok(tvec!((Cost::FMA(inputs[0].datum_type), fma)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn resize(input: Tensor, scales: &[f32], op: Resize) -> TractResult<Tensor> {
+        let axes = scales
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| **s != 1.0)
+            .map(|(ax, s)| {
+                let len = (input.shape()[ax] as f32 * s).floor() as usize;
+                ResizeAxis::new(ax, len, *s, 0.0, 1.0)
+            })
+            .collect();
+        let op = Resize { axes, ..op };
+        Ok(op.eval(tvec!(input.into_arc_tensor()))?.remove(0).into_tensor())
+    }
+
+    #[test]
+    fn upsample_nearest() -> TractResult<()> {
+        let input = tensor4(&[[[[1f32, 2.], [3., 4.]]]]);
+        let output = resize(input, &[1.0, 1.0, 2.0, 3.0], Resize::default())?;
+        let expected = tensor4(&[[[
+            [1f32, 1., 1., 2., 2., 2.],
+            [1., 1., 1., 2., 2., 2.],
+            [3., 3., 3., 4., 4., 4.],
+            [3., 3., 3., 4., 4., 4.],
+        ]]]);
+        output.close_enough(&expected, false)
+    }
+
+    #[test]
+    fn downsample_linear() -> TractResult<()> {
+        let input = tensor4(&[[[[1f32, 2., 3., 4.], [5., 6., 7., 8.]]]]);
+        let op = Resize { interpolator: Interpolator::Linear, ..Resize::default() };
+        let output = resize(input, &[1.0, 1.0, 0.6, 0.6], op)?;
+        output.close_enough(&tensor4(&[[[[2.6666665f32, 4.3333331]]]]), true)
+    }
+
+    #[test]
+    fn downsample_linear_align_corners() -> TractResult<()> {
+        let input = tensor4(&[[[[1f32, 2., 3., 4.], [5., 6., 7., 8.]]]]);
+        let op = Resize {
+            coord_transformer: CoordTransformer::AlignCorners,
+            interpolator: Interpolator::Linear,
+            ..Resize::default()
+        };
+        let output = resize(input, &[1.0, 1.0, 0.6, 0.6], op)?;
+        output.close_enough(&tensor4(&[[[[1f32, 3.142857]]]]), true)
+    }
+
+    #[test]
+    fn downsample_cubic() -> TractResult<()> {
+        let input: Vec<f32> = (1..=16).map(|i| i as f32).collect();
+        let input = tensor1(&input).into_shape(&[1, 1, 4, 4])?;
+        let op = Resize { interpolator: Interpolator::Cubic, ..Resize::default() };
+        let output = resize(input, &[1.0, 1.0, 0.8, 0.8], op)?;
+        let expected = tensor4(&[[[
+            [1.47119141f32, 2.78125, 4.08251953],
+            [6.71142578, 8.02148438, 9.32275391],
+            [11.91650391, 13.2265625, 14.52783203],
+        ]]]);
+        output.close_enough(&expected, true)
+    }
+}
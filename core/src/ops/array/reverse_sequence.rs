@@ -0,0 +1,102 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Reverse the first `sequence_lens[b]` elements along `time_axis`, for each
+/// batch entry `b` along `batch_axis`.
+#[derive(Debug, Clone, new, Hash)]
+pub struct ReverseSequence {
+    pub batch_axis: usize,
+    pub time_axis: usize,
+}
+tract_linalg::impl_dyn_hash!(ReverseSequence);
+
+impl Op for ReverseSequence {
+    fn name(&self) -> Cow<str> {
+        "ReverseSequence".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("batch axis: {} time axis: {}", self.batch_axis, self.time_axis)])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl ReverseSequence {
+    unsafe fn eval_t<T: Datum>(&self, data: &Tensor, lens: &[i64]) -> TractResult<Tensor> {
+        let input = data.to_array_view_unchecked::<T>();
+        let mut output = input.to_owned();
+        let time_axis =
+            if self.time_axis > self.batch_axis { self.time_axis - 1 } else { self.time_axis };
+        let time_len = input.shape()[self.time_axis];
+        for (b, &len) in lens.iter().enumerate() {
+            if len < 0 || len as usize > time_len {
+                bail!("Invalid sequence length {} for time axis of length {}", len, time_len)
+            }
+            let len = len as usize;
+            let input = input.index_axis(Axis(self.batch_axis), b);
+            let mut output = output.index_axis_mut(Axis(self.batch_axis), b);
+            for t in 0..len {
+                output
+                    .index_axis_mut(Axis(time_axis), t)
+                    .assign(&input.index_axis(Axis(time_axis), len - 1 - t));
+            }
+        }
+        let mut output = output.into_tensor();
+        output.set_datum_type(data.datum_type());
+        Ok(output)
+    }
+}
+
+impl EvalOp for ReverseSequence {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, lens) = args_2!(inputs);
+        let lens = lens.cast_to::<i64>()?;
+        let lens = lens.as_slice::<i64>()?;
+        if lens.len() != data.shape()[self.batch_axis] {
+            bail!("Expected {} sequence lengths, got {}", data.shape()[self.batch_axis], lens.len())
+        }
+        let output =
+            unsafe { dispatch_datum_by_size!(Self::eval_t(data.datum_type())(self, &data, lens))? };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for ReverseSequence {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].without_value()))
+    }
+
+    fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
+        let rank = model.outlet_fact(node.inputs[0])?.rank();
+        Ok((0..rank)
+            .filter(|&ax| ax != self.batch_axis && ax != self.time_axis)
+            .map(|ax| AxisInfo {
+                inputs: tvec!(Some(ax), None),
+                outputs: tvec!(Some(ax)),
+                period: 1,
+                disposable: true,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_rows() {
+        let op = ReverseSequence::new(0, 1);
+        let data = rctensor2(&[[1i32, 2, 3], [4, 5, 6]]);
+        let output = op.eval(tvec!(data, rctensor1(&[3i64, 2]))).unwrap();
+        assert_eq!(*output[0], tensor2(&[[3i32, 2, 1], [5, 4, 6]]));
+    }
+}
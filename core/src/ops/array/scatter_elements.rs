@@ -0,0 +1,76 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Copy data, then overwrite the elements addressed along `axis` by indices
+/// with the matching updates. Indices and updates have the same shape.
+#[derive(Debug, Clone, new, Hash)]
+pub struct ScatterElements {
+    pub axis: usize,
+}
+tract_linalg::impl_dyn_hash!(ScatterElements);
+
+impl Op for ScatterElements {
+    fn name(&self) -> Cow<str> {
+        "ScatterElements".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {}", self.axis)])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl ScatterElements {
+    unsafe fn eval_t<T: Datum>(
+        &self,
+        data: &Tensor,
+        indices: &ArrayViewD<i64>,
+        updates: &Tensor,
+    ) -> TractResult<Tensor> {
+        let mut output = data.to_array_view_unchecked::<T>().to_owned();
+        let updates = updates.to_array_view_unchecked::<T>();
+        let len = output.shape()[self.axis] as i64;
+        for (mut coords, &index) in indices.indexed_iter() {
+            if index < -len || index >= len {
+                bail!("Index {} out of bounds for axis {} of {:?}", index, self.axis, data.shape())
+            }
+            let update = updates[&coords].clone();
+            coords[self.axis] = if index < 0 { index + len } else { index } as usize;
+            output[&coords] = update;
+        }
+        let mut output = output.into_tensor();
+        output.set_datum_type(data.datum_type());
+        Ok(output)
+    }
+}
+
+impl EvalOp for ScatterElements {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, indices, updates) = args_3!(inputs);
+        if indices.shape() != updates.shape() {
+            bail!("Indices and updates shapes differ: {:?} {:?}", indices.shape(), updates.shape())
+        }
+        let indices = indices.cast_to::<i64>()?;
+        let indices = indices.to_array_view::<i64>()?;
+        let output = unsafe {
+            dispatch_datum_by_size!(Self::eval_t(data.datum_type())(
+                self, &data, &indices, &updates
+            ))?
+        };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for ScatterElements {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].without_value()))
+    }
+}
@@ -0,0 +1,99 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Copy data, then overwrite the slices addressed by the tuples along the last
+/// axis of indices with the matching slices of updates.
+#[derive(Debug, Clone, Default, Hash)]
+pub struct ScatterNd;
+tract_linalg::impl_dyn_hash!(ScatterNd);
+
+impl Op for ScatterNd {
+    fn name(&self) -> Cow<str> {
+        "ScatterNd".into()
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl ScatterNd {
+    unsafe fn eval_t<T: Datum>(
+        &self,
+        data: &Tensor,
+        indices: &Tensor,
+        updates: &Tensor,
+    ) -> TractResult<Tensor> {
+        let mut output = data.to_array_view_unchecked::<T>().to_owned();
+        let n = *indices.shape().last().unwrap();
+        let outer: usize = indices.shape()[..indices.rank() - 1].iter().product();
+        let mut updates_shape: TVec<usize> = tvec!(outer);
+        updates_shape.extend(data.shape()[n..].iter().cloned());
+        let updates = updates.to_array_view_unchecked::<T>().into_shape(&*updates_shape)?;
+        let indices = indices.as_slice::<i64>()?;
+        for i in 0..outer {
+            let mut slice = output.view_mut();
+            for &index in &indices[i * n..][..n] {
+                let len = slice.shape()[0] as i64;
+                if index < -len || index >= len {
+                    bail!("Index {} out of bounds for axis of length {}", index, len)
+                }
+                let index = if index < 0 { index + len } else { index };
+                slice = slice.index_axis_move(Axis(0), index as usize);
+            }
+            slice.assign(&updates.index_axis(Axis(0), i));
+        }
+        let mut output = output.into_tensor();
+        output.set_datum_type(data.datum_type());
+        Ok(output)
+    }
+}
+
+impl EvalOp for ScatterNd {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, indices, updates) = args_3!(inputs);
+        let indices = indices.cast_to::<i64>()?;
+        let n = indices.shape().last().cloned().unwrap_or(0);
+        let mut expected: TVec<usize> = indices.shape()[..indices.rank().max(1) - 1].into();
+        expected.extend(data.shape().iter().skip(n).cloned());
+        if indices.rank() == 0 || n > data.rank() || updates.shape() != &*expected {
+            bail!(
+                "Inconsistent ScatterNd shapes: data {:?}, indices {:?}, updates {:?}",
+                data.shape(),
+                indices.shape(),
+                updates.shape()
+            )
+        }
+        let output = unsafe {
+            dispatch_datum_by_size!(Self::eval_t(data.datum_type())(
+                self, &data, &indices, &updates
+            ))?
+        };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for ScatterNd {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].without_value()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scatter_rows() {
+        let data = rctensor2(&[[0i32, 0], [0, 0], [0, 0]]);
+        let indices = rctensor2(&[[2i64], [0]]);
+        let updates = rctensor2(&[[1i32, 2], [3, 4]]);
+        let output = ScatterNd.eval(tvec!(data, indices, updates)).unwrap();
+        assert_eq!(*output[0], tensor2(&[[3i32, 4], [0, 0], [1, 2]]));
+    }
+}
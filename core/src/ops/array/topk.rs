@@ -0,0 +1,157 @@
+use std::cmp::Ordering;
+
+use crate::internal::*;
+use ndarray::*;
+
+/// Extract the `k` largest (or smallest) values along `axis`, and their
+/// indices.
+///
+/// Values are sorted, and ties are resolved by picking the lowest index first.
+#[derive(Debug, Clone, new, Hash)]
+pub struct TopK {
+    pub axis: usize,
+    pub k: usize,
+    pub largest: bool,
+}
+tract_linalg::impl_dyn_hash!(TopK);
+
+impl Op for TopK {
+    fn name(&self) -> Cow<str> {
+        "TopK".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {} k: {} largest: {}", self.axis, self.k, self.largest)])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl TopK {
+    fn eval_t<T: Datum + PartialOrd + Copy>(
+        &self,
+        input: &Tensor,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = input.to_array_view::<T>()?;
+        if input.shape()[self.axis] < self.k {
+            bail!("Can not extract top {} from axis {} of {:?}", self.k, self.axis, input.shape())
+        }
+        let mut shape: TVec<usize> = input.shape().into();
+        shape[self.axis] = self.k;
+        let mut values = unsafe { ArrayD::<T>::uninitialized(&*shape) };
+        let mut indices = ArrayD::<i64>::zeros(&*shape);
+        for ((lane, mut values), mut indices) in input
+            .lanes(Axis(self.axis))
+            .into_iter()
+            .zip(values.lanes_mut(Axis(self.axis)))
+            .zip(indices.lanes_mut(Axis(self.axis)))
+        {
+            let mut order: Vec<usize> = (0..lane.len()).collect();
+            order.sort_by(|&a, &b| value_order(&lane[a], &lane[b], self.largest));
+            for (ix, &pos) in order.iter().take(self.k).enumerate() {
+                values[ix] = lane[pos];
+                indices[ix] = pos as i64;
+            }
+        }
+        Ok(tvec!(values.into_arc_tensor(), indices.into_arc_tensor()))
+    }
+}
+
+/// Total order on values, best first, NaN values last.
+fn value_order<T: PartialOrd>(a: &T, b: &T, largest: bool) -> Ordering {
+    match (a.partial_cmp(a).is_none(), b.partial_cmp(b).is_none()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) if largest => b.partial_cmp(a).unwrap(),
+        (false, false) => a.partial_cmp(b).unwrap(),
+    }
+}
+
+impl EvalOp for TopK {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        dispatch_numbers!(Self::eval_t(input.datum_type())(self, &input))
+    }
+}
+
+impl TypedOp for TopK {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let mut shape = inputs[0].shape.to_tvec();
+        shape[self.axis] = self.k.to_dim();
+        Ok(tvec!(
+            TypedFact::dt_shape(inputs[0].datum_type, &*shape)?,
+            TypedFact::dt_shape(i64::datum_type(), &*shape)?
+        ))
+    }
+
+    fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
+        let rank = model.outlet_fact(node.inputs[0])?.rank();
+        (0..rank)
+            .filter(|&ax| ax != self.axis)
+            .map(|ax| AxisInfo::for_node(model, node, ax))
+            .collect()
+    }
+
+    fn change_axes(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+        _io: InOut,
+        change: &AxisOp,
+    ) -> TractResult<Option<AxisChangeConsequence>> {
+        if let Some(axis) = change.transform_axis(self.axis) {
+            let op = if axis != self.axis {
+                Some(Box::new(TopK { axis, ..self.clone() }) as _)
+            } else {
+                None
+            };
+            Ok(Some(AxisChangeConsequence::new(model, node, op, change)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_2_of_rows() {
+        let op = TopK::new(1, 2, true);
+        let input = tensor2(&[[1f32, 4., 2., 4.], [3., 0., 1., 2.]]);
+        let output = op.eval(tvec!(input.into_arc_tensor())).unwrap();
+        assert_eq!(*output[0], tensor2(&[[4f32, 4.], [3., 2.]]));
+        assert_eq!(*output[1], tensor2(&[[1i64, 3], [0, 3]]));
+    }
+
+    #[test]
+    fn smallest() {
+        let op = TopK::new(0, 1, false);
+        let input = tensor1(&[3i32, 1, 2]);
+        let output = op.eval(tvec!(input.into_arc_tensor())).unwrap();
+        assert_eq!(*output[0], tensor1(&[1i32]));
+        assert_eq!(*output[1], tensor1(&[1i64]));
+    }
+
+    #[test]
+    fn nan_last() {
+        let input = tensor1(&[1f32, std::f32::NAN, 3., 2.]).into_arc_tensor();
+        let output = TopK::new(0, 4, true).eval(tvec!(input.clone())).unwrap();
+        assert_eq!(&output[0].as_slice::<f32>().unwrap()[..3], &[3f32, 2., 1.]);
+        assert!(output[0].as_slice::<f32>().unwrap()[3].is_nan());
+        assert_eq!(*output[1], tensor1(&[2i64, 3, 0, 1]));
+        let output = TopK::new(0, 4, false).eval(tvec!(input)).unwrap();
+        assert_eq!(&output[0].as_slice::<f32>().unwrap()[..3], &[1f32, 2., 3.]);
+        assert!(output[0].as_slice::<f32>().unwrap()[3].is_nan());
+        assert_eq!(*output[1], tensor1(&[0i64, 3, 2, 1]));
+    }
+}
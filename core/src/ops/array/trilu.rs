@@ -0,0 +1,81 @@
+use crate::internal::*;
+
+/// Keep the upper (or lower) triangular part of the matrices formed by the
+/// two innermost axes, zeroing the rest.
+///
+/// `k` shifts the diagonal: positive values move it up, negative values move
+/// it down.
+#[derive(Debug, Clone, new, Hash)]
+pub struct Trilu {
+    pub upper: bool,
+    pub k: i64,
+}
+tract_linalg::impl_dyn_hash!(Trilu);
+
+impl Op for Trilu {
+    fn name(&self) -> Cow<str> {
+        "Trilu".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("upper: {} k: {}", self.upper, self.k)])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl Trilu {
+    unsafe fn eval_t<T: Datum + Copy + Default>(&self, input: &mut Tensor) {
+        let rank = input.rank();
+        let mut view = input.to_array_view_mut_unchecked::<T>();
+        for (coords, value) in view.indexed_iter_mut() {
+            let offset = coords[rank - 1] as i64 - coords[rank - 2] as i64;
+            let keep = if self.upper { offset >= self.k } else { offset <= self.k };
+            if !keep {
+                *value = T::default();
+            }
+        }
+    }
+}
+
+impl EvalOp for Trilu {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        if input.rank() < 2 {
+            bail!("Trilu expects an input of rank 2 or more, got {:?}", input.shape())
+        }
+        let mut output = input.into_tensor();
+        unsafe { dispatch_copy_by_size!(Self::eval_t(output.datum_type())(self, &mut output)) };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for Trilu {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].without_value()))
+    }
+
+    fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
+        let rank = model.outlet_fact(node.inputs[0])?.rank();
+        Ok((0..rank.saturating_sub(2)).map(AxisInfo::simple).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lower_with_offset() {
+        let input = rctensor2(&[[1i32, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        let output = Trilu::new(false, -1).eval(tvec!(input)).unwrap();
+        assert_eq!(*output[0], tensor2(&[[0i32, 0, 0], [4, 0, 0], [7, 8, 0]]));
+    }
+}
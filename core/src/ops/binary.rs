@@ -59,9 +59,12 @@ pub trait BinMiniOp:
     ) -> TractResult<Option<TypedModelPatch>> {
         Ok(None)
     }
-    #[allow(unused_variables)]
     fn cost_per_element(&self, dt: DatumType) -> TVec<(Cost, usize)> {
-        tvec!()
+        tvec!((Cost::ElementWise(dt), 1))
+    }
+    #[allow(unused_variables)]
+    fn same_as(&self, other: &dyn BinMiniOp) -> bool {
+        false
     }
 }
 dyn_clone::clone_trait_object!(BinMiniOp);
@@ -87,6 +90,10 @@ impl Op for TypedBinOp {
         self.0.validation()
     }
 
+    fn same_as(&self, other: &dyn Op) -> bool {
+        other.downcast_ref::<Self>().map(|other| self.0.same_as(&*other.0)).unwrap_or(false)
+    }
+
     op_core_mir!();
     op_as_typed_op!();
 }
@@ -283,6 +290,13 @@ impl Op for UnaryOp {
         self.mini_op.validation()
     }
 
+    fn same_as(&self, other: &dyn Op) -> bool {
+        other
+            .downcast_ref::<Self>()
+            .map(|other| self.mini_op.same_as(&*other.mini_op) && self.a == other.a)
+            .unwrap_or(false)
+    }
+
     op_core_lir_mir!();
     op_as_typed_op!();
 }
@@ -414,6 +428,10 @@ impl Op for MergeOpUnicast {
         self.0.name().into()
     }
 
+    fn same_as(&self, other: &dyn Op) -> bool {
+        other.downcast_ref::<Self>().map(|other| self.0.same_as(&*other.0)).unwrap_or(false)
+    }
+
     op_core_lir_mir!();
     op_as_typed_op!();
 }
@@ -468,6 +486,10 @@ macro_rules! bin_to_super_type {
                 stringify!($Op)
             }
 
+            fn same_as(&self, other: &dyn $crate::ops::binary::BinMiniOp) -> bool {
+                other.downcast_ref::<$Op>().is_some()
+            }
+
             fn eval_in_place(&self, a: &Tensor, b: &mut Tensor) -> TractResult<()> {
                 $(
                     $(if a.datum_type() == $typ::datum_type() {
@@ -558,7 +580,6 @@ macro_rules! bin_to_super_type {
 
 macro_rules! bin_to_bool {
     ($func:ident, $Op:ident,
-     $( cost: $cost:expr, )?
      $( flip: $flip:expr, )?
      $( [$($typ:ident),*] => $cab:expr),*) => {
         #[derive(Debug, Clone, Hash)]
@@ -569,6 +590,10 @@ macro_rules! bin_to_bool {
                 stringify!($Op)
             }
 
+            fn same_as(&self, other: &dyn $crate::ops::binary::BinMiniOp) -> bool {
+                other.downcast_ref::<$Op>().is_some()
+            }
+
             #[allow(unreachable_code)]
             fn eval_in_place(&self, a: &Tensor, b: &mut Tensor) -> TractResult<()> {
                 $(
@@ -615,11 +640,9 @@ macro_rules! bin_to_bool {
                     ($flip)(self, b)
                 }
             )?
-            $(
-                fn cost_per_element(&self, dt: DatumType) -> TVec<(Cost, usize)> {
-                    ($cost)(dt)
-                }
-            )?
+            fn cost_per_element(&self, dt: DatumType) -> TVec<(Cost, usize)> {
+                tvec!((Cost::Comparison(dt), 1))
+            }
         }
 
         pub mod $func {
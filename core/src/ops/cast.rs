@@ -5,7 +5,7 @@ pub fn cast(to: DatumType) -> ElementWiseOp {
     ElementWiseOp(Box::new(Cast { to }))
 }
 
-#[derive(Debug, Clone, new, Hash)]
+#[derive(Debug, Clone, new, Hash, PartialEq)]
 pub struct Cast {
     pub to: DatumType,
 }
@@ -21,6 +21,10 @@ impl ElementWiseMiniOp for Cast {
         Some(self.to)
     }
 
+    fn same_as(&self, other: &dyn ElementWiseMiniOp) -> bool {
+        other.downcast_ref::<Self>().map(|other| other == self).unwrap_or(false)
+    }
+
     fn eval_out_of_place(&self, t: &Tensor) -> TractResult<Tensor> {
         t.cast_to_dt(self.to).map(|t| t.into_owned())
     }
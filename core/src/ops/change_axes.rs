@@ -403,6 +403,7 @@ impl Op for AxisOp {
 
     op_core_lir_mir!();
     op_as_typed_op!();
+    impl_op_same_as!();
 }
 
 tract_linalg::impl_dyn_hash!(AxisOp);
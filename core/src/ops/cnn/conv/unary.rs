@@ -14,11 +14,12 @@ use crate::ops::cnn::conv::KernelFormat;
 use crate::ops::cnn::PoolSpec;
 use crate::ops::matmul;
 use crate::ops::matmul::mmm_wrapper::MMMWrapper;
+use crate::ops::matmul::PackedA;
 use crate::ops::nn::{DataFormat, DataShape};
 use crate::ops::quant::QParams;
 
 use tract_linalg::frame::mmm::FusedSpec;
-use tract_linalg::frame::PackA;
+use tract_linalg::frame::{BlockSparsePackA, PackA};
 
 use std::iter::Sum;
 
@@ -32,6 +33,9 @@ pub struct ConvUnary {
 
     pub bias: Option<Arc<Tensor>>,
     pub q_params: Option<QParams>,
+    /// Kernel packed ahead of time, see `TypedModel::prepack`.
+    #[new(default)]
+    pub prepacked: Option<PackedA>,
 }
 
 tract_linalg::impl_dyn_hash!(ConvUnary);
@@ -82,6 +86,7 @@ impl ConvUnary {
     fn kernel_as_packed_as<T: Datum + Copy + Zero>(
         &self,
         packer: &PackA<T>,
+        block_sparse: Option<&BlockSparsePackA<T>>,
     ) -> TractResult<ArrayD<Arc<Tensor>>> {
         let kernel = self.kernel_as_group_o_ihw()?;
         let kernel = kernel.to_array_view::<T>()?;
@@ -89,15 +94,17 @@ impl ConvUnary {
             kernel
                 .outer_iter()
                 .map(|subkernel| {
-                    let mut packed = unsafe {
-                        Tensor::uninitialized_aligned::<T>(&[packer.len()], packer.alignment())?
-                    };
-                    packer.pack(
-                        packed.as_slice_mut()?.as_mut_ptr(),
-                        subkernel.as_ptr(),
-                        subkernel.strides()[0],
-                        subkernel.strides()[1],
-                    );
+                    let (len, alignment) = block_sparse
+                        .map(|p| (p.len(), p.alignment()))
+                        .unwrap_or((packer.len(), packer.alignment()));
+                    let mut packed =
+                        unsafe { Tensor::uninitialized_aligned::<T>(&[len], alignment)? };
+                    let (rsa, csa) = (subkernel.strides()[0], subkernel.strides()[1]);
+                    if let Some(block_sparse) = block_sparse {
+                        block_sparse.pack(packed.as_ptr_mut()?, subkernel.as_ptr(), rsa, csa);
+                    } else {
+                        packer.pack(packed.as_ptr_mut()?, subkernel.as_ptr(), rsa, csa);
+                    }
                     Ok(packed.into_arc_tensor())
                 })
                 .collect::<TractResult<Vec<_>>>()?,
@@ -136,27 +143,53 @@ impl ConvUnary {
         name: &str,
         wire: OutletId,
         direct: bool,
-    ) -> TractResult<OutletId> {
+    ) -> TractResult<(OutletId, PackedA)> {
         let a = self.kernel.datum_type();
         let b = model.outlet_fact(wire)?.datum_type;
         if (a, b) == (f32::datum_type(), f32::datum_type()) {
-            return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
-                MMMWrapper::Plain((tract_linalg::ops().mmm_f32)(m, k, n))
+            return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                Ok(MMMWrapper::Plain(tract_linalg::ops().mmm_f32.make(m, k, n)?))
+            });
+        } else if (a, b) == (f64::datum_type(), f64::datum_type()) {
+            return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                Ok(MMMWrapper::Plain(tract_linalg::ops().mmm_f64.make(m, k, n)?))
             });
         } else if (a, b) == (u8::datum_type(), u8::datum_type()) {
-            return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
-                MMMWrapper::Quant((tract_linalg::ops().qmmm_u8_i32)(m, k, n))
+            return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_u8_i32.make(m, k, n)?))
             });
         } else if (a, b) == (i8::datum_type(), i8::datum_type()) {
             if let Some(q) = &self.q_params {
                 if q.c_datum_type == i8::datum_type() {
-                    return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
-                        MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i8)(m, k, n))
+                    return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                        Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_i8.make(m, k, n)?))
                     });
                 }
             } else {
-                return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
-                    MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i32)(m, k, n))
+                return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_i32.make(m, k, n)?))
+                });
+            }
+        } else if (a, b) == (i8::datum_type(), u8::datum_type()) {
+            let c = self.q_params.as_ref().map(|q| q.c_datum_type).unwrap_or(i32::datum_type());
+            if c == u8::datum_type() {
+                return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_u8_u8.make(m, k, n)?))
+                });
+            } else if c == i32::datum_type() {
+                return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_u8_i32.make(m, k, n)?))
+                });
+            }
+        } else if (a, b) == (u8::datum_type(), i8::datum_type()) {
+            let c = self.q_params.as_ref().map(|q| q.c_datum_type).unwrap_or(i32::datum_type());
+            if c == u8::datum_type() {
+                return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_u8_i8_u8.make(m, k, n)?))
+                });
+            } else if c == i32::datum_type() {
+                return self.wire_as_im2col_pair_t(model, name, wire, direct, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_u8_i8_i32.make(m, k, n)?))
                 });
             }
         }
@@ -169,8 +202,8 @@ impl ConvUnary {
         name: &str,
         mut wire: OutletId,
         direct: bool,
-        mmm: impl Fn(usize, usize, usize) -> MMMWrapper<TA, TB, TC, TI>,
-    ) -> TractResult<OutletId>
+        mmm: impl Fn(usize, usize, usize) -> TractResult<MMMWrapper<TA, TB, TC, TI>>,
+    ) -> TractResult<(OutletId, PackedA)>
     where
         TA: Datum + Copy + Zero,
         TB: Datum + Copy + Zero,
@@ -188,7 +221,7 @@ impl ConvUnary {
         let k = self.kernel.len() / self.output_channels();
         let n = geo.output_shape.iter().cloned().product::<usize>();
 
-        let mut mmm = mmm(m, k, n);
+        let mut mmm = mmm(m, k, n)?;
         let (rsc, csc) = match output_shape.fmt {
             DataFormat::NHWC | DataFormat::HWC => (1, self.output_channels() as isize),
             DataFormat::NCHW | DataFormat::CHW => (n as isize, 1),
@@ -196,6 +229,9 @@ impl ConvUnary {
         mmm.as_mmm_mut().c_from_data_and_strides(rsc, csc);
 
         if let Some(q) = self.q_params.as_ref() {
+            if self.group != 1 && q.scale_factor_rows.is_some() {
+                bail!("Per-channel requantization is not supported for grouped convolution")
+            }
             mmm.set_quant_params(q)?;
         }
 
@@ -243,6 +279,34 @@ impl ConvUnary {
             )?[0];
         }
 
+        let reused = match &self.prepacked {
+            Some(prepacked) => prepacked.reuse(mmm.as_mmm_mut(), &[1, self.group])?,
+            None => None,
+        };
+        let packed_as = if let Some(packed_as) = reused {
+            packed_as
+        } else {
+            // only a single group can share the sparsity pattern of the packed kernel
+            let block_sparse = if self.group == 1 {
+                let kernel = self.kernel_as_group_o_ihw()?;
+                let kernel = kernel.to_array_view::<TA>()?;
+                let kernel = kernel.index_axis(Axis(0), 0);
+                let packer = mmm.as_mmm().a_block_sparse_pack(
+                    kernel.as_ptr(),
+                    kernel.strides()[0],
+                    kernel.strides()[1],
+                );
+                Some(packer).filter(|p| p.sparsity() >= matmul::BLOCK_SPARSITY_THRESHOLD)
+            } else {
+                None
+            };
+            if let Some(packer) = &block_sparse {
+                mmm.as_mmm_mut().a_from_block_sparse(packer);
+            }
+            self.kernel_as_packed_as(&mmm.as_mmm().a_pack(), block_sparse.as_ref())?
+        };
+        let packed = PackedA::new(mmm.as_mmm(), packed_as.clone());
+
         let c_prefix_dim_and_stride = if *output_shape.n().unwrap_or(&1) != 1 || self.group != 1 {
             let mut dims = tvec!(self.group as usize);
             let mut strides =
@@ -263,14 +327,14 @@ impl ConvUnary {
                 bc_c_shape: output_shape.shape.clone(),
                 c_fact: TypedFact::dt_shape(TC::datum_type(), &*output_shape.shape)?,
                 c_prefix_dim_and_stride,
-                packed_as: self.kernel_as_packed_as(&mmm.as_mmm().a_pack())?,
+                packed_as,
                 fused_ops: self.bias_as_non_linear()?,
                 mmm,
             },
             &[wire],
         )?[0];
 
-        Ok(wire)
+        Ok((wire, packed))
     }
 
     pub fn to_depth_wise<T>(&self, input_full_shape: &[usize]) -> TractResult<Box<dyn TypedOp>>
@@ -359,6 +423,12 @@ impl ConvUnary {
                 self.q_params.as_ref().map(|qp| qp.c_datum_type).unwrap_or(input_fact.datum_type);
             let must_split_quant =
                 self.bias.is_some() && self.bias.as_ref().unwrap().datum_type() != output_type;
+            if must_split_quant
+                && self.q_params.as_ref().map(|qp| qp.scale_factor_rows.is_some()) == Some(true)
+            {
+                // per-channel requantization can not be expressed as a quantize_linear
+                return Ok(None);
+            }
             let q_params = if must_split_quant {
                 Some(QParams {
                     c_datum_type: self.bias.as_ref().unwrap().datum_type(),
@@ -369,7 +439,10 @@ impl ConvUnary {
             } else {
                 self.q_params.clone()
             };
-            let op = MatMulUnary::new(a, a_trans, trans_data, trans_data, q_params);
+            let op = MatMulUnary {
+                prepacked: self.prepacked.clone(),
+                ..MatMulUnary::new(a, a_trans, trans_data, trans_data, q_params)
+            };
             wire = patch.wire_node(&*node.name, op, &[wire])?[0];
             if let Some(b) = &self.bias {
                 let mut bias_shape = tvec!(1; input_shape.rank());
@@ -448,7 +521,8 @@ impl EvalOp for ConvUnary {
         let mut model = TypedModel::default();
         let dt = inputs[0].datum_type();
         let wire = model.add_source("source", TypedFact::dt_shape(dt, inputs[0].shape())?)?;
-        let wire = unsafe { self.wire_as_im2col_pair(&mut model, "im2col-adhoc", wire, false)? };
+        let (wire, _) =
+            unsafe { self.wire_as_im2col_pair(&mut model, "im2col-adhoc", wire, false)? };
         model.set_output_outlets(&[wire])?;
         let plan = SimplePlan::new(model)?;
         plan.run(inputs.into_iter().map(|t| t.into_tensor()).collect())
@@ -622,6 +696,8 @@ impl TypedOp for ConvUnary {
             group: self.group,
             bias: self.bias.clone(),
             q_params: self.q_params.clone(),
+            // moving spatial axes reorders the packed kernel
+            prepacked: if let Move(..) = change { None } else { self.prepacked.clone() },
         };
         return Ok(Some(AxisChangeConsequence {
             substitute_op: Some(Box::new(new_op)),
@@ -634,6 +710,42 @@ impl TypedOp for ConvUnary {
         model: &TypedModel,
         node: &TypedNode,
     ) -> TractResult<Option<TypedModelPatch>> {
+        Ok(self.codegen_and_pack(model, node)?.map(|(patch, _)| patch))
+    }
+
+    fn prepack(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<Box<dyn TypedOp>>> {
+        use crate::ops::matmul::MatMulUnary;
+        let packed = match self.codegen_and_pack(model, node)? {
+            Some((_, Some(packed))) => Some(packed),
+            // 1x1 kernels go through MatMulUnary, which packs at its own codegen
+            Some((patch, None)) => {
+                let mut packed = None;
+                for mm in patch.nodes().iter().filter(|n| n.op_is::<MatMulUnary>()) {
+                    let op = mm.op_as::<MatMulUnary>().unwrap();
+                    packed = op.codegen_and_pack(&patch, mm)?.map(|(_, packed)| packed);
+                }
+                packed
+            }
+            None => None,
+        };
+        Ok(packed.map(|p| Box::new(ConvUnary { prepacked: Some(p), ..self.clone() }) as _))
+    }
+
+    as_op!();
+}
+
+impl ConvUnary {
+    /// Codegen patch, along with the kernel as it was packed for it, if the
+    /// patch packs it.
+    fn codegen_and_pack(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<(TypedModelPatch, Option<PackedA>)>> {
         let full_input_shape = model.outlet_fact(node.inputs[0])?.shape.to_tvec();
         let input_fact = model.outlet_fact(node.inputs[0])?;
         let input_shape = self.pool_spec.data_format.shape(&full_input_shape)?;
@@ -674,13 +786,16 @@ impl TypedOp for ConvUnary {
                         .broadcast_into_rank(operating_rank)?;
                     wire = patch.wire_node(
                         &*node.name,
-                        MatMulUnary::new(
-                            kernel.into_arc_tensor(),
-                            self.kernel_fmt == KernelFormat::HWIO,
-                            input_c_is_last,
-                            input_c_is_last,
-                            self.q_params.clone(),
-                        ),
+                        MatMulUnary {
+                            prepacked: self.prepacked.clone(),
+                            ..MatMulUnary::new(
+                                kernel.into_arc_tensor(),
+                                self.kernel_fmt == KernelFormat::HWIO,
+                                input_c_is_last,
+                                input_c_is_last,
+                                self.q_params.clone(),
+                            )
+                        },
                         &[wire],
                     )?[0];
                     if let Some(ref bias) = self.bias {
@@ -708,7 +823,7 @@ impl TypedOp for ConvUnary {
                         &[wire],
                     )?[0];
                     patch.shunt_outside(model, OutletId::new(node.id, 0), wire)?;
-                    return Ok(Some(patch));
+                    return Ok(Some((patch, None)));
                 } else if should_use_direct(
                     &self.pool_spec.data_format.shape(shape.to_owned())?,
                     &self.pool_spec,
@@ -716,28 +831,29 @@ impl TypedOp for ConvUnary {
                 ) {
                     let mut patch = TypedModelPatch::default();
                     let wire = patch.tap_model(model, node.inputs[0])?;
-                    let wire = self.wire_as_im2col_pair(&mut patch, &*node.name, wire, true)?;
+                    let (wire, packed) =
+                        self.wire_as_im2col_pair(&mut patch, &*node.name, wire, true)?;
                     patch.shunt_outside(model, OutletId::new(node.id, 0), wire)?;
-                    return Ok(Some(patch));
+                    return Ok(Some((patch, Some(packed))));
                 } else if self.group != 1 && self.group == self.output_channels() {
-                    return Ok(Some(TypedModelPatch::single_unary_op(
+                    let patch = TypedModelPatch::single_unary_op(
                         model,
                         node,
                         dispatch_floatlike!(Self::to_depth_wise(dt)(self, &shape))?,
-                    )?));
+                    )?;
+                    return Ok(Some((patch, None)));
                 } else {
                     let mut patch = TypedModelPatch::default();
                     let wire = patch.tap_model(model, node.inputs[0])?;
-                    let wire = self.wire_as_im2col_pair(&mut patch, &*node.name, wire, false)?;
+                    let (wire, packed) =
+                        self.wire_as_im2col_pair(&mut patch, &*node.name, wire, false)?;
                     patch.shunt_outside(model, OutletId::new(node.id, 0), wire)?;
-                    return Ok(Some(patch));
+                    return Ok(Some((patch, Some(packed))));
                 }
             }
         }
         Ok(None)
     }
-
-    as_op!();
 }
 
 fn should_use_direct(input_shape: &DataShape, pool_spec: &PoolSpec, group: usize) -> bool {
@@ -762,6 +878,23 @@ mod test {
     use crate::ops::cnn::PaddingSpec;
     use DataFormat::{HWC, NHWC};
 
+    #[test]
+    fn eval_f64() -> TractResult<()> {
+        let conv = ConvUnary {
+            pool_spec: PoolSpec::new(HWC, tvec!(3), PaddingSpec::Valid, None, None, Some(1)),
+            kernel_fmt: KernelFormat::OIHW,
+            kernel: rctensor3(&[[[1f64, 1.0, 1.0], [1.0, 1.0, 1.0]]]),
+            group: 1,
+            bias: None,
+            q_params: None,
+            prepacked: None,
+        };
+        let input = tensor2(&[[0f64, 1.0], [2.0, 3.0], [4.0, 5.0], [6.0, 7.0]]);
+        let output = conv.eval(tvec!(input.into_arc_tensor()))?;
+        assert_eq!(*output[0], tensor2(&[[15f64], [27.0]]));
+        Ok(())
+    }
+
     #[test]
     fn conv_vs_direct_arm_ml_kws_cnn_m_0() {
         let input = NHWC.from_n_c_hw(1, 1, &[49, 10]).unwrap();
@@ -0,0 +1,418 @@
+use crate::internal::*;
+use crate::ops::cnn::KernelFormat;
+use crate::ops::matmul::MatMulUnary;
+use crate::ops::nn::DataFormat;
+use ndarray::*;
+use num_traits::Float;
+
+/// Transposed convolution, a.k.a. the gradient of a convolution with respect
+/// to its input.
+///
+/// Inputs are the data and the kernel. The kernel uses the layout of the
+/// matching forward convolution: its O axis is the deconvolution input
+/// channels, I its output channels. Each input frame is scattered at
+/// `stride * x + dilation * k - pads_before`, and the result is cropped to
+/// `output_shape` (spatial dimensions only).
+///
+/// With a constant kernel, it is decluttered to a MatMulUnary computing the
+/// contribution of each input frame to each output channel and kernel
+/// position, followed by a DeconvSum adding them up in the output.
+#[derive(Debug, Clone, new, Hash)]
+pub struct Deconv {
+    pub data_format: DataFormat,
+    pub kernel_format: KernelFormat,
+    pub strides: TVec<usize>,
+    pub dilations: TVec<usize>,
+    pub pads_before: TVec<usize>,
+    pub output_shape: TVec<usize>,
+}
+tract_linalg::impl_dyn_hash!(Deconv);
+
+impl Deconv {
+    /// Kernel as a OIHW view.
+    fn kernel_oihw<'a, T: Datum>(&self, kernel: &'a Tensor) -> TractResult<ArrayViewD<'a, T>> {
+        let kernel = kernel.to_array_view::<T>()?;
+        Ok(match self.kernel_format {
+            KernelFormat::OIHW => kernel,
+            KernelFormat::HWIO => {
+                let rank = kernel.ndim();
+                let mut axes = vec![rank - 1, rank - 2];
+                axes.extend(0..rank - 2);
+                kernel.permuted_axes(axes)
+            }
+        })
+    }
+
+    fn eval_t<T: Datum + Float>(&self, input: &Tensor, kernel: &Tensor) -> TractResult<Tensor> {
+        let input_shape = self.data_format.shape(input.shape())?;
+        let kernel = self.kernel_oihw::<T>(kernel)?;
+        let input = input.to_array_view::<T>()?;
+        let geo_rank = input_shape.hw_rank();
+        if kernel.ndim() != geo_rank + 2 || kernel.shape()[0] != *input_shape.c() {
+            bail!("Kernel {:?} does not match input {:?}", kernel.shape(), input_shape.shape)
+        }
+        let output_channels = kernel.shape()[1];
+        let output_shape = self.data_format.from_n_c_hw(
+            input_shape.n().cloned().unwrap_or(1),
+            output_channels,
+            &*self.output_shape,
+        )?;
+        let mut output = ArrayD::<T>::zeros(&*output_shape.shape);
+        let mut output_coords: TVec<usize> = tvec!(0; output_shape.rank());
+        let mut kernel_coords: TVec<usize> = tvec!(0; kernel.ndim());
+        for (input_coords, &x) in input.indexed_iter() {
+            let input_coords = input_coords.slice();
+            let c = input_coords[input_shape.c_axis()];
+            if let Some(n) = input_shape.n_axis() {
+                output_coords[n] = input_coords[n];
+            }
+            kernel_coords[0] = c;
+            'kernel: for k in indices(&kernel.shape()[2..]) {
+                for geo in 0..geo_rank {
+                    let y = (input_coords[input_shape.hw_axes()][geo] * self.strides[geo]
+                        + k[geo] * self.dilations[geo]) as isize
+                        - self.pads_before[geo] as isize;
+                    if y < 0 || y >= self.output_shape[geo] as isize {
+                        continue 'kernel;
+                    }
+                    output_coords[output_shape.hw_axes()][geo] = y as usize;
+                    kernel_coords[2 + geo] = k[geo];
+                }
+                for co in 0..output_channels {
+                    output_coords[output_shape.c_axis()] = co;
+                    kernel_coords[1] = co;
+                    let y = &mut output[&*output_coords];
+                    *y = *y + x * kernel[&*kernel_coords];
+                }
+            }
+        }
+        Ok(output.into_tensor())
+    }
+}
+
+impl Op for Deconv {
+    fn name(&self) -> Cow<str> {
+        "Deconv".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![
+            format!("Data format: {:?}, kernel format: {:?}", self.data_format, self.kernel_format),
+            format!(
+                "Strides: {:?}, dilations: {:?}, pads before: {:?}, output: {:?}",
+                self.strides, self.dilations, self.pads_before, self.output_shape
+            ),
+        ])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for Deconv {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (input, kernel) = args_2!(inputs);
+        let output =
+            dispatch_floatlike!(Self::eval_t(input.datum_type())(self, &*input, &*kernel))?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for Deconv {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let input_shape = self.data_format.shape(inputs[0].shape.to_tvec())?;
+        let output_channels = match self.kernel_format {
+            KernelFormat::OIHW => inputs[1].shape[1].clone(),
+            KernelFormat::HWIO => inputs[1].shape[inputs[1].rank() - 2].clone(),
+        };
+        let output_spatial: TVec<TDim> = self.output_shape.iter().map(|d| d.to_dim()).collect();
+        let output_shape = self.data_format.from_n_c_hw(
+            input_shape.n().cloned().unwrap_or(1.into()),
+            output_channels,
+            &*output_spatial,
+        )?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*output_shape.shape)?))
+    }
+
+    fn invariants(&self, _model: &TypedModel, _node: &TypedNode) -> TractResult<Invariants> {
+        if self.data_format.has_n() {
+            Ok(tvec!(AxisInfo {
+                inputs: tvec!(Some(0), None),
+                outputs: tvec!(Some(0)),
+                period: 1,
+                disposable: true,
+            })
+            .into())
+        } else {
+            Ok(Invariants::none())
+        }
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        let input_fact = model.outlet_fact(node.inputs[0])?;
+        let kernel = if let Some(kernel) = &model.outlet_fact(node.inputs[1])?.konst {
+            kernel.clone()
+        } else {
+            return Ok(None);
+        };
+        let dt = input_fact.datum_type;
+        if (dt != f32::datum_type() && dt != f64::datum_type()) || kernel.datum_type() != dt {
+            return Ok(None);
+        }
+        let input_shape = self.data_format.shape(input_fact.shape.to_tvec())?;
+        let geo_rank = input_shape.hw_rank();
+        let input_spatial: TVec<usize> =
+            match input_shape.hw_dims().iter().map(|d| d.to_usize()).collect::<TractResult<_>>() {
+                Ok(spatial) => spatial,
+                Err(_) => return Ok(None),
+            };
+        if geo_rank == 0 || kernel.rank() != geo_rank + 2 {
+            return Ok(None);
+        }
+        // kernel as [input channels, output channels, spatial...]
+        let kernel = match self.kernel_format {
+            KernelFormat::OIHW => kernel.into_tensor(),
+            KernelFormat::HWIO => {
+                let mut axes = vec![geo_rank + 1, geo_rank];
+                axes.extend(0..geo_rank);
+                kernel.into_tensor().permute_axes(&axes)?
+            }
+        };
+        let (input_channels, output_channels) = (kernel.shape()[0], kernel.shape()[1]);
+        if input_shape.c().to_usize()? != input_channels {
+            bail!("Kernel {:?} does not match input {:?}", kernel.shape(), input_shape.shape)
+        }
+        let kernel_shape: TVec<usize> = kernel.shape()[2..].into();
+        let kernel_len = kernel_shape.iter().product::<usize>();
+        let mut a = kernel
+            .into_shape(&[input_channels, output_channels * kernel_len])?
+            .permute_axes(&[1, 0])?;
+        if input_shape.n_axis().is_some() {
+            a.insert_axis(0)?;
+        }
+
+        let mut patch = TypedModelPatch::default();
+        let mut wire = patch.tap_model(model, node.inputs[0])?;
+        if geo_rank > 1 {
+            let input_len = input_spatial.iter().product::<usize>();
+            wire = patch.wire_node(
+                format!("{}.flatten", node.name),
+                AxisOp::Reshape(
+                    input_shape.h_axis(),
+                    input_shape.hw_dims().into(),
+                    tvec!(input_len.to_dim()),
+                ),
+                &[wire],
+            )?[0];
+        }
+        let channels_last = input_shape.c_axis() == input_shape.rank() - 1;
+        wire = patch.wire_node(
+            format!("{}.matmul", node.name),
+            MatMulUnary::new(a.into_arc_tensor(), false, channels_last, false, None),
+            &[wire],
+        )?[0];
+        let sum = DeconvSum::new(self.clone(), output_channels, kernel_shape, input_spatial);
+        wire = patch.wire_node(&*node.name, sum, &[wire])?[0];
+        patch.shunt_outside(model, OutletId::new(node.id, 0), wire)?;
+        Ok(Some(patch))
+    }
+}
+
+/// Second half of a decluttered Deconv (col2im).
+///
+/// Its input is the product of the kernel and the input frames, shaped as
+/// `[N, output_channels * kernel size, input size]` (without N if the data
+/// format has none). The value for output channel `co`, kernel position `k`
+/// and input frame `x` is added to the output of channel `co` at
+/// `stride * x + dilation * k - pads_before`.
+#[derive(Debug, Clone, new, Hash)]
+pub struct DeconvSum {
+    pub deconv: Deconv,
+    pub output_channels: usize,
+    pub kernel_shape: TVec<usize>,
+    pub input_shape: TVec<usize>,
+}
+tract_linalg::impl_dyn_hash!(DeconvSum);
+
+impl DeconvSum {
+    fn eval_t<T: Datum + Float>(&self, columns: &Tensor) -> TractResult<Tensor> {
+        let columns = columns.to_array_view::<T>()?;
+        let n = if self.deconv.data_format.has_n() { columns.shape()[0] } else { 1 };
+        let kernel_len = self.kernel_shape.iter().product::<usize>();
+        let input_len = self.input_shape.iter().product::<usize>();
+        let columns = columns.into_shape((n, self.output_channels, kernel_len, input_len))?;
+        let output_shape = self.deconv.data_format.from_n_c_hw(
+            n,
+            self.output_channels,
+            &*self.deconv.output_shape,
+        )?;
+        let mut output = ArrayD::<T>::zeros(&*output_shape.shape);
+        let mut coords: TVec<usize> = tvec!(0; output_shape.rank());
+        for (kix, k) in indices(&*self.kernel_shape).into_iter().enumerate() {
+            'input: for (xix, x) in indices(&*self.input_shape).into_iter().enumerate() {
+                for geo in 0..self.kernel_shape.len() {
+                    let y = (x[geo] * self.deconv.strides[geo]
+                        + k[geo] * self.deconv.dilations[geo]) as isize
+                        - self.deconv.pads_before[geo] as isize;
+                    if y < 0 || y >= self.deconv.output_shape[geo] as isize {
+                        continue 'input;
+                    }
+                    coords[output_shape.hw_axes()][geo] = y as usize;
+                }
+                for i in 0..n {
+                    if let Some(n_axis) = output_shape.n_axis() {
+                        coords[n_axis] = i;
+                    }
+                    for co in 0..self.output_channels {
+                        coords[output_shape.c_axis()] = co;
+                        let y = &mut output[&*coords];
+                        *y = *y + columns[(i, co, kix, xix)];
+                    }
+                }
+            }
+        }
+        Ok(output.into_tensor())
+    }
+}
+
+impl Op for DeconvSum {
+    fn name(&self) -> Cow<str> {
+        "DeconvSum".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        let mut info = self.deconv.info()?;
+        info.push(format!("Kernel: {:?}, input: {:?}", self.kernel_shape, self.input_shape));
+        Ok(info)
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for DeconvSum {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let columns = args_1!(inputs);
+        let output = dispatch_floatlike!(Self::eval_t(columns.datum_type())(self, &*columns))?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for DeconvSum {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let n = if self.deconv.data_format.has_n() { inputs[0].shape[0].clone() } else { 1.into() };
+        let output_spatial: TVec<TDim> =
+            self.deconv.output_shape.iter().map(|d| d.to_dim()).collect();
+        let output_shape = self.deconv.data_format.from_n_c_hw(
+            n,
+            self.output_channels.to_dim(),
+            &*output_spatial,
+        )?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*output_shape.shape)?))
+    }
+
+    fn invariants(&self, _model: &TypedModel, _node: &TypedNode) -> TractResult<Invariants> {
+        if self.deconv.data_format.has_n() {
+            Ok(tvec!(AxisInfo {
+                inputs: tvec!(Some(0)),
+                outputs: tvec!(Some(0)),
+                period: 1,
+                disposable: true,
+            })
+            .into())
+        } else {
+            Ok(Invariants::none())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stride_2() {
+        let op = Deconv::new(
+            DataFormat::NCHW,
+            KernelFormat::OIHW,
+            tvec!(2),
+            tvec!(1),
+            tvec!(0),
+            tvec!(5),
+        );
+        let input = rctensor3(&[[[1f32, 2.]]]);
+        let kernel = rctensor3(&[[[1f32, 10., 100.]]]);
+        let output = op.eval(tvec!(input, kernel)).unwrap();
+        assert_eq!(*output[0], tensor3(&[[[1f32, 10., 102., 20., 200.]]]));
+    }
+
+    fn lowered_matches_direct(op: Deconv, input: Tensor, kernel: Tensor) -> TractResult<()> {
+        let expected = op.eval(tvec!(input.clone().into_arc_tensor(), kernel.clone().into()))?;
+        let mut model = TypedModel::default();
+        let source =
+            model.add_source("input", TypedFact::dt_shape(f32::datum_type(), input.shape())?)?;
+        let kernel = model.add_const("kernel", kernel)?;
+        let output = model.wire_node("deconv", op, &[source, kernel])?;
+        model.set_output_outlets(&output)?;
+        let model = model.declutter()?;
+        assert!(model.nodes().iter().all(|n| !n.op_is::<Deconv>()));
+        assert!(model.nodes().iter().any(|n| n.op_is::<DeconvSum>()));
+        let output = SimplePlan::new(&model)?.run(tvec!(input))?;
+        output[0].close_enough(&expected[0], true)
+    }
+
+    #[test]
+    fn lowered_stride_2() -> TractResult<()> {
+        let op = Deconv::new(
+            DataFormat::NCHW,
+            KernelFormat::OIHW,
+            tvec!(2),
+            tvec!(1),
+            tvec!(0),
+            tvec!(5),
+        );
+        lowered_matches_direct(op, tensor3(&[[[1f32, 2.]]]), tensor3(&[[[1f32, 10., 100.]]]))
+    }
+
+    #[test]
+    fn lowered_nhwc_hwio_2d() -> TractResult<()> {
+        // input 1x3x2x2 (NHWC), kernel 2x3x3x2 (HWIO: 3 output channels, 2 input channels)
+        let op = Deconv::new(
+            DataFormat::NHWC,
+            KernelFormat::HWIO,
+            tvec!(2, 1),
+            tvec!(1, 2),
+            tvec!(1, 0),
+            tvec!(5, 6),
+        );
+        let input = Tensor::from(
+            tract_ndarray::Array::from_shape_fn((1, 3, 2, 2), |(_, h, w, c)| {
+                (h * 4 + w * 2 + c) as f32
+            })
+            .into_dyn(),
+        );
+        let kernel = Tensor::from(
+            tract_ndarray::Array::from_shape_fn((2, 3, 3, 2), |(h, w, o, i)| {
+                (h * 18 + w * 6 + o * 2 + i) as f32 / 10.
+            })
+            .into_dyn(),
+        );
+        lowered_matches_direct(op, input, kernel)
+    }
+}
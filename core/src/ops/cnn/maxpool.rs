@@ -59,6 +59,10 @@ impl TypedOp for MaxPool {
         Ok(facts)
     }
 
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        self.pool_spec.cost(inputs, Cost::Comparison(inputs[0].datum_type))
+    }
+
     fn declutter(
         &self,
         model: &TypedModel,
@@ -190,4 +194,10 @@ impl TypedOp for MaxPoolFixed {
         }
         Ok(facts)
     }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let outputs = self.output_shape.shape.iter().product::<usize>();
+        let taps = self.patch.spec.kernel_shape.iter().product::<usize>();
+        Ok(tvec!((Cost::Comparison(inputs[0].datum_type), (outputs * taps).to_dim())))
+    }
 }
@@ -1,5 +1,6 @@
 mod sumpool;
 pub mod conv;
+mod deconv;
 mod maxpool;
 mod padding;
 mod patch_axis;
@@ -8,6 +9,7 @@ pub mod pools;
 
 pub use self::sumpool::SumPool;
 pub use self::conv::{ConvUnary, KernelFormat};
+pub use self::deconv::{Deconv, DeconvSum};
 pub use self::maxpool::MaxPool;
 pub use self::padding::PaddingSpec;
 pub use self::patch_axis::PatchAxis;
@@ -96,6 +96,11 @@ impl PoolSpec {
         Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*oshape.shape)?))
     }
 
+    /// Counts one `per_tap` operation for each kernel tap of each output value.
+    pub fn cost(&self, inputs: &[&TypedFact], per_tap: Cost) -> TractResult<TVec<(Cost, TDim)>> {
+        let outputs: TDim = self.output_facts(inputs)?[0].shape.iter().maybe_product()?;
+        Ok(tvec!((per_tap, outputs * self.kernel_shape.iter().product::<usize>())))
+    }
 
     pub fn dispose_n_axis(&self) -> PoolSpec {
         PoolSpec { data_format: self.data_format.dispose_n_axis(), ..self.clone() }
@@ -66,6 +66,16 @@ impl TypedOp for SumPool {
         self.pool_spec.output_facts(inputs)
     }
 
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let dt = inputs[0].datum_type;
+        let mut cost = self.pool_spec.cost(inputs, Cost::ElementWise(dt))?;
+        if self.normalize {
+            let outputs = self.output_facts(inputs)?[0].shape.iter().maybe_product()?;
+            cost.push((Cost::FMA(dt), outputs));
+        }
+        Ok(cost)
+    }
+
     fn codegen(
         &self,
         model: &TypedModel,
@@ -173,5 +183,16 @@ impl TypedOp for SumPoolFixed {
         Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*self.output_shape.shape)?))
     }
 
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let dt = inputs[0].datum_type;
+        let outputs = self.output_shape.shape.iter().product::<usize>();
+        let taps = self.patch.spec.kernel_shape.iter().product::<usize>();
+        let mut cost = tvec!((Cost::ElementWise(dt), (outputs * taps).to_dim()));
+        if self.normalize {
+            cost.push((Cost::FMA(dt), outputs.to_dim()));
+        }
+        Ok(cost)
+    }
+
     as_op!();
 }
@@ -0,0 +1,416 @@
+use std::fmt;
+use std::ops::{Add, Mul};
+
+use crate::internal::*;
+use crate::ops::change_axes::perm_to_ops;
+use crate::ops::matmul::MatMul;
+use itertools::Itertools;
+use ndarray::*;
+use num_traits::Zero;
+
+/// An einsum expression, with ellipsis already expanded to explicit axes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Expr {
+    pub inputs: TVec<TVec<char>>,
+    pub output: TVec<char>,
+}
+
+impl Expr {
+    /// Parse an expression like "bij,bjk->bik". Whitespace is ignored, the
+    /// output is inferred if "->" is missing, and ellipsis are expanded
+    /// according to the input ranks.
+    pub fn parse(expr: &str, ranks: &[usize]) -> TractResult<Expr> {
+        let expr: String = expr.chars().filter(|c| !c.is_whitespace()).collect();
+        let (inputs, output) = match expr.find("->") {
+            Some(ix) => (&expr[..ix], Some(&expr[ix + 2..])),
+            None => (&*expr, None),
+        };
+        let inputs: Vec<&str> = inputs.split(',').collect();
+        if inputs.len() != ranks.len() {
+            bail!("Einsum expression {} expects {} inputs, got {}", expr, inputs.len(), ranks.len())
+        }
+        let used: Vec<char> = expr.chars().filter(|c| c.is_alphabetic()).collect();
+        let mut spare = ('A'..='Z').rev().chain(('a'..='z').rev()).filter(|c| !used.contains(c));
+        let mut ellipsis_rank = 0;
+        for (input, rank) in inputs.iter().zip(ranks) {
+            if input.contains("...") {
+                let explicit = input.len() - 3;
+                if explicit > *rank {
+                    bail!("Einsum term {} does not match rank {}", input, rank)
+                }
+                ellipsis_rank = ellipsis_rank.max(rank - explicit);
+            }
+        }
+        let ellipsis: TVec<char> = (0..ellipsis_rank)
+            .map(|_| spare.next())
+            .collect::<Option<_>>()
+            .ok_or_else(|| format_err!("Too many axes in einsum expression {}", expr))?;
+        let expand = |term: &str, rank: usize| -> TractResult<TVec<char>> {
+            let mut axes = tvec!();
+            if let Some(ix) = term.find("...") {
+                let explicit = term.len() - 3;
+                axes.extend(term[..ix].chars());
+                axes.extend(ellipsis[ellipsis_rank - (rank - explicit)..].iter().cloned());
+                axes.extend(term[ix + 3..].chars());
+            } else {
+                axes.extend(term.chars());
+            }
+            if let Some(c) = axes.iter().find(|c| !c.is_alphabetic()) {
+                bail!("Invalid character {:?} in einsum expression {}", c, expr)
+            }
+            Ok(axes)
+        };
+        let inputs = inputs
+            .iter()
+            .zip(ranks)
+            .map(|(term, rank)| {
+                let axes = expand(term, *rank)?;
+                if axes.len() != *rank {
+                    bail!("Einsum term {} does not match rank {}", term, rank)
+                }
+                Ok(axes)
+            })
+            .collect::<TractResult<TVec<_>>>()?;
+        let output = if let Some(output) = output {
+            expand(output, ellipsis_rank + output.len().saturating_sub(3))?
+        } else {
+            let all: Vec<char> = inputs.iter().flat_map(|i| i.iter().cloned()).collect();
+            let mut output: TVec<char> = ellipsis.clone();
+            output.extend(
+                all.iter()
+                    .filter(|c| !ellipsis.contains(c) && all.iter().filter(|x| x == c).count() == 1)
+                    .cloned()
+                    .sorted(),
+            );
+            output
+        };
+        if let Some(c) = output.iter().find(|c| !inputs.iter().any(|i| i.contains(c))) {
+            bail!("Output axis {} does not appear in inputs of {}", c, expr)
+        }
+        Ok(Expr { inputs, output })
+    }
+
+    /// All the axes of the expression, in order of appearance in the inputs.
+    pub fn axes(&self) -> TVec<char> {
+        self.inputs.iter().flat_map(|i| i.iter().cloned()).unique().collect()
+    }
+
+    fn axis_dims<D: DimLike>(&self, shapes: &[&[D]]) -> TractResult<TVec<D>> {
+        self.axes()
+            .iter()
+            .map(|axis| {
+                let mut dim = D::from(1);
+                for (input, shape) in self.inputs.iter().zip(shapes) {
+                    for (c, d) in input.iter().zip(shape.iter()) {
+                        if c == axis && *d != D::from(1) {
+                            if dim != D::from(1) && dim != *d {
+                                bail!("Inconsistent dimensions for axis {}: {} and {}", c, dim, d)
+                            }
+                            dim = d.clone();
+                        }
+                    }
+                }
+                Ok(dim)
+            })
+            .collect()
+    }
+
+    pub fn output_shape<D: DimLike>(&self, shapes: &[&[D]]) -> TractResult<TVec<D>> {
+        let axes = self.axes();
+        let dims = self.axis_dims(shapes)?;
+        Ok(self
+            .output
+            .iter()
+            .map(|c| dims[axes.iter().position(|a| a == c).unwrap()].clone())
+            .collect())
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let inputs = self.inputs.iter().map(|i| i.iter().collect::<String>()).join(",");
+        write!(fmt, "{}->{}", inputs, self.output.iter().collect::<String>())
+    }
+}
+
+/// Generic tensor contraction, described by an einsum expression.
+#[derive(Debug, Clone, new, Hash)]
+pub struct EinSum {
+    pub expr: Expr,
+}
+tract_linalg::impl_dyn_hash!(EinSum);
+
+impl Op for EinSum {
+    fn name(&self) -> Cow<str> {
+        "EinSum".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![self.expr.to_string()])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EinSum {
+    fn eval_t<T: Datum + Copy + Zero + Add<Output = T> + Mul<Output = T>>(
+        &self,
+        inputs: &[Arc<Tensor>],
+    ) -> TractResult<Tensor> {
+        let shapes: TVec<&[usize]> = inputs.iter().map(|i| i.shape()).collect();
+        let axes = self.expr.axes();
+        let dims = self.expr.axis_dims(&shapes)?;
+        let position = |c: &char| axes.iter().position(|a| a == c).unwrap();
+        let views =
+            inputs.iter().map(|i| i.to_array_view::<T>()).collect::<TractResult<TVec<_>>>()?;
+        let input_axes: TVec<TVec<usize>> =
+            self.expr.inputs.iter().map(|i| i.iter().map(position).collect()).collect();
+        let output_axes: TVec<usize> = self.expr.output.iter().map(position).collect();
+        let output_shape: TVec<usize> = output_axes.iter().map(|&a| dims[a]).collect();
+        let mut output = ArrayD::<T>::zeros(&*output_shape);
+        let mut output_coords = tvec!(0; output_axes.len());
+        let mut input_coords: TVec<TVec<usize>> =
+            input_axes.iter().map(|i| tvec!(0; i.len())).collect();
+        for coords in indices(&*dims) {
+            let mut product = T::zero();
+            for (ix, view) in views.iter().enumerate() {
+                for (axis, &letter) in input_axes[ix].iter().enumerate() {
+                    input_coords[ix][axis] =
+                        if view.shape()[axis] == 1 { 0 } else { coords[letter] };
+                }
+                let value = view[&*input_coords[ix]];
+                product = if ix == 0 { value } else { product * value };
+            }
+            for (axis, &letter) in output_axes.iter().enumerate() {
+                output_coords[axis] = coords[letter];
+            }
+            let sum = &mut output[&*output_coords];
+            *sum = *sum + product;
+        }
+        Ok(output.into_tensor())
+    }
+
+    /// Rewrite a two-input contraction over a single axis (and batch axes) as
+    /// a MatMul, transposing the operands and the result as needed.
+    fn declutter_as_matmul(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if node.inputs.len() != 2 {
+            return Ok(None);
+        }
+        let a_fact = model.outlet_fact(node.inputs[0])?;
+        let b_fact = model.outlet_fact(node.inputs[1])?;
+        if !a_fact.datum_type.is_float() || a_fact.datum_type != b_fact.datum_type {
+            return Ok(None);
+        }
+        let (a, b, c) = (&*self.expr.inputs[0], &*self.expr.inputs[1], &*self.expr.output);
+        if [a, b, c].iter().any(|term| term.iter().unique().count() != term.len()) {
+            return Ok(None);
+        }
+        let batch: TVec<char> =
+            c.iter().filter(|x| a.contains(x) && b.contains(x)).cloned().collect();
+        let m: TVec<char> = c.iter().filter(|x| a.contains(x) && !b.contains(x)).cloned().collect();
+        let n: TVec<char> = c.iter().filter(|x| !a.contains(x) && b.contains(x)).cloned().collect();
+        let k: TVec<char> = a.iter().filter(|x| b.contains(x) && !c.contains(x)).cloned().collect();
+        // axes summed over in a single input are not a matrix product
+        if k.len() != 1
+            || m.len() > 1
+            || n.len() > 1
+            || a.len() != batch.len() + m.len() + 1
+            || b.len() != batch.len() + n.len() + 1
+        {
+            return Ok(None);
+        }
+        let position = |term: &[char], x: char| term.iter().position(|y| *y == x).unwrap();
+        if a_fact.shape[position(a, k[0])] != b_fact.shape[position(b, k[0])] {
+            return Ok(None);
+        }
+        let mut patch = TypedModelPatch::default();
+        let permute = |patch: &mut TypedModelPatch,
+                       name: &str,
+                       mut wire: OutletId,
+                       from: &[char],
+                       to: &[char]|
+         -> TractResult<OutletId> {
+            let perm: TVec<usize> = to.iter().map(|x| position(from, *x)).collect();
+            for (ix, op) in perm_to_ops(&perm).into_iter().enumerate() {
+                wire = patch.wire_node(format!("{}.{}-{}", name, op.name(), ix), op, &[wire])?[0];
+            }
+            Ok(wire)
+        };
+        let mut a_axes = batch.clone();
+        a_axes.extend(m.iter().cloned());
+        a_axes.push(k[0]);
+        let wire = patch.tap_model(model, node.inputs[0])?;
+        let mut wire_a = permute(&mut patch, &format!("{}.a", node.name), wire, a, &*a_axes)?;
+        if m.len() == 0 {
+            wire_a = patch.wire_node(
+                format!("{}.a.m", node.name),
+                AxisOp::Add(batch.len()),
+                &[wire_a],
+            )?[0];
+        }
+        let mut b_axes = batch.clone();
+        b_axes.push(k[0]);
+        b_axes.extend(n.iter().cloned());
+        let wire = patch.tap_model(model, node.inputs[1])?;
+        let mut wire_b = permute(&mut patch, &format!("{}.b", node.name), wire, b, &*b_axes)?;
+        if n.len() == 0 {
+            wire_b = patch.wire_node(
+                format!("{}.b.n", node.name),
+                AxisOp::Add(batch.len() + 1),
+                &[wire_b],
+            )?[0];
+        }
+        let mut wire = patch.wire_node(
+            format!("{}.matmul", node.name),
+            MatMul::default(),
+            &[wire_a, wire_b],
+        )?[0];
+        if n.len() == 0 {
+            wire = patch.wire_node(
+                format!("{}.c.n", node.name),
+                AxisOp::Rm(batch.len() + 1),
+                &[wire],
+            )?[0];
+        }
+        if m.len() == 0 {
+            wire =
+                patch.wire_node(format!("{}.c.m", node.name), AxisOp::Rm(batch.len()), &[wire])?[0];
+        }
+        let mut c_axes = batch;
+        c_axes.extend(m.into_iter());
+        c_axes.extend(n.into_iter());
+        let wire = permute(&mut patch, &node.name, wire, &*c_axes, c)?;
+        patch.shunt_outside(model, OutletId::new(node.id, 0), wire)?;
+        Ok(Some(patch))
+    }
+}
+
+impl EvalOp for EinSum {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if inputs.len() != self.expr.inputs.len() {
+            bail!("Expected {} inputs, got {}", self.expr.inputs.len(), inputs.len())
+        }
+        let output = dispatch_numbers!(Self::eval_t(inputs[0].datum_type())(self, &inputs))?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for EinSum {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shapes: TVec<TVec<TDim>> = inputs.iter().map(|i| i.shape.to_tvec()).collect();
+        let shapes: TVec<&[TDim]> = shapes.iter().map(|s| &**s).collect();
+        let shape = self.expr.output_shape(&shapes)?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        self.declutter_as_matmul(model, node)
+    }
+
+    fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
+        let inputs = model.node_input_facts(node.id)?;
+        let output = &node.outputs[0].fact;
+        Ok(self
+            .expr
+            .output
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| {
+                self.expr.inputs.iter().all(|i| i.iter().filter(|x| x == c).count() <= 1)
+            })
+            .map(|(axis, c)| AxisInfo {
+                // an input broadcasting along the axis does not follow it
+                inputs: self
+                    .expr
+                    .inputs
+                    .iter()
+                    .zip(inputs.iter())
+                    .map(|(i, fact)| {
+                        i.iter()
+                            .position(|x| x == c)
+                            .filter(|&pos| !fact.shape[pos].is_one() || output.shape[axis].is_one())
+                    })
+                    .collect(),
+                outputs: tvec!(Some(axis)),
+                period: 1,
+                disposable: true,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ellipsis() {
+        let expr = Expr::parse("...ii ->...i", &[4]).unwrap();
+        assert_eq!(expr.to_string(), "ZYii->ZYi");
+        let expr = Expr::parse("ij,jk", &[2, 2]).unwrap();
+        assert_eq!(expr.to_string(), "ij,jk->ik");
+    }
+
+    #[test]
+    fn batch_matmul() {
+        let op = EinSum::new(Expr::parse("bij,bjk->bik", &[3, 3]).unwrap());
+        let a = rctensor3(&[[[1f32, 2.], [3., 4.]]]);
+        let b = rctensor3(&[[[1f32, 0.], [1., 1.]]]);
+        let output = op.eval(tvec!(a, b)).unwrap();
+        assert_eq!(*output[0], tensor3(&[[[3f32, 2.], [7., 4.]]]));
+    }
+
+    fn check_matmul(expr: &str, a: Tensor, b: Tensor) -> TractResult<()> {
+        let op = EinSum::new(Expr::parse(expr, &[a.rank(), b.rank()])?);
+        let expected = op.eval(tvec!(a.clone().into_arc_tensor(), b.clone().into_arc_tensor()))?;
+        let mut model = TypedModel::default();
+        let a_source = model.add_source("a", TypedFact::dt_shape(f32::datum_type(), a.shape())?)?;
+        let b_source = model.add_source("b", TypedFact::dt_shape(f32::datum_type(), b.shape())?)?;
+        let c = model.wire_node("c", op, &[a_source, b_source])?;
+        model.set_output_outlets(&c)?;
+        let decluttered = model.declutter()?;
+        assert!(decluttered.nodes().iter().any(|n| n.op_is::<MatMul>()));
+        assert!(!decluttered.nodes().iter().any(|n| n.op_is::<EinSum>()));
+        let found = SimplePlan::new(&decluttered)?.run(tvec!(a, b))?;
+        found[0].close_enough(&expected[0], true)
+    }
+
+    #[test]
+    fn declutter_to_matmul() -> TractResult<()> {
+        let a = tensor3(&[[[1f32, 2., 3.], [4., 5., 6.]]]);
+        check_matmul("bij,bjk->bik", a.clone(), tensor3(&[[[1f32, 0.], [1., 1.], [0., 2.]]]))?;
+        check_matmul("bij,bkj->bki", a, tensor3(&[[[1f32, 0., 1.], [1., 1., 2.]]]))?;
+        check_matmul("ji,jk->ik", tensor2(&[[1f32, 2.], [3., 4.]]), tensor2(&[[1f32], [-1.]]))?;
+        check_matmul("ij,j->i", tensor2(&[[1f32, 2., 3.], [4., 5., 6.]]), tensor1(&[1f32, 0., 2.]))
+    }
+
+    #[test]
+    fn invariants_skip_broadcast_inputs() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let a =
+            model.add_source("a", TypedFact::dt_shape(f32::datum_type(), &[1usize, 2, 3][..])?)?;
+        let b =
+            model.add_source("b", TypedFact::dt_shape(f32::datum_type(), &[4usize, 3, 5][..])?)?;
+        let op = EinSum::new(Expr::parse("bij,bjk->bik", &[3, 3])?);
+        let c = model.wire_node("c", op.clone(), &[a, b])?;
+        let invariants = op.invariants(&model, model.node(c[0].node))?;
+        let batch = invariants.axes.iter().find(|axis| axis.outputs[0] == Some(0)).unwrap();
+        assert_eq!(batch.inputs, tvec!(None, Some(0)));
+        Ok(())
+    }
+}
@@ -24,9 +24,8 @@ pub trait ElementWiseMiniOp:
     fn eval_out_of_place(&self, t: &Tensor) -> TractResult<Tensor> {
         unreachable!()
     }
-    #[allow(unused_variables)]
     fn cost_per_element(&self, dt: DatumType) -> TVec<(Cost, usize)> {
-        tvec!()
+        tvec!((Cost::ElementWise(dt), 1))
     }
     #[allow(unused_variables)]
     fn declutter(
@@ -50,6 +49,10 @@ pub trait ElementWiseMiniOp:
     fn info(&self) -> TractResult<Vec<String>> {
         Ok(vec![])
     }
+    #[allow(unused_variables)]
+    fn same_as(&self, other: &dyn ElementWiseMiniOp) -> bool {
+        false
+    }
 }
 
 impl Hash for Box<dyn ElementWiseMiniOp> {
@@ -78,6 +81,10 @@ impl Op for ElementWiseOp {
         self.0.validation()
     }
 
+    fn same_as(&self, other: &dyn Op) -> bool {
+        other.downcast_ref::<Self>().map(|other| self.0.same_as(&*other.0)).unwrap_or(false)
+    }
+
     op_core_lir_mir!();
     op_as_typed_op!();
 }
@@ -167,8 +174,9 @@ macro_rules! element_wise {
         $(; prefix: $prefix:expr )?
         $(; quantize: $quantize:expr )?
         $(; validation: $validation:expr )?
+        $(; declutter: $declutter:expr )?
     ) => {
-        #[derive(Debug, Clone, Educe)]
+        #[derive(Debug, Clone, PartialEq, Educe)]
         #[educe(Hash)]
         pub struct $Op { $( $( $(#[$meta])? pub $var: $var_typ),* )? }
         tract_linalg::impl_dyn_hash!($Op);
@@ -176,6 +184,9 @@ macro_rules! element_wise {
             fn name(&self) -> String {
                 format!("{}{}", self.prefix(), stringify!($Op))
             }
+            fn same_as(&self, other: &dyn $crate::ops::element_wise::ElementWiseMiniOp) -> bool {
+                other.downcast_ref::<$Op>().map(|other| other == self).unwrap_or(false)
+            }
             fn eval_in_place(&self, t: &mut Tensor) -> TractResult<()> {
                 $(
                     $(if t.datum_type() == $typ::datum_type() {
@@ -212,6 +223,15 @@ macro_rules! element_wise {
                 $validation
             }
             )?
+            $(
+            fn declutter(
+                &self,
+                model: &TypedModel,
+                node: &TypedNode,
+            ) -> TractResult<Option<TypedModelPatch>> {
+                $declutter(self, model, node)
+            }
+            )?
         }
         pub fn $func($( $($var: $var_typ),* )?) -> $crate::ops::element_wise::ElementWiseOp {
             $crate::ops::element_wise::ElementWiseOp(Box::new($Op { $( $($var),* )? } ))
@@ -228,8 +248,9 @@ macro_rules! element_wise_oop {
         $(; prefix: $prefix:expr )?
         $(; quantize: $quantize:expr )?
         $(; validation: $validation:expr )?
+        $(; declutter: $declutter:expr )?
     ) => {
-        #[derive(Debug, Clone, Educe)]
+        #[derive(Debug, Clone, PartialEq, Educe)]
         #[educe(Hash)]
         pub struct $Op { $( $($(#[$meta])? pub $var: $var_typ),* )? }
         tract_linalg::impl_dyn_hash!($Op);
@@ -237,6 +258,9 @@ macro_rules! element_wise_oop {
             fn name(&self) -> String {
                 format!("{}{}", self.prefix(), stringify!($Op))
             }
+            fn same_as(&self, other: &dyn $crate::ops::element_wise::ElementWiseMiniOp) -> bool {
+                other.downcast_ref::<$Op>().map(|other| other == self).unwrap_or(false)
+            }
             fn output_type(&self, input_type: DatumType) -> Option<DatumType> {
                 $(
                     $(if input_type == $typ::datum_type() {
@@ -287,6 +311,15 @@ macro_rules! element_wise_oop {
                 $validation
             }
             )?
+            $(
+            fn declutter(
+                &self,
+                model: &TypedModel,
+                node: &TypedNode,
+            ) -> TractResult<Option<TypedModelPatch>> {
+                $declutter(self, model, node)
+            }
+            )?
         }
         pub fn $func($( $($var: $var_typ),* )?) -> $crate::ops::element_wise::ElementWiseOp {
             $crate::ops::element_wise::ElementWiseOp(Box::new($Op { $( $($var),* )? } ))
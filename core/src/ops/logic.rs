@@ -92,4 +92,9 @@ impl TypedOp for Iff {
         .unwrap();
         Ok(tvec!(TypedFact::dt_shape(inputs[1].datum_type, &*shape)?))
     }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let count: TDim = self.output_facts(inputs)?[0].shape.iter().maybe_product()?;
+        Ok(tvec!((Cost::ElementWise(inputs[1].datum_type), count)))
+    }
 }
@@ -0,0 +1,110 @@
+use crate::internal::*;
+use ndarray::*;
+use num_traits::Zero;
+
+/// Cumulative sum along `axis`.
+///
+/// `exclusive` leaves the current element out of its own sum, `reverse` sums
+/// from the end of the axis.
+#[derive(Debug, Clone, new, Hash)]
+pub struct CumSum {
+    pub axis: usize,
+    pub exclusive: bool,
+    pub reverse: bool,
+}
+tract_linalg::impl_dyn_hash!(CumSum);
+
+impl Op for CumSum {
+    fn name(&self) -> Cow<str> {
+        "CumSum".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "axis: {} exclusive: {} reverse: {}",
+            self.axis, self.exclusive, self.reverse
+        )])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl CumSum {
+    fn eval_t<T: Datum + Zero + Copy>(&self, input: &Tensor) -> TractResult<Arc<Tensor>> {
+        let input = input.to_array_view::<T>()?;
+        let mut output = ArrayD::<T>::zeros(input.shape());
+        for (lane, mut output) in
+            input.lanes(Axis(self.axis)).into_iter().zip(output.lanes_mut(Axis(self.axis)))
+        {
+            let len = lane.len();
+            let mut sum = T::zero();
+            for i in 0..len {
+                let ix = if self.reverse { len - 1 - i } else { i };
+                if self.exclusive {
+                    output[ix] = sum;
+                    sum = sum + lane[ix];
+                } else {
+                    sum = sum + lane[ix];
+                    output[ix] = sum;
+                }
+            }
+        }
+        Ok(output.into_arc_tensor())
+    }
+}
+
+impl EvalOp for CumSum {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        Ok(tvec!(dispatch_numbers!(Self::eval_t(input.datum_type())(self, &input))?))
+    }
+}
+
+impl TypedOp for CumSum {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].without_value()))
+    }
+
+    fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
+        let rank = model.outlet_fact(node.inputs[0])?.rank();
+        Ok((0..rank).filter(|&ax| ax != self.axis).map(AxisInfo::simple).collect())
+    }
+
+    fn change_axes(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+        _io: InOut,
+        change: &AxisOp,
+    ) -> TractResult<Option<AxisChangeConsequence>> {
+        if let Some(axis) = change.transform_axis(self.axis) {
+            let op = if axis != self.axis {
+                Some(Box::new(CumSum { axis, ..self.clone() }) as _)
+            } else {
+                None
+            };
+            Ok(Some(AxisChangeConsequence::new(model, node, op, change)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclusive_reverse() {
+        let op = CumSum::new(0, true, true);
+        let output = op.eval(tvec!(rctensor1(&[1i64, 2, 3, 4]))).unwrap();
+        assert_eq!(*output[0], tensor1(&[9i64, 7, 4, 0]));
+    }
+}
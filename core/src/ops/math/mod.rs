@@ -3,6 +3,9 @@ use num_traits::{Float, Zero};
 
 use super::binary::*;
 
+mod cum_sum;
+pub use cum_sum::CumSum;
+
 bin_to_super_type!(add, Add,
                    flip:commute,
                    validation: Validation::Rounding,
@@ -66,10 +69,14 @@ bin_to_super_type!(rem, Rem,
                    },
                    [f32, i8, i16, i32, i64, u8, u16, u32, u64, f16, f64] => |c, a, b| *c = a.clone() % b);
 
-bin_to_super_type!(min, Min, flip:commute,
+bin_to_super_type!(min, Min,
+                   cost: |dt| tvec!((Cost::Comparison(dt), 1)),
+                   flip:commute,
                    [f32, f64] => |c,a,b| *c = a.min(*b),
                    [i8, i16, i32, i64, u8, u16, u32, u64] => |c, a, b| *c = *a.min(b));
-bin_to_super_type!(max, Max, flip:commute,
+bin_to_super_type!(max, Max,
+                   cost: |dt| tvec!((Cost::Comparison(dt), 1)),
+                   flip:commute,
                    [f32, f64] => |c,a,b| *c = a.max(*b),
                    [i8, i16, i32, i64, u8, u16, u32, u64] => |c, a, b| *c = *a.max(b));
 
@@ -249,7 +256,8 @@ element_wise!(sqrt, Sqrt, [f16, f32, f64] => |_, xs| {
     xs.iter_mut().for_each(|x| *x = x.sqrt());
     Ok(())
 };
-validation: Validation::Rounding
+validation: Validation::Rounding;
+declutter: |_, model, node| crate::ops::nn::layer_norm::detect(model, node)
 );
 
 element_wise!(recip, Recip, [f16, f32, f64] => |_, xs| {
@@ -264,7 +272,8 @@ element_wise!(rsqrt, Rsqrt, [f16, f32, f64] => |_, xs| {
     xs.iter_mut().for_each(|x| *x = x.sqrt().recip());
     Ok(())
 };
-validation: Validation::Rounding
+validation: Validation::Rounding;
+declutter: |_, model, node| crate::ops::nn::layer_norm::detect(model, node)
 );
 
 element_wise!(ceil, Ceil, [f16, f32, f64] => |_, xs| {
@@ -354,6 +363,35 @@ element_wise!(tanh, Tanh,
  cost: |dt| {tvec!((Cost::FMA(dt), 11), (Cost::Div(dt), 1))}
 );
 
+element_wise!(erf, Erf, [f32] => |_, xs| {
+    xs.iter_mut().for_each(|x| *x = erf_f32(*x));
+    Ok(())
+};
+declutter: |_, model, node| crate::ops::nn::gelu::detect(model, node)
+);
+
+#[allow(non_upper_case_globals)]
+pub(crate) fn erf_f32(x: f32) -> f32 {
+    const a1: f32 = 0.0705230784;
+    const a2: f32 = 0.0422820123;
+    const a3: f32 = 0.0092705272;
+    const a4: f32 = 0.0001520143;
+    const a5: f32 = 0.0002765672;
+    const a6: f32 = 0.0000430638;
+
+    let signum = x.signum();
+    let x = x.abs();
+    let y = a6 * x;
+    let y = (a5 + y) * x;
+    let y = (a4 + y) * x;
+    let y = (a3 + y) * x;
+    let y = (a2 + y) * x;
+    let y = (a1 + y) * x;
+    let y = 1.0 - (y + 1.0).powi(16).recip();
+
+    y.copysign(signum)
+}
+
 element_wise!(acosh, Acosh, [f16, f32, f64] => |_, xs| { xs.iter_mut().for_each(|x| *x = x.acosh()); Ok(()) });
 element_wise!(asinh, Asinh, [f16, f32, f64] => |_, xs| { xs.iter_mut().for_each(|x| *x = x.asinh()); Ok(()) });
 element_wise!(atanh, Atanh, [f16, f32, f64] => |_, xs| { xs.iter_mut().for_each(|x| *x = x.atanh()); Ok(()) });
@@ -422,4 +460,24 @@ mod tests {
         assert!(op.mini_op.downcast_ref::<FlippedShiftRight>().is_some());
         Ok(())
     }
+
+    #[test]
+    fn element_wise_and_comparison_costs() -> TractResult<()> {
+        let dt = f32::datum_type();
+        let fact = TypedFact::dt_shape(dt, [2usize, 3].as_ref())?;
+        assert_eq!(exp().cost(&[&fact])?, tvec!((Cost::ElementWise(dt), TDim::from(6))));
+        assert_eq!(
+            add::unary(rctensor2(&[[1f32, 2.0, 3.0]])).cost(&[&fact])?,
+            tvec!((Cost::ElementWise(dt), TDim::from(6)), (Cost::Params(dt), TDim::from(3)))
+        );
+        assert_eq!(
+            max::bin_typed().cost(&[&fact, &fact])?,
+            tvec!((Cost::Comparison(dt), TDim::from(6)))
+        );
+        assert_eq!(
+            crate::ops::logic::lesser::bin_typed().cost(&[&fact, &fact])?,
+            tvec!((Cost::Comparison(dt), TDim::from(6)))
+        );
+        Ok(())
+    }
 }
@@ -1,8 +1,14 @@
 pub mod lir;
 pub mod mir;
 pub mod mmm_wrapper;
+pub mod pack_a;
 pub mod pack_b;
 
 use self::pack_b::MatMatMulPackB;
 pub use self::mir::{compute_shape, MatMul, MatMulUnary};
 pub use mmm_wrapper::MMMWrapper;
+pub use pack_a::PackedA;
+
+/// Constant weights are packed block-sparse when at least this fraction of
+/// their blocks (one kernel panel high, one column wide) is entirely zero.
+pub const BLOCK_SPARSITY_THRESHOLD: f32 = 0.5;
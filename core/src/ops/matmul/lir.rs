@@ -146,8 +146,14 @@ where
     fn cost(&self, _inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
         let mmm = self.mmm.as_mmm();
         let mul = self.c_prefix_dim_and_stride.as_ref().map(|c| c.0.iter().product()).unwrap_or(1);
+        let mut fma = mul * mmm.m() * mmm.n() * mmm.k();
+        let density = mmm.a_storage().density();
+        if density < 1.0 {
+            // block-sparse A skips the zero blocks
+            fma = (fma as f64 * density as f64).round() as usize;
+        }
         Ok(tvec!(
-            (Cost::FMA(TI::datum_type()), (mul * mmm.m() * mmm.n() * mmm.k()).to_dim()),
+            (Cost::FMA(TI::datum_type()), fma.to_dim()),
             (
                 Cost::Params(TA::datum_type()),
                 self.packed_as.iter().fold(0.to_dim(), |sum, a| sum + a.len())
@@ -18,30 +18,58 @@ fn eval(
     q_params: Option<&QParams>,
 ) -> TractResult<Tensor> {
     if let Some(q) = q_params {
+        if q.scale_factor_cols.is_some() && q.scale_factor_rows.is_none() {
+            // kernels requantize per row: compute the transposed product instead
+            return eval(b, a, !b_trans, !a_trans, !c_trans, Some(&q.flipped()));
+        }
         if (a.datum_type(), b.datum_type()) == (i8::datum_type(), i8::datum_type()) {
             if q.c_datum_type == i32::datum_type() {
-                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, &|m, k, n| {
-                    MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i32)(m, k, n))
+                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_i32.make(m, k, n)?))
                 });
             } else if q.c_datum_type == i8::datum_type() {
-                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, &|m, k, n| {
-                    MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i8)(m, k, n))
+                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_i8.make(m, k, n)?))
                 });
             }
         } else if (a.datum_type(), b.datum_type()) == (u8::datum_type(), u8::datum_type()) {
             if q.c_datum_type == i32::datum_type() {
-                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, &|m, k, n| {
-                    MMMWrapper::Quant((tract_linalg::ops().qmmm_u8_i32)(m, k, n))
+                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_u8_i32.make(m, k, n)?))
+                });
+            } else if q.c_datum_type == u8::datum_type() {
+                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_u8_u8.make(m, k, n)?))
+                });
+            }
+        } else if (a.datum_type(), b.datum_type()) == (i8::datum_type(), u8::datum_type()) {
+            if q.c_datum_type == i32::datum_type() {
+                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_u8_i32.make(m, k, n)?))
+                });
+            } else if q.c_datum_type == u8::datum_type() {
+                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_u8_u8.make(m, k, n)?))
+                });
+            }
+        } else if (a.datum_type(), b.datum_type()) == (u8::datum_type(), i8::datum_type()) {
+            if q.c_datum_type == i32::datum_type() {
+                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_u8_i8_i32.make(m, k, n)?))
                 });
             } else if q.c_datum_type == u8::datum_type() {
-                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, &|m, k, n| {
-                    MMMWrapper::Quant((tract_linalg::ops().qmmm_u8_u8)(m, k, n))
+                return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+                    Ok(MMMWrapper::Quant(tract_linalg::ops().qmmm_u8_i8_u8.make(m, k, n)?))
                 });
             }
         }
     } else if (a.datum_type(), b.datum_type()) == (f32::datum_type(), f32::datum_type()) {
-        return eval_t(a, b, a_trans, b_trans, c_trans, q_params, &|m, k, n| {
-            MMMWrapper::Plain((tract_linalg::ops().mmm_f32)(m, k, n))
+        return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+            Ok(MMMWrapper::Plain(tract_linalg::ops().mmm_f32.make(m, k, n)?))
+        });
+    } else if (a.datum_type(), b.datum_type()) == (f64::datum_type(), f64::datum_type()) {
+        return eval_t(a, b, a_trans, b_trans, c_trans, q_params, |m, k, n| {
+            Ok(MMMWrapper::Plain(tract_linalg::ops().mmm_f64.make(m, k, n)?))
         });
     }
     bail!(
@@ -59,7 +87,7 @@ fn eval_t<TA, TB, TC, TI>(
     b_trans: bool,
     c_trans: bool,
     q_params: Option<&QParams>,
-    mmm: impl Fn(usize, usize, usize) -> MMMWrapper<TA, TB, TC, TI>,
+    mmm: impl Fn(usize, usize, usize) -> TractResult<MMMWrapper<TA, TB, TC, TI>>,
 ) -> TractResult<Tensor>
 where
     TA: Datum + Copy + Zero,
@@ -73,7 +101,7 @@ where
     let m = a.shape()[a.shape().len() - 2 + a_trans as usize];
     let k = a.shape()[a.shape().len() - 1 - a_trans as usize];
     let n = b.shape()[b.shape().len() - 1 - b_trans as usize];
-    let mut mm = mmm(m, k, n);
+    let mut mm = mmm(m, k, n)?;
     let c_shape = compute_shape(a.shape(), b.shape(), a_trans, b_trans, c_trans)?;
     unsafe {
         mm.as_mmm_mut().c_from_data_and_strides(
@@ -193,6 +221,18 @@ impl MatMul {
     pub fn with_q_params(self, q_params: QParams) -> MatMul {
         MatMul { q_params: Some(q_params), ..self }
     }
+
+    /// Equivalent MatMulUnary, with `konst` the value of input `konst_ix`.
+    ///
+    /// The resulting op takes the other input.
+    pub fn to_unary(&self, konst_ix: usize, konst: Arc<Tensor>) -> MatMulUnary {
+        let flip = konst_ix == 1;
+        let t_konst = [self.a_trans, self.b_trans][konst_ix] ^ flip;
+        let t_var = [self.b_trans, self.a_trans][konst_ix] ^ flip;
+        let q_params =
+            if flip { self.q_params.as_ref().map(|q| q.flipped()) } else { self.q_params.clone() };
+        MatMulUnary::new(konst, t_konst, t_var, self.c_trans ^ flip, q_params)
+    }
 }
 
 impl Op for MatMul {
@@ -250,6 +290,9 @@ impl TypedOp for MatMul {
         model: &TypedModel,
         node: &TypedNode,
     ) -> TractResult<Option<TypedModelPatch>> {
+        if let Some(patch) = crate::ops::nn::attention::detect(model, node, self)? {
+            return Ok(Some(patch));
+        }
         let a_fact = model.outlet_fact(node.inputs[0])?;
         let b_fact = model.outlet_fact(node.inputs[1])?;
         let konst_ix = if a_fact.konst.is_some() {
@@ -261,15 +304,12 @@ impl TypedOp for MatMul {
         };
 
         let var_ix = 1 - konst_ix;
-        let flip = konst_ix == 1;
-        let t_konst = [self.a_trans, self.b_trans][konst_ix] ^ flip;
-        let t_var = [self.b_trans, self.a_trans][konst_ix] ^ flip;
         let konst = model.outlet_fact(node.inputs[konst_ix])?.konst.clone().unwrap();
         let patch = TypedModelPatch::replace_single_op(
             model,
             node,
             &node.inputs[var_ix..][..1],
-            MatMulUnary::new(konst, t_konst, t_var, self.c_trans ^ flip, self.q_params.clone()),
+            self.to_unary(konst_ix, konst),
         )?
         .with_context("to unary");
         return Ok(Some(patch));
@@ -295,6 +335,9 @@ pub struct MatMulUnary {
     pub b_trans: bool,
     pub c_trans: bool,
     pub q_params: Option<QParams>,
+    /// `a` packed ahead of time, see `TypedModel::prepack`.
+    #[new(default)]
+    pub prepacked: Option<PackedA>,
 }
 
 tract_linalg::impl_dyn_hash!(MatMulUnary);
@@ -315,6 +358,9 @@ impl Op for MatMulUnary {
         if let Some(qp) = &self.q_params {
             v.push(format!("{:?}", qp));
         }
+        if let Some(packed) = &self.prepacked {
+            v.push(format!("Prepacked for {} ({})", packed.kernel, packed.storage));
+        }
         Ok(v)
     }
 
@@ -468,12 +514,12 @@ impl TypedOp for MatMulUnary {
                         }
                     };
                     let mut a = self.a.slice(k_axis, offsets[ix], offsets[ix + 1])?;
-                    while a.rank() > 0 && a.shape()[0] == 1 {
+                    while a.rank() > 2 && a.shape()[0] == 1 {
                         a.remove_axis(0)?;
                     }
                     let wire = patch.wire_node(
                         format!("{}.k-{}-{}", node.name, offsets[ix], offsets[ix + 1]),
-                        MatMulUnary { a: a.into_arc_tensor(), ..self.clone() },
+                        MatMulUnary { a: a.into_arc_tensor(), prepacked: None, ..self.clone() },
                         &[wire],
                     )?[0];
                     wires.push(wire)
@@ -512,7 +558,7 @@ impl TypedOp for MatMulUnary {
             return Ok(Some(
                 patch.wire_node(
                     format!("{}.sliced-m-{}-{}", node.name, start, end),
-                    Self { a, ..self.clone() },
+                    Self { a, prepacked: None, ..self.clone() },
                     &[wire],
                 )?[0],
             ));
@@ -537,9 +583,31 @@ impl TypedOp for MatMulUnary {
         model: &TypedModel,
         node: &TypedNode,
     ) -> TractResult<Option<TypedModelPatch>> {
+        Ok(self.codegen_and_pack(model, node)?.map(|(patch, _)| patch))
+    }
+
+    fn prepack(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<Box<dyn TypedOp>>> {
+        let packed = self.codegen_and_pack(model, node)?.map(|(_, packed)| packed);
+        Ok(packed.map(|p| Box::new(MatMulUnary { prepacked: Some(p), ..self.clone() }) as _))
+    }
+
+    as_op!();
+}
+
+impl MatMulUnary {
+    /// Codegen patch, along with `a` as it was packed for it.
+    pub(crate) fn codegen_and_pack(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<(TypedModelPatch, PackedA)>> {
         let b = args_1!(model.node_input_facts(node.id)?);
         if let Some(b_shape) = b.shape.as_finite() {
-            let patch =
+            let (patch, packed) =
                 if (self.a.datum_type(), b.datum_type) == (f32::datum_type(), f32::datum_type()) {
                     new_mat_mul_unary_finite(
                         model,
@@ -550,7 +618,32 @@ impl TypedOp for MatMulUnary {
                         self.b_trans,
                         self.c_trans,
                         self.q_params.as_ref(),
-                        &|m, k, n| MMMWrapper::Plain((tract_linalg::ops().mmm_f32)(m, k, n)),
+                        self.prepacked.as_ref(),
+                        &|m, k, n| {
+                            let mmm = tract_linalg::ops().mmm_f32.make(m, k, n)?;
+                            Ok(MMMWrapper::Plain(mmm))
+                        },
+                    )?
+                } else if (
+                    self.a.datum_type(),
+                    b.datum_type,
+                    self.q_params.as_ref().map(|q| q.c_datum_type),
+                ) == (f64::datum_type(), f64::datum_type(), None)
+                {
+                    new_mat_mul_unary_finite(
+                        model,
+                        node,
+                        self.a.clone(),
+                        &b_shape,
+                        self.a_trans,
+                        self.b_trans,
+                        self.c_trans,
+                        self.q_params.as_ref(),
+                        self.prepacked.as_ref(),
+                        &|m, k, n| {
+                            let mmm = tract_linalg::ops().mmm_f64.make(m, k, n)?;
+                            Ok(MMMWrapper::Plain(mmm))
+                        },
                     )?
                 } else if (
                     self.a.datum_type(),
@@ -567,7 +660,11 @@ impl TypedOp for MatMulUnary {
                         self.b_trans,
                         self.c_trans,
                         self.q_params.as_ref(),
-                        &|m, k, n| MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i8)(m, k, n)),
+                        self.prepacked.as_ref(),
+                        &|m, k, n| {
+                            let mmm = tract_linalg::ops().qmmm_i8_i8.make(m, k, n)?;
+                            Ok(MMMWrapper::Quant(mmm))
+                        },
                     )?
                 } else if (
                     self.a.datum_type(),
@@ -584,7 +681,95 @@ impl TypedOp for MatMulUnary {
                         self.b_trans,
                         self.c_trans,
                         self.q_params.as_ref(),
-                        &|m, k, n| MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i32)(m, k, n)),
+                        self.prepacked.as_ref(),
+                        &|m, k, n| {
+                            let mmm = tract_linalg::ops().qmmm_i8_i32.make(m, k, n)?;
+                            Ok(MMMWrapper::Quant(mmm))
+                        },
+                    )?
+                } else if (
+                    self.a.datum_type(),
+                    b.datum_type,
+                    self.q_params.as_ref().map(|q| q.c_datum_type),
+                ) == (i8::datum_type(), u8::datum_type(), Some(u8::datum_type()))
+                {
+                    new_mat_mul_unary_finite(
+                        model,
+                        node,
+                        self.a.clone(),
+                        &b_shape,
+                        self.a_trans,
+                        self.b_trans,
+                        self.c_trans,
+                        self.q_params.as_ref(),
+                        self.prepacked.as_ref(),
+                        &|m, k, n| {
+                            let mmm = tract_linalg::ops().qmmm_i8_u8_u8.make(m, k, n)?;
+                            Ok(MMMWrapper::Quant(mmm))
+                        },
+                    )?
+                } else if (
+                    self.a.datum_type(),
+                    b.datum_type,
+                    self.q_params.as_ref().map(|q| q.c_datum_type),
+                ) == (i8::datum_type(), u8::datum_type(), Some(i32::datum_type()))
+                {
+                    new_mat_mul_unary_finite(
+                        model,
+                        node,
+                        self.a.clone(),
+                        &b_shape,
+                        self.a_trans,
+                        self.b_trans,
+                        self.c_trans,
+                        self.q_params.as_ref(),
+                        self.prepacked.as_ref(),
+                        &|m, k, n| {
+                            let mmm = tract_linalg::ops().qmmm_i8_u8_i32.make(m, k, n)?;
+                            Ok(MMMWrapper::Quant(mmm))
+                        },
+                    )?
+                } else if (
+                    self.a.datum_type(),
+                    b.datum_type,
+                    self.q_params.as_ref().map(|q| q.c_datum_type),
+                ) == (u8::datum_type(), i8::datum_type(), Some(u8::datum_type()))
+                {
+                    new_mat_mul_unary_finite(
+                        model,
+                        node,
+                        self.a.clone(),
+                        &b_shape,
+                        self.a_trans,
+                        self.b_trans,
+                        self.c_trans,
+                        self.q_params.as_ref(),
+                        self.prepacked.as_ref(),
+                        &|m, k, n| {
+                            let mmm = tract_linalg::ops().qmmm_u8_i8_u8.make(m, k, n)?;
+                            Ok(MMMWrapper::Quant(mmm))
+                        },
+                    )?
+                } else if (
+                    self.a.datum_type(),
+                    b.datum_type,
+                    self.q_params.as_ref().map(|q| q.c_datum_type),
+                ) == (u8::datum_type(), i8::datum_type(), Some(i32::datum_type()))
+                {
+                    new_mat_mul_unary_finite(
+                        model,
+                        node,
+                        self.a.clone(),
+                        &b_shape,
+                        self.a_trans,
+                        self.b_trans,
+                        self.c_trans,
+                        self.q_params.as_ref(),
+                        self.prepacked.as_ref(),
+                        &|m, k, n| {
+                            let mmm = tract_linalg::ops().qmmm_u8_i8_i32.make(m, k, n)?;
+                            Ok(MMMWrapper::Quant(mmm))
+                        },
                     )?
                 } else {
                     bail!(
@@ -594,12 +779,10 @@ impl TypedOp for MatMulUnary {
                         self.q_params
                     );
                 };
-            return Ok(Some(patch));
+            return Ok(Some((patch, packed)));
         }
         Ok(None)
     }
-
-    as_op!();
 }
 
 fn new_mat_mul_unary_finite<TA, TB, TC, TI>(
@@ -611,8 +794,9 @@ fn new_mat_mul_unary_finite<TA, TB, TC, TI>(
     b_trans: bool,
     c_trans: bool,
     q_params: Option<&QParams>,
-    mmm: &impl Fn(usize, usize, usize) -> MMMWrapper<TA, TB, TC, TI>,
-) -> TractResult<TypedModelPatch>
+    prepacked: Option<&PackedA>,
+    mmm: &impl Fn(usize, usize, usize) -> TractResult<MMMWrapper<TA, TB, TC, TI>>,
+) -> TractResult<(TypedModelPatch, PackedA)>
 where
     TA: Datum + Copy + Zero,
     TB: Datum + Copy + Zero,
@@ -626,29 +810,16 @@ where
     let k = a.shape()[a.rank() - 1 - a_trans as usize];
     let n = b_shape[b_shape.len() - 1 - b_trans as usize];
 
-    let mut mm = mmm(m, k, n);
+    let mut mm = mmm(m, k, n)?;
     let c_shape = compute_shape(&a.shape(), b_shape, a_trans, b_trans, c_trans)?;
-    let a = a.to_array_view::<TA>()?;
-    let packed_as = Array::from_shape_fn(&a.shape()[0..a.ndim() - 2], |a_prefix| {
-        let mut a = a.view();
-        for x in a_prefix.slice() {
-            a.index_axis_inplace(Axis(0), *x);
-        }
-        let mut pa = unsafe {
-            Tensor::uninitialized_aligned::<TA>(
-                &[mm.as_mmm().a_pack().len()],
-                mm.as_mmm().a_pack().alignment(),
-            )
-            .unwrap()
-        };
-        mm.as_mmm().a_pack().pack(
-            pa.as_ptr_mut().unwrap(),
-            a.as_ptr(),
-            a.strides()[a_trans as usize],
-            a.strides()[!a_trans as usize],
-        );
-        pa.into_arc_tensor()
-    });
+    let a_prefix = &a.shape()[..a.rank() - 2];
+    let reused = match prepacked {
+        Some(prepacked) => unsafe { prepacked.reuse(mm.as_mmm_mut(), a_prefix)? },
+        None => None,
+    };
+    let packed_as =
+        if let Some(packed_as) = reused { packed_as } else { pack_a(&mut mm, &a, a_trans)? };
+    let packed = PackedA::new(mm.as_mmm(), packed_as.clone());
     unsafe {
         if n == 1 {
             mm.as_mmm_mut().b_vec_from_data_and_stride(if b_trans {
@@ -718,7 +889,60 @@ where
         &[wire],
     )?[0];
     patch.shunt_outside(model, OutletId::new(node.id, 0), wire)?;
-    Ok(patch)
+    Ok((patch, packed))
+}
+
+fn pack_a<TA, TB, TC, TI>(
+    mm: &mut MMMWrapper<TA, TB, TC, TI>,
+    a: &Tensor,
+    a_trans: bool,
+) -> TractResult<ArrayD<Arc<Tensor>>>
+where
+    TA: Datum + Copy + Zero,
+    TB: Datum + Copy + Zero,
+    TC: Datum + Copy,
+    TI: Datum + Copy + Add + Mul + Zero + fmt::Debug,
+{
+    let (m, k) = (mm.as_mmm().m(), mm.as_mmm().k());
+    let a = a.to_array_view::<TA>()?;
+    let block_sparse = if a.len() == m * k {
+        let mut a = a.view();
+        while a.ndim() > 2 {
+            a.index_axis_inplace(Axis(0), 0);
+        }
+        let packer = unsafe {
+            mm.as_mmm().a_block_sparse_pack(
+                a.as_ptr(),
+                a.strides()[a_trans as usize],
+                a.strides()[!a_trans as usize],
+            )
+        };
+        Some(packer).filter(|p| p.sparsity() >= super::BLOCK_SPARSITY_THRESHOLD)
+    } else {
+        None
+    };
+    if let Some(packer) = &block_sparse {
+        unsafe { mm.as_mmm_mut().a_from_block_sparse(packer) };
+    }
+    Ok(Array::from_shape_fn(&a.shape()[0..a.ndim() - 2], |a_prefix| {
+        let mut a = a.view();
+        for x in a_prefix.slice() {
+            a.index_axis_inplace(Axis(0), *x);
+        }
+        let (len, alignment) = if let Some(packer) = &block_sparse {
+            (packer.len(), packer.alignment())
+        } else {
+            (mm.as_mmm().a_pack().len(), mm.as_mmm().a_pack().alignment())
+        };
+        let mut pa = unsafe { Tensor::uninitialized_aligned::<TA>(&[len], alignment).unwrap() };
+        let (rsa, csa) = (a.strides()[a_trans as usize], a.strides()[!a_trans as usize]);
+        if let Some(packer) = &block_sparse {
+            packer.pack(pa.as_ptr_mut().unwrap(), a.as_ptr(), rsa, csa);
+        } else {
+            mm.as_mmm().a_pack().pack(pa.as_ptr_mut().unwrap(), a.as_ptr(), rsa, csa);
+        }
+        pa.into_arc_tensor()
+    }))
 }
 
 fn cost<A: DimLike + Clone, B: DimLike + Clone>(
@@ -766,6 +990,197 @@ mod test {
         c.close_enough(&c_found, true).unwrap();
     }
 
+    #[test]
+    fn bin_mixed_signedness() {
+        let a = rctensor2(&[[128u8, 129, 130], [131, 132, 133]]);
+        let b = rctensor2(&[[0i8], [1], [2]]);
+        let c = tensor2(&[[5i32], [14]]);
+        let op = MatMul::default()
+            .with_q_params(QParams::new(i32::datum_type()).with_zero_point_a(&rctensor0(128u8)));
+        let c_found = op.eval(tvec!(a, b)).unwrap().pop().unwrap();
+        assert_eq!(c, *c_found);
+    }
+
+    #[test]
+    fn unary_mixed_signedness() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let a = model.add_source("a", TypedFact::dt_shape(u8::datum_type(), [2, 3].as_ref())?)?;
+        let b = model.add_const("b", tensor2(&[[0i8], [1], [2]]))?;
+        let op = MatMul::default()
+            .with_q_params(QParams::new(i32::datum_type()).with_zero_point_a(&rctensor0(128u8)));
+        let c = model.wire_node("c", op, &[a, b])?;
+        model.set_output_outlets(&c)?;
+        let input = tensor2(&[[128u8, 129, 130], [131, 132, 133]]);
+        let expected = tensor2(&[[5i32], [14]]);
+        let output = model.declutter()?.optimize()?.into_runnable()?.run(tvec!(input))?;
+        assert_eq!(expected, *output[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn bin_per_column_scale() {
+        let a = rctensor2(&[[1i8, 2, 3], [4, 5, 7]]);
+        let b = rctensor2(&[[1i8, 2], [3, 4], [5, 6]]);
+        let c = tensor2(&[[11i8, 7], [27, 18]]);
+        let qp = QParams::new(i8::datum_type())
+            .with_scale_factor_cols(&tensor1(&[0.5f32, 0.25]))
+            .unwrap();
+        let c_found = MatMul::default().with_q_params(qp).eval(tvec!(a, b)).unwrap();
+        assert_eq!(c, *c_found[0]);
+    }
+
+    #[test]
+    fn unary_per_column_scale() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let a = model.add_source("a", TypedFact::dt_shape(i8::datum_type(), [2, 3].as_ref())?)?;
+        let b = model.add_const("b", tensor2(&[[1i8, 2], [3, 4], [5, 6]]))?;
+        let qp =
+            QParams::new(i8::datum_type()).with_scale_factor_cols(&tensor1(&[0.5f32, 0.25]))?;
+        let c = model.wire_node("c", MatMul::default().with_q_params(qp), &[a, b])?;
+        model.set_output_outlets(&c)?;
+        let input = tensor2(&[[1i8, 2, 3], [4, 5, 7]]);
+        let expected = tensor2(&[[11i8, 7], [27, 18]]);
+        let output = model.declutter()?.optimize()?.into_runnable()?.run(tvec!(input))?;
+        assert_eq!(expected, *output[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn bin_f64() {
+        let a = rctensor2(&[[1f64, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = rctensor2(&[[0f64], [1.0], [2.0]]);
+        let c = tensor2(&[[8f64], [17.0]]);
+        let c_found = MatMul::default().eval(tvec!(a, b)).unwrap().pop().unwrap();
+        assert_eq!(c, *c_found);
+    }
+
+    #[test]
+    fn unary_f64() -> TractResult<()> {
+        let a = Array2::from_shape_fn((5, 7), |(i, j)| (i * 7 + j) as f64 / 10.0);
+        let b = Array2::from_shape_fn((7, 9), |(i, j)| (i + j) as f64 / 3.0);
+        let expected = a.dot(&b).into_tensor();
+        let mut model = TypedModel::default();
+        let wire =
+            model.add_source("b", TypedFact::dt_shape(f64::datum_type(), [7, 9].as_ref())?)?;
+        let op = MatMulUnary::new(a.into_arc_tensor(), false, false, false, None);
+        let wire = model.wire_node("c", op, &[wire])?;
+        model.set_output_outlets(&wire)?;
+        let optimized = model.declutter()?.optimize()?;
+        assert!(optimized
+            .nodes()
+            .iter()
+            .any(|n| n.op_is::<super::super::lir::MatMatMulUnaryFinite<f64, f64, f64, f64>>()));
+        let output = optimized.into_runnable()?.run(tvec!(b.into_tensor()))?;
+        expected.close_enough(&output[0], true)?;
+        Ok(())
+    }
+
+    #[test]
+    fn unary_block_sparse() -> TractResult<()> {
+        let a =
+            Array2::from_shape_fn((8, 16), |(i, j)| if j % 4 == 0 { (i + j) as f32 } else { 0.0 });
+        let b = Array2::from_shape_fn((16, 3), |(i, j)| (i * 3 + j) as f32);
+        let expected = a.dot(&b).into_tensor();
+        let mut model = TypedModel::default();
+        let wire =
+            model.add_source("b", TypedFact::dt_shape(f32::datum_type(), [16, 3].as_ref())?)?;
+        let op = MatMulUnary::new(a.into_arc_tensor(), false, false, false, None);
+        let wire = model.wire_node("c", op, &[wire])?;
+        model.set_output_outlets(&wire)?;
+        let optimized = model.declutter()?.optimize()?;
+        let mmm = optimized
+            .nodes()
+            .iter()
+            .find_map(|n| n.op_as::<super::super::lir::MatMatMulUnaryFinite<f32, f32, f32, f32>>())
+            .unwrap();
+        assert_eq!(mmm.mmm.as_mmm().a_storage().density(), 0.25);
+        let output = optimized.into_runnable()?.run(tvec!(b.into_tensor()))?;
+        expected.close_enough(&output[0], true)?;
+        Ok(())
+    }
+
+    /// Prepacks a 5x7 by 7x9 product, and blanks the original weights so the
+    /// result tells if the packed ones were used.
+    fn prepacked_model(kernel: Option<&str>) -> TractResult<(TypedModel, Tensor, Tensor)> {
+        let a = Array2::from_shape_fn((5, 7), |(i, j)| (i * 7 + j) as f32 / 10.0);
+        let b = Array2::from_shape_fn((7, 9), |(i, j)| (i + j) as f32 / 3.0);
+        let expected = a.dot(&b).into_tensor();
+        let mut model = TypedModel::default();
+        let wire =
+            model.add_source("b", TypedFact::dt_shape(f32::datum_type(), [7, 9].as_ref())?)?;
+        let op = MatMulUnary::new(a.into_arc_tensor(), false, false, false, None);
+        let wire = model.wire_node("c", op, &[wire])?;
+        model.set_output_outlets(&wire)?;
+        let mut model = model.prepack()?;
+        let op = model.node_mut(1).op_as_mut::<MatMulUnary>().unwrap();
+        let packed = op.prepacked.as_mut().unwrap();
+        if let Some(kernel) = kernel {
+            packed.kernel = kernel.to_string();
+        }
+        op.a = Tensor::zero::<f32>(&[5, 7])?.into_arc_tensor();
+        Ok((model, b.into_tensor(), expected))
+    }
+
+    #[test]
+    fn unary_prepacked() -> TractResult<()> {
+        let (model, b, expected) = prepacked_model(None)?;
+        let output = model.optimize()?.into_runnable()?.run(tvec!(b))?;
+        expected.close_enough(&output[0], true)?;
+        Ok(())
+    }
+
+    #[test]
+    fn unary_prepacked_for_other_kernel() -> TractResult<()> {
+        let (model, b, _) = prepacked_model(Some("other 4x4"))?;
+        let output = model.optimize()?.into_runnable()?.run(tvec!(b))?;
+        assert_eq!(*output[0], Tensor::zero::<f32>(&[5, 9])?);
+        Ok(())
+    }
+
+    #[test]
+    fn prepacked_unpack() -> TractResult<()> {
+        let dense = Array2::from_shape_fn((5, 7), |(i, j)| (i * 7 + j) as f32 / 10.0);
+        let sparse =
+            Array2::from_shape_fn((8, 16), |(i, j)| if j % 4 == 0 { (i + j) as f32 } else { 0.0 });
+        for a in vec![dense, sparse] {
+            let mut model = TypedModel::default();
+            let fact = TypedFact::dt_shape(f32::datum_type(), [a.shape()[1], 3].as_ref())?;
+            let wire = model.add_source("b", fact)?;
+            let op = MatMulUnary::new(a.clone().into_arc_tensor(), false, false, false, None);
+            let wire = model.wire_node("c", op, &[wire])?;
+            model.set_output_outlets(&wire)?;
+            let model = model.prepack()?;
+            let op = model.node(1).op_as::<MatMulUnary>().unwrap();
+            assert_eq!(op.prepacked.as_ref().unwrap().unpack()?, a.into_tensor());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unary_split_over_k_concat_keeps_single_row() -> TractResult<()> {
+        use crate::ops::array::{ConcatSlice, TypedConcat};
+        // a is 1xk: splitting it over k must not squeeze its m axis away
+        let a = tensor2(&[[1f32, 2., 3., 4.]]);
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [3, 2].as_ref())?;
+        let left = model.add_source("left", fact.clone())?;
+        let right = model.add_source("right", fact)?;
+        let concat = TypedConcat::new(1, tvec!(ConcatSlice::Var, ConcatSlice::Var));
+        let b = model.wire_node("b", concat, &[left, right])?;
+        let op = MatMulUnary::new(a.into_arc_tensor(), false, true, false, None);
+        let c = model.wire_node("c", op, &b)?;
+        model.set_output_outlets(&c)?;
+        let inputs = tvec!(
+            tensor2(&[[1f32, 0.], [0., 1.], [1., 1.]]),
+            tensor2(&[[0f32, 1.], [1., 0.], [-1., 1.]])
+        );
+        let expected = model.clone().into_runnable()?.run(inputs.clone())?;
+        let decluttered = model.declutter()?;
+        assert!(!decluttered.nodes().iter().any(|n| n.op_is::<TypedConcat>()));
+        let found = decluttered.into_runnable()?.run(inputs)?;
+        expected[0].close_enough(&found[0], true)
+    }
+
     #[test]
     fn batch_input() -> TractResult<()> {
         crate::setup_test_logger();
@@ -777,7 +1192,14 @@ mod test {
         let a = unsafe { Tensor::uninitialized::<f32>(&[1, ci, co])?.into_arc_tensor() };
         wire = model.wire_node(
             "m",
-            MatMulUnary { a, a_trans: true, b_trans: true, c_trans: true, q_params: None },
+            MatMulUnary {
+                a,
+                a_trans: true,
+                b_trans: true,
+                c_trans: true,
+                q_params: None,
+                prepacked: None,
+            },
             &wire,
         )?;
         let b = unsafe { Tensor::uninitialized::<f32>(&[1, 1, co])?.into_arc_tensor() };
@@ -66,6 +66,12 @@ where
         let q = self
             .as_quant_mut()
             .ok_or_else(|| format_err!("try to zero_point on a float mat mul"))?;
+        let row_scale_factors = params.row_scale_factors()?;
+        if let Some(factors) = &row_scale_factors {
+            if factors.len() != q.as_mmm().m() {
+                bail!("Expected {} row scale factors, got {}", q.as_mmm().m(), factors.len())
+            }
+        }
         unsafe {
             if let Some(t) = params.zero_point_a.as_ref() {
                 if t.rank() == 0 {
@@ -84,7 +90,9 @@ where
             if let Some(t) = params.zero_point_c.as_ref() {
                 q.set_zero_point_c_scalar(t.cast_to_scalar()?)
             }
-            if let Some(factor) = params.scale_factor {
+            if let Some(factors) = row_scale_factors {
+                q.set_scale_factor_vector(factors);
+            } else if let Some(factor) = params.scale_factor {
                 q.set_scale_factor(factor);
             }
         }
@@ -106,3 +114,20 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn row_scale_factors_must_match_rows() -> TractResult<()> {
+        let mut mmm = MMMWrapper::Quant(tract_linalg::ops().qmmm_i8_i8.make(2, 3, 4)?);
+        let factors = tensor1(&[0.5f32, 0.25, 0.125]);
+        let params = QParams::new(i8::datum_type()).with_scale_factor_rows(&factors)?;
+        assert!(mmm.set_quant_params(&params).is_err());
+        let factors = tensor1(&[0.5f32, 0.25]);
+        let params = QParams::new(i8::datum_type()).with_scale_factor_rows(&factors)?;
+        mmm.set_quant_params(&params)?;
+        Ok(())
+    }
+}
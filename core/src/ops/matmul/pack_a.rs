@@ -0,0 +1,169 @@
+use std::fmt;
+use std::ops::{Add, Mul};
+
+use ndarray::*;
+use num_traits::Zero;
+
+use crate::internal::*;
+
+use tract_linalg::mmm::{MatMatMul, MatrixStoreSpec};
+
+/// Constant A operand of a matrix product, packed ahead of time.
+///
+/// Packing depends on the kernel, so the packed tensors are tagged with the id
+/// of the kernel they were packed for (`MatMatMul::kernel_id`) and with the
+/// storage the kernel reads them with. Codegen reuses them when it selects the
+/// same kernel for the same product, and packs the original weights again
+/// otherwise.
+#[derive(Debug, Clone, Hash, PartialEq)]
+pub struct PackedA {
+    pub kernel: String,
+    pub m: usize,
+    pub k: usize,
+    pub storage: MatrixStoreSpec,
+    /// One packed matrix for each position in the prefix axes of A.
+    pub packed: ArrayD<Arc<Tensor>>,
+}
+
+impl PackedA {
+    pub fn new<TA, TB, TC, TI>(
+        mmm: &dyn MatMatMul<TA, TB, TC, TI>,
+        packed: ArrayD<Arc<Tensor>>,
+    ) -> PackedA
+    where
+        TA: Datum + Copy + Zero,
+        TB: Datum + Copy + Zero,
+        TC: Datum + Copy,
+        TI: Datum + Copy + Add + Mul + Zero + fmt::Debug,
+    {
+        PackedA {
+            kernel: mmm.kernel_id(),
+            m: mmm.m(),
+            k: mmm.k(),
+            storage: mmm.a_storage().clone(),
+            packed,
+        }
+    }
+
+    /// Packed matrices for `mmm`, with `prefix` the prefix shape of A, if
+    /// they were packed for this kernel and product.
+    ///
+    /// On success, `mmm` is set up to read A from them.
+    pub unsafe fn reuse<TA, TB, TC, TI>(
+        &self,
+        mmm: &mut dyn MatMatMul<TA, TB, TC, TI>,
+        prefix: &[usize],
+    ) -> TractResult<Option<ArrayD<Arc<Tensor>>>>
+    where
+        TA: Datum + Copy + Zero,
+        TB: Datum + Copy + Zero,
+        TC: Datum + Copy,
+        TI: Datum + Copy + Add + Mul + Zero + fmt::Debug,
+    {
+        if self.kernel != mmm.kernel_id() || (self.m, self.k) != (mmm.m(), mmm.k()) {
+            return Ok(None);
+        }
+        // axis changes may have added or removed unit axes since packing
+        if self.packed.shape().iter().filter(|d| **d != 1).ne(prefix.iter().filter(|d| **d != 1)) {
+            return Ok(None);
+        }
+        let packer = mmm.a_pack();
+        let len = match &self.storage {
+            MatrixStoreSpec::Packed { .. } if self.storage == *mmm.a_storage() => packer.len(),
+            MatrixStoreSpec::BlockSparse { k, panel_offsets, panel_ks, ks } => {
+                let panels = (self.m + packer.mr() - 1) / packer.mr();
+                if *k != self.k
+                    || panel_offsets.len() != panels
+                    || panel_ks.len() != panels + 1
+                    || panel_ks.windows(2).any(|w| w[0] > w[1])
+                    || panel_ks[panels] != ks.len()
+                    || ks.iter().any(|&i| i >= self.k)
+                {
+                    return Ok(None);
+                }
+                (0..panels)
+                    .map(|p| panel_offsets[p] + (panel_ks[p + 1] - panel_ks[p]) * packer.mr())
+                    .max()
+                    .unwrap_or(0)
+            }
+            _ => return Ok(None),
+        };
+        if self.packed.iter().any(|t| t.datum_type() != TA::datum_type() || t.len() < len) {
+            return Ok(None);
+        }
+        let packed = self
+            .packed
+            .iter()
+            .map(|t| aligned(t, packer.alignment()))
+            .collect::<TractResult<Vec<_>>>()?;
+        mmm.a_from_prepacked(&self.storage);
+        Ok(Some(ArrayD::from_shape_vec(prefix, packed)?))
+    }
+
+    /// The matrices before packing, as a tensor of shape `prefix x m x k`.
+    pub fn unpack(&self) -> TractResult<Tensor> {
+        let dt = self.packed.iter().next().map(|t| t.datum_type()).unwrap_or(f32::datum_type());
+        dispatch_numbers!(Self::unpack_t(dt)(self))
+    }
+
+    fn unpack_t<T: Datum + Copy + Zero>(&self) -> TractResult<Tensor> {
+        let (m, k, mr) = (self.m, self.k, self.mr()?);
+        let dense: Vec<usize> = (0..k).collect();
+        let mut unpacked = Array3::<T>::zeros((self.packed.len(), m, k));
+        for (t, mut matrix) in self.packed.iter().zip(unpacked.outer_iter_mut()) {
+            let t = t.as_slice::<T>()?;
+            for p in 0..(m + mr - 1) / mr {
+                let (offset, ks) = match &self.storage {
+                    MatrixStoreSpec::Packed { panel_len } => (p * panel_len, &*dense),
+                    MatrixStoreSpec::BlockSparse { panel_offsets, panel_ks, ks, .. } => {
+                        match (panel_offsets.get(p), panel_ks.get(p), panel_ks.get(p + 1)) {
+                            (Some(&offset), Some(&start), Some(&end))
+                                if start <= end && end <= ks.len() =>
+                            {
+                                (offset, &ks[start..end])
+                            }
+                            _ => bail!("Inconsistent block sparse storage: {:?}", self.storage),
+                        }
+                    }
+                    _ => bail!("Unexpected storage for packed weights: {:?}", self.storage),
+                };
+                if offset + ks.len() * mr > t.len() || ks.iter().any(|&i| i >= k) {
+                    bail!("Packed weights do not match their storage: {:?}", self.storage)
+                }
+                for (ix, &i) in ks.iter().enumerate() {
+                    for j in 0..mr.min(m - p * mr) {
+                        matrix[(p * mr + j, i)] = t[offset + ix * mr + j];
+                    }
+                }
+            }
+        }
+        let mut shape: TVec<usize> = self.packed.shape().into();
+        shape.push(m);
+        shape.push(k);
+        Ok(unpacked.into_shape(&*shape)?.into_tensor())
+    }
+
+    /// Height of the kernel panels, from the kernel id (see
+    /// `MatMatMul::kernel_id`).
+    fn mr(&self) -> TractResult<usize> {
+        self.kernel
+            .rsplit(' ')
+            .next()
+            .and_then(|geometry| geometry.split('x').next())
+            .and_then(|mr| mr.parse::<usize>().ok())
+            .filter(|&mr| mr > 0)
+            .ok_or_else(|| format_err!("No panel geometry in kernel id {:?}", self.kernel))
+    }
+}
+
+/// Packed tensors loaded from a file may not be aligned as the kernel needs.
+fn aligned(t: &Arc<Tensor>, alignment: usize) -> TractResult<Arc<Tensor>> {
+    unsafe {
+        if t.as_bytes().as_ptr() as usize % alignment == 0 {
+            return Ok(t.clone());
+        }
+        let mut copy = Tensor::uninitialized_aligned_dt(t.datum_type(), t.shape(), alignment)?;
+        copy.as_bytes_mut().copy_from_slice(t.as_bytes());
+        Ok(copy.into_arc_tensor())
+    }
+}
@@ -20,6 +20,7 @@ pub mod change_axes;
 pub mod cnn;
 pub mod downsample;
 pub mod dummy;
+pub mod einsum;
 pub mod identity;
 pub mod konst;
 pub mod logic;
@@ -49,16 +50,40 @@ pub enum Validation {
 pub enum Cost {
     Div(DatumType),
     FMA(DatumType),
+    /// Element-wise arithmetic or transcendental operations.
+    ElementWise(DatumType),
+    Comparison(DatumType),
     Buffer(DatumType),
     Params(DatumType),
+    /// Bytes loaded from memory.
+    ReadBytes,
+    /// Bytes stored to memory.
+    WrittenBytes,
 }
 
 impl Cost {
     pub fn is_compute(&self) -> bool {
         use Cost::*;
         match self {
-            FMA(_) | Div(_) => true,
-            Buffer(_) | Params(_) => false,
+            FMA(_) | Div(_) | ElementWise(_) | Comparison(_) => true,
+            Buffer(_) | Params(_) | ReadBytes | WrittenBytes => false,
+        }
+    }
+
+    pub fn is_memory_traffic(&self) -> bool {
+        match self {
+            Cost::ReadBytes | Cost::WrittenBytes => true,
+            _ => false,
+        }
+    }
+
+    /// Number of arithmetic operations accounted for by one unit of this cost.
+    pub fn flops(&self) -> usize {
+        use Cost::*;
+        match self {
+            FMA(_) => 2,
+            Div(_) | ElementWise(_) | Comparison(_) => 1,
+            Buffer(_) | Params(_) | ReadBytes | WrittenBytes => 0,
         }
     }
 }
@@ -247,6 +272,19 @@ pub trait TypedOp:
         Ok(None)
     }
 
+    /// Pack constant weights ahead of time, for the kernel codegen selects
+    /// on this machine.
+    ///
+    /// Returns None if the op has nothing to pack.
+    #[allow(unused_variables)]
+    fn prepack(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<Box<dyn TypedOp>>> {
+        Ok(None)
+    }
+
     /// Nested model multipliers, with label (for profiling).
     #[allow(unused_variables)]
     fn nested_model_multipliers(&self, inputs: &[&TypedFact]) -> Vec<(Cow<str>, f64)> {
@@ -0,0 +1,319 @@
+use crate::internal::*;
+use crate::ops::binary::UnaryOp;
+use crate::ops::math::{Add, Div, Exp, Mul, Recip, Sub};
+use crate::ops::matmul::MatMul;
+use ndarray::*;
+use num_traits::Float;
+
+use super::patterns::*;
+use super::Reducer;
+
+/// `softmax(q·kᵀ·scale + mask)·v`, over the two innermost axes.
+///
+/// Inputs are q `[.., Sq, D]`, k `[.., Sk, D]`, v `[.., Sk, Dv]` and an
+/// optional additive mask broadcastable to `[.., Sq, Sk]`. All inputs have the
+/// same rank, and their leading axes are broadcast together.
+#[derive(Clone, Debug, new, Educe)]
+#[educe(Hash)]
+pub struct ScaledDotProductAttention {
+    #[educe(Hash(method = "hash_f32"))]
+    pub scale: f32,
+}
+
+tract_linalg::impl_dyn_hash!(ScaledDotProductAttention);
+
+impl ScaledDotProductAttention {
+    fn output_shape<D: DimLike>(&self, shapes: &[&[D]]) -> TractResult<TVec<D>> {
+        let rank = shapes[0].len();
+        if rank < 2 || shapes.iter().any(|s| s.len() != rank) {
+            bail!("Attention inputs must have the same rank, at least 2, got {:?}", shapes);
+        }
+        let (q, k, v) = (shapes[0], shapes[1], shapes[2]);
+        if q[rank - 1] != k[rank - 1] || k[rank - 2] != v[rank - 2] {
+            bail!("Inconsistent attention inputs q: {:?} k: {:?} v: {:?}", q, k, v);
+        }
+        let leading: TVec<&[D]> = shapes.iter().map(|s| &s[..rank - 2]).collect();
+        let mut shape = crate::broadcast::multi_broadcast(&leading)
+            .ok_or_else(|| format_err!("Can not broadcast attention inputs {:?}", shapes))?;
+        shape.push(q[rank - 2].clone());
+        shape.push(v[rank - 1].clone());
+        Ok(shape)
+    }
+
+    fn eval_t<T: Datum + Float + LinalgScalar>(
+        &self,
+        inputs: &[Arc<Tensor>],
+    ) -> TractResult<Tensor> {
+        let shapes: TVec<&[usize]> = inputs.iter().map(|t| t.shape()).collect();
+        let output_shape = self.output_shape(&shapes)?;
+        let rank = output_shape.len();
+        let leading = &output_shape[..rank - 2];
+        let batch = leading.iter().product::<usize>();
+        let (sq, sk, d, dv) =
+            (shapes[0][rank - 2], shapes[1][rank - 2], shapes[0][rank - 1], shapes[2][rank - 1]);
+        let broadcast = |t: &Tensor, rows: usize, cols: usize| -> TractResult<Array3<T>> {
+            let mut shape: TVec<usize> = leading.into();
+            shape.push(rows);
+            shape.push(cols);
+            let view = t.to_array_view::<T>()?;
+            let view = view
+                .broadcast(&*shape)
+                .ok_or_else(|| format_err!("Can not broadcast {:?} to {:?}", t.shape(), shape))?;
+            Ok(view.to_owned().into_shape((batch, rows, cols))?)
+        };
+        let q = broadcast(&inputs[0], sq, d)?;
+        let k = broadcast(&inputs[1], sk, d)?;
+        let v = broadcast(&inputs[2], sk, dv)?;
+        let mask = inputs.get(3).map(|m| broadcast(m, sq, sk)).transpose()?;
+        let scale = T::from(self.scale).unwrap();
+        let mut output = Array3::<T>::zeros((batch, sq, dv));
+        for b in 0..batch {
+            let mut scores = q.index_axis(Axis(0), b).dot(&k.index_axis(Axis(0), b).t());
+            scores.mapv_inplace(|s| s * scale);
+            if let Some(mask) = &mask {
+                scores.zip_mut_with(&mask.index_axis(Axis(0), b), |s, &m| *s = *s + m);
+            }
+            for mut row in scores.outer_iter_mut() {
+                let max = row.fold(T::neg_infinity(), |acc, &x| acc.max(x));
+                row.mapv_inplace(|x| (x - max).exp());
+                let sum = row.fold(T::zero(), |acc, &x| acc + x);
+                row.mapv_inplace(|x| x / sum);
+            }
+            output.index_axis_mut(Axis(0), b).assign(&scores.dot(&v.index_axis(Axis(0), b)));
+        }
+        Ok(output.into_shape(&*output_shape)?.into_tensor())
+    }
+}
+
+impl Op for ScaledDotProductAttention {
+    fn name(&self) -> Cow<str> {
+        "ScaledDotProductAttention".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("scale: {}", self.scale)])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for ScaledDotProductAttention {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let output = match inputs[0].datum_type() {
+            DatumType::F32 => self.eval_t::<f32>(&inputs)?,
+            DatumType::F64 => self.eval_t::<f64>(&inputs)?,
+            dt => bail!("ScaledDotProductAttention does not support {:?}", dt),
+        };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for ScaledDotProductAttention {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs.len() != 3 && inputs.len() != 4 {
+            bail!("ScaledDotProductAttention expects 3 or 4 inputs, got {}", inputs.len());
+        }
+        let dt = inputs[0].datum_type;
+        if inputs.iter().any(|i| i.datum_type != dt) {
+            bail!("ScaledDotProductAttention inputs must share a datum type, got {:?}", inputs);
+        }
+        let shapes: TVec<TVec<TDim>> = inputs.iter().map(|i| i.shape.to_tvec()).collect();
+        let shapes: TVec<&[TDim]> = shapes.iter().map(|s| &**s).collect();
+        Ok(tvec!(TypedFact::dt_shape(dt, &*self.output_shape(&shapes)?)?))
+    }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let dt = inputs[0].datum_type;
+        let output = self.output_facts(inputs)?.remove(0);
+        let rank = output.rank();
+        let d = inputs[0].shape[rank - 1].to_i64()?;
+        let dv = output.shape[rank - 1].to_i64()?;
+        let sk = inputs[1].shape[rank - 2].to_i64()?;
+        let scores: TDim = output.shape.iter().take(rank - 1).maybe_product()? * sk;
+        Ok(tvec!((Cost::FMA(dt), scores.clone() * (d + dv + 2)), (Cost::Div(dt), scores)))
+    }
+}
+
+/// Recognizes `softmax(q·kᵀ·scale + mask)·v`, anchored on the final matrix
+/// product.
+pub(crate) fn detect(
+    model: &TypedModel,
+    node: &TypedNode,
+    op: &MatMul,
+) -> TractResult<Option<TypedModelPatch>> {
+    if op.a_trans || op.b_trans || op.c_trans || op.q_params.is_some() {
+        return Ok(None);
+    }
+    let (probs, v) = (node.inputs[0], node.inputs[1]);
+    let dt = model.outlet_fact(probs)?.datum_type;
+    if dt != f32::datum_type() && dt != f64::datum_type() {
+        return Ok(None);
+    }
+    let rank = model.outlet_fact(probs)?.rank();
+    if rank < 2 || model.outlet_fact(v)?.rank() != rank {
+        return Ok(None);
+    }
+    let last = tvec!(rank - 1);
+
+    // softmax over the last axis
+    let exp = if let Some([num, denum]) = binary::<Div>(model, probs) {
+        Some((num, denum))
+    } else if let Some([a, b]) = binary::<Mul>(model, probs) {
+        element_wise::<Recip>(model, b)
+            .map(|(_, denum)| (a, denum))
+            .or_else(|| element_wise::<Recip>(model, a).map(|(_, denum)| (b, denum)))
+    } else {
+        None
+    };
+    let exp = match exp {
+        Some((exp, denum)) if reduce(model, denum, Reducer::Sum) == Some((last.clone(), exp)) => {
+            exp
+        }
+        _ => return Ok(None),
+    };
+    let scores = match element_wise::<Exp>(model, exp).and_then(|(_, e)| binary::<Sub>(model, e)) {
+        Some([s, max]) if reduce(model, max, Reducer::Max) == Some((last, s)) => s,
+        _ => return Ok(None),
+    };
+
+    // optional mask, optional scaling
+    let mut mask: Option<Mask> = None;
+    let mut scaled = scores;
+    if let Some([a, b]) = binary::<Add>(model, scores) {
+        let (m, s) = if model.node(a.node).op_is::<MatMul>() || unary::<Mul>(model, a)?.is_some() {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        mask = Some(Mask::Wire(m));
+        scaled = s;
+    } else if let Some(add) = model.node(scores.node).op_as::<UnaryOp>() {
+        if add.mini_op.is::<Add>() {
+            mask = Some(Mask::Konst(add.a.clone()));
+            scaled = model.node(scores.node).inputs[0];
+        }
+    }
+    let (scale, product) = unary::<Mul>(model, scaled)?.unwrap_or((1.0, scaled));
+
+    let qk = model.node(product.node);
+    let qk_op = if let Some(qk_op) = qk.op_as::<MatMul>() {
+        qk_op
+    } else {
+        return Ok(None);
+    };
+    if qk_op.a_trans || qk_op.c_trans || qk_op.q_params.is_some() {
+        return Ok(None);
+    }
+    let q = qk.inputs[0];
+    if model.outlet_fact(q)?.rank() != rank || model.outlet_fact(qk.inputs[1])?.rank() != rank {
+        return Ok(None);
+    }
+
+    let mut patch = TypedModelPatch::new("fuse scaled dot-product attention");
+    let name = &node.name;
+    let mut inputs = tvec!(patch.tap_model(model, q)?);
+    if qk_op.b_trans {
+        inputs.push(patch.tap_model(model, qk.inputs[1])?);
+    } else {
+        let kt = model.node(qk.inputs[1].node);
+        let swap = match kt.op_as::<AxisOp>() {
+            Some(AxisOp::Move(from, to)) => from.min(to) + 2 == rank && from.max(to) + 1 == rank,
+            _ => false,
+        };
+        if swap {
+            inputs.push(patch.tap_model(model, kt.inputs[0])?);
+        } else {
+            let kt = patch.tap_model(model, qk.inputs[1])?;
+            let k =
+                patch.wire_node(format!("{}.k", name), AxisOp::Move(rank - 2, rank - 1), &[kt])?[0];
+            inputs.push(k);
+        }
+    }
+    inputs.push(patch.tap_model(model, v)?);
+    match mask {
+        Some(Mask::Wire(mask)) => inputs.push(patch.tap_model(model, mask)?),
+        Some(Mask::Konst(mask)) => {
+            inputs.push(patch.add_const(format!("{}.mask", name), mask)?);
+        }
+        None => (),
+    }
+    for input in &inputs {
+        let fact = patch.outlet_fact(*input)?;
+        if fact.rank() != rank || fact.datum_type != dt {
+            return Ok(None);
+        }
+    }
+    let wire = patch.wire_node(name, ScaledDotProductAttention::new(scale), &inputs)?[0];
+    if patch.outlet_fact(wire)? != model.outlet_fact(node.id.into())? {
+        return Ok(None);
+    }
+    patch.shunt_outside(model, node.id.into(), wire)?;
+    Ok(Some(patch))
+}
+
+enum Mask {
+    Wire(OutletId),
+    Konst(Arc<Tensor>),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ops::math;
+    use crate::ops::nn::Reduce;
+
+    fn source(model: &mut TypedModel, name: &str, shape: &[usize]) -> TractResult<OutletId> {
+        model.add_source(name, TypedFact::dt_shape(f32::datum_type(), shape)?)
+    }
+
+    fn data(shape: &[usize], seed: f32) -> TractResult<Tensor> {
+        let len = shape.iter().product::<usize>();
+        let values: Vec<f32> = (0..len).map(|i| (i as f32 * seed).sin()).collect();
+        tensor1(&values).into_shape(shape)
+    }
+
+    #[test]
+    fn detect_decomposed_attention() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let q = source(&mut model, "q", &[2, 3, 4])?;
+        let k = source(&mut model, "k", &[2, 5, 4])?;
+        let v = source(&mut model, "v", &[2, 5, 6])?;
+        let mask = source(&mut model, "mask", &[2, 3, 5])?;
+        let kt = model.wire_node("kt", AxisOp::Move(2, 1), &[k])?[0];
+        let s = model.wire_node("qk", MatMul::default(), &[q, kt])?[0];
+        let scale = model.add_const("scale", tensor3(&[[[0.5f32]]]))?;
+        let s = model.wire_node("scaled", math::mul::bin_typed(), &[s, scale])?[0];
+        let s = model.wire_node("masked", math::add::bin_typed(), &[s, mask])?[0];
+        let max = model.wire_node("max", Reduce::new(tvec!(2), Reducer::Max), &[s])?[0];
+        let s = model.wire_node("normed", math::sub::bin_typed(), &[s, max])?[0];
+        let e = model.wire_node("exp", math::exp(), &[s])?[0];
+        let sum = model.wire_node("sum", Reduce::new(tvec!(2), Reducer::Sum), &[e])?[0];
+        let p = model.wire_node("softmax", math::div::bin_typed(), &[e, sum])?[0];
+        let y = model.wire_node("y", MatMul::default(), &[p, v])?[0];
+        model.set_output_outlets(&[y])?;
+
+        let inputs = tvec!(
+            data(&[2, 3, 4], 0.3)?,
+            data(&[2, 5, 4], 0.7)?,
+            data(&[2, 5, 6], 1.1)?,
+            data(&[2, 3, 5], 0.2)?
+        );
+        let expected = SimplePlan::new(&model)?.run(inputs.clone())?;
+        let decluttered = model.declutter()?;
+        assert!(decluttered.nodes().iter().any(|n| n.op_is::<ScaledDotProductAttention>()));
+        assert!(!decluttered.nodes().iter().any(|n| n.op_is::<MatMul>()));
+        let found = SimplePlan::new(&decluttered)?.run(inputs)?;
+        found[0].close_enough(&expected[0], true)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::internal::*;
+use crate::ops::math::{erf_f32, Add, Mul};
+
+use super::patterns::*;
+
+element_wise!(gelu, Gelu { approximate: bool },
+    [f32] => |op, xs| {
+        if op.approximate {
+            xs.iter_mut().for_each(|x| *x = gelu_tanh_f32(*x));
+        } else {
+            xs.iter_mut().for_each(|x| *x = 0.5 * *x * (1.0 + erf_f32(*x * std::f32::consts::FRAC_1_SQRT_2)));
+        }
+        Ok(())
+    };
+    cost: |dt| {tvec!((Cost::FMA(dt), 16), (Cost::Div(dt), 1))};
+    validation: Validation::Rounding
+);
+
+fn gelu_tanh_f32(x: f32) -> f32 {
+    const SQRT_2_OVER_PI: f32 = 0.7978845608;
+    0.5 * x * (1.0 + (SQRT_2_OVER_PI * (x + 0.044715 * x * x * x)).tanh())
+}
+
+/// Recognizes `0.5 * x * (1 + erf(x / √2))`, anchored on the erf node.
+///
+/// The 0.5 factor may be applied to x, to the erf term or to the product.
+pub(crate) fn detect(model: &TypedModel, node: &TypedNode) -> TractResult<Option<TypedModelPatch>> {
+    if node.outputs[0].fact.datum_type != f32::datum_type() {
+        return Ok(None);
+    }
+    let x = match unary::<Mul>(model, node.inputs[0])? {
+        Some((k, x)) if approx(k, std::f32::consts::FRAC_1_SQRT_2) => x,
+        _ => return Ok(None),
+    };
+    let mut term = match single_successor(model, node.id.into()) {
+        Some(succ) if unary::<Add>(model, succ.id.into())? == Some((1.0, node.id.into())) => {
+            OutletId::from(succ.id)
+        }
+        _ => return Ok(None),
+    };
+    let mut halved = false;
+    if let Some(succ) = single_successor(model, term) {
+        if unary::<Mul>(model, succ.id.into())? == Some((0.5, term)) {
+            term = succ.id.into();
+            halved = true;
+        }
+    }
+    for succ in model.outlet_successors(term) {
+        let product = OutletId::new(succ.node, 0);
+        let other = match binary::<Mul>(model, product) {
+            Some([a, b]) if a == term => b,
+            Some([a, b]) if b == term => a,
+            _ => continue,
+        };
+        if other == x && halved {
+            return patch(model, x, product);
+        } else if other == x {
+            if let Some(half) = single_successor(model, product) {
+                if unary::<Mul>(model, half.id.into())? == Some((0.5, product)) {
+                    return patch(model, x, half.id.into());
+                }
+            }
+        } else if !halved && unary::<Mul>(model, other)? == Some((0.5, x)) {
+            return patch(model, x, product);
+        }
+    }
+    Ok(None)
+}
+
+fn patch(
+    model: &TypedModel,
+    x: OutletId,
+    output: OutletId,
+) -> TractResult<Option<TypedModelPatch>> {
+    if model.outlet_fact(x)?.shape != model.outlet_fact(output)?.shape {
+        return Ok(None);
+    }
+    let mut patch = TypedModelPatch::new("fuse gelu");
+    let wire = patch.tap_model(model, x)?;
+    let wire = patch.wire_node(&model.node(output.node).name, gelu(false), &[wire])?[0];
+    patch.shunt_outside(model, output, wire)?;
+    Ok(Some(patch))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ops::element_wise::ElementWiseOp;
+    use crate::ops::math;
+
+    #[test]
+    fn detect_decomposed_gelu() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize, 5].as_ref())?)?;
+        let sqrt2 = model.add_const("sqrt2", tensor2(&[[std::f32::consts::SQRT_2]]))?;
+        let h = model.wire_node("h", math::div::bin_typed(), &[x, sqrt2])?[0];
+        let e = model.wire_node("erf", math::erf(), &[h])?[0];
+        let one = model.add_const("one", tensor2(&[[1f32]]))?;
+        let a = model.wire_node("a", math::add::bin_typed(), &[e, one])?[0];
+        let m = model.wire_node("m", math::mul::bin_typed(), &[x, a])?[0];
+        let half = model.add_const("half", tensor2(&[[0.5f32]]))?;
+        let y = model.wire_node("y", math::mul::bin_typed(), &[m, half])?[0];
+        model.set_output_outlets(&[y])?;
+
+        let input: Vec<f32> = (0..10).map(|i| i as f32 * 0.6 - 3.0).collect();
+        let input = tensor1(&input).into_shape(&[2, 5])?;
+        let expected = SimplePlan::new(&model)?.run(tvec!(input.clone()))?;
+        let decluttered = model.declutter()?;
+        assert_eq!(decluttered.nodes().len(), 2);
+        let op = decluttered.node(1).op_as::<ElementWiseOp>().unwrap();
+        assert!(op.0.is::<Gelu>());
+        let found = SimplePlan::new(&decluttered)?.run(tvec!(input))?;
+        found[0].close_enough(&expected[0], true)
+    }
+}
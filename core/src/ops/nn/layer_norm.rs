@@ -0,0 +1,240 @@
+use crate::internal::*;
+use crate::ops::element_wise::ElementWiseOp;
+use crate::ops::math::{Add, Div, Mul, Pow, Recip, Rsqrt, Square, Sub};
+use num_traits::Float;
+
+use super::patterns::*;
+
+/// Normalizes the input to zero mean and unit variance over `axes`.
+///
+/// Scale and bias are not part of the op: they stay as the element-wise
+/// operations following it.
+#[derive(Clone, Debug, new, Educe)]
+#[educe(Hash)]
+pub struct LayerNorm {
+    pub axes: TVec<usize>,
+    #[educe(Hash(method = "hash_f32"))]
+    pub epsilon: f32,
+}
+
+tract_linalg::impl_dyn_hash!(LayerNorm);
+
+impl LayerNorm {
+    fn eval_t<T: Datum + Float>(&self, input: &Tensor) -> TractResult<Tensor> {
+        let rank = input.rank();
+        let mut permutation: TVec<usize> = (0..rank).filter(|ax| !self.axes.contains(ax)).collect();
+        permutation.extend(self.axes.iter().copied());
+        let inner = self.axes.iter().map(|&ax| input.shape()[ax]).product::<usize>();
+        let mut t = input.clone().permute_axes(&permutation)?;
+        if inner > 0 {
+            let n = T::from(inner).unwrap();
+            let epsilon = T::from(self.epsilon).unwrap();
+            for chunk in t.as_slice_mut::<T>()?.chunks_mut(inner) {
+                let mean = chunk.iter().fold(T::zero(), |acc, &x| acc + x) / n;
+                let var = chunk.iter().fold(T::zero(), |acc, &x| acc + (x - mean) * (x - mean)) / n;
+                let norm = (var + epsilon).sqrt().recip();
+                chunk.iter_mut().for_each(|x| *x = (*x - mean) * norm);
+            }
+        }
+        let mut inverse = tvec!(0; rank);
+        for (ix, &ax) in permutation.iter().enumerate() {
+            inverse[ax] = ix;
+        }
+        t.permute_axes(&inverse)
+    }
+}
+
+impl Op for LayerNorm {
+    fn name(&self) -> Cow<str> {
+        "LayerNorm".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axes: {:?} epsilon: {}", self.axes, self.epsilon)])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for LayerNorm {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let output = match inputs[0].datum_type() {
+            DatumType::F32 => self.eval_t::<f32>(&inputs[0])?,
+            DatumType::F64 => self.eval_t::<f64>(&inputs[0])?,
+            dt => bail!("LayerNorm does not support {:?}", dt),
+        };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for LayerNorm {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*inputs[0].shape.to_tvec())?))
+    }
+
+    fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
+        let input = model.outlet_fact(node.inputs[0])?;
+        let axes = (0..input.rank())
+            .filter(|axis| !self.axes.contains(axis))
+            .map(|axis| AxisInfo::simple(axis))
+            .collect::<TVec<_>>();
+        Ok(axes.into())
+    }
+
+    fn change_axes(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+        _io: InOut,
+        change: &AxisOp,
+    ) -> TractResult<Option<AxisChangeConsequence>> {
+        let mut axes = tvec!();
+        for normalized in &self.axes {
+            if let Some(axis) = change.transform_axis(*normalized) {
+                axes.push(axis);
+            } else {
+                return Ok(None);
+            }
+        }
+        let op = Some(Box::new(Self { axes, ..self.clone() }) as _);
+        Ok(Some(AxisChangeConsequence::new(model, node, op, change)))
+    }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let dt = inputs[0].datum_type;
+        let count: TDim = inputs[0].shape.iter().maybe_product()?;
+        Ok(tvec!((Cost::FMA(dt), count * 4)))
+    }
+}
+
+/// Recognizes `(x - mean(x)) / sqrt(mean((x - mean(x))²) + ε)`, anchored on
+/// the square root (or reciprocal square root) node.
+pub(crate) fn detect(model: &TypedModel, node: &TypedNode) -> TractResult<Option<TypedModelPatch>> {
+    let dt = node.outputs[0].fact.datum_type;
+    if dt != f32::datum_type() && dt != f64::datum_type() {
+        return Ok(None);
+    }
+    let mut variance = node.inputs[0];
+    let mut epsilon = 0.0;
+    if let Some((eps, input)) = unary::<Add>(model, variance)? {
+        epsilon = eps;
+        variance = input;
+    }
+    let (squared, axes) = if let Some(mean) = mean(model, variance)? {
+        mean
+    } else {
+        return Ok(None);
+    };
+    let centered = if let Some((_, input)) = element_wise::<Square>(model, squared) {
+        input
+    } else if let Some([input, exponent]) = binary::<Pow>(model, squared) {
+        match &model.outlet_fact(exponent)?.konst {
+            Some(k) if uniform(k)? == Some(2.0) => input,
+            _ => return Ok(None),
+        }
+    } else if let Some([a, b]) = binary::<Mul>(model, squared) {
+        if a != b {
+            return Ok(None);
+        }
+        a
+    } else {
+        return Ok(None);
+    };
+    let x = match binary::<Sub>(model, centered) {
+        Some([x, m]) if mean(model, m)? == Some((x, axes.clone())) => x,
+        _ => return Ok(None),
+    };
+
+    let mut norm = OutletId::new(node.id, 0);
+    let is_rsqrt = node.op_as::<ElementWiseOp>().map(|op| op.0.is::<Rsqrt>()).unwrap_or(false);
+    if !is_rsqrt {
+        let succ = if let Some(succ) = single_successor(model, norm) {
+            succ
+        } else {
+            return Ok(None);
+        };
+        if binary::<Div>(model, succ.id.into()) == Some([centered, norm]) {
+            return patch(model, x, axes, epsilon, succ.id.into());
+        } else if element_wise::<Recip>(model, succ.id.into()).is_some() {
+            norm = succ.id.into();
+        } else {
+            return Ok(None);
+        }
+    }
+    for succ in model.outlet_successors(norm) {
+        let output = OutletId::new(succ.node, 0);
+        match binary::<Mul>(model, output) {
+            Some([a, b]) if (a == centered && b == norm) || (a == norm && b == centered) => {
+                return patch(model, x, axes, epsilon, output)
+            }
+            _ => (),
+        }
+    }
+    Ok(None)
+}
+
+fn patch(
+    model: &TypedModel,
+    x: OutletId,
+    axes: TVec<usize>,
+    epsilon: f32,
+    output: OutletId,
+) -> TractResult<Option<TypedModelPatch>> {
+    let mut patch = TypedModelPatch::new("fuse layer normalization");
+    let wire = patch.tap_model(model, x)?;
+    let wire =
+        patch.wire_node(&model.node(output.node).name, LayerNorm::new(axes, epsilon), &[wire])?[0];
+    patch.shunt_outside(model, output, wire)?;
+    Ok(Some(patch))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ops::math;
+    use crate::ops::nn::{Reduce, Reducer};
+
+    fn wire_mean(model: &mut TypedModel, name: &str, x: OutletId) -> TractResult<OutletId> {
+        let sum =
+            model.wire_node(format!("{}.sum", name), Reduce::new(tvec!(2), Reducer::Sum), &[x])?[0];
+        let n = model.add_const(format!("{}.n", name), tensor3(&[[[4f32]]]))?;
+        Ok(model.wire_node(name, math::div::bin_typed(), &[sum, n])?[0])
+    }
+
+    #[test]
+    fn detect_decomposed_layer_norm() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = model
+            .add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize, 3, 4].as_ref())?)?;
+        let mean = wire_mean(&mut model, "mean", x)?;
+        let d = model.wire_node("d", math::sub::bin_typed(), &[x, mean])?[0];
+        let two = model.add_const("two", tensor3(&[[[2f32]]]))?;
+        let sq = model.wire_node("sq", math::pow::bin_typed(), &[d, two])?[0];
+        let var = wire_mean(&mut model, "var", sq)?;
+        let eps = model.add_const("eps", tensor3(&[[[1e-5f32]]]))?;
+        let var = model.wire_node("var_eps", math::add::bin_typed(), &[var, eps])?[0];
+        let std = model.wire_node("std", math::sqrt(), &[var])?[0];
+        let y = model.wire_node("y", math::div::bin_typed(), &[d, std])?[0];
+        model.set_output_outlets(&[y])?;
+
+        let input: Vec<f32> = (0..24).map(|i| (i as f32 * 0.37).sin()).collect();
+        let input = tensor1(&input).into_shape(&[2, 3, 4])?;
+        let expected = SimplePlan::new(&model)?.run(tvec!(input.clone()))?;
+        let decluttered = model.declutter()?;
+        assert!(decluttered.nodes().iter().any(|n| n.op_is::<LayerNorm>()));
+        assert_eq!(decluttered.nodes().len(), 2);
+        let found = SimplePlan::new(&decluttered)?.run(tvec!(input))?;
+        found[0].close_enough(&expected[0], true)
+    }
+}
@@ -1,7 +1,14 @@
+pub(crate) mod attention;
 mod data_formats;
+pub(crate) mod gelu;
+pub(crate) mod layer_norm;
+mod patterns;
 mod reduce;
 
+pub use self::attention::ScaledDotProductAttention;
 pub use self::data_formats::{BaseDataShape, DataFormat, DataShape};
+pub use self::gelu::{gelu, Gelu};
+pub use self::layer_norm::LayerNorm;
 pub use self::reduce::{Reduce, Reducer};
 
 pub use crate::internal::*;
@@ -0,0 +1,97 @@
+//! Small helpers for the declutter rules recognizing decomposed
+//! normalization, activation and attention subgraphs.
+use crate::internal::*;
+use crate::ops::binary::{BinMiniOp, TypedBinOp, UnaryOp};
+use crate::ops::element_wise::{ElementWiseMiniOp, ElementWiseOp};
+
+use super::{Reduce, Reducer};
+
+/// Value of a floating point tensor if all its elements are equal.
+pub fn uniform(t: &Tensor) -> TractResult<Option<f32>> {
+    if t.datum_type().is_float() && t.len() > 0 && t.is_uniform()? {
+        Ok(Some(t.cast_to_scalar::<f32>()?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Loose float comparison, for constants computed in another precision.
+pub fn approx(a: f32, b: f32) -> bool {
+    (a - b).abs() <= 1e-4 * b.abs().max(1e-6)
+}
+
+/// Element-wise op of type `O` producing `outlet`, and its input.
+pub fn element_wise<O: ElementWiseMiniOp>(
+    model: &TypedModel,
+    outlet: OutletId,
+) -> Option<(&O, OutletId)> {
+    let node = model.node(outlet.node);
+    let op = node.op_as::<ElementWiseOp>()?.0.downcast_ref::<O>()?;
+    Some((op, node.inputs[0]))
+}
+
+/// Uniform constant of the unary op `O` producing `outlet`, and its input.
+pub fn unary<O: BinMiniOp>(
+    model: &TypedModel,
+    outlet: OutletId,
+) -> TractResult<Option<(f32, OutletId)>> {
+    let node = model.node(outlet.node);
+    if let Some(op) = node.op_as::<UnaryOp>() {
+        if op.mini_op.is::<O>() {
+            if let Some(a) = uniform(&op.a)? {
+                return Ok(Some((a, node.inputs[0])));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Inputs of the binary op `O` producing `outlet`.
+pub fn binary<O: BinMiniOp>(model: &TypedModel, outlet: OutletId) -> Option<[OutletId; 2]> {
+    let node = model.node(outlet.node);
+    if node.op_as::<TypedBinOp>()?.0.is::<O>() {
+        Some([node.inputs[0], node.inputs[1]])
+    } else {
+        None
+    }
+}
+
+/// Reduced axes and input of a reduction of kind `reducer` producing `outlet`.
+pub fn reduce(
+    model: &TypedModel,
+    outlet: OutletId,
+    reducer: Reducer,
+) -> Option<(TVec<usize>, OutletId)> {
+    let node = model.node(outlet.node);
+    let op = node.op_as::<Reduce>()?;
+    if op.reducer == reducer {
+        Some((op.axes.clone(), node.inputs[0]))
+    } else {
+        None
+    }
+}
+
+/// The only node consuming `outlet`, if any.
+pub fn single_successor(model: &TypedModel, outlet: OutletId) -> Option<&TypedNode> {
+    match model.outlet_successors(outlet) {
+        [succ] => Some(model.node(succ.node)),
+        _ => None,
+    }
+}
+
+/// Input and reduced axes of a mean: a sum reduction scaled by the
+/// reciprocal of the number of reduced elements.
+pub fn mean(model: &TypedModel, outlet: OutletId) -> TractResult<Option<(OutletId, TVec<usize>)>> {
+    if let Some((scale, sum)) = unary::<crate::ops::math::Mul>(model, outlet)? {
+        if let Some((axes, input)) = reduce(model, sum, Reducer::Sum) {
+            let shape = &model.outlet_fact(input)?.shape;
+            let count: TDim = axes.iter().map(|&ax| &shape[ax]).maybe_product()?;
+            if let Ok(count) = count.to_usize() {
+                if approx(scale, (count as f32).recip()) {
+                    return Ok(Some((input, axes)));
+                }
+            }
+        }
+    }
+    Ok(None)
+}
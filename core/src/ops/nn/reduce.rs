@@ -252,4 +252,16 @@ impl TypedOp for Reduce {
         let op = Some(Box::new(Self { axes, ..self.clone() }) as _);
         Ok(Some(AxisChangeConsequence::new(model, node, op, change)))
     }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let dt = inputs[0].datum_type;
+        let count: TDim = inputs[0].shape.iter().maybe_product()?;
+        let cost = match self.reducer {
+            Reducer::Sum | Reducer::Prod => Cost::ElementWise(dt),
+            Reducer::Max | Reducer::Min | Reducer::ArgMax(_) | Reducer::ArgMin(_) => {
+                Cost::Comparison(dt)
+            }
+        };
+        Ok(tvec!((cost, count)))
+    }
 }
@@ -13,6 +13,12 @@ pub struct QParams {
     pub zero_point_c: Option<Arc<Tensor>>,
     #[educe(Hash(method = "hash_scale"))]
     pub scale_factor: Option<f32>,
+    /// Per-channel factors, one per row of a, applied on top of `scale_factor`.
+    pub scale_factor_rows: Option<Arc<Tensor>>,
+    /// Per-channel factors, one per column of b, applied on top of `scale_factor`.
+    /// Kernels only requantize per row, so these must be moved to rows by
+    /// flipping the product before codegen.
+    pub scale_factor_cols: Option<Arc<Tensor>>,
 }
 
 fn hash_scale<H: std::hash::Hasher>(it: &Option<f32>, state: &mut H) {
@@ -42,6 +48,19 @@ fn cleanup_zeropoint_t<T: Datum + Zero + Copy>(zp: &Arc<Tensor>) -> Option<Arc<T
     }
 }
 
+/// Split per-channel factors into a common scalar factor and, if they are not
+/// all equal, the vector of factors.
+fn cleanup_scale_factors(factors: &Tensor) -> TractResult<(f32, Option<Arc<Tensor>>)> {
+    let factors = factors.cast_to::<f32>()?;
+    let slice = factors.as_slice::<f32>()?;
+    if !slice.is_empty() && slice[1..].iter().all(|&x| x == slice[0]) {
+        Ok((slice[0], None))
+    } else {
+        let len = slice.len();
+        Ok((1.0, Some(factors.into_owned().into_shape(&[len])?.into_arc_tensor())))
+    }
+}
+
 impl QParams {
     pub fn new(dt: DatumType) -> QParams {
         QParams {
@@ -50,6 +69,8 @@ impl QParams {
             zero_point_b: None,
             zero_point_c: None,
             scale_factor: None,
+            scale_factor_rows: None,
+            scale_factor_cols: None,
         }
     }
 
@@ -84,6 +105,53 @@ impl QParams {
     pub fn set_scale_factor(&mut self, scale_factor: f32) {
         self.scale_factor = Some(scale_factor)
     }
+
+    pub fn with_scale_factor_rows(mut self, factors: &Tensor) -> TractResult<QParams> {
+        self.set_scale_factor_rows(factors)?;
+        Ok(self)
+    }
+
+    pub fn with_scale_factor_cols(mut self, factors: &Tensor) -> TractResult<QParams> {
+        self.set_scale_factor_cols(factors)?;
+        Ok(self)
+    }
+
+    pub fn set_scale_factor_rows(&mut self, factors: &Tensor) -> TractResult<()> {
+        let (common, factors) = cleanup_scale_factors(factors)?;
+        self.scale_factor = Some(self.scale_factor.unwrap_or(1.0) * common);
+        self.scale_factor_rows = factors;
+        Ok(())
+    }
+
+    pub fn set_scale_factor_cols(&mut self, factors: &Tensor) -> TractResult<()> {
+        let (common, factors) = cleanup_scale_factors(factors)?;
+        self.scale_factor = Some(self.scale_factor.unwrap_or(1.0) * common);
+        self.scale_factor_cols = factors;
+        Ok(())
+    }
+
+    /// Scale factor of each row of the product, if they are not all the same.
+    pub fn row_scale_factors(&self) -> TractResult<Option<Vec<f32>>> {
+        if self.scale_factor_cols.is_some() {
+            bail!("Per-column requantization is not supported, a and b must be flipped")
+        }
+        let scale = self.scale_factor.unwrap_or(1.0);
+        self.scale_factor_rows
+            .as_ref()
+            .map(|factors| Ok(factors.as_slice::<f32>()?.iter().map(|f| f * scale).collect()))
+            .transpose()
+    }
+
+    /// Parameters for the same product computed with a and b swapped.
+    pub fn flipped(&self) -> QParams {
+        QParams {
+            zero_point_a: self.zero_point_b.clone(),
+            zero_point_b: self.zero_point_a.clone(),
+            scale_factor_rows: self.scale_factor_cols.clone(),
+            scale_factor_cols: self.scale_factor_rows.clone(),
+            ..self.clone()
+        }
+    }
 }
 
 pub fn quantize_linear_f32_u8(x: f32, scale: f32, zero_point: i32) -> u8 {
@@ -150,8 +218,8 @@ fn info_quantize_linear_i8(q: &QuantizeLinearI8) -> TractResult<Vec<String>> {
 #[educe(Hash)]
 pub struct DequantizeLinearF32 {
     #[educe(Hash(method = "hash_f32"))]
-    scale: f32,
-    zero_point: i32,
+    pub scale: f32,
+    pub zero_point: i32,
 }
 
 impl DequantizeLinearF32 {
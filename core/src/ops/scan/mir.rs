@@ -384,6 +384,11 @@ impl Scan {
     ) -> TractResult<Option<TypedModelPatch>> {
         for (model_ix, mapping) in self.output_mapping.iter().enumerate() {
             let slot = if let Some(slot) = mapping.full_slot { slot } else { continue };
+            // the emitter is also needed in the loop if the output feeds a state,
+            // and its last value is not recomputed outside
+            if mapping.state || mapping.last_value_slot.is_some() {
+                continue;
+            }
             let emitter_outlet = self.body.output_outlets()?[model_ix];
             let emitter_node = self.body.node(emitter_outlet.node);
             if emitter_node.outputs[emitter_outlet.slot].successors.len() > 1
@@ -3,9 +3,11 @@ use std::fmt;
 
 mod lir;
 mod mir;
+mod while_loop;
 
 pub use lir::LirScan;
 pub use mir::Scan;
+pub use while_loop::While;
 
 #[derive(Clone, new, Hash)]
 pub enum InputMapping {
@@ -0,0 +1,187 @@
+use crate::internal::*;
+
+/// Runs a body model as long as its condition holds.
+///
+/// The body inputs are the loop state followed by the loop invariants. Its
+/// first output is the condition, a boolean scalar computed from the current
+/// state, the other ones are the state for the next iteration. The op inputs
+/// are the initial state and the invariants, its outputs the state once the
+/// condition is false.
+///
+/// As the iteration count depends on the data, the state must keep the same
+/// type and shape from one iteration to the next.
+#[derive(Debug, Clone, Hash)]
+pub struct While {
+    pub body: TypedModel,
+    pub state_len: usize,
+    decluttered: bool,
+    optimized: bool,
+}
+
+tract_linalg::impl_dyn_hash!(While);
+
+impl While {
+    pub fn new(body: TypedModel, state_len: usize) -> TractResult<While> {
+        if body.output_outlets()?.len() != state_len + 1 {
+            bail!(
+                "While body must output the condition and {} states, found {} outputs",
+                state_len,
+                body.output_outlets()?.len()
+            )
+        }
+        if body.input_outlets()?.len() < state_len {
+            bail!(
+                "While body must take at least {} states, found {} inputs",
+                state_len,
+                body.input_outlets()?.len()
+            )
+        }
+        Ok(While { body, state_len, decluttered: false, optimized: false })
+    }
+}
+
+impl Op for While {
+    fn name(&self) -> Cow<str> {
+        "While".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "{} states, {} invariants",
+            self.state_len,
+            self.body.input_outlets()?.len() - self.state_len
+        )])
+    }
+
+    op_core_mir!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for While {
+    fn is_stateless(&self) -> bool {
+        false
+    }
+
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        let outputs = self.body.output_outlets()?;
+        let cond = SimplePlan::new_for_outputs(self.body.clone(), &outputs[..1])?;
+        let step = SimplePlan::new_for_outputs(self.body.clone(), &outputs[1..])?;
+        Ok(Some(Box::new(WhileState {
+            state_len: self.state_len,
+            cond: TypedSimpleState::new(Arc::new(cond))?,
+            step: TypedSimpleState::new(Arc::new(step))?,
+        })))
+    }
+}
+
+/// Plans for the condition and the step of a While, built once per session.
+#[derive(Clone, Debug)]
+struct WhileState {
+    state_len: usize,
+    cond: TypedSimpleState<TypedModel, Arc<TypedSimplePlan<TypedModel>>>,
+    step: TypedSimpleState<TypedModel, Arc<TypedSimplePlan<TypedModel>>>,
+}
+
+impl OpState for WhileState {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        _op: &dyn Op,
+        inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let mut values: TVec<Arc<Tensor>> = inputs;
+        loop {
+            let inputs = || values.iter().map(|t| t.clone().into_tensor()).collect();
+            if !*self.cond.run(inputs())?[0].to_scalar::<bool>()? {
+                break;
+            }
+            let next = self.step.run(inputs())?;
+            for (ix, t) in next.into_iter().enumerate() {
+                values[ix] = t;
+            }
+        }
+        values.truncate(self.state_len);
+        Ok(values)
+    }
+}
+
+impl TypedOp for While {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs.len() != self.body.input_outlets()?.len() {
+            bail!("While expects {} inputs, got {}", self.body.input_outlets()?.len(), inputs.len())
+        }
+        (1..=self.state_len)
+            .map(|ix| {
+                let fact = self.body.output_fact(ix)?;
+                TypedFact::dt_shape(fact.datum_type, fact.shape.clone())
+            })
+            .collect()
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if !self.decluttered {
+            let mut new = self.clone();
+            new.body = self.body.clone().declutter()?;
+            new.decluttered = true;
+            Ok(Some(TypedModelPatch::replace_single_op(model, node, &node.inputs, new)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn codegen(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if !self.optimized {
+            let mut new = self.clone();
+            new.body = self.body.clone().optimize()?;
+            new.optimized = true;
+            Ok(Some(TypedModelPatch::replace_single_op(model, node, &node.inputs, new)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ops::{logic, math};
+
+    #[test]
+    fn doubles_until_limit() -> TractResult<()> {
+        // while x < limit { x = 2 * x }
+        let mut body = TypedModel::default();
+        let x = body.add_source("x", TypedFact::dt_shape(f32::datum_type(), ())?)?;
+        let limit = body.add_source("limit", TypedFact::dt_shape(f32::datum_type(), ())?)?;
+        let cond = body.wire_node("cond", logic::lesser::bin_typed(), &[x, limit])?[0];
+        let two = body.add_const("two", tensor0(2f32))?;
+        let next = body.wire_node("next", math::mul::bin_typed(), &[x, two])?[0];
+        body.set_output_outlets(&[cond, next])?;
+
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), ())?)?;
+        let limit = model.add_source("limit", TypedFact::dt_shape(f32::datum_type(), ())?)?;
+        let y = model.wire_node("while", While::new(body, 1)?, &[x, limit])?;
+        model.set_output_outlets(&y)?;
+        let mut state = SimpleState::new(SimplePlan::new(&model)?)?;
+        let output = state.run(tvec!(tensor0(3f32), tensor0(100f32)))?;
+        assert_eq!(output, tvec!(rctensor0(192f32)));
+        // the plans live in the op state, and are reused from one run to the next
+        let output = state.run(tvec!(tensor0(5f32), tensor0(100f32)))?;
+        assert_eq!(output, tvec!(rctensor0(160f32)));
+        Ok(())
+    }
+}
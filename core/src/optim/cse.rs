@@ -0,0 +1,132 @@
+use crate::internal::*;
+use std::any::TypeId;
+
+/// Common subexpression elimination: merges nodes applying the same op to
+/// the same inputs.
+///
+/// Nodes are bucketed by op type and inputs, so op parameters (and the
+/// tensors some ops embed) are never hashed, and only merged if `Op::same_as`
+/// says so. Ops not implementing it are never merged. Nodes are visited in
+/// evaluation order, so duplicated chains (like the Shape, Gather, Concat
+/// sequences found in imported graphs) collapse in a single pass.
+#[derive(Debug)]
+pub struct Cse;
+
+impl super::TypedPass for Cse {
+    fn reset(&mut self) -> TractResult<()> {
+        Ok(())
+    }
+
+    fn next(&mut self, model: &TypedModel) -> TractResult<Option<TypedModelPatch>> {
+        let mut patch = TypedModelPatch::default();
+        let mut replaced: HashMap<OutletId, OutletId> = HashMap::default();
+        let mut buckets: HashMap<(TypeId, TVec<OutletId>), TVec<usize>> = HashMap::default();
+        let mut merged = 0;
+        for id in model.eval_order()? {
+            let node = model.node(id);
+            // sources are distinct by definition, constants are handled by
+            // DedupConsts
+            if node.inputs.is_empty() {
+                continue;
+            }
+            let inputs: TVec<OutletId> =
+                node.inputs.iter().map(|i| *replaced.get(i).unwrap_or(i)).collect();
+            let bucket = buckets.entry((node.op().as_any().type_id(), inputs)).or_default();
+            let keep =
+                bucket.iter().find(|other| model.node(**other).op().same_as(node.op())).cloned();
+            if let Some(keep) = keep {
+                for slot in 0..node.outputs.len() {
+                    let tap = patch.tap_model(model, OutletId::new(keep, slot))?;
+                    patch.shunt_outside(model, OutletId::new(id, slot), tap)?;
+                    replaced.insert(OutletId::new(id, slot), OutletId::new(keep, slot));
+                }
+                patch.obliterate(id)?;
+                merged += 1;
+            } else {
+                bucket.push(id);
+            }
+        }
+        if merged == 0 {
+            return Ok(None);
+        }
+        info!("Merged {} common subexpressions", merged);
+        patch.push_context(format!("merged {} common subexpressions", merged));
+        Ok(Some(patch))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ops::array::{ConcatSlice, Gather, TypedConcat};
+    use crate::ops::binary::UnaryOp;
+    use crate::ops::change_axes::AxisOp;
+    use crate::ops::math;
+
+    fn check_merged(model: TypedModel, input: Tensor, nodes: usize) -> TractResult<()> {
+        let expected = SimplePlan::new(&model)?.run(tvec!(input.clone()))?;
+        let decluttered = model.declutter()?;
+        assert_eq!(decluttered.nodes().len(), nodes);
+        let found = SimplePlan::new(&decluttered)?.run(tvec!(input))?;
+        found[0].close_enough(&expected[0], true)
+    }
+
+    #[test]
+    fn shape_chains_are_merged() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let shape = model
+            .add_source("shape", TypedFact::dt_shape(i64::datum_type(), [4usize].as_ref())?)?;
+        let mut wires = tvec!();
+        for ix in 0..2 {
+            let indices = model.add_const(format!("indices-{}", ix), tensor1(&[0i64, 2]))?;
+            let gather =
+                model.wire_node(format!("gather-{}", ix), Gather::new(0), &[shape, indices])?;
+            let concat = TypedConcat {
+                axis: 0,
+                slices: tvec!(ConcatSlice::Var, ConcatSlice::Const(rctensor1(&[-1i64]))),
+            };
+            wires.push(model.wire_node(format!("concat-{}", ix), concat, &gather)?[0]);
+        }
+        let sum = model.wire_node("sum", math::add::bin_typed(), &wires)?;
+        model.set_output_outlets(&sum)?;
+        // shape, indices, gather, concat, sum
+        check_merged(model, tensor1(&[1i64, 3, 5, 7]), 5)
+    }
+
+    #[test]
+    fn duplicate_transposes_are_merged() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?)?;
+        let a = model.wire_node("a", AxisOp::Move(1, 0), &[x])?[0];
+        let b = model.wire_node("b", AxisOp::Move(1, 0), &[x])?[0];
+        let y = model.wire_node("y", math::mul::bin_typed(), &[a, b])?;
+        model.set_output_outlets(&y)?;
+        // x, transpose, mul
+        check_merged(model, tensor2(&[[1f32, 2., 3.], [4., 5., 6.]]), 3)
+    }
+
+    #[test]
+    fn ops_with_different_large_tensors_are_kept() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [1000usize].as_ref())?)?;
+        let ones = tensor1(&[1f32; 1000]);
+        let mut other = ones.clone();
+        other.as_slice_mut::<f32>()?[500] = 2.0;
+        let a = model.wire_node(
+            "a",
+            UnaryOp::new(Box::new(math::Add), ones.into_arc_tensor()),
+            &[x],
+        )?[0];
+        let b = model.wire_node(
+            "b",
+            UnaryOp::new(Box::new(math::Add), other.into_arc_tensor()),
+            &[x],
+        )?[0];
+        let y = model.wire_node("y", math::mul::bin_typed(), &[a, b])?;
+        model.set_output_outlets(&y)?;
+        // x, a, b, mul
+        check_merged(model, tensor1(&[0f32; 1000]), 4)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::internal::*;
+use crate::ops::konst::Const;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Weak;
+
+/// Merges `Const` nodes holding identical tensors, so the weights are only
+/// stored once.
+///
+/// Tensors are bucketed by their hash (floats are hashed by bit pattern, as
+/// with `SloppyHash`) and only merged after a full comparison.
+///
+/// Hashes are remembered across calls, keyed by tensor address: the pass runs
+/// again after every declutter round, and the weights seldom change. The weak
+/// reference keeps the address from being reused while the entry lives.
+#[derive(Debug, Default)]
+pub struct DedupConsts {
+    hashes: HashMap<usize, (Weak<Tensor>, u64)>,
+}
+
+impl DedupConsts {
+    fn hash(&mut self, tensor: &Arc<Tensor>) -> u64 {
+        let key = &**tensor as *const Tensor as usize;
+        if let Some((weak, hash)) = self.hashes.get(&key) {
+            if weak.upgrade().map(|t| Arc::ptr_eq(&t, tensor)).unwrap_or(false) {
+                return *hash;
+            }
+        }
+        let mut hasher = DefaultHasher::new();
+        tensor.hash(&mut hasher);
+        let hash = hasher.finish();
+        self.hashes.insert(key, (Arc::downgrade(tensor), hash));
+        hash
+    }
+}
+
+impl super::TypedPass for DedupConsts {
+    fn reset(&mut self) -> TractResult<()> {
+        self.hashes.clear();
+        Ok(())
+    }
+
+    fn next(&mut self, model: &TypedModel) -> TractResult<Option<TypedModelPatch>> {
+        self.hashes.retain(|_, (weak, _)| weak.strong_count() > 0);
+        let mut buckets: HashMap<u64, TVec<usize>> = HashMap::default();
+        for node in model.nodes() {
+            if let Some(k) = node.op_as::<Const>() {
+                buckets.entry(self.hash(&k.0)).or_default().push(node.id);
+            }
+        }
+        let mut patch = TypedModelPatch::default();
+        let mut merged = 0;
+        let mut saved = 0;
+        for bucket in buckets.values().filter(|b| b.len() > 1) {
+            let mut kept: TVec<(usize, &Arc<Tensor>)> = tvec!();
+            for &id in bucket {
+                let tensor = &model.node(id).op_as::<Const>().unwrap().0;
+                if let Some((keep, _)) = kept.iter().find(|(_, k)| *k == tensor) {
+                    let tap = patch.tap_model(model, OutletId::new(*keep, 0))?;
+                    patch.shunt_outside(model, OutletId::new(id, 0), tap)?;
+                    patch.obliterate(id)?;
+                    merged += 1;
+                    saved += tensor.len() * tensor.datum_type().size_of();
+                } else {
+                    kept.push((id, tensor));
+                }
+            }
+        }
+        if merged == 0 {
+            return Ok(None);
+        }
+        info!("Merged {} duplicate constants, saving {} bytes", merged, saved);
+        patch.push_context(format!("merged {} constants ({} bytes)", merged, saved));
+        Ok(Some(patch))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ops::math;
+
+    #[test]
+    fn shared_weights_are_merged() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?)?;
+        let w = tensor2(&[[1f32, 2., 3.], [4., 5., 6.]]);
+        let w1 = model.add_const("w1", w.clone())?;
+        let w2 = model.add_const("w2", w)?;
+        let y1 = model.wire_node("y1", math::add::bin_typed(), &[x, w1])?[0];
+        let y2 = model.wire_node("y2", math::add::bin_typed(), &[x, w2])?[0];
+        let z = model.wire_node("z", math::mul::bin_typed(), &[y1, y2])?[0];
+        model.set_output_outlets(&[z])?;
+
+        let input = tensor2(&[[1f32, 0., -1.], [2., 3., 4.]]);
+        let expected = SimplePlan::new(&model)?.run(tvec!(input.clone()))?;
+        let decluttered = model.declutter()?;
+        // the two additions become one once they read the same weights
+        assert_eq!(decluttered.nodes().len(), 3);
+        let found = SimplePlan::new(&decluttered)?.run(tvec!(input))?;
+        found[0].close_enough(&expected[0], true)
+    }
+}
@@ -2,12 +2,14 @@ use crate::internal::*;
 use std::fmt::Debug;
 
 pub mod change_axes;
+mod cse;
+mod dedup_consts;
 mod prop_const;
-mod push_split_down;
 
 use self::change_axes::ChangeAxes;
+use self::cse::Cse;
+use self::dedup_consts::DedupConsts;
 use self::prop_const::PropConst;
-use self::push_split_down::PushSplitDown;
 
 pub trait TypedPass: Debug + Send + Sync {
     fn reset(&mut self) -> TractResult<()>;
@@ -16,9 +18,10 @@ pub trait TypedPass: Debug + Send + Sync {
 
 pub fn declutter() -> Vec<Box<dyn TypedPass>> {
     vec![
+        Box::<DedupConsts>::default(),
         Box::new(OpOptim("declutter", TypedOp::declutter)),
         Box::new(PropConst),
-        Box::new(PushSplitDown),
+        Box::new(Cse),
         Box::new(ChangeAxes),
     ]
 }
@@ -26,7 +29,7 @@ pub fn declutter() -> Vec<Box<dyn TypedPass>> {
 pub fn codegen() -> Vec<Box<dyn TypedPass>> {
     vec![
         Box::new(OpOptim("codegen", TypedOp::codegen)),
-        Box::new(PushSplitDown),
+        Box::new(Cse),
         Box::new(OpOptim("fuse", TypedOp::fuse)),
     ]
 }
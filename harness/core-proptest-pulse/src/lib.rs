@@ -137,6 +137,69 @@ proptest! {
         let input = Array1::range(1.0f32, input_len as f32 + 1.0, 1.0);
         proptest_regular_against_pulse(model, pulse as _, input.into_dyn(), 0)?;
     }
+
+    #[test]
+    fn proptest_reflect_pad(pulse in 1i32..3, input_len in 0i32..10, begin in 0i32..3, end in 0i32..3) {
+        use tract_hir::ops::array::{ Pad, PadMode };
+        // reflection needs more input frames than padding frames
+        let input_len = input_len + begin.max(end) + 1;
+        let mut model = InferenceModel::default();
+        let a = model
+            .add_source("a", InferenceFact::dt_shape(f32::datum_type(), shapefactoid!(S)))
+            .unwrap();
+        let pad = model.wire_node("pad", Pad::new(vec![(begin as _, end as _)], PadMode::Reflect), &[a]).unwrap();
+        model.set_output_outlets(&pad).unwrap();
+
+        let input = Array1::range(1.0f32, input_len as f32 + 1.0, 1.0);
+        proptest_regular_against_pulse(model, pulse as _, input.into_dyn(), 0)?;
+    }
+
+    #[test]
+    fn proptest_gather(pulse in 1i32..3, input_len in 0i32..10, indices in proptest::collection::vec(0i64..3, 0..4)) {
+        use tract_hir::ops::array::Gather;
+        let mut model = InferenceModel::default();
+        let a = model
+            .add_source("a", InferenceFact::dt_shape(f32::datum_type(), shapefactoid!(S, 3)))
+            .unwrap();
+        let indices = model.add_const("indices", tensor1(&*indices)).unwrap();
+        let gather = model.wire_node("gather", Gather::new(1), &[a, indices]).unwrap();
+        model.set_output_outlets(&gather).unwrap();
+
+        let input = Array2::from_shape_fn((input_len as usize, 3), |(i, j)| (3 * i + j) as f32);
+        proptest_regular_against_pulse(model, pulse as _, input.into_dyn(), 0)?;
+    }
+
+    #[test]
+    fn proptest_tile(pulse in 1i32..3, input_len in 0i32..10, mult in 1i64..3) {
+        use tract_hir::ops::array::Tile;
+        let mut model = InferenceModel::default();
+        let a = model
+            .add_source("a", InferenceFact::dt_shape(f32::datum_type(), shapefactoid!(S, 2)))
+            .unwrap();
+        let mult = model.add_const("mult", tensor1(&[1i64, mult])).unwrap();
+        let tile = model.wire_node("tile", expand(Tile::default()), &[a, mult]).unwrap();
+        model.set_output_outlets(&tile).unwrap();
+
+        let input = Array2::from_shape_fn((input_len as usize, 2), |(i, j)| (2 * i + j) as f32);
+        proptest_regular_against_pulse(model, pulse as _, input.into_dyn(), 0)?;
+    }
+
+    #[test]
+    fn proptest_binary_with_const(pulse in 1i32..3, input_len in 0i32..10, konst_first in any::<bool>()) {
+        use tract_hir::ops::binary::IntoHir;
+        use tract_hir::ops::math::Sub;
+        let mut model = InferenceModel::default();
+        let a = model
+            .add_source("a", InferenceFact::dt_shape(f32::datum_type(), shapefactoid!(S, 2)))
+            .unwrap();
+        let b = model.add_const("b", tensor2(&[[1f32, -2.0]])).unwrap();
+        let inputs = if konst_first { [b, a] } else { [a, b] };
+        let sub = model.wire_node("sub", Sub.into_hir(), &inputs).unwrap();
+        model.set_output_outlets(&sub).unwrap();
+
+        let input = Array2::from_shape_fn((input_len as usize, 2), |(i, j)| (2 * i + j) as f32);
+        proptest_regular_against_pulse(model, pulse as _, input.into_dyn(), 0)?;
+    }
 }
 
 fn vec(len: impl Strategy<Value = usize>) -> impl Strategy<Value = Vec<f32>> {
@@ -100,6 +100,63 @@ impl InferenceFact {
     pub fn without_value(self) -> InferenceFact {
         InferenceFact { value: GenericFactoid::Any, ..self }
     }
+
+    /// Parses the command line syntax for facts: dimensions separated by `x`,
+    /// optionally followed by a datum type, like `1x3x224x224xf32`. A
+    /// dimension is `_` if unknown, or an expression like `12`, `S` or
+    /// `2.S+1`, whose symbols are looked up (or created) in `symbols`.
+    pub fn parse(symbols: &mut SymbolTable, spec: &str) -> TractResult<InferenceFact> {
+        if spec.len() == 0 {
+            return Ok(InferenceFact::default());
+        }
+        let splits = split_spec(spec);
+        let last = splits.last().unwrap();
+        let (datum_type, shape) = if let Ok(dt) = last.to_lowercase().parse::<DatumType>() {
+            (Some(dt), &splits[0..splits.len() - 1])
+        } else {
+            (None, &*splits)
+        };
+        let shape = ShapeFactoid::closed(
+            shape
+                .iter()
+                .map(|&s| {
+                    Ok(if s == "_" {
+                        GenericFactoid::Any
+                    } else {
+                        GenericFactoid::Only(parse_tdim(symbols, s).with_context(|| {
+                            format!("Parsing dimension {:?} of fact {:?}", s, spec)
+                        })?)
+                    })
+                })
+                .collect::<TractResult<TVec<DimFact>>>()?,
+        );
+        if let Some(dt) = datum_type {
+            Ok(InferenceFact::dt_shape(dt, shape))
+        } else {
+            Ok(InferenceFact::shape(shape))
+        }
+    }
+}
+
+/// Splits a fact spec on its "x" separators, leaving alone the ones inside
+/// dimension expressions, like in `max(S,2)`.
+fn split_spec(spec: &str) -> Vec<&str> {
+    let mut splits = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (ix, c) in spec.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            'x' if depth == 0 && !spec[ix + 1..].starts_with("(") => {
+                splits.push(&spec[start..ix]);
+                start = ix + 1;
+            }
+            _ => (),
+        }
+    }
+    splits.push(&spec[start..]);
+    splits
 }
 
 impl Factoid for InferenceFact {
@@ -145,6 +202,10 @@ impl Fact for InferenceFact {
         Ok(self.unify(&InferenceFact::from(t)).is_ok())
     }
 
+    fn matches_resolved(&self, t: &Tensor, _values: &SymbolValues) -> TractResult<bool> {
+        self.matches(t)
+    }
+
     fn same_as(&self, other: &dyn Fact) -> bool {
         if let Some(other) = other.downcast_ref::<Self>() {
             self.unify(other).is_ok()
@@ -209,3 +270,37 @@ impl<'a> From<&'a Tensor> for InferenceFact {
         InferenceFact::from(t.clone())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dims(dims: &[TDim]) -> ShapeFactoid {
+        ShapeFactoid::closed(dims.iter().map(|d| GenericFactoid::Only(d.clone())).collect())
+    }
+
+    #[test]
+    fn parse_facts() -> TractResult<()> {
+        let mut symbols = SymbolTable::default();
+        let s: TDim = symbols.sym('S').into();
+        assert_eq!(
+            InferenceFact::parse(&mut symbols, "1x3xf32")?,
+            InferenceFact::dt_shape(f32::datum_type(), dims(&[1.into(), 3.into()]))
+        );
+        assert_eq!(
+            InferenceFact::parse(&mut symbols, "SxI64")?,
+            InferenceFact::dt_shape(i64::datum_type(), dims(&[s.clone()]))
+        );
+        let max = parse_tdim(&mut symbols, "max(S,2)")?;
+        assert_eq!(
+            InferenceFact::parse(&mut symbols, "max(S,2)x_")?,
+            InferenceFact::shape(ShapeFactoid::closed(tvec!(
+                GenericFactoid::Only(max),
+                GenericFactoid::Any
+            )))
+        );
+        assert_eq!(InferenceFact::parse(&mut symbols, "")?, InferenceFact::default());
+        assert!(InferenceFact::parse(&mut symbols, "1x3xf31").is_err());
+        Ok(())
+    }
+}
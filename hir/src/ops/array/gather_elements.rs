@@ -0,0 +1,70 @@
+use crate::infer::*;
+use crate::internal::*;
+
+/// GatherElements: high level gather_elements op, accepting a negative axis.
+#[derive(Debug, Clone, new, Hash)]
+pub struct GatherElements {
+    axis: i64,
+}
+
+tract_linalg::impl_dyn_hash!(GatherElements);
+
+impl GatherElements {
+    fn resolve_axis(&self, rank: i64) -> TractResult<usize> {
+        if 0 <= self.axis && self.axis <= rank - 1 {
+            Ok(self.axis as usize)
+        } else if -rank <= self.axis && self.axis < 0 {
+            Ok((self.axis + rank) as usize)
+        } else {
+            bail!("Illegal combination of values for rank and axis: {} and {}", rank, self.axis)
+        }
+    }
+}
+
+impl Expansion for GatherElements {
+    fn name(&self) -> Cow<str> {
+        "GatherElements".into()
+    }
+
+    op_hir!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].rank, &inputs[1].rank)?;
+        s.equals(&outputs[0].shape, &inputs[1].shape)?;
+        s.given(&inputs[0].rank, move |_, rank| self.resolve_axis(rank as i64).map(|_| ()))?;
+        Ok(())
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let rank = target.outlet_fact(inputs[0])?.rank();
+        let axis = self.resolve_axis(rank as i64)?;
+        target.wire_node(prefix, tract_core::ops::array::GatherElements::new(axis), inputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_axis() -> TractResult<()> {
+        let data = tensor2(&[[1i32, 2], [3, 4]]);
+        let indices = tensor2(&[[0i64, 0], [1, 0]]);
+        let output = expand(GatherElements::new(-1)).eval(tvec!(data.into(), indices.into()))?;
+        assert_eq!(*output[0], tensor2(&[[1i32, 1], [4, 3]]));
+        Ok(())
+    }
+}
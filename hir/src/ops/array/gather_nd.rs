@@ -0,0 +1,23 @@
+use crate::infer::*;
+use crate::internal::*;
+
+pub use tract_core::ops::array::GatherNd;
+
+impl InferenceRulesOp for GatherNd {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.given_2(&inputs[0].shape, &inputs[1].shape, move |s, data_shape, indices_shape| {
+            s.equals(&outputs[0].shape, self.compute_shape(&*data_shape, &*indices_shape)?)
+        })
+    }
+
+    as_op!();
+    to_typed!();
+}
@@ -4,12 +4,19 @@ mod concat;
 mod constant_like;
 mod constant_of_shape;
 mod crop;
+mod depth_to_space;
 mod flatten;
 mod gather;
+mod gather_elements;
+mod gather_nd;
 mod pad;
 pub mod permute_axes;
+mod range;
 mod reshape;
+mod reverse_sequence;
 mod rm_dims;
+mod scatter_elements;
+mod scatter_nd;
 mod shape;
 mod size;
 mod slice;
@@ -24,12 +31,19 @@ pub use concat::{Concat, ConcatSlice, TypedConcat};
 pub use constant_like::{ConstantLike, EyeLike};
 pub use constant_of_shape::ConstantOfShape;
 pub use crop::Crop;
+pub use depth_to_space::{DepthToSpace, DepthToSpaceMode, SpaceToDepth};
 pub use flatten::Flatten;
 pub use gather::Gather;
+pub use gather_elements::GatherElements;
+pub use gather_nd::GatherNd;
 pub use pad::{Pad, PadMode};
 pub use permute_axes::PermuteAxes;
+pub use range::Range;
 pub use reshape::Reshape;
+pub use reverse_sequence::ReverseSequence;
 pub use rm_dims::RmDims;
+pub use scatter_elements::ScatterElements;
+pub use scatter_nd::ScatterNd;
 pub use shape::Shape;
 pub use size::Size;
 pub use slice::Slice;
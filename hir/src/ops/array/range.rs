@@ -0,0 +1,33 @@
+use crate::infer::*;
+use crate::internal::*;
+
+pub use tract_core::ops::array::Range;
+
+impl InferenceRulesOp for Range {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+            s.equals(&input.rank, 0)?;
+        }
+        s.equals(&outputs[0].rank, 1)?;
+        s.given_3(
+            &inputs[0].value,
+            &inputs[1].value,
+            &inputs[2].value,
+            move |s, start, limit, delta| {
+                let len = Self::len_dim(&start, &limit, &delta)?;
+                s.equals(&outputs[0].shape[0], len)
+            },
+        )
+    }
+
+    as_op!();
+    to_typed!();
+}
@@ -80,6 +80,15 @@ impl Conv {
         Conv { k_zero_point_input: Some(input), ..self }
     }
 
+    /// Quantized convolutions may mix signedness between data and kernel
+    /// (u8 activations with i8 weights, for instance).
+    fn is_quantized(&self) -> bool {
+        self.override_output_datum_type.is_some()
+            || self.x_zero_point_input.is_some()
+            || self.k_zero_point_input.is_some()
+            || self.y_zero_point_input.is_some()
+    }
+
     pub fn output_shape<D: DimLike>(&self, ishape: &[D], kshape: &[usize]) -> TractResult<TVec<D>> {
         debug_assert_eq!(
             ishape.len()
@@ -123,9 +132,16 @@ impl Conv {
             bail!("Input has {} channels, kernel expects {}", input_shape.c_dim(), channels_in)
         }
         if let Some(kvalue) = kernel.konst.clone() {
+            let kshape = kvalue.shape();
+            let group = self.group.unwrap_or(1);
+            let output_channels = match self.kernel_fmt {
+                KernelFormat::OIHW => kshape[0],
+                KernelFormat::HWIO => kshape[kshape.len() - 1] * group,
+            };
             let mut qp = None;
             let dt = self.override_output_datum_type.unwrap_or(input.datum_type);
             let mut scale = 1.0;
+            let mut k_scales = None;
             if let Some(slot) = self.x_scale_input {
                 if let Some(ref value) = inputs[slot].borrow().konst {
                     scale *= value.to_scalar::<f32>()?;
@@ -135,7 +151,12 @@ impl Conv {
             }
             if let Some(slot) = self.k_scale_input {
                 if let Some(ref value) = inputs[slot].borrow().konst {
-                    scale *= value.to_scalar::<f32>()?;
+                    if value.len() == 1 {
+                        scale *= value.to_scalar::<f32>()?;
+                    } else {
+                        // per output channel scales
+                        k_scales = Some(value.clone());
+                    }
                 } else {
                     bail!("Filter scale must be const")
                 }
@@ -150,6 +171,16 @@ impl Conv {
             if scale != 1.0 {
                 qp.get_or_insert(QParams::new(dt)).set_scale_factor(scale);
             }
+            if let Some(k_scales) = k_scales {
+                if k_scales.len() != output_channels {
+                    bail!(
+                        "Filter has {} output channels, but {} scale factors",
+                        output_channels,
+                        k_scales.len()
+                    )
+                }
+                qp.get_or_insert(QParams::new(dt)).set_scale_factor_rows(&k_scales)?;
+            }
             if let Some(slot) = self.x_zero_point_input {
                 if let Some(ref value) = inputs[slot].borrow().konst {
                     qp.get_or_insert(QParams::new(dt)).set_zero_point_b(value);
@@ -181,12 +212,6 @@ impl Conv {
                 None
             };
             let spatial_rank = kernel.rank() - 2;
-            let kshape = kvalue.shape();
-            let group = self.group.unwrap_or(1);
-            let output_channels = match self.kernel_fmt {
-                KernelFormat::OIHW => kshape[0],
-                KernelFormat::HWIO => kshape[kshape.len() - 1] * group,
-            };
             let pool_spec = PoolSpec {
                 data_format: self.data_format,
                 padding: self.padding.clone(),
@@ -234,7 +259,9 @@ impl Expansion for Conv {
         s.equals(&inputs[0].rank, k_input.rank.bex() + (has_n as usize as i64 - 1))?;
         s.equals(&outputs[0].rank, &inputs[0].rank)?;
         check_output_arity(&outputs, 1)?;
-        s.equals(&inputs[0].datum_type, &k_input.datum_type)?;
+        if !self.is_quantized() {
+            s.equals(&inputs[0].datum_type, &k_input.datum_type)?;
+        }
         if let Some(dt) = self.override_output_datum_type {
             s.equals(&outputs[0].datum_type, dt)?;
         } else {
@@ -324,6 +351,17 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_filter_scales_per_output_channel() {
+        let op = Conv { k_scale_input: Some(2), ..Conv::default() };
+        let ifact = TypedFact::dt_shape(DatumType::F32, [1usize, 2, 1, 1].as_ref()).unwrap();
+        let kfact = TypedFact::from(rctensor4(&[[[[1f32]], [[1.0]]], [[[1.0]], [[1.0]]]]));
+        let scales = TypedFact::from(rctensor1(&[0.5f32, 0.25]));
+        assert!(op.to_unary(&[&ifact, &kfact, &scales]).unwrap().unwrap().q_params.is_some());
+        let scales = TypedFact::from(rctensor1(&[0.5f32, 0.25, 0.125]));
+        assert!(op.to_unary(&[&ifact, &kfact, &scales]).is_err());
+    }
+
     #[test]
     fn test_infer_onnx_strides_no_padding() {
         let mut op = expand(Conv::default().strides(tvec![2, 2]));
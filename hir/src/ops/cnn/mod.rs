@@ -3,4 +3,4 @@ mod pools;
 
 pub use conv::Conv;
 pub use pools::{SumPool, MaxPool};
-pub use tract_core::ops::cnn::{ConvUnary, PaddingSpec, PoolSpec};
+pub use tract_core::ops::cnn::{ConvUnary, Deconv, KernelFormat, PaddingSpec, PoolSpec};
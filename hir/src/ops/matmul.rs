@@ -47,10 +47,11 @@ impl Expansion for MatMulInference {
     ) -> InferenceResult {
         check_input_arity(&inputs, 2)?;
         check_output_arity(&outputs, 1)?;
-        s.equals(&inputs[0].datum_type, &inputs[1].datum_type)?;
         if let Some(qp) = &self.q_params {
+            // quantized operands may differ in signedness (u8 x i8)
             s.equals(&outputs[0].datum_type, &qp.c_datum_type)?;
         } else {
+            s.equals(&inputs[0].datum_type, &inputs[1].datum_type)?;
             s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
         }
         s.given_2(&inputs[0].shape, &inputs[1].shape, move |s, ashape, bshape| {
@@ -2,7 +2,7 @@ use crate::infer::*;
 use crate::internal::*;
 
 pub use tract_core::ops::scan::Scan;
-pub use tract_core::ops::scan::{InputMapping, OutputMapping, StateInitializer};
+pub use tract_core::ops::scan::{InputMapping, OutputMapping, StateInitializer, While};
 
 #[derive(Debug, Clone, new, Default, Hash)]
 pub struct InferenceScan {
@@ -180,12 +180,12 @@ impl InferenceScan {
                     ])?;
                     facts.push(&mut inputs[*outer_input_ix]);
                     if Factoid::unify_all(
-                        &mut *facts.iter_mut().map(|f| &mut f.datum_type).collect::<TVec<_>>(),
+                        &mut facts.iter_mut().map(|f| &mut f.datum_type).collect::<TVec<_>>(),
                     )? {
                         changed = true;
                     }
                     if Factoid::unify_all(
-                        &mut *facts.iter_mut().map(|f| &mut f.shape).collect::<TVec<_>>(),
+                        &mut facts.iter_mut().map(|f| &mut f.shape).collect::<TVec<_>>(),
                     )? {
                         changed = true;
                     }
@@ -328,3 +328,145 @@ impl InferenceOp for InferenceScan {
 
     as_op!();
 }
+
+/// Inference counterpart of `While`: the body is an `InferenceModel`, with
+/// the same interface.
+#[derive(Debug, Clone, new, Default, Hash)]
+pub struct InferenceWhile {
+    pub body: InferenceModel,
+    pub state_len: usize,
+}
+
+tract_linalg::impl_dyn_hash!(InferenceWhile);
+
+impl Op for InferenceWhile {
+    fn name(&self) -> Cow<str> {
+        "While".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "{} states, {} invariants",
+            self.state_len,
+            self.body.input_outlets()?.len() - self.state_len
+        )])
+    }
+
+    op_hir!();
+    not_a_typed_op!();
+}
+
+impl EvalOp for InferenceWhile {
+    fn is_stateless(&self) -> bool {
+        false
+    }
+
+    fn state(
+        &self,
+        session: &mut SessionState,
+        node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        self.to_mir_while()?.state(session, node_id)
+    }
+}
+
+impl InferenceWhile {
+    pub(super) fn to_mir_while(&self) -> TractResult<While> {
+        While::new(self.body.clone().into_typed()?, self.state_len)
+    }
+
+    fn unify_facts(
+        &mut self,
+        inputs: &mut [InferenceFact],
+        outputs: &mut [InferenceFact],
+    ) -> TractResult<bool> {
+        let mut changed = false;
+        let cond = self.body.output_fact_mut(0)?;
+        if cond.unify_with_mut(&mut InferenceFact::dt_shape(
+            bool::datum_type(),
+            tvec!() as TVec<usize>,
+        ))? {
+            changed = true;
+        }
+        for state in 0..self.state_len {
+            let mut outlets = tvec!(self.body.input_outlets()?[state]);
+            if self.body.output_outlets()?[state + 1] != outlets[0] {
+                outlets.push(self.body.output_outlets()?[state + 1]);
+            }
+            let mut facts = self.body.outlets_fact_mut(&outlets)?;
+            facts.push(&mut inputs[state]);
+            facts.push(&mut outputs[state]);
+            if Factoid::unify_all(
+                &mut facts.iter_mut().map(|f| &mut f.datum_type).collect::<TVec<_>>(),
+            )? {
+                changed = true;
+            }
+            if Factoid::unify_all(&mut facts.iter_mut().map(|f| &mut f.shape).collect::<TVec<_>>())?
+            {
+                changed = true;
+            }
+        }
+        for invariant in self.state_len..inputs.len() {
+            if inputs[invariant].unify_with_mut(self.body.input_fact_mut(invariant)?)? {
+                changed = true;
+            }
+        }
+        Ok(changed)
+    }
+}
+
+impl InferenceOp for InferenceWhile {
+    fn infer_facts(
+        &mut self,
+        inputs: TVec<&InferenceFact>,
+        outputs: TVec<&InferenceFact>,
+        _observed: TVec<&InferenceFact>,
+    ) -> TractResult<(TVec<InferenceFact>, TVec<InferenceFact>, TVec<InferenceFact>)> {
+        if inputs.len() != self.body.input_outlets()?.len() {
+            bail!(
+                "While receives {} inputs, body expects {}",
+                inputs.len(),
+                self.body.input_outlets()?.len()
+            )
+        }
+        if outputs.len() != self.state_len
+            || self.body.output_outlets()?.len() != self.state_len + 1
+        {
+            bail!(
+                "While has {} outputs and a body with {} outputs, expected {} states",
+                outputs.len(),
+                self.body.output_outlets()?.len(),
+                self.state_len
+            )
+        }
+        let mut inputs: TVec<InferenceFact> = inputs.into_iter().cloned().collect();
+        let mut outputs: TVec<InferenceFact> = outputs.into_iter().cloned().collect();
+        loop {
+            let mut changed = self.unify_facts(&mut inputs, &mut outputs)?;
+            if self.body.analyse(false).context("analysing inner model")? {
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+        Ok((inputs, outputs, tvec!()))
+    }
+
+    fn to_typed(
+        &self,
+        _source: &InferenceModel,
+        node: &InferenceNode,
+        target: &mut TypedModel,
+        mapping: &HashMap<OutletId, OutletId>,
+    ) -> TractResult<TVec<OutletId>> {
+        let inputs = node.inputs.iter().map(|m| mapping[m]).collect::<TVec<_>>();
+        target.wire_node(&*node.name, self.to_mir_while()?, &*inputs)
+    }
+
+    fn nboutputs(&self) -> TractResult<usize> {
+        Ok(self.state_len)
+    }
+
+    as_op!();
+}
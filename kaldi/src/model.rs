@@ -32,25 +32,70 @@ pub struct OutputLine {
 #[derive(Clone, Debug, PartialEq)]
 pub enum GeneralDescriptor {
     Append(Vec<GeneralDescriptor>),
+    Const(f32, usize),
+    Failover(Box<GeneralDescriptor>, Box<GeneralDescriptor>),
     IfDefined(Box<GeneralDescriptor>),
     Name(String),
     Offset(Box<GeneralDescriptor>, isize),
+    ReplaceIndex(Box<GeneralDescriptor>, char, isize),
+    Round(Box<GeneralDescriptor>, usize),
+    Scale(f32, Box<GeneralDescriptor>),
+    Sum(Box<GeneralDescriptor>, Box<GeneralDescriptor>),
+    Switch(Vec<GeneralDescriptor>),
 }
 
 impl GeneralDescriptor {
     pub fn inputs(&self) -> TVec<&str> {
-        match self {
-            GeneralDescriptor::Append(ref gds) => gds.iter().fold(tvec!(), |mut acc, gd| {
+        fn union<'a>(gds: &[&'a GeneralDescriptor]) -> TVec<&'a str> {
+            gds.iter().fold(tvec!(), |mut acc, gd| {
                 gd.inputs().iter().for_each(|i| {
                     if !acc.contains(i) {
                         acc.push(i)
                     }
                 });
                 acc
-            }),
+            })
+        }
+        match self {
+            GeneralDescriptor::Append(ref gds) | GeneralDescriptor::Switch(ref gds) => {
+                union(&*gds.iter().collect::<Vec<_>>())
+            }
+            GeneralDescriptor::Const(_, _) => tvec!(),
+            GeneralDescriptor::Failover(ref gd, _) => gd.inputs(),
             GeneralDescriptor::IfDefined(ref gd) => gd.inputs(),
             GeneralDescriptor::Name(ref s) => tvec!(&**s),
             GeneralDescriptor::Offset(ref gd, _) => gd.inputs(),
+            GeneralDescriptor::ReplaceIndex(ref gd, _, _) => gd.inputs(),
+            GeneralDescriptor::Round(ref gd, _) => gd.inputs(),
+            GeneralDescriptor::Scale(_, ref gd) => gd.inputs(),
+            GeneralDescriptor::Sum(ref a, ref b) => union(&[&**a, &**b]),
+        }
+    }
+
+    /// Whether the descriptor reads frames from the past, which are not
+    /// computable at the beginning of the stream.
+    fn refers_to_past(&self) -> bool {
+        use GeneralDescriptor::*;
+        match self {
+            Offset(_, o) if *o < 0 => true,
+            Append(gds) | Switch(gds) => gds.iter().any(|gd| gd.refers_to_past()),
+            Failover(a, b) | Sum(a, b) => a.refers_to_past() || b.refers_to_past(),
+            IfDefined(gd)
+            | Offset(gd, _)
+            | ReplaceIndex(gd, _, _)
+            | Round(gd, _)
+            | Scale(_, gd) => gd.refers_to_past(),
+            Const(_, _) | Name(_) => false,
+        }
+    }
+
+    /// Const and ReplaceIndex(.., t, ..) produce a single frame, broadcast along time by
+    /// their consumer.
+    fn is_time_invariant(&self) -> bool {
+        match self {
+            GeneralDescriptor::Const(_, _) => true,
+            GeneralDescriptor::ReplaceIndex(_, 't', _) => true,
+            _ => false,
         }
     }
 
@@ -96,19 +141,99 @@ impl GeneralDescriptor {
                     expand(tract_hir::ops::array::Concat::new(1)),
                     tvec!(InferenceFact::default()),
                 )?;
-                model.add_edge(OutletId::new(id, 0), inlet)?;
-                for (ix, appendee) in appendees.iter().enumerate() {
-                    let name = format!("{}-{}", name, ix);
-                    appendee.wire(
-                        InletId::new(id, ix),
+                deferred.insert(inlet, name.to_string());
+                // concat needs all appendees to span the same frames: time invariant ones are
+                // broadcast along the first time varying one
+                let reference = if appendees.iter().any(|app| app.is_time_invariant()) {
+                    let ix = appendees
+                        .iter()
+                        .position(|app| !app.is_time_invariant())
+                        .ok_or_else(|| format_err!("Append without time varying input"))?;
+                    let name = format!("{}-{}.ref", name, ix);
+                    let id = model.add_node(
+                        &*name,
+                        tract_hir::ops::identity::Identity::default(),
+                        tvec!(InferenceFact::default()),
+                    )?;
+                    appendees[ix].wire(
+                        InletId::new(id, 0),
                         &*name,
                         model,
                         deferred,
                         adjust_final_offset,
                     )?;
+                    Some(name)
+                } else {
+                    None
+                };
+                for (ix, appendee) in appendees.iter().enumerate() {
+                    let name = format!("{}-{}", name, ix);
+                    let inlet = InletId::new(id, ix);
+                    match &reference {
+                        Some(reference) if appendee.is_time_invariant() => {
+                            let name = format!("{}.TimeBroadcast", name);
+                            let broadcast = model.add_node(
+                                &*name,
+                                crate::ops::descriptor::TimeBroadcast::default(),
+                                tvec!(InferenceFact::default()),
+                            )?;
+                            deferred.insert(inlet, name.to_string());
+                            deferred.insert(InletId::new(broadcast, 0), reference.to_string());
+                            appendee.wire(
+                                InletId::new(broadcast, 1),
+                                &*name,
+                                model,
+                                deferred,
+                                adjust_final_offset,
+                            )?;
+                        }
+                        Some(reference)
+                            if appendees[..ix].iter().all(|a| a.is_time_invariant()) =>
+                        {
+                            deferred.insert(inlet, reference.to_string());
+                        }
+                        _ => appendee.wire(inlet, &*name, model, deferred, adjust_final_offset)?,
+                    }
                 }
                 return Ok(());
             }
+            &Const(value, dim) => {
+                let name = format!("{}.Const", name);
+                model.add_const(
+                    &*name,
+                    tract_ndarray::Array2::from_elem((1, *dim), *value).into_arc_tensor(),
+                )?;
+                deferred.insert(inlet, name);
+                return Ok(());
+            }
+            &Failover(ref first, ref second) => {
+                // without past references, the first descriptor is always computable
+                if !first.refers_to_past() {
+                    return first.wire(inlet, name, model, deferred, adjust_final_offset);
+                }
+                // memories are zero until the referred frame is computed, which is
+                // the fallback to zeros
+                let past = if let IfDefined(past) = &**first { &**past } else { &**first };
+                if let (Offset(n, o), Const(value, _)) = (past, &**second) {
+                    if let Name(_) = &**n {
+                        if *o < 0 && *value == 0.0 {
+                            let if_defined = IfDefined(Box::new(past.clone()));
+                            return if_defined.wire(
+                                inlet,
+                                name,
+                                model,
+                                deferred,
+                                adjust_final_offset,
+                            );
+                        }
+                    }
+                }
+                bail!(
+                    "Unsupported failover from {:?} to {:?}: past frames can only fall back to zeros",
+                    first,
+                    second
+                )
+            }
             &IfDefined(ref o) => {
                 if let &Offset(ref n, ref o) = &**o {
                     if let Name(n) = &**n {
@@ -134,10 +259,76 @@ impl GeneralDescriptor {
                     expand(tract_hir::ops::array::Crop::new(0, crop as usize, 0)),
                     tvec!(InferenceFact::default()),
                 )?;
-                model.add_edge(OutletId::new(id, 0), inlet)?;
+                deferred.insert(inlet, name.to_string());
+                n.wire(InletId::new(id, 0), &*name, model, deferred, adjust_final_offset)?;
+                return Ok(());
+            }
+            &ReplaceIndex(ref n, 'x', 0) => {
+                return n.wire(inlet, name, model, deferred, adjust_final_offset);
+            }
+            &ReplaceIndex(ref n, 't', t) if *t >= 0 => {
+                let name = format!("{}.ReplaceIndex", name);
+                let id = model.add_node(
+                    &*name,
+                    tract_hir::ops::array::Slice::new(0, *t as usize, *t as usize + 1),
+                    tvec!(InferenceFact::default()),
+                )?;
+                deferred.insert(inlet, name.to_string());
                 n.wire(InletId::new(id, 0), &*name, model, deferred, adjust_final_offset)?;
                 return Ok(());
             }
+            &Round(ref n, modulus) if *modulus > 0 => {
+                let name = format!("{}.Round", name);
+                let id = model.add_node(
+                    &*name,
+                    crate::ops::descriptor::Round::new(*modulus),
+                    tvec!(InferenceFact::default()),
+                )?;
+                deferred.insert(inlet, name.to_string());
+                n.wire(InletId::new(id, 0), &*name, model, deferred, adjust_final_offset)?;
+                return Ok(());
+            }
+            &Scale(factor, ref n) => {
+                let name = format!("{}.Scale", name);
+                model.add_const(format!("{}.factor", name), tensor2(&[[*factor]]))?;
+                let id = model.add_node(
+                    &*name,
+                    tract_hir::ops::math::Mul.into_hir(),
+                    tvec!(InferenceFact::default()),
+                )?;
+                deferred.insert(InletId::new(id, 1), format!("{}.factor", name));
+                deferred.insert(inlet, name.to_string());
+                n.wire(InletId::new(id, 0), &*name, model, deferred, adjust_final_offset)?;
+                return Ok(());
+            }
+            &Sum(ref a, ref b) => {
+                let name = format!("{}.Sum", name);
+                let id = model.add_node(
+                    &*name,
+                    tract_hir::ops::math::Add.into_hir(),
+                    tvec!(InferenceFact::default()),
+                )?;
+                deferred.insert(inlet, name.to_string());
+                for (ix, term) in [a, b].iter().enumerate() {
+                    let name = format!("{}-{}", name, ix);
+                    term.wire(InletId::new(id, ix), &*name, model, deferred, adjust_final_offset)?;
+                }
+                return Ok(());
+            }
+            &Switch(ref cases) if cases.len() > 0 => {
+                let name = format!("{}.Switch", name);
+                let id = model.add_node(
+                    &*name,
+                    crate::ops::descriptor::Switch::default(),
+                    tvec!(InferenceFact::default()),
+                )?;
+                deferred.insert(inlet, name.to_string());
+                for (ix, case) in cases.iter().enumerate() {
+                    let name = format!("{}-{}", name, ix);
+                    case.wire(InletId::new(id, ix), &*name, model, deferred, adjust_final_offset)?;
+                }
+                return Ok(());
+            }
             _ => (),
         }
         bail!("Unhandled input descriptor: {:?}", self)
@@ -287,3 +478,43 @@ impl Framework<KaldiProtoModel, InferenceModel> for Kaldi {
         Ok(model)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::GeneralDescriptor::*;
+    use super::*;
+
+    fn wire(descriptor: GeneralDescriptor) -> TractResult<(InferenceModel, String)> {
+        let mut model = InferenceModel::default();
+        let id = model.add_node(
+            "consumer",
+            tract_hir::ops::identity::Identity::default(),
+            tvec!(InferenceFact::default()),
+        )?;
+        let mut deferred = BTreeMap::new();
+        descriptor.wire(InletId::new(id, 0), "consumer", &mut model, &mut deferred, None)?;
+        let input = deferred[&InletId::new(id, 0)].clone();
+        Ok((model, input))
+    }
+
+    #[test]
+    fn failover_without_past_reference() -> TractResult<()> {
+        let (_, input) = wire(Failover(Box::new(Name("a".into())), Box::new(Name("b".into()))))?;
+        assert_eq!(input, "a");
+        Ok(())
+    }
+
+    #[test]
+    fn failover_from_past_to_zeros() -> TractResult<()> {
+        let past = Offset(Box::new(Name("a".into())), -1);
+        let (model, input) = wire(Failover(Box::new(past), Box::new(Const(0.0, 2))))?;
+        assert!(model.node_by_name(&input)?.op_is::<crate::ops::memory::Memory>());
+        Ok(())
+    }
+
+    #[test]
+    fn failover_from_past_to_other_frames() {
+        let past = Offset(Box::new(Name("a".into())), -1);
+        assert!(wire(Failover(Box::new(past), Box::new(Name("b".into())))).is_err());
+    }
+}
@@ -11,6 +11,7 @@ macro_rules! op_kaldi {
 }
 
 pub(crate) mod affine;
+pub(crate) mod descriptor;
 pub(crate) mod lstm_nonlin;
 pub(crate) mod memory;
 mod renorm;
@@ -103,6 +103,7 @@ impl Expansion for Affine {
                 group: 1,
                 bias: Some(self.bias_params.clone().into_arc_tensor()),
                 q_params: None,
+                prepacked: None,
             },
             inputs,
         )
@@ -0,0 +1,675 @@
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::Downsample;
+use tract_ndarray::prelude::*;
+use tract_pulse::internal::{OpPulsifier, PulsedFact, PulsedModel, PulsedOp};
+use tract_pulse_opl::ops::Delay;
+
+inventory::submit!(OpPulsifier {
+    type_id: std::any::TypeId::of::<Round>(),
+    name: "Round",
+    func: pulsify_round,
+});
+
+inventory::submit!(OpPulsifier {
+    type_id: std::any::TypeId::of::<Switch>(),
+    name: "Switch",
+    func: pulsify_switch,
+});
+
+inventory::submit!(OpPulsifier {
+    type_id: std::any::TypeId::of::<TimeBroadcast>(),
+    name: "TimeBroadcast",
+    func: pulsify_time_broadcast,
+});
+
+/// Phase of the frames the node successors actually look at, if they all downsample the
+/// time axis with a stride multiple of `period`.
+fn observed_phase(
+    model: &TypedModel,
+    node: &TypedNode,
+    period: usize,
+) -> TractResult<Option<usize>> {
+    if model.output_outlets()?.contains(&node.id.into()) || node.outputs[0].successors.len() == 0 {
+        return Ok(None);
+    }
+    let mut phase = None;
+    for succ in &node.outputs[0].successors {
+        let down = if let Some(down) = model.node(succ.node).op_as::<Downsample>() {
+            down
+        } else {
+            return Ok(None);
+        };
+        if down.axis != 0 || down.stride <= 0 || down.stride as usize % period != 0 {
+            return Ok(None);
+        }
+        if phase.is_some() && phase != Some(down.modulo % period) {
+            return Ok(None);
+        }
+        phase = Some(down.modulo % period);
+    }
+    Ok(phase)
+}
+
+/// `Round(<descriptor>, <t-modulus>)`: frame t is a copy of frame t - t % modulus.
+#[derive(Clone, Debug, new, Hash)]
+pub struct Round {
+    pub modulus: usize,
+}
+
+tract_linalg::impl_dyn_hash!(Round);
+
+impl Op for Round {
+    fn name(&self) -> Cow<str> {
+        "Round".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("modulus: {}", self.modulus)])
+    }
+
+    op_kaldi!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for Round {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        let input = input.to_array_view::<f32>()?;
+        let output = ArrayD::from_shape_fn(input.shape(), |mut coords| {
+            coords[0] -= coords[0] % self.modulus;
+            input[coords]
+        });
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for Round {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 1)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        Ok(())
+    }
+
+    as_op!();
+    to_typed!();
+}
+
+impl TypedOp for Round {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].clone()))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if self.modulus == 1 || observed_phase(model, node, self.modulus)? == Some(0) {
+            return Ok(Some(TypedModelPatch::shunt_one_op(model, node)?));
+        }
+        Ok(None)
+    }
+
+    as_op!();
+}
+
+fn pulsify_round(
+    _source: &TypedModel,
+    node: &TypedNode,
+    target: &mut PulsedModel,
+    mapping: &HashMap<OutletId, OutletId>,
+    _pulse: usize,
+) -> TractResult<TVec<OutletId>> {
+    let op = node.op_as::<Round>().unwrap();
+    let input = mapping[&node.inputs[0]];
+    let fact = target.outlet_fact(input)?.clone();
+    if fact.axis != 0 {
+        bail!("Round expects time on the first axis, streaming axis is {}", fact.axis);
+    }
+    target.wire_node(&*node.name, PulsedRound::new(op.modulus, fact.delay), &[input])
+}
+
+/// Pulsed Round, keeping the last frame at a multiple of the modulus between pulses.
+#[derive(Clone, Debug, new, Hash)]
+pub struct PulsedRound {
+    pub modulus: usize,
+    pub delay: usize,
+}
+
+tract_linalg::impl_dyn_hash!(PulsedRound);
+
+impl Op for PulsedRound {
+    fn name(&self) -> Cow<str> {
+        "PulsedRound".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("modulus: {} delay: {}", self.modulus, self.delay)])
+    }
+
+    op_kaldi!();
+    op_as_typed_op!();
+}
+
+#[derive(Clone, Debug, Default)]
+struct PulsedRoundState {
+    frame: usize,
+    held: Option<ArrayD<f32>>,
+}
+
+impl OpState for PulsedRoundState {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        op: &dyn Op,
+        mut inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let op = op.downcast_ref::<PulsedRound>().ok_or_else(|| format_err!("Wrong Op type"))?;
+        let input = args_1!(inputs);
+        let mut output = input.to_array_view::<f32>()?.to_owned();
+        for (ix, mut frame) in output.axis_iter_mut(Axis(0)).enumerate() {
+            let t = self.frame + ix;
+            if t >= op.delay && (t - op.delay) % op.modulus == 0 {
+                self.held = Some(frame.to_owned());
+            } else if let Some(held) = &self.held {
+                frame.assign(held);
+            }
+        }
+        self.frame += output.shape()[0];
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl EvalOp for PulsedRound {
+    fn is_stateless(&self) -> bool {
+        false
+    }
+
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(PulsedRoundState::default())))
+    }
+}
+
+impl TypedOp for PulsedRound {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].clone()))
+    }
+
+    as_op!();
+}
+
+impl PulsedOp for PulsedRound {
+    fn pulsed_output_facts(&self, inputs: &[&PulsedFact]) -> TractResult<TVec<PulsedFact>> {
+        Ok(tvec!(inputs[0].clone()))
+    }
+
+    fn to_typed(&self) -> Box<dyn TypedOp> {
+        Box::new(self.clone())
+    }
+
+    as_op!();
+}
+
+/// `Switch(<descriptor>, <descriptor>, ...)`: frame t comes from input t % n.
+#[derive(Clone, Debug, Default, Hash)]
+pub struct Switch;
+
+tract_linalg::impl_dyn_hash!(Switch);
+
+impl Op for Switch {
+    fn name(&self) -> Cow<str> {
+        "Switch".into()
+    }
+
+    op_kaldi!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for Switch {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let inputs =
+            inputs.iter().map(|i| i.to_array_view::<f32>()).collect::<TractResult<TVec<_>>>()?;
+        if inputs.iter().any(|i| i.shape() != inputs[0].shape()) {
+            bail!("Switch inputs must have the same shape");
+        }
+        let output = ArrayD::from_shape_fn(inputs[0].shape(), |coords| {
+            inputs[coords[0] % inputs.len()][coords]
+        });
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for Switch {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+            s.equals(&input.shape, &outputs[0].shape)?;
+        }
+        Ok(())
+    }
+
+    as_op!();
+    to_typed!();
+}
+
+impl TypedOp for Switch {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].clone()))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if let Some(phase) = observed_phase(model, node, node.inputs.len())? {
+            let mut patch = TypedModelPatch::default();
+            let tap = patch.tap_model(model, node.inputs[phase])?;
+            patch.shunt_outside(model, node.id.into(), tap)?;
+            return Ok(Some(patch));
+        }
+        Ok(None)
+    }
+
+    as_op!();
+}
+
+fn pulsify_switch(
+    _source: &TypedModel,
+    node: &TypedNode,
+    target: &mut PulsedModel,
+    mapping: &HashMap<OutletId, OutletId>,
+    _pulse: usize,
+) -> TractResult<TVec<OutletId>> {
+    let delays = node
+        .inputs
+        .iter()
+        .map(|i| Ok(target.outlet_fact(mapping[i])?.delay))
+        .collect::<TractResult<TVec<usize>>>()?;
+    let delay = delays.iter().copied().max().unwrap();
+    let mut inputs = tvec!();
+    for (ix, input) in node.inputs.iter().enumerate() {
+        let mut input = mapping[input];
+        let fact = target.outlet_fact(input)?.clone();
+        if fact.axis != 0 {
+            bail!("Switch expects time on the first axis, streaming axis is {}", fact.axis);
+        }
+        if fact.delay < delay {
+            let add_delay = delay - fact.delay;
+            input = target.wire_node(
+                format!("{}.Delay-{}", node.name, ix),
+                Delay::new(fact.axis, &fact.into(), add_delay, 0),
+                &[input],
+            )?[0];
+        }
+        inputs.push(input);
+    }
+    target.wire_node(&*node.name, PulsedSwitch::new(delay), &inputs)
+}
+
+/// Pulsed Switch, tracking the position in the stream to select the input.
+#[derive(Clone, Debug, new, Hash)]
+pub struct PulsedSwitch {
+    pub delay: usize,
+}
+
+tract_linalg::impl_dyn_hash!(PulsedSwitch);
+
+impl Op for PulsedSwitch {
+    fn name(&self) -> Cow<str> {
+        "PulsedSwitch".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("delay: {}", self.delay)])
+    }
+
+    op_kaldi!();
+    op_as_typed_op!();
+}
+
+#[derive(Clone, Debug, Default)]
+struct PulsedSwitchState {
+    frame: usize,
+}
+
+impl OpState for PulsedSwitchState {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        op: &dyn Op,
+        inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let op = op.downcast_ref::<PulsedSwitch>().ok_or_else(|| format_err!("Wrong Op type"))?;
+        let inputs =
+            inputs.iter().map(|i| i.to_array_view::<f32>()).collect::<TractResult<TVec<_>>>()?;
+        let output = ArrayD::from_shape_fn(inputs[0].shape(), |coords| {
+            let t = self.frame + coords[0];
+            let ix = if t >= op.delay { (t - op.delay) % inputs.len() } else { 0 };
+            inputs[ix][coords]
+        });
+        self.frame += output.shape()[0];
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl EvalOp for PulsedSwitch {
+    fn is_stateless(&self) -> bool {
+        false
+    }
+
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(PulsedSwitchState::default())))
+    }
+}
+
+impl TypedOp for PulsedSwitch {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].clone()))
+    }
+
+    as_op!();
+}
+
+impl PulsedOp for PulsedSwitch {
+    fn pulsed_output_facts(&self, inputs: &[&PulsedFact]) -> TractResult<TVec<PulsedFact>> {
+        Ok(tvec!(inputs[0].clone()))
+    }
+
+    fn to_typed(&self) -> Box<dyn TypedOp> {
+        Box::new(self.clone())
+    }
+
+    as_op!();
+}
+
+/// Repeats a single frame (second input) along the time axis of the first input, so time
+/// invariant descriptors (`Const`, `ReplaceIndex(.., t, ..)`) can be appended.
+#[derive(Clone, Debug, Default, Hash)]
+pub struct TimeBroadcast;
+
+tract_linalg::impl_dyn_hash!(TimeBroadcast);
+
+impl TimeBroadcast {
+    fn broadcast(frame: &Tensor, frames: usize) -> TractResult<Tensor> {
+        let frame = frame.to_array_view::<f32>()?;
+        if frame.ndim() != 2 || frame.shape()[0] != 1 {
+            bail!("TimeBroadcast expects a single frame, got {:?}", frame.shape());
+        }
+        let output =
+            ArrayD::from_shape_fn(&[frames, frame.shape()[1]][..], |coords| frame[[0, coords[1]]]);
+        Ok(output.into_tensor())
+    }
+}
+
+impl Op for TimeBroadcast {
+    fn name(&self) -> Cow<str> {
+        "TimeBroadcast".into()
+    }
+
+    op_kaldi!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for TimeBroadcast {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (reference, frame) = args_2!(inputs);
+        Ok(tvec!(Self::broadcast(&frame, reference.shape()[0])?.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for TimeBroadcast {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[1].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].rank, 2)?;
+        s.equals(&inputs[1].rank, 2)?;
+        s.equals(&outputs[0].rank, 2)?;
+        s.equals(&inputs[1].shape[0], 1.to_dim())?;
+        s.equals(&inputs[0].shape[0], &outputs[0].shape[0])?;
+        s.equals(&inputs[1].shape[1], &outputs[0].shape[1])?;
+        Ok(())
+    }
+
+    as_op!();
+    to_typed!();
+}
+
+impl TypedOp for TimeBroadcast {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(TypedFact::dt_shape(
+            inputs[1].datum_type,
+            [inputs[0].shape[0].clone(), inputs[1].shape[1].clone()].as_ref()
+        )?))
+    }
+
+    as_op!();
+}
+
+fn pulsify_time_broadcast(
+    source: &TypedModel,
+    node: &TypedNode,
+    target: &mut PulsedModel,
+    mapping: &HashMap<OutletId, OutletId>,
+    _pulse: usize,
+) -> TractResult<TVec<OutletId>> {
+    let frame = source.outlet_fact(node.inputs[1])?.konst.clone().ok_or_else(|| {
+        format_err!("Only constant frames can be broadcast along a streaming axis")
+    })?;
+    target.wire_node(&*node.name, PulsedTimeBroadcast::new(frame), &[mapping[&node.inputs[0]]])
+}
+
+/// Pulsed TimeBroadcast, repeating a constant frame over each pulse.
+#[derive(Clone, Debug, new, Hash)]
+pub struct PulsedTimeBroadcast {
+    pub frame: Arc<Tensor>,
+}
+
+tract_linalg::impl_dyn_hash!(PulsedTimeBroadcast);
+
+impl Op for PulsedTimeBroadcast {
+    fn name(&self) -> Cow<str> {
+        "PulsedTimeBroadcast".into()
+    }
+
+    op_kaldi!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for PulsedTimeBroadcast {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let output = TimeBroadcast::broadcast(&self.frame, inputs[0].shape()[0])?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for PulsedTimeBroadcast {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(TypedFact::dt_shape(
+            self.frame.datum_type(),
+            [inputs[0].shape[0].clone(), self.frame.shape()[1].to_dim()].as_ref()
+        )?))
+    }
+
+    as_op!();
+}
+
+impl PulsedOp for PulsedTimeBroadcast {
+    fn pulsed_output_facts(&self, inputs: &[&PulsedFact]) -> TractResult<TVec<PulsedFact>> {
+        let mut fact = inputs[0].clone();
+        fact.datum_type = self.frame.datum_type();
+        fact.shape[1] = self.frame.shape()[1].to_dim();
+        Ok(tvec!(fact))
+    }
+
+    fn to_typed(&self) -> Box<dyn TypedOp> {
+        Box::new(self.clone())
+    }
+
+    as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tract_pulse::internal::{stream_dim, PulsedModelExt};
+
+    fn streaming_model(op: impl Into<Box<dyn TypedOp>>, inputs: usize) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [stream_dim(), 2.to_dim()].as_ref())?;
+        let sources = (0..inputs)
+            .map(|ix| model.add_source(format!("source-{}", ix), fact.clone()))
+            .collect::<TractResult<TVec<_>>>()?;
+        let output = model.wire_node("op", op, &sources)?;
+        model.set_output_outlets(&output)?;
+        Ok(model)
+    }
+
+    fn run_pulsed(model: &TypedModel, pulse: usize, inputs: &[Tensor]) -> TractResult<Tensor> {
+        let pulsed = PulsedModel::new(model, pulse)?;
+        let delay = pulsed.output_fact(0)?.delay;
+        let plan = SimplePlan::new(pulsed.into_typed()?)?;
+        let mut state = SimpleState::new(&plan)?;
+        let frames = inputs[0].shape()[0];
+        let mut chunks = vec![];
+        for p in 0..(frames + delay + pulse - 1) / pulse {
+            let chunk = inputs
+                .iter()
+                .map(|i| {
+                    let i = i.to_array_view::<f32>()?;
+                    let chunk = ArrayD::from_shape_fn(&[pulse, 2][..], |coords| {
+                        let t = p * pulse + coords[0];
+                        if t < frames {
+                            i[[t, coords[1]]]
+                        } else {
+                            std::f32::NAN
+                        }
+                    });
+                    Ok(chunk.into_tensor())
+                })
+                .collect::<TractResult<TVec<_>>>()?;
+            chunks.push(state.run(chunk)?.remove(0).into_tensor());
+        }
+        let output = Tensor::stack_tensors(0, &chunks)?;
+        output.slice(0, delay, delay + frames)
+    }
+
+    fn input(seed: f32) -> Tensor {
+        tract_ndarray::Array2::from_shape_fn((12, 2), |(t, x)| seed + t as f32 + x as f32 / 10.)
+            .into_tensor()
+    }
+
+    #[test]
+    fn round_eval() -> TractResult<()> {
+        let output = Round::new(3).eval(tvec!(input(0.).into_arc_tensor()))?;
+        let output = output[0].to_array_view::<f32>()?;
+        assert_eq!(output[[4, 1]], 3.1);
+        assert_eq!(output[[6, 0]], 6.0);
+        Ok(())
+    }
+
+    #[test]
+    fn round_absorbed_by_downsample() -> TractResult<()> {
+        let mut model = streaming_model(Round::new(3), 1)?;
+        let output = model.output_outlets()?[0];
+        let down = model.wire_node("down", Downsample::new(0, 3, 0), &[output])?;
+        model.set_output_outlets(&down)?;
+        let model = model.declutter()?;
+        assert!(model.nodes().iter().all(|n| !n.op_is::<Round>()));
+        Ok(())
+    }
+
+    #[test]
+    fn switch_absorbed_by_downsample() -> TractResult<()> {
+        let mut model = streaming_model(Switch, 2)?;
+        let output = model.output_outlets()?[0];
+        let down = model.wire_node("down", Downsample::new(0, 4, 1), &[output])?;
+        model.set_output_outlets(&down)?;
+        let model = model.declutter()?;
+        assert!(model.nodes().iter().all(|n| !n.op_is::<Switch>()));
+        let down = model.node(model.output_outlets()?[0].node);
+        assert_eq!(model.node(down.inputs[0].node).name, "source-1");
+        Ok(())
+    }
+
+    #[test]
+    fn round_pulsed() -> TractResult<()> {
+        let model = streaming_model(Round::new(3), 1)?;
+        let inputs = [input(0.)];
+        let expected = Round::new(3).eval(tvec!(inputs[0].clone().into_arc_tensor()))?;
+        let found = run_pulsed(&model, 2, &inputs)?;
+        found.close_enough(&expected[0], false)
+    }
+
+    #[test]
+    fn switch_pulsed() -> TractResult<()> {
+        let model = streaming_model(Switch, 3)?;
+        let inputs = [input(0.), input(100.), input(200.)];
+        let expected = Switch.eval(inputs.iter().map(|i| i.clone().into_arc_tensor()).collect())?;
+        let found = run_pulsed(&model, 4, &inputs)?;
+        found.close_enough(&expected[0], false)
+    }
+
+    #[test]
+    fn descriptors_pulsed() -> TractResult<()> {
+        let nnet3 = r#"<Nnet3>
+
+input-node name=input dim=2
+output-node name=output input=Append(Sum(Scale(2.0, input), Const(1.0, 2)), Const(3.0, 1), Round(input, 3))
+
+<NumComponents> 0
+</Nnet3>"#;
+        let model = crate::kaldi().model_for_read(&mut nnet3.as_bytes())?;
+        let model = model.into_typed()?.declutter()?;
+        let inputs = [input(0.)];
+        let expected = SimplePlan::new(&model)?.run(tvec!(inputs[0].clone()))?;
+        let expected = expected[0].to_array_view::<f32>()?;
+        assert_eq!(expected.shape(), &[12, 5]);
+        assert_eq!(expected.index_axis(Axis(0), 4), arr1(&[9.0, 9.2, 3.0, 3.0, 3.1]).into_dyn());
+        let found = run_pulsed(&model, 4, &inputs)?;
+        found.close_enough(&expected.to_owned().into_tensor(), false)
+    }
+}
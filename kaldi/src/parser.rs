@@ -24,7 +24,7 @@ mod text;
 
 pub fn nnet3(slice: &[u8]) -> TractResult<KaldiProtoModel> {
     let (_, (config, components)) = parse_top_level(slice).map_err(|e| match e {
-        nom::Err::Error(err) => format_err!(
+        nom::Err::Error(err) | nom::Err::Failure(err) => format_err!(
             "Parsing kaldi enveloppe at: {:?}",
             err.0.iter().take(120).map(|b| format!("{}", *b as char)).join("")
         ),
@@ -44,6 +44,8 @@ pub fn if_then_else<'a, T>(
 
 fn parse_top_level(i: &[u8]) -> IResult<&[u8], (&str, HashMap<String, Component>)> {
     let (i, bin) = map(opt(tag([0, 0x42])), |o| Option::is_some(&o))(i)?;
+    // acoustic models (final.mdl) start with the HMM transition model
+    let (i, _) = opt(transition_model)(i)?;
     let (i, _) = open(i, "Nnet3")?;
     let (i, config_lines) = map_res(take_until("<NumComponents>"), std::str::from_utf8)(i)?;
     let (i, num_components) = num_components(bin, i)?;
@@ -60,6 +62,12 @@ fn parse_top_level(i: &[u8]) -> IResult<&[u8], (&str, HashMap<String, Component>
     Ok((i, (config_lines, components)))
 }
 
+fn transition_model(i: &[u8]) -> IResult<&[u8], ()> {
+    let (i, _) = open(i, "TransitionModel")?;
+    let (i, _) = take_until("</TransitionModel>")(i)?;
+    close(i, "TransitionModel")
+}
+
 fn num_components(bin: bool, i: &[u8]) -> IResult<&[u8], usize> {
     let (i, _) = open(i, "NumComponents")?;
     let (i, n) = multispaced(integer(bin))(i)?;
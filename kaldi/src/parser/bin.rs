@@ -10,7 +10,67 @@ pub fn attributes<'a>(i: &'a [u8], klass: &str) -> IResult<&'a [u8], HashMap<Str
 }
 
 fn attribute<'a>(i: &'a [u8], klass: &str) -> IResult<&'a [u8], (String, Arc<Tensor>)> {
-    let (i, name) = super::open_any(i)?;
-    let (i, value) = COMPONENTS[klass][name].parse_bin(i)?;
+    let (j, name) = super::open_any(i)?;
+    let kind = COMPONENTS
+        .get(klass)
+        .and_then(|attributes| attributes.get(name))
+        .ok_or(nom::Err::Failure((i, nom::error::ErrorKind::Tag)))?;
+    let (i, value) = kind.parse_bin(j)?;
     Ok((i, (name.to_string(), value.into_arc_tensor())))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::nnet3;
+    use crate::model::KaldiProtoModel;
+
+    fn assert_same_model(bin: &KaldiProtoModel, text: &KaldiProtoModel) {
+        assert_eq!(format!("{:?}", bin.config_lines), format!("{:?}", text.config_lines));
+        assert_eq!(bin.components.len(), text.components.len());
+        for (name, component) in &text.components {
+            let bin_component = &bin.components[name];
+            assert_eq!(bin_component.klass, component.klass);
+            // text dumps and binary files do not always carry the same
+            // training hyper-parameters, compare what both have
+            for (attribute, value) in &component.attributes {
+                if let Some(bin_value) = bin_component.attributes.get(attribute) {
+                    bin_value.close_enough(value, true).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn binary_and_text_models_match() {
+        for dir in std::fs::read_dir("test_cases").unwrap() {
+            let dir = dir.unwrap().path();
+            if !dir.is_dir() {
+                continue;
+            }
+            let bin = nnet3(&std::fs::read(dir.join("model.raw")).unwrap()).unwrap();
+            let text = nnet3(&std::fs::read(dir.join("model.raw.txt")).unwrap()).unwrap();
+            assert_same_model(&bin, &text);
+        }
+    }
+
+    #[test]
+    fn acoustic_model() {
+        let raw = std::fs::read("test_cases/lstm_10-4-6_T10_S1_delay1/model.raw").unwrap();
+        let mut mdl = vec![0, b'B'];
+        mdl.extend(b"<TransitionModel> <Topology> ");
+        mdl.extend(&[4, 1, 0, 0, 0, 4, 2, 0, 0, 0]);
+        mdl.extend(b"</Topology> <Triples> ");
+        mdl.extend(&[4, 0, 0, 0, 0]);
+        mdl.extend(b"</Triples> <LogProbs> FV ");
+        mdl.extend(&[4, 0, 0, 0, 0]);
+        mdl.extend(b"</LogProbs> </TransitionModel> ");
+        mdl.extend(&raw[2..]);
+        mdl.extend(b"<LeftContext> ");
+        mdl.extend(&[4, 1, 0, 0, 0]);
+        mdl.extend(b"<RightContext> ");
+        mdl.extend(&[4, 0, 0, 0, 0]);
+        mdl.extend(b"<Priors> FV ");
+        mdl.extend(&[4, 0, 0, 0, 0]);
+        assert_same_model(&nnet3(&mdl).unwrap(), &nnet3(&raw).unwrap());
+    }
+}
@@ -9,8 +9,7 @@ use nom::{
     branch::*,
     bytes::complete::*,
     combinator::*,
-    multi::many_m_n,
-    number::complete::{le_f32, le_f64},
+    number::complete::{le_f32, le_f64, le_i32},
     sequence::*,
     IResult,
 };
@@ -32,8 +31,15 @@ impl KaldiAttributeKind {
             ))(i),
             Int => map(super::integer(true), Tensor::from)(i),
             Float => map(Self::parse_float_value, Tensor::from)(i),
-            FloatVector => preceded(multispaced(tag("FV")), Self::parse_float_vector)(i),
-            FloatMatrix => preceded(multispaced(tag("FM")), Self::parse_float_matrix)(i),
+            FloatVector => alt((
+                preceded(multispaced(tag("FV")), |i| Self::parse_vector(i, 4)),
+                preceded(multispaced(tag("DV")), |i| Self::parse_vector(i, 8)),
+            ))(i),
+            FloatMatrix => alt((
+                preceded(multispaced(tag("FM")), |i| Self::parse_matrix(i, 4)),
+                preceded(multispaced(tag("DM")), |i| Self::parse_matrix(i, 8)),
+                Self::parse_compressed_matrix,
+            ))(i),
         }
     }
 
@@ -41,31 +47,84 @@ impl KaldiAttributeKind {
         alt((preceded(tag([4]), le_f32), map(preceded(tag([8]), le_f64), |f| f as f32)))(i)
     }
 
-    fn parse_float_vector<'a>(i: &'a [u8]) -> IResult<&'a [u8], Tensor> {
+    /// Decode `len` little endian floats of `size` bytes (4 or 8).
+    fn parse_floats<'a>(i: &'a [u8], len: usize, size: usize) -> IResult<&'a [u8], Vec<f32>> {
+        map(take(len * size), move |bytes: &[u8]| {
+            bytes
+                .chunks(size)
+                .map(|b| {
+                    if size == 4 {
+                        f32::from_le_bytes([b[0], b[1], b[2], b[3]])
+                    } else {
+                        f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32
+                    }
+                })
+                .collect()
+        })(i)
+    }
+
+    fn parse_vector<'a>(i: &'a [u8], size: usize) -> IResult<&'a [u8], Tensor> {
         let (i, len) = super::integer(true)(i)?;
-        // FIXME pending merge of https://github.com/Geal/nom/pull/995
-        if len == 0 {
-            Ok((i, tensor1(&[0.0f32])))
-        } else {
-            map(many_m_n(len as usize, len as usize, le_f32), |data| tensor1(&*data))(i)
-        }
+        let (i, data) = Self::parse_floats(i, len as usize, size)?;
+        Ok((i, tensor1(&*data)))
     }
 
-    fn parse_float_matrix<'a>(i: &'a [u8]) -> IResult<&'a [u8], Tensor> {
+    fn parse_matrix<'a>(i: &'a [u8], size: usize) -> IResult<&'a [u8], Tensor> {
         let (i, rows) = super::integer(true)(i)?;
         let (i, cols) = super::integer(true)(i)?;
-        let len = (rows * cols) as usize;
-        // FIXME pending merge of https://github.com/Geal/nom/pull/995
-        if len == 0 {
-            Ok((i, tensor2(&[[0.0f32; 0]; 0])))
-        } else {
-            map(
-                map_res(many_m_n(len, len, le_f32), move |buf| {
-                    tract_ndarray::Array2::from_shape_vec((rows as usize, cols as usize), buf)
-                }),
-                Tensor::from,
-            )(i)
-        }
+        let (rows, cols) = (rows as usize, cols as usize);
+        let (i, data) = Self::parse_floats(i, rows * cols, size)?;
+        let matrix = tract_ndarray::Array2::from_shape_vec((rows, cols), data).unwrap();
+        Ok((i, matrix.into_tensor()))
+    }
+
+    /// Kaldi CompressedMatrix, as written by its binary Write.
+    ///
+    /// A global header (min value, range, rows, cols) follows the format token:
+    /// CM2 and CM3 store each value as a 16 or 8 bits fraction of the range,
+    /// row major; CM stores a header of four 16 bits percentiles (0, 25, 75,
+    /// 100) for each column, then a byte per value, column major, interpolating
+    /// piecewise linearly between the percentiles.
+    fn parse_compressed_matrix<'a>(i: &'a [u8]) -> IResult<&'a [u8], Tensor> {
+        let (i, format) = terminated(
+            alt((map(tag("CM2"), |_| 2), map(tag("CM3"), |_| 3), map(tag("CM"), |_| 1))),
+            tag(" "),
+        )(i)?;
+        let (i, (min, range, rows, cols)) = tuple((le_f32, le_f32, le_i32, le_i32))(i)?;
+        let (rows, cols) = (rows as usize, cols as usize);
+        let u16_to_float = |v: u16| min + range * (1.0 / 65535.0) * v as f32;
+        let (i, data) = match format {
+            1 => {
+                let (i, headers) = take(8 * cols)(i)?;
+                let (i, bytes) = take(rows * cols)(i)?;
+                let mut data = vec![0f32; rows * cols];
+                for (c, header) in headers.chunks(8).enumerate() {
+                    let p: TVec<f32> = header
+                        .chunks(2)
+                        .map(|b| u16_to_float(u16::from_le_bytes([b[0], b[1]])))
+                        .collect();
+                    for r in 0..rows {
+                        let v = bytes[c * rows + r] as f32;
+                        data[r * cols + c] = if v <= 64.0 {
+                            p[0] + (p[1] - p[0]) * v * (1.0 / 64.0)
+                        } else if v <= 192.0 {
+                            p[1] + (p[2] - p[1]) * (v - 64.0) * (1.0 / 128.0)
+                        } else {
+                            p[2] + (p[3] - p[2]) * (v - 192.0) * (1.0 / 63.0)
+                        };
+                    }
+                }
+                (i, data)
+            }
+            2 => map(take(2 * rows * cols), |bytes: &[u8]| {
+                bytes.chunks(2).map(|b| u16_to_float(u16::from_le_bytes([b[0], b[1]]))).collect()
+            })(i)?,
+            _ => map(take(rows * cols), |bytes: &[u8]| {
+                bytes.iter().map(|&b| min + range * (1.0 / 255.0) * b as f32).collect()
+            })(i)?,
+        };
+        let matrix = tract_ndarray::Array2::from_shape_vec((rows, cols), data).unwrap();
+        Ok((i, matrix.into_tensor()))
     }
 }
 
@@ -80,6 +139,7 @@ lazy_static::lazy_static! {
         "NaturalGradientAffineComponent" => hashmap! {
             "LearningRateFactor" => Float,
             "MaxChange" => Float,
+            "L2Regularize" => Float,
             "LearningRate" => Float,
             "LinearParams" => FloatMatrix,
             "BiasParams" => FloatVector,
@@ -87,6 +147,7 @@ lazy_static::lazy_static! {
             "RankOut" => Int,
             "UpdatePeriod" => Int,
             "NumSamplesHistory" => Float,
+            "OrthonormalConstraint" => Float,
             "Alpha" => Float,
             "IsGradient" => Bool,
         },
@@ -102,7 +163,10 @@ lazy_static::lazy_static! {
             "MinValue" => Float,
         },
         "LstmNonlinearityComponent" => hashmap!{
+            "LearningRateFactor" => Float,
+            "IsGradient" => Bool,
             "MaxChange" => Float,
+            "L2Regularize" => Float,
             "LearningRate" => Float,
             "Params" => FloatMatrix,
             "ValueAvg" => FloatMatrix,
@@ -110,6 +174,7 @@ lazy_static::lazy_static! {
             "SelfRepairConfig" => FloatVector,
             "SelfRepairProb" => FloatVector,
             "Count" => Float,
+            "UseDropout" => Bool,
         },
         "BackpropTruncationComponent" => hashmap!{
             "Dim" => Int,
@@ -142,3 +207,57 @@ lazy_static::lazy_static! {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compressed(
+        format: &str,
+        min: f32,
+        range: f32,
+        rows: i32,
+        cols: i32,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let mut bytes = format!("{} ", format).into_bytes();
+        bytes.extend(&min.to_le_bytes());
+        bytes.extend(&range.to_le_bytes());
+        bytes.extend(&rows.to_le_bytes());
+        bytes.extend(&cols.to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    #[test]
+    fn compressed_two_bytes() {
+        let data: Vec<u8> =
+            [0u16, 65535, 16384, 49151].iter().flat_map(|v| v.to_le_bytes().to_vec()).collect();
+        let bytes = compressed("CM2", -1.0, 2.0, 2, 2, &data);
+        let (rest, t) = FloatMatrix.parse_bin(&bytes).unwrap();
+        assert!(rest.is_empty());
+        t.close_enough(&tensor2(&[[-1.0f32, 1.0], [-0.5, 0.5]]), true).unwrap();
+    }
+
+    #[test]
+    fn compressed_one_byte() {
+        let bytes = compressed("CM3", -1.0, 2.0, 1, 3, &[0, 255, 51]);
+        let (rest, t) = FloatMatrix.parse_bin(&bytes).unwrap();
+        assert!(rest.is_empty());
+        t.close_enough(&tensor2(&[[-1.0f32, 1.0, -0.6]]), true).unwrap();
+    }
+
+    #[test]
+    fn compressed_with_col_headers() {
+        // percentiles 0, 64, 192, 255 make the byte to float mapping the identity
+        let header: Vec<u8> =
+            [0u16, 64, 192, 255].iter().flat_map(|v| v.to_le_bytes().to_vec()).collect();
+        let mut data = header.clone();
+        data.extend(&header);
+        data.extend(&[1, 2, 3, 100, 200, 250]);
+        let bytes = compressed("CM", 0.0, 65535.0, 3, 2, &data);
+        let (rest, t) = FloatMatrix.parse_bin(&bytes).unwrap();
+        assert!(rest.is_empty());
+        t.close_enough(&tensor2(&[[1.0f32, 100.0], [2.0, 200.0], [3.0, 250.0]]), true).unwrap();
+    }
+}
@@ -1,6 +1,7 @@
 use nom::IResult;
 use nom::{
-    bytes::complete::*, character::complete::*, combinator::*, multi::separated_list, sequence::*,
+    bytes::complete::*, character::complete::*, combinator::*, multi::separated_list,
+    number::complete::float, sequence::*,
 };
 
 use crate::model::GeneralDescriptor;
@@ -21,14 +22,25 @@ pub fn parse_general(i: &str) -> IResult<&str, GeneralDescriptor> {
         ),
         map(
             preceded(
-                tag("Offset"),
+                tag("Const"),
                 cut(delimited(
                     spaced(tag("(")),
-                    separated_pair(parse_general, spaced(tag(",")), integer),
+                    separated_pair(float, spaced(tag(",")), digit1),
                     spaced(tag(")")),
                 )),
             ),
-            |(inner, offset)| GeneralDescriptor::Offset(Box::new(inner), offset as isize),
+            |(value, dim): (f32, &str)| GeneralDescriptor::Const(value, dim.parse().unwrap()),
+        ),
+        map(
+            preceded(
+                tag("Failover"),
+                cut(delimited(
+                    spaced(tag("(")),
+                    separated_pair(parse_general, spaced(tag(",")), parse_general),
+                    spaced(tag(")")),
+                )),
+            ),
+            |(a, b)| GeneralDescriptor::Failover(Box::new(a), Box::new(b)),
         ),
         map(
             preceded(
@@ -37,6 +49,90 @@ pub fn parse_general(i: &str) -> IResult<&str, GeneralDescriptor> {
             ),
             |inner| GeneralDescriptor::IfDefined(Box::new(inner)),
         ),
+        preceded(
+            tag("Offset"),
+            cut(map_opt(
+                delimited(
+                    spaced(tag("(")),
+                    tuple((
+                        parse_general,
+                        preceded(spaced(tag(",")), integer),
+                        opt(preceded(spaced(tag(",")), integer)),
+                    )),
+                    spaced(tag(")")),
+                ),
+                |(inner, offset, x)| {
+                    // offsets along the x index are not supported
+                    if x.unwrap_or(0) != 0 {
+                        return None;
+                    }
+                    Some(GeneralDescriptor::Offset(Box::new(inner), offset as isize))
+                },
+            )),
+        ),
+        map(
+            preceded(
+                tag("ReplaceIndex"),
+                cut(delimited(
+                    spaced(tag("(")),
+                    tuple((
+                        parse_general,
+                        preceded(spaced(tag(",")), one_of("tx")),
+                        preceded(spaced(tag(",")), integer),
+                    )),
+                    spaced(tag(")")),
+                )),
+            ),
+            |(inner, index, value)| {
+                GeneralDescriptor::ReplaceIndex(Box::new(inner), index, value as isize)
+            },
+        ),
+        map(
+            preceded(
+                tag("Round"),
+                cut(delimited(
+                    spaced(tag("(")),
+                    separated_pair(parse_general, spaced(tag(",")), digit1),
+                    spaced(tag(")")),
+                )),
+            ),
+            |(inner, modulus): (GeneralDescriptor, &str)| {
+                GeneralDescriptor::Round(Box::new(inner), modulus.parse().unwrap())
+            },
+        ),
+        map(
+            preceded(
+                tag("Scale"),
+                cut(delimited(
+                    spaced(tag("(")),
+                    separated_pair(float, spaced(tag(",")), parse_general),
+                    spaced(tag(")")),
+                )),
+            ),
+            |(scale, inner)| GeneralDescriptor::Scale(scale, Box::new(inner)),
+        ),
+        map(
+            preceded(
+                tag("Sum"),
+                cut(delimited(
+                    spaced(tag("(")),
+                    separated_pair(parse_general, spaced(tag(",")), parse_general),
+                    spaced(tag(")")),
+                )),
+            ),
+            |(a, b)| GeneralDescriptor::Sum(Box::new(a), Box::new(b)),
+        ),
+        map(
+            preceded(
+                tag("Switch"),
+                cut(delimited(
+                    spaced(tag("(")),
+                    separated_list(spaced(tag(",")), parse_general),
+                    spaced(tag(")")),
+                )),
+            ),
+            GeneralDescriptor::Switch,
+        ),
         map(super::config_lines::identifier, |i| GeneralDescriptor::Name(i.to_string())),
     )))(i)
 }
@@ -78,4 +174,47 @@ mod test {
             Append(vec!(name("input"), IfDefined(Offset(name("lstm1.c").into(), -1).into())))
         )
     }
+
+    #[test]
+    fn test_tdnnf_bypass() {
+        assert_eq!(
+            parse_general("Sum(Scale(0.66, tdnnf1.noop), tdnnf2.linear)").unwrap().1,
+            Sum(Scale(0.66, name("tdnnf1.noop").into()).into(), name("tdnnf2.linear").into())
+        )
+    }
+
+    #[test]
+    fn test_const() {
+        assert_eq!(
+            parse_general("Append(input, Const(1.0, 1))").unwrap().1,
+            Append(vec!(name("input"), Const(1.0, 1)))
+        )
+    }
+
+    #[test]
+    fn test_round_replace_index() {
+        assert_eq!(
+            parse_general("Append(Round(input, 3), ReplaceIndex(ivector, t, 0))").unwrap().1,
+            Append(vec!(
+                Round(name("input").into(), 3),
+                ReplaceIndex(name("ivector").into(), 't', 0)
+            ))
+        )
+    }
+
+    #[test]
+    fn test_switch_failover() {
+        assert_eq!(
+            parse_general("Switch(Failover(a, b), Offset(a, -1, 0))").unwrap().1,
+            Switch(vec!(
+                Failover(name("a").into(), name("b").into()),
+                Offset(name("a").into(), -1)
+            ))
+        )
+    }
+
+    #[test]
+    fn test_offset_x_unsupported() {
+        assert!(parse_general("Offset(input, -1, 2)").is_err())
+    }
 }
@@ -10,22 +10,25 @@ pub fn vec(len: usize, align: usize) -> *mut f32 {
 
 fn mat_vec_mul(c: &mut Criterion) {
     let mut group = c.benchmark_group("mat_vec_mul");
-    for (m, k) in [(64usize, 64usize)].iter() {
+    for (m, k) in [(64usize, 64usize), (256, 256), (1024, 256)].iter() {
         group.throughput(Throughput::Elements((m * k) as u64));
-        group.bench_with_input(
-            BenchmarkId::from_parameter(format!("{}x{}", m, k)),
-            &(m, k),
-            |be, (&m, &k)| {
-                let mut mm = (tract_linalg::ops().mmm_f32)(m, k, 1);
-                let pa = vec(mm.a_pack().len(), mm.a_pack().alignment());
-                let b = vec![0.0; k];
-                let mut c = vec![0.0; m];
-                unsafe {
-                    mm.b_vec_from_data();
-                }
-                be.iter(move || unsafe { mm.run(pa, b.as_ptr(), c.as_mut_ptr(), &[]) });
-            },
-        );
+        for kernel in tract_linalg::ops().mmm_f32.kernels() {
+            group.bench_with_input(
+                BenchmarkId::new(kernel.name, format!("{}x{}", m, k)),
+                &(m, k),
+                |be, (&m, &k)| {
+                    let mut mm = kernel.instantiate(m, k, 1);
+                    let pa = vec(mm.a_pack().len(), mm.a_pack().alignment());
+                    let b = vec![0.0; k];
+                    let mut c = vec![0.0; m];
+                    unsafe {
+                        mm.b_vec_from_data();
+                        mm.c_vec_from_data();
+                    }
+                    be.iter(move || unsafe { mm.run(pa, b.as_ptr(), c.as_mut_ptr(), &[]) });
+                },
+            );
+        }
     }
     group.finish();
 }
@@ -7,7 +7,7 @@ pub fn vec<T>(len: usize, align: usize) -> *mut T {
 }
 
 fn mat_mul_f32(be: &mut Bencher, &(m, k, n): &(usize, usize, usize)) {
-    let mm = (tract_linalg::ops().mmm_f32)(m, k, n);
+    let mm = tract_linalg::ops().mmm_f32.make(m, k, n).unwrap();
     let pa = vec(mm.a_pack().len(), mm.a_pack().alignment());
     let pb = vec(mm.b_pack().len(), mm.b_pack().alignment());
     let mut c = vec![0.0; m * n];
@@ -15,7 +15,7 @@ fn mat_mul_f32(be: &mut Bencher, &(m, k, n): &(usize, usize, usize)) {
 }
 
 fn mat_mul_i8(be: &mut criterion::Bencher, &(m, k, n): &(usize, usize, usize)) {
-    let mm = (tract_linalg::ops().qmmm_i8_i8)(m, k, n);
+    let mm = tract_linalg::ops().qmmm_i8_i8.make(m, k, n).unwrap();
     let pa = vec(mm.as_mmm().a_pack().len(), mm.as_mmm().a_pack().alignment());
     let pb = vec(mm.as_mmm().b_pack().len(), mm.as_mmm().b_pack().alignment());
     let mut c = vec![0i8; m * n];
@@ -44,11 +44,11 @@ fn direct_conv_geo(
 
 fn direct_conv_mmm_f32(be: &mut Bencher, geo: &ConvGeo) {
     let (m, k, n, rows_offsets, cols_offsets, b_len) = direct_conv_geo(geo);
-    let mm = (tract_linalg::ops().mmm_f32)(m, k, n);
+    let mm = tract_linalg::ops().mmm_f32.make(m, k, n).unwrap();
     let pa = vec(mm.a_pack().len(), mm.a_pack().alignment());
     let pb = vec![0.0; b_len];
     let mut c = vec![0.0; m * n];
-    let mut mm = (tract_linalg::ops().mmm_f32)(m, k, n);
+    let mut mm = tract_linalg::ops().mmm_f32.make(m, k, n).unwrap();
     unsafe {
         mm.b_from_data_and_offsets(&rows_offsets, &cols_offsets);
     }
@@ -57,11 +57,11 @@ fn direct_conv_mmm_f32(be: &mut Bencher, geo: &ConvGeo) {
 
 fn direct_conv_i8(be: &mut Bencher, geo: &ConvGeo) {
     let (m, k, n, rows_offsets, cols_offsets, b_len) = direct_conv_geo(geo);
-    let mm = (tract_linalg::ops().mmm_f32)(m, k, n);
+    let mm = tract_linalg::ops().mmm_f32.make(m, k, n).unwrap();
     let pa = vec(mm.a_pack().len(), mm.a_pack().alignment());
     let pb = vec![0; b_len];
     let mut c = vec![0; m * n];
-    let mut mm = (tract_linalg::ops().qmmm_i8_i8)(m, k, n);
+    let mut mm = tract_linalg::ops().qmmm_i8_i8.make(m, k, n).unwrap();
     unsafe {
         mm.as_mmm_mut().b_from_data_and_offsets(&rows_offsets, &cols_offsets);
     }
@@ -16,7 +16,7 @@ fn main() {
 
     match arch.as_ref() {
         "x86_64" => {
-            let files = preprocess_files("x86_64/fma", &[]);
+            let files = preprocess_files("x86_64/fma", &[("qtypes", vec!("i8", "i8u8", "u8i8"))]);
 
             match os.as_ref() {
                 "windows" => {
@@ -59,7 +59,15 @@ fn main() {
                         // root directory that we need to clean up so we don't pollute
                         // the build output/working directory
                         let _ = fs::remove_file("fma_mmm_f32_16x6.asm");
+                        let _ = fs::remove_file("fma_mmm_f64_8x6.asm");
                         let _ = fs::remove_file("fma_mmm_i8_8x8.asm");
+                        let _ = fs::remove_file("fma_mmm_i8u8_8x8.asm");
+                        let _ = fs::remove_file("fma_mmm_u8i8_8x8.asm");
+                        let _ = fs::remove_file("fma_mmv_f32_64x1.asm");
+                        let _ = fs::remove_file("fma_mmv_i8_32x1.asm");
+                        let _ = fs::remove_file("fma_sigmoid_f32.asm");
+                        let _ = fs::remove_file("fma_tanh_f32.asm");
+                        let _ = fs::remove_file("fma_lut_u8.asm");
                     }
                 }
                 "macos" => {
@@ -1,8 +1,6 @@
 use std::{env, fs};
 mod armv7neon;
 mod armvfpv2;
-use crate::frame::MatMatMulImpl;
-use crate::frame::QMatMatMulImpl;
 use crate::frame::SigmoidImpl;
 use crate::frame::TanhImpl;
 
@@ -25,35 +23,47 @@ fn has_neon() -> bool {
 pub fn plug(ops: &mut Ops) {
     if has_neon() {
         log::info!("armv7neon activated (smmm, ssigmoid), stanh)");
-        ops.mmm_f32 = Box::new(|m, k, n| {
-            Box::new(MatMatMulImpl::<armv7neon::MatMatMulF32x8x4, f32, f32, f32, f32>::new(m, k, n))
-        });
-        ops.qmmm_i8_i8 = Box::new(|m, k, n| {
-            Box::new(QMatMatMulImpl::from(MatMatMulImpl::<
-                armv7neon::MatMatMulI8x8x4,
-                i8,
-                i8,
-                i8,
-                i32,
-            >::new(m, k, n)))
-        });
-        ops.qmmm_i8_i32 = Box::new(|m, k, n| {
-            Box::new(QMatMatMulImpl::from(MatMatMulImpl::<
-                armv7neon::MatMatMulI8xI32x8x4,
-                i8,
-                i8,
-                i32,
-                i32,
-            >::new(m, k, n)))
-        });
+        ops.mmm_f32.register(mmm_kernel!(
+            "armv7neon_8x4",
+            armv7neon::MatMatMulF32x8x4,
+            f32,
+            f32,
+            f32,
+            f32,
+            16.0
+        ));
+        ops.qmmm_i8_i8.register(qmmm_kernel!(
+            "armv7neon_8x4",
+            armv7neon::MatMatMulI8x8x4,
+            i8,
+            i8,
+            i8,
+            i32,
+            16.0
+        ));
+        ops.qmmm_i8_i32.register(qmmm_kernel!(
+            "armv7neon_8x4",
+            armv7neon::MatMatMulI8xI32x8x4,
+            i8,
+            i8,
+            i32,
+            i32,
+            16.0
+        ));
         ops.sigmoid_f32 =
             Box::new(|| Box::new(SigmoidImpl::<armv7neon::SigmoidF32x4n, f32>::new()));
         ops.tanh_f32 = Box::new(|| Box::new(TanhImpl::<armv7neon::TanhF32x4n, f32>::new()));
     } else {
         log::info!("armvfpv2 activated for smmm");
-        ops.mmm_f32 = Box::new(|m, k, n| {
-            Box::new(MatMatMulImpl::<armvfpv2::MatMatMulF32x4x4, f32, f32, f32, f32>::new(m, k, n))
-        });
+        ops.mmm_f32.register(mmm_kernel!(
+            "armvfpv2_4x4",
+            armvfpv2::MatMatMulF32x4x4,
+            f32,
+            f32,
+            f32,
+            f32,
+            4.0
+        ));
     }
 }
 
@@ -2,8 +2,6 @@ mod arm64simd;
 
 use crate::Ops;
 
-use crate::frame::MatMatMulImpl;
-use crate::frame::QMatMatMulImpl;
 use crate::frame::SigmoidImpl;
 use crate::frame::TanhImpl;
 
@@ -18,31 +16,45 @@ fn is_cortex_a5x() -> std::io::Result<bool> {
 pub fn plug(ops: &mut Ops) {
     if is_cortex_a5x().unwrap_or(false) {
         log::info!("arm64simd activated for smmm (cortex A53/A55 variant)");
-        ops.mmm_f32 = Box::new(|m, k, n| {
-            Box::new(MatMatMulImpl::<arm64simd::MatMatMulF32x8x8A5x, f32, f32, f32, f32>::new(
-                m, k, n,
-            ))
-        });
+        ops.mmm_f32.register(mmm_kernel!(
+            "arm64simd_8x8",
+            arm64simd::MatMatMulF32x8x8A5x,
+            f32,
+            f32,
+            f32,
+            f32,
+            32.0
+        ));
     } else {
         log::info!("arm64simd activated for smmm (generic variant)");
-        ops.mmm_f32 = Box::new(|m, k, n| {
-            Box::new(MatMatMulImpl::<arm64simd::MatMatMulF32x8x8, f32, f32, f32, f32>::new(m, k, n))
-        })
+        ops.mmm_f32.register(mmm_kernel!(
+            "arm64simd_8x8",
+            arm64simd::MatMatMulF32x8x8,
+            f32,
+            f32,
+            f32,
+            f32,
+            32.0
+        ));
     }
-    ops.qmmm_i8_i8 = Box::new(|m, k, n| {
-        Box::new(QMatMatMulImpl::from(
-            MatMatMulImpl::<arm64simd::MatMatMulI8x8x8, i8, i8, i8, i32>::new(m, k, n),
-        ))
-    });
-    ops.qmmm_i8_i32 = Box::new(|m, k, n| {
-        Box::new(QMatMatMulImpl::from(MatMatMulImpl::<
-            arm64simd::MatMatMulI8xI32x8x8,
-            i8,
-            i8,
-            i32,
-            i32,
-        >::new(m, k, n)))
-    });
+    ops.qmmm_i8_i8.register(qmmm_kernel!(
+        "arm64simd_8x8",
+        arm64simd::MatMatMulI8x8x8,
+        i8,
+        i8,
+        i8,
+        i32,
+        32.0
+    ));
+    ops.qmmm_i8_i32.register(qmmm_kernel!(
+        "arm64simd_8x8",
+        arm64simd::MatMatMulI8xI32x8x8,
+        i8,
+        i8,
+        i32,
+        i32,
+        32.0
+    ));
     ops.sigmoid_f32 = Box::new(|| Box::new(SigmoidImpl::<arm64simd::SigmoidF32x4n, f32>::new()));
     ops.tanh_f32 = Box::new(|| Box::new(TanhImpl::<arm64simd::TanhF32x4n, f32>::new()));
 }
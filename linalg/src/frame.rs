@@ -9,7 +9,7 @@ pub mod sigmoid;
 #[macro_use]
 pub mod tanh;
 
-pub use pack_a::PackA;
+pub use pack_a::{BlockSparsePackA, PackA};
 pub use pack_b::PackB;
 
 pub use self::mmm::{MatMatMul, MatMatMulImpl, QMatMatMul, QMatMatMulImpl};
@@ -9,12 +9,19 @@ pub trait Lut: fmt::Debug + dyn_clone::DynClone + Send + Sync {
 
 dyn_clone::clone_trait_object!(Lut);
 
+impl PartialEq for Box<dyn Lut> {
+    fn eq(&self, other: &Box<dyn Lut>) -> bool {
+        self.table() == other.table()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LutImpl<K>
 where
     K: LutKer,
 {
     table: Buffer<u8>,
+    len: usize,
     _boo: PhantomData<K>,
 }
 
@@ -23,10 +30,11 @@ where
     K: LutKer,
 {
     pub fn new(table: &[u8]) -> LutImpl<K> {
-        LutImpl {
-            table: Buffer::realign_data(table, K::table_alignment_bytes()),
-            _boo: PhantomData,
-        }
+        // kernels may read the whole 256 entries, whatever the table length
+        let mut padded = Buffer::uninitialized(table.len().max(256), K::table_alignment_bytes());
+        padded[..table.len()].copy_from_slice(table);
+        padded[table.len()..].iter_mut().for_each(|x| *x = 0);
+        LutImpl { table: padded, len: table.len(), _boo: PhantomData }
     }
 }
 
@@ -35,7 +43,7 @@ where
     K: LutKer,
 {
     fn table(&self) -> &[u8] {
-        &self.table
+        &self.table[..self.len]
     }
 
     fn run(&self, buf: &mut [u8]) {
@@ -6,10 +6,14 @@ pub(crate) mod kernel;
 pub(crate) mod mmm;
 #[macro_use]
 pub(crate) mod qmmm;
+#[macro_use]
+pub(crate) mod registry;
 mod storage;
+pub mod tuning;
 
 pub use fuse::*;
 pub use kernel::*;
 pub use mmm::*;
 pub use qmmm::*;
+pub use registry::*;
 pub use storage::*;
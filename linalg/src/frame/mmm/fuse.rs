@@ -19,6 +19,7 @@ pub enum FusedSpec<TI: Copy + Debug> {
     ScalarAdd(TI),
     QTowardsEven(TI, usize),
     QTowardsPlusInf(TI, usize),
+    PerRowQTowardsPlusInf(Vec<TI>, Vec<usize>),
 }
 
 impl<TI: Copy + Debug> Debug for FusedSpec<TI> {
@@ -36,6 +37,7 @@ impl<TI: Copy + Debug> Debug for FusedSpec<TI> {
             FusedSpec::ScalarAdd(_) => write!(fmt, "ScalarAdd"),
             FusedSpec::QTowardsEven(_, _) => write!(fmt, "QTowardsEven"),
             FusedSpec::QTowardsPlusInf(_, _) => write!(fmt, "QTowardsPlusInf"),
+            FusedSpec::PerRowQTowardsPlusInf(_, _) => write!(fmt, "PerRowQTowardsPlusInf"),
         }
     }
 }
@@ -69,6 +71,10 @@ impl<TI: Copy + Debug> std::hash::Hash for FusedSpec<TI> {
                 h(&[*a], state);
                 h(&[*b], state);
             }
+            PerRowQTowardsPlusInf(a, b) => {
+                h(&*a, state);
+                h(&*b, state);
+            }
         }
     }
 }
@@ -89,16 +95,22 @@ pub enum FusedKerSpec<TI: Copy> {
     ScalarAdd(TI),
     QTowardsEven(TI, usize),
     QTowardsPlusInf(TI, usize),
+    PerRowQTowardsPlusInf(*const TI, *const usize),
 }
 
 pub struct ScratchSpaceFusedNonLinear<TI: Copy> {
     uspecs: Vec<FusedKerSpec<TI>>,
     non_linear_buffers: Vec<Vec<TI>>,
+    shift_buffers: Vec<Vec<usize>>,
 }
 
 impl<TI: Copy> Default for ScratchSpaceFusedNonLinear<TI> {
     fn default() -> ScratchSpaceFusedNonLinear<TI> {
-        ScratchSpaceFusedNonLinear { uspecs: vec![], non_linear_buffers: vec![] }
+        ScratchSpaceFusedNonLinear {
+            uspecs: vec![],
+            non_linear_buffers: vec![],
+            shift_buffers: vec![],
+        }
     }
 }
 
@@ -200,6 +212,23 @@ impl<TI: Copy> ScratchSpaceFusedNonLinear<TI> {
                 FusedSpec::ScalarAdd(t) => FusedKerSpec::ScalarAdd(*t),
                 FusedSpec::QTowardsEven(m, s) => FusedKerSpec::QTowardsEven(*m, *s),
                 FusedSpec::QTowardsPlusInf(m, s) => FusedKerSpec::QTowardsPlusInf(*m, *s),
+                FusedSpec::PerRowQTowardsPlusInf(mults, shifts) => {
+                    let have = mults.len() - down * K::mr();
+                    let (mults_ptr, shifts_ptr) = if have < K::mr() {
+                        let mut buf = vec![TI::zero(); K::mr()];
+                        buf[..have].copy_from_slice(&mults[down * K::mr()..][..have]);
+                        let mults_ptr = buf.as_ptr();
+                        self.non_linear_buffers.push(buf);
+                        let mut buf = vec![0; K::mr()];
+                        buf[..have].copy_from_slice(&shifts[down * K::mr()..][..have]);
+                        let shifts_ptr = buf.as_ptr();
+                        self.shift_buffers.push(buf);
+                        (mults_ptr, shifts_ptr)
+                    } else {
+                        (mults.as_ptr().add(down * K::mr()), shifts.as_ptr().add(down * K::mr()))
+                    };
+                    FusedKerSpec::PerRowQTowardsPlusInf(mults_ptr, shifts_ptr)
+                }
             };
             self.uspecs.push(s);
         }
@@ -374,6 +403,16 @@ pub mod test {
                     }
                 }
 
+                #[test]
+                fn return_per_row_q_towards_plusinf() {
+                    if $cond {
+                        let len = <$ker>::mr() * <$ker>::nr();
+                        let v: Vec<$tc> = (0..len).map(|f| ((f * 7) % 41) as $tc).collect();
+                        let pb = QTowardsPlusInfProblem::<$ker, $ta, $tb, $tc, $ti>::new(v);
+                        assert_eq!(pb.run_per_row(), pb.reference_per_row())
+                    }
+                }
+
                 proptest::proptest! {
                     #[test]
                     fn return_q_towards_plusinf_prop(pb in any::<QTowardsPlusInfProblem<$ker, $ta, $tb, $tc, $ti>>()) {
@@ -381,6 +420,13 @@ pub mod test {
                             prop_assert_eq!(pb.run(), pb.reference())
                         }
                     }
+
+                    #[test]
+                    fn return_per_row_q_towards_plusinf_prop(pb in any::<QTowardsPlusInfProblem<$ker, $ta, $tb, $tc, $ti>>()) {
+                        if $cond {
+                            prop_assert_eq!(pb.run_per_row(), pb.reference_per_row())
+                        }
+                    }
                 }
             }
         };
@@ -817,6 +863,42 @@ pub mod test {
                 ],
             )
         }
+
+        fn per_row_params(row: usize) -> (i64, usize) {
+            (if row % 2 == 0 { 1 << 30 } else { 1 << 29 }, 1 + row % 3)
+        }
+
+        pub fn reference_per_row(&self) -> Vec<TC> {
+            self.c
+                .iter()
+                .enumerate()
+                .map(|(ix, input)| {
+                    let (mult, shift) = Self::per_row_params(ix / K::nr());
+                    let input: TI = input.as_();
+                    let input: i64 = input.as_();
+                    ((((input * 4 * mult) >> (30 + shift)) + 1) >> 1).as_()
+                })
+                .collect()
+        }
+
+        pub fn run_per_row(&self) -> Vec<TC>
+        where
+            i64: AsPrimitive<TI>,
+        {
+            let (mults, shifts): (Vec<TI>, Vec<usize>) = (0..K::mr())
+                .map(|row| {
+                    let (mult, shift) = Self::per_row_params(row);
+                    (mult.as_(), shift)
+                })
+                .unzip();
+            fused_ops::<K, TA, TB, TC, TI>(
+                &*self.c,
+                &[
+                    FusedKerSpec::ScalarMul(4.as_()),
+                    FusedKerSpec::PerRowQTowardsPlusInf(mults.as_ptr(), shifts.as_ptr()),
+                ],
+            )
+        }
     }
 
     #[derive(Debug, new)]
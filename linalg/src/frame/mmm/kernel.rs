@@ -60,6 +60,20 @@ macro_rules! test_mmm_kernel_f32 {
     };
 }
 
+#[macro_export]
+macro_rules! test_mmm_kernel_f64 {
+    ($k: ty, $id: ident, $cond: expr) => {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod $id {
+            mmm_kernel_tests!($cond, $k, f64, f64, f64, f64);
+            mmm_frame_tests!($cond, $k, f64, f64, f64, f64);
+            mmm_kernel_fuse_tests!($cond, $k, f64, f64, f64, f64);
+            mmm_s_frame_tests!($cond, $k, f64, f64, f64, f64);
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! test_mmm_kernel_i8 {
     ($k: ty, $id: ident, $cond: expr) => {
@@ -107,6 +121,20 @@ macro_rules! test_mmm_kernel_u8 {
     };
 }
 
+#[macro_export]
+macro_rules! test_qmmm_kernel_mixed {
+    ($k: ty, $id: ident, $cond: expr, $ta: ty, $tb: ty, $tc: ty) => {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod $id {
+            mmm_kernel_tests!($cond, $k, $ta, $tb, $tc, i32);
+            mmm_kernel_fuse_tests!($cond, $k, $ta, $tb, $tc, i32);
+            qmmm_kernel_fuse_tests!($cond, $k, $ta, $tb, $tc, i32);
+            qmmm_frame_tests!($cond, $k, $ta, $tb, $tc, i32);
+        }
+    };
+}
+
 #[cfg(test)]
 #[macro_use]
 pub mod test {
@@ -5,7 +5,8 @@ use std::ops::{Add, Mul};
 
 use num_traits::Zero;
 
-use crate::frame::{PackA, PackB};
+use crate::align::Buffer;
+use crate::frame::{BlockSparsePackA, PackA, PackB};
 
 use super::fuse::ScratchSpaceFusedNonLinear;
 use super::*;
@@ -29,6 +30,19 @@ where
     fn k(&self) -> usize;
     fn n(&self) -> usize;
 
+    /// Identifies the kernel, and thus the packing of A it expects.
+    fn kernel_id(&self) -> String;
+
+    unsafe fn a_block_sparse_pack(
+        &self,
+        a: *const TA,
+        rsa: isize,
+        csa: isize,
+    ) -> BlockSparsePackA<TA>;
+    unsafe fn a_from_block_sparse(&mut self, packer: &BlockSparsePackA<TA>);
+    /// Use A packed ahead of time, dense or block-sparse.
+    unsafe fn a_from_prepacked(&mut self, storage: &MatrixStoreSpec);
+
     unsafe fn b_from_data_and_offsets(&mut self, rows_offsets: &[isize], cols_offsets: &[isize]);
 
     unsafe fn b_vec_from_data_and_stride(&mut self, stride: isize);
@@ -156,6 +170,10 @@ where
         self.k
     }
 
+    fn kernel_id(&self) -> String {
+        format!("{} {}x{}", K::name(), K::mr(), K::nr())
+    }
+
     fn a_storage(&self) -> &MatrixStoreSpec {
         &self.a_storage
     }
@@ -166,6 +184,28 @@ where
         &self.c_storage
     }
 
+    unsafe fn a_block_sparse_pack(
+        &self,
+        a: *const TA,
+        rsa: isize,
+        csa: isize,
+    ) -> BlockSparsePackA<TA> {
+        BlockSparsePackA::new(self.k, self.m, K::mr(), K::alignment_bytes_packed_a(), a, rsa, csa)
+    }
+
+    unsafe fn a_from_block_sparse(&mut self, packer: &BlockSparsePackA<TA>) {
+        self.a_storage = packer.storage()
+    }
+
+    unsafe fn a_from_prepacked(&mut self, storage: &MatrixStoreSpec) {
+        match storage {
+            MatrixStoreSpec::Packed { .. } | MatrixStoreSpec::BlockSparse { .. } => {
+                self.a_storage = storage.clone()
+            }
+            _ => panic!("{} is not a packed storage for A", storage),
+        }
+    }
+
     unsafe fn b_from_data_and_offsets(&mut self, rows_offsets: &[isize], cols_offsets: &[isize]) {
         debug_assert!(rows_offsets.len() > 0);
         debug_assert!(cols_offsets.len() > 0);
@@ -225,6 +265,9 @@ where
     }
 
     unsafe fn run(&self, a: *const TA, b: *const TB, c: *mut TC, non_linear: &[FusedSpec<TI>]) {
+        if let MatrixStoreSpec::BlockSparse { .. } = self.a_storage {
+            return self.run_block_sparse(a, b, c, non_linear);
+        }
         let mr = K::mr();
         let nr = K::nr();
         let m = self.m;
@@ -306,6 +349,88 @@ where
     }
 }
 
+impl<K, TA, TB, TC, TI> MatMatMulImpl<K, TA, TB, TC, TI>
+where
+    TA: Copy + Zero + Debug + 'static,
+    TB: Copy + Zero + Debug + 'static,
+    TC: Copy + Debug + 'static,
+    TI: Copy + Add + Mul + Zero + Debug + 'static,
+    K: MatMatMulKer<TA, TB, TC, TI> + 'static,
+{
+    /// Each A panel only stores its non-zero blocks: the matching rows of the
+    /// B panels are gathered so the dense kernel runs on a shorter k.
+    unsafe fn run_block_sparse(
+        &self,
+        a: *const TA,
+        b: *const TB,
+        c: *mut TC,
+        non_linear: &[FusedSpec<TI>],
+    ) {
+        let (panel_ks, ks) = match &self.a_storage {
+            MatrixStoreSpec::BlockSparse { panel_ks, ks, .. } => (panel_ks, ks),
+            _ => unreachable!(),
+        };
+        let mr = K::mr();
+        let nr = K::nr();
+        let m = self.m;
+        let n = self.n;
+        let mut scratch = ScratchSpaceFusedNonLinear::default();
+        let mut tmpc = Vec::with_capacity(mr * nr);
+        tmpc.set_len(mr * nr);
+        let tmp_c_storage = MatrixStoreSpec::Strides {
+            row_byte_stride: (std::mem::size_of::<TC>() * nr) as isize,
+            col_byte_stride: std::mem::size_of::<TC>() as isize,
+            mr,
+            nr,
+        };
+        let ref mut tmp_tile = tmp_c_storage.wrap(tmpc.as_ptr());
+        let mut gathered_b: Buffer<TB> =
+            Buffer::uninitialized((self.k + 4) * nr, K::alignment_bytes_packed_b());
+        let mut row_byte_offsets = Vec::with_capacity(self.k + 4);
+        let a = self.a_storage.wrap(a);
+        let b = self.b_storage.wrap(b);
+        let mut c = self.c_storage.wrap(c);
+        for ia in 0..(m + mr - 1) / mr {
+            let ref panel_a = a.panel_a(ia);
+            let ks = &ks[panel_ks[ia]..panel_ks[ia + 1]];
+            let ref linear = LinearSpec::k(ks.len());
+            let rows = mr.min(m - ia * mr);
+            for ib in 0..(n + nr - 1) / nr {
+                let cols = nr.min(n - ib * nr);
+                let ref b = b.panel_b(nr, ib, cols).gather_rows(
+                    ks,
+                    nr,
+                    gathered_b.as_mut_ptr(),
+                    &mut row_byte_offsets,
+                );
+                let non_linear = scratch.for_tile::<TA, TB, TC, K>(non_linear, ia, ib);
+                if rows == mr && cols == nr {
+                    let ref direct_c = c.tile_c(ia, ib);
+                    let err = K::kernel(&MatMatMulKerSpec {
+                        a: panel_a as _,
+                        b: b as _,
+                        c: direct_c as _,
+                        linear,
+                        non_linear,
+                    });
+                    debug_assert_eq!(err, 0, "Kernel return error {}", err);
+                } else {
+                    let ref tmp_tile_c = tmp_tile.tile_c(0, 0);
+                    let err = K::kernel(&MatMatMulKerSpec {
+                        a: panel_a as _,
+                        b: b as _,
+                        c: tmp_tile_c as _,
+                        linear,
+                        non_linear,
+                    });
+                    debug_assert_eq!(err, 0, "Kernel return error {}", err);
+                    c.set_from_tile(ia, ib, rows, cols, &*tmpc);
+                }
+            }
+        }
+    }
+}
+
 impl<K, TA, TB, TC, TI> crate::hash::DynHash for MatMatMulImpl<K, TA, TB, TC, TI>
 where
     TA: Copy + Zero + 'static,
@@ -384,6 +509,23 @@ pub mod test {
                             crate::test::check_close(&found, &expected)?;
                         }
                     }
+
+                    #[test]
+                    fn mat_mul_block_sparse_prop((m, k, n, ref a, ref b) in strat_mat_mat_mul()) {
+                        if $cond {
+                            test_mat_mat_mul_block_sparse::<$ker, $ta, $tb, $tc, $ti>(m, k, n, &**a, &*b)?
+                        }
+                    }
+
+                    #[test]
+                    fn conv_block_sparse_prop(pb in strat_conv_1d()) {
+                        if $cond {
+                            let pb = pb.with_sparse_filters();
+                            let found = pb.run_block_sparse::<$ker, $tc, $ti>();
+                            let expected = pb.expected::<$tc, $ti>();
+                            crate::test::check_close(&found, &expected)?;
+                        }
+                    }
                 }
 
                 #[test]
@@ -611,6 +753,61 @@ pub mod test {
         }
     }
 
+    /// Zero two columns of A out of three, so that most blocks can be skipped.
+    pub fn sparsify<T: Datum>(a: &[T], k: usize) -> Vec<T> {
+        a.iter().enumerate().map(|(ix, &x)| if ix % k % 3 == 0 { x } else { T::zero() }).collect()
+    }
+
+    pub fn test_mat_mat_mul_block_sparse<
+        K: MatMatMulKer<TA, TB, TC, TI> + 'static,
+        TA,
+        TB,
+        TC,
+        TI,
+    >(
+        m: usize,
+        k: usize,
+        n: usize,
+        a: &[TA],
+        b: &[TB],
+    ) -> Result<(), proptest::test_runner::TestCaseError>
+    where
+        TA: Datum + AsPrimitive<TI> + 'static,
+        TB: Datum + AsPrimitive<TI> + 'static,
+        TC: Datum + 'static,
+        TI: Datum + AsPrimitive<TC> + 'static,
+    {
+        let a = sparsify(a, k);
+        let mut op = MatMatMulImpl::<K, TA, TB, TC, TI>::new(m, k, n);
+        unsafe {
+            let packer = op.a_block_sparse_pack(a.as_ptr(), k as isize, 1);
+            op.a_from_block_sparse(&packer);
+            let mut packed_a = Buffer::uninitialized(packer.len().max(1), packer.alignment());
+            packer.pack(packed_a.as_mut_ptr(), a.as_ptr(), k as isize, 1);
+
+            let mut packed_b = Buffer::uninitialized(op.b_pack().len(), op.b_pack().alignment());
+            op.b_pack().pack(packed_b.as_mut_ptr(), b.as_ptr(), n as isize, 1);
+
+            let mut found = vec![TC::max_value(); m * n];
+
+            op.run(packed_a.as_ptr(), packed_b.as_ptr(), found.as_mut_ptr(), &[]);
+
+            let mut expected = vec![TC::zero(); m * n];
+            for x in 0..n {
+                for y in 0..m {
+                    let mut v: TI = TI::zero();
+                    for i in 0..k {
+                        let a: TI = a[i + k * y].as_();
+                        let b: TI = b[x + i * n].as_();
+                        v = v + a * b;
+                    }
+                    expected[x + y * n] = v.as_();
+                }
+            }
+            crate::test::check_close(&*found, &*expected)
+        }
+    }
+
     pub fn test_mat_vec_mul_prep<K: MatMatMulKer<TA, TB, TC, TI> + 'static, TA, TB, TC, TI>(
         m: usize,
         k: usize,
@@ -913,6 +1110,29 @@ pub mod test {
                 found
             }
         }
+
+        pub fn with_sparse_filters(&self) -> ConvProblem<TA, TB> {
+            ConvProblem { filters: sparsify(&self.filters, self.k()), ..self.clone() }
+        }
+
+        pub fn run_block_sparse<K: MatMatMulKer<TA, TB, TC, TI>, TC, TI>(&self) -> Vec<TC>
+        where
+            TI: Datum,
+            TC: Datum,
+        {
+            unsafe {
+                let mut op = MatMatMulImpl::<K, TA, TB, TC, TI>::new(self.m(), self.k(), self.n());
+                op.b_from_data_and_offsets(&self.data_rows_offsets(), &self.data_cols_offsets());
+                let packer = op.a_block_sparse_pack(self.filters.as_ptr(), self.k() as isize, 1);
+                op.a_from_block_sparse(&packer);
+                let mut packed_a = Buffer::uninitialized(packer.len().max(1), packer.alignment());
+                packer.pack(packed_a.as_mut_ptr(), self.filters.as_ptr(), self.k() as isize, 1);
+
+                let mut found: Vec<TC> = vec![TC::max_value(); self.co * self.output_width()];
+                op.run(packed_a.as_ptr(), self.data.as_ptr(), found.as_mut_ptr(), &[]);
+                found
+            }
+        }
     }
 
     pub fn strat_conv_1d<TA: Datum, TB: Datum>() -> BoxedStrategy<ConvProblem<TA, TB>>
@@ -27,6 +27,7 @@ where
 
     unsafe fn set_zero_point_c_scalar(&mut self, value: TC);
     unsafe fn set_scale_factor(&mut self, factor: f32);
+    unsafe fn set_scale_factor_vector(&mut self, factors: Vec<f32>);
 
     unsafe fn run(&self, a: *const TA, b: *const TB, c: *mut TC, non_linear: &[FusedSpec<TI>]);
 }
@@ -83,6 +84,18 @@ where
 
     pub zero_point_c: Option<TC>,
     pub scale_factor: Option<(TI, usize)>,
+    pub scale_factor_per_row: Option<(Vec<TI>, Vec<usize>)>,
+}
+
+/// Fixed point multiplier and right shift approximating a positive factor.
+fn scale_to_mult_shift(factor: f32) -> (i32, usize) {
+    // https://github.com/microsoft/onnxruntime/blob/master/onnxruntime/core/util/gemmlowp_common.h#L16
+    let factor_bits = factor.to_bits();
+    let current_exponent = factor_bits >> 23;
+    let bumped_multi = f32::from_bits(factor_bits & 0x007fffff | 0x3f000000);
+    let int_multi = (bumped_multi * (1i64 << 31) as f32).round() as i32;
+    let shift = 126 - current_exponent;
+    (int_multi, shift as usize)
 }
 
 impl<K, TA, TB, TC, TI> QMatMatMulImpl<K, TA, TB, TC, TI>
@@ -121,6 +134,20 @@ where
                 }
                 result
             }
+            MatrixStoreSpec::BlockSparse { panel_offsets, panel_ks, .. } => {
+                let mr = K::mr();
+                let mut result = vec![TI::zero(); self.m];
+                for (p, &offset) in panel_offsets.iter().enumerate() {
+                    let rows = mr.min(self.m - p * mr);
+                    for block in 0..panel_ks[p + 1] - panel_ks[p] {
+                        for row in 0..rows {
+                            let value = unsafe { *a.add(offset + block * mr + row) };
+                            result[p * mr + row] = result[p * mr + row] + value.as_();
+                        }
+                    }
+                }
+                result
+            }
             a => panic!("Storage for A {:?} not supported for quantized ops", a),
         }
     }
@@ -180,6 +207,7 @@ where
             zero_point_b: None,
             zero_point_c: None,
             scale_factor: None,
+            scale_factor_per_row: None,
         }
     }
 }
@@ -265,13 +293,22 @@ where
     }
 
     unsafe fn set_scale_factor(&mut self, factor: f32) {
-        // https://github.com/microsoft/onnxruntime/blob/master/onnxruntime/core/util/gemmlowp_common.h#L16
-        let factor_bits = factor.to_bits();
-        let current_exponent = factor_bits >> 23;
-        let bumped_multi = f32::from_bits(factor_bits & 0x007fffff | 0x3f000000);
-        let int_multi = (bumped_multi * (1i64 << 31) as f32).round() as i32;
-        let shift = 126 - current_exponent;
-        self.scale_factor = Some((int_multi.as_(), shift as usize));
+        let (mult, shift) = scale_to_mult_shift(factor);
+        self.scale_factor = Some((mult.as_(), shift));
+        self.scale_factor_per_row = None;
+    }
+
+    unsafe fn set_scale_factor_vector(&mut self, factors: Vec<f32>) {
+        assert_eq!(factors.len(), self.m());
+        let (mults, shifts) = factors
+            .iter()
+            .map(|&f| {
+                let (mult, shift) = scale_to_mult_shift(f);
+                (mult.as_(), shift)
+            })
+            .unzip();
+        self.scale_factor_per_row = Some((mults, shifts));
+        self.scale_factor = None;
     }
 
     unsafe fn run(&self, a: *const TA, b: *const TB, c: *mut TC, non_linear: &[FusedSpec<TI>]) {
@@ -331,6 +368,8 @@ where
         }
         if let Some(scale) = self.scale_factor {
             non_linear.push(FusedSpec::QTowardsPlusInf(scale.0, scale.1));
+        } else if let Some((mults, shifts)) = &self.scale_factor_per_row {
+            non_linear.push(FusedSpec::PerRowQTowardsPlusInf(mults.clone(), shifts.clone()));
         }
         if let Some(c0) = self.zero_point_c {
             non_linear.push(FusedSpec::ScalarAdd(c0.as_()));
@@ -370,6 +409,7 @@ where
 
         pub zero_point_c: Option<TC>,
         pub scale_factor: Option<(TI, usize)>,
+        pub scale_factor_per_row: Option<(Vec<TI>, Vec<usize>)>,
         */
         self.mmm.hash(state);
         if let Some(a) = &self.zero_point_a {
@@ -521,6 +561,13 @@ pub mod test {
         }
 
         pub fn run<K: MatMatMulKer<TA, TB, TC, TI>>(&self) -> Vec<TC> {
+            self.run_with_scale_factors::<K>(None)
+        }
+
+        pub fn run_with_scale_factors<K: MatMatMulKer<TA, TB, TC, TI>>(
+            &self,
+            scale_factors: Option<Vec<f32>>,
+        ) -> Vec<TC> {
             unsafe {
                 let mut c = vec![TC::zero(); self.m * self.n];
                 let mut mmm = QMatMatMulImpl::from(MatMatMulImpl::<K, TA, TB, TC, TI>::new(
@@ -540,6 +587,9 @@ pub mod test {
                     QuantizedParam::Scalar(b0) => mmm.set_zero_point_b_scalar(*b0),
                     QuantizedParam::Vector(b0) => mmm.set_zero_point_b_vector(b0.clone()),
                 }
+                if let Some(scale_factors) = scale_factors {
+                    mmm.set_scale_factor_vector(scale_factors);
+                }
                 mmm.run(packed_a.as_ptr(), packed_b.as_ptr(), c.as_mut_ptr(), &[]);
                 c
             }
@@ -633,6 +683,25 @@ pub mod test {
                     }
                 }
 
+                #[test]
+                fn q_mat_mul_per_row_scale() {
+                    if $cond {
+                        let pb = QMatMulProblem {
+                            m: 3,
+                            k: 2,
+                            n: 2,
+                            a: vec![1, 2, 3, 4, 5, 6],
+                            a0: QuantizedParam::Scalar(0),
+                            b: vec![1, 2, 3, 4],
+                            b0: QuantizedParam::Scalar(0),
+                            boo: PhantomData,
+                        };
+                        let scales = vec![0.5, 0.25, 0.125];
+                        let found = pb.run_with_scale_factors::<$ker>(Some(scales));
+                        assert_eq!(found, vec![4, 5, 4, 6, 3, 4]);
+                    }
+                }
+
                 #[test]
                 fn q_mat_mul_k2() {
                     if $cond {
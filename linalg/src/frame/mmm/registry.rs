@@ -0,0 +1,291 @@
+use std::fmt::Debug;
+use std::ops::{Add, Mul};
+use std::time::{Duration, Instant};
+
+use num_traits::Zero;
+
+use crate::align::Buffer;
+
+use super::tuning;
+use super::{MatMatMul, QMatMatMul};
+
+/// A kernel candidate for a matrix multiplication type.
+///
+/// `throughput` is a relative estimate of the multiply-accumulates the kernel
+/// performs per unit of time on a full tile. It is only used by the heuristic
+/// chooser, so it only needs to be consistent within a registry.
+pub struct MMMKernel<M: ?Sized> {
+    pub name: &'static str,
+    pub mr: usize,
+    pub nr: usize,
+    pub throughput: f32,
+    factory: Box<dyn Fn(usize, usize, usize) -> Box<M> + Send + Sync>,
+}
+
+impl<M: ?Sized> MMMKernel<M> {
+    pub fn new(
+        name: &'static str,
+        mr: usize,
+        nr: usize,
+        throughput: f32,
+        factory: impl Fn(usize, usize, usize) -> Box<M> + Send + Sync + 'static,
+    ) -> MMMKernel<M> {
+        MMMKernel { name, mr, nr, throughput, factory: Box::new(factory) }
+    }
+
+    pub fn instantiate(&self, m: usize, k: usize, n: usize) -> Box<M> {
+        (self.factory)(m, k, n)
+    }
+
+    /// Heuristic cost of a (m, k, n) product: the padded work of all the tiles
+    /// divided by the kernel throughput.
+    pub fn cost(&self, m: usize, k: usize, n: usize) -> f32 {
+        let padded_m = (m + self.mr - 1) / self.mr * self.mr;
+        let padded_n = (n + self.nr - 1) / self.nr * self.nr;
+        (padded_m * padded_n * k.max(1)) as f32 / self.throughput
+    }
+}
+
+impl<M: ?Sized> Debug for MMMKernel<M> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "{} ({}x{})", self.name, self.mr, self.nr)
+    }
+}
+
+/// All the kernels registered for a matrix multiplication type.
+///
+/// `make` picks one for a given (m, k, n): a decision from the tuning cache if
+/// there is one, a micro-benchmark of all the candidates if auto-tuning is on,
+/// or the cheapest candidate according to `MMMKernel::cost` otherwise.
+#[derive(Debug)]
+pub struct MMMRegistry<M: ?Sized> {
+    pub op: &'static str,
+    kernels: Vec<MMMKernel<M>>,
+}
+
+/// Error raised when asked for a kernel on a registry without any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmptyRegistry(pub &'static str);
+
+impl std::fmt::Display for EmptyRegistry {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "No kernel registered for {}", self.0)
+    }
+}
+
+impl std::error::Error for EmptyRegistry {}
+
+impl<M: ?Sized + Tunable> MMMRegistry<M> {
+    pub fn new(op: &'static str) -> MMMRegistry<M> {
+        MMMRegistry { op, kernels: vec![] }
+    }
+
+    /// Register a kernel. Registering a kernel with the same name as an
+    /// existing one replaces it.
+    pub fn register(&mut self, kernel: MMMKernel<M>) {
+        if let Some(existing) = self.kernels.iter_mut().find(|k| k.name == kernel.name) {
+            *existing = kernel;
+        } else {
+            self.kernels.push(kernel);
+        }
+    }
+
+    pub fn kernels(&self) -> &[MMMKernel<M>] {
+        &self.kernels
+    }
+
+    pub fn kernel(&self, name: &str) -> Option<&MMMKernel<M>> {
+        self.kernels.iter().find(|k| k.name == name)
+    }
+
+    pub fn heuristic(&self, m: usize, k: usize, n: usize) -> Result<&MMMKernel<M>, EmptyRegistry> {
+        let mut best: Option<&MMMKernel<M>> = None;
+        for kernel in &self.kernels {
+            if best.map(|b| kernel.cost(m, k, n) < b.cost(m, k, n)).unwrap_or(true) {
+                best = Some(kernel)
+            }
+        }
+        best.ok_or(EmptyRegistry(self.op))
+    }
+
+    pub fn tune(&self, m: usize, k: usize, n: usize) -> Result<&MMMKernel<M>, EmptyRegistry> {
+        let mut best: Option<(&MMMKernel<M>, Duration)> = None;
+        for kernel in &self.kernels {
+            let time = unsafe { time_kernel(&*kernel.instantiate(m, k, n)) };
+            log::debug!("{} {}x{}x{} {:?}: {:?}", self.op, m, k, n, kernel, time);
+            if best.map(|b| time < b.1).unwrap_or(true) {
+                best = Some((kernel, time))
+            }
+        }
+        best.map(|b| b.0).ok_or(EmptyRegistry(self.op))
+    }
+
+    pub fn select(&self, m: usize, k: usize, n: usize) -> Result<&MMMKernel<M>, EmptyRegistry> {
+        if self.kernels.len() == 1 {
+            return Ok(&self.kernels[0]);
+        }
+        if let Some(kernel) = tuning::lookup(self.op, m, k, n).and_then(|name| self.kernel(&name)) {
+            return Ok(kernel);
+        }
+        if tuning::auto_tune() {
+            let kernel = self.tune(m, k, n)?;
+            log::info!("{} {}x{}x{} tuned to {:?}", self.op, m, k, n, kernel);
+            if let Err(e) = tuning::record(self.op, m, k, n, kernel.name) {
+                log::warn!("Failed to save tuning decision: {}", e);
+            }
+            return Ok(kernel);
+        }
+        self.heuristic(m, k, n)
+    }
+
+    pub fn make(&self, m: usize, k: usize, n: usize) -> Result<Box<M>, EmptyRegistry> {
+        Ok(self.select(m, k, n)?.instantiate(m, k, n))
+    }
+}
+
+/// Matrix multiplier that can be run on dummy operands for auto-tuning.
+pub trait Tunable {
+    unsafe fn run_dummy(&self);
+}
+
+unsafe fn dummy_operands<TA, TB, TC, TI>(
+    mmm: &dyn MatMatMul<TA, TB, TC, TI>,
+) -> (Buffer<TA>, Buffer<TB>, Buffer<TC>)
+where
+    TA: Copy + Zero + 'static,
+    TB: Copy + Zero + 'static,
+    TC: Copy + Debug + 'static,
+    TI: Copy + Add + Mul + Zero + Debug + 'static,
+{
+    let mut a = Buffer::uninitialized(mmm.a_pack().len(), mmm.a_pack().alignment());
+    a.iter_mut().for_each(|x| *x = TA::zero());
+    let mut b = Buffer::uninitialized(mmm.b_pack().len(), mmm.b_pack().alignment());
+    b.iter_mut().for_each(|x| *x = TB::zero());
+    let c = Buffer::uninitialized((mmm.m() * mmm.n()).max(1), std::mem::align_of::<TC>());
+    (a, b, c)
+}
+
+impl<TA, TB, TC, TI> Tunable for dyn MatMatMul<TA, TB, TC, TI>
+where
+    TA: Copy + Zero + 'static,
+    TB: Copy + Zero + 'static,
+    TC: Copy + Debug + 'static,
+    TI: Copy + Add + Mul + Zero + Debug + 'static,
+{
+    unsafe fn run_dummy(&self) {
+        let (a, b, mut c) = dummy_operands(self);
+        self.run(a.as_ptr(), b.as_ptr(), c.as_mut_ptr(), &[])
+    }
+}
+
+impl<TA, TB, TC, TI> Tunable for dyn QMatMatMul<TA, TB, TC, TI>
+where
+    TA: Copy + Zero + 'static,
+    TB: Copy + Zero + 'static,
+    TC: Copy + Debug + 'static,
+    TI: Copy + Add + Mul + Zero + Debug + 'static,
+{
+    unsafe fn run_dummy(&self) {
+        let (a, b, mut c) = dummy_operands(self.as_mmm());
+        self.run(a.as_ptr(), b.as_ptr(), c.as_mut_ptr(), &[])
+    }
+}
+
+unsafe fn time_kernel<M: ?Sized + Tunable>(mmm: &M) -> Duration {
+    mmm.run_dummy();
+    let budget = Instant::now();
+    let mut best = Duration::from_secs(u64::MAX);
+    for _ in 0..20 {
+        let start = Instant::now();
+        mmm.run_dummy();
+        best = best.min(start.elapsed());
+        if budget.elapsed() > Duration::from_millis(50) {
+            break;
+        }
+    }
+    best
+}
+
+/// Builds an `MMMKernel` instantiating `MatMatMulImpl` over the `$ker` kernel.
+#[macro_export]
+macro_rules! mmm_kernel {
+    ($name: expr, $ker: ty, $ta: ty, $tb: ty, $tc: ty, $ti: ty, $throughput: expr) => {
+        $crate::mmm::MMMKernel::new(
+            $name,
+            <$ker as $crate::mmm::MatMatMulKer<$ta, $tb, $tc, $ti>>::mr(),
+            <$ker as $crate::mmm::MatMatMulKer<$ta, $tb, $tc, $ti>>::nr(),
+            $throughput,
+            |m, k, n| {
+                Box::new($crate::mmm::MatMatMulImpl::<$ker, $ta, $tb, $tc, $ti>::new(m, k, n))
+            },
+        )
+    };
+}
+
+/// Same as `mmm_kernel!`, wrapping the multiplier in a `QMatMatMulImpl`.
+#[macro_export]
+macro_rules! qmmm_kernel {
+    ($name: expr, $ker: ty, $ta: ty, $tb: ty, $tc: ty, $ti: ty, $throughput: expr) => {
+        $crate::mmm::MMMKernel::new(
+            $name,
+            <$ker as $crate::mmm::MatMatMulKer<$ta, $tb, $tc, $ti>>::mr(),
+            <$ker as $crate::mmm::MatMatMulKer<$ta, $tb, $tc, $ti>>::nr(),
+            $throughput,
+            |m, k, n| {
+                Box::new($crate::mmm::QMatMatMulImpl::from($crate::mmm::MatMatMulImpl::<
+                    $ker,
+                    $ta,
+                    $tb,
+                    $tc,
+                    $ti,
+                >::new(m, k, n)))
+            },
+        )
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::MatMatMulImpl;
+    use crate::generic::{GenericMmm4x4, GenericMmv4x1};
+
+    fn registry() -> MMMRegistry<dyn MatMatMul<f32, f32, f32, f32>> {
+        let mut reg = MMMRegistry::new("test_mmm_f32");
+        reg.register(MMMKernel::new("generic_4x4", 4, 4, 4.0, |m, k, n| {
+            Box::new(MatMatMulImpl::<GenericMmm4x4<f32, f32, f32, f32>, f32, f32, f32, f32>::new(
+                m, k, n,
+            ))
+        }));
+        reg.register(MMMKernel::new("generic_4x1", 4, 1, 2.0, |m, k, n| {
+            Box::new(MatMatMulImpl::<GenericMmv4x1<f32, f32, f32, f32>, f32, f32, f32, f32>::new(
+                m, k, n,
+            ))
+        }));
+        reg
+    }
+
+    #[test]
+    fn heuristic_picks_matvec_for_single_column() {
+        assert_eq!(registry().heuristic(64, 64, 1).unwrap().name, "generic_4x1");
+    }
+
+    #[test]
+    fn heuristic_picks_tiles_for_square() {
+        assert_eq!(registry().heuristic(64, 64, 64).unwrap().name, "generic_4x4");
+    }
+
+    #[test]
+    fn empty_registry_is_an_error() {
+        let reg: MMMRegistry<dyn MatMatMul<f32, f32, f32, f32>> = MMMRegistry::new("empty");
+        assert_eq!(reg.heuristic(8, 8, 8).err(), Some(EmptyRegistry("empty")));
+        assert!(reg.make(8, 8, 8).is_err());
+    }
+
+    #[test]
+    fn tune_runs_all_candidates() {
+        let reg = registry();
+        let kernel = reg.tune(8, 8, 3).unwrap();
+        assert!(reg.kernel(kernel.name).is_some());
+    }
+}
@@ -7,9 +7,20 @@ pub enum MatrixStoreSpec {
     Strides { row_byte_stride: isize, col_byte_stride: isize, mr: usize, nr: usize },
     OffsetsAndPtrs { row_byte_offsets: Vec<isize>, col_byte_offsets: Vec<isize>, nr: usize },
     VecStride { byte_stride: isize, mr: usize, nr: usize },
+    BlockSparse { k: usize, panel_offsets: Vec<usize>, panel_ks: Vec<usize>, ks: Vec<usize> },
 }
 
 impl MatrixStoreSpec {
+    /// Fraction of the A blocks actually multiplied: 1.0 for dense storages.
+    pub fn density(&self) -> f32 {
+        match self {
+            MatrixStoreSpec::BlockSparse { k, panel_offsets, ks, .. } if *k > 0 => {
+                ks.len() as f32 / (panel_offsets.len() * k) as f32
+            }
+            _ => 1.0,
+        }
+    }
+
     pub unsafe fn wrap<T: Copy + Debug>(&self, ptr: *const T) -> MatrixStore<T> {
         match self {
             MatrixStoreSpec::Packed { panel_len } => {
@@ -32,6 +43,9 @@ impl MatrixStoreSpec {
                     col_byte_offsets.iter().map(|&i| (ptr as *const u8).offset(i) as _).collect();
                 MatrixStore::OffsetsAndPtrs { col_ptrs, row_byte_offsets, nr: *nr }
             }
+            MatrixStoreSpec::BlockSparse { panel_offsets, .. } => {
+                MatrixStore::BlockSparse { ptr, panel_offsets }
+            }
         }
     }
 }
@@ -43,6 +57,7 @@ impl fmt::Display for MatrixStoreSpec {
             MatrixStoreSpec::Strides { .. } => write!(fmt, "Strides"),
             MatrixStoreSpec::OffsetsAndPtrs { .. } => write!(fmt, "OffsetsAndPtrs"),
             MatrixStoreSpec::VecStride { .. } => write!(fmt, "VecStrides"),
+            MatrixStoreSpec::BlockSparse { .. } => write!(fmt, "BlockSparse"),
         }
     }
 }
@@ -53,6 +68,7 @@ pub enum MatrixStore<'a, T: Copy> {
     Packed { ptr: *const T, panel_len: usize },
     OffsetsAndPtrs { row_byte_offsets: &'a [isize], col_ptrs: Vec<*const T>, nr: usize },
     VecStride { ptr: *const T, byte_stride: isize, mr: usize, nr: usize },
+    BlockSparse { ptr: *const T, panel_offsets: &'a [usize] },
 }
 
 impl<'a, T: Copy> MatrixStore<'a, T> {
@@ -61,6 +77,9 @@ impl<'a, T: Copy> MatrixStore<'a, T> {
             MatrixStore::Packed { ptr, panel_len } => {
                 PanelStore::Packed { ptr: ptr.offset((panel_len * i) as isize) }
             }
+            MatrixStore::BlockSparse { ptr, panel_offsets } => {
+                PanelStore::Packed { ptr: ptr.add(panel_offsets[i]) }
+            }
             _ => unimplemented!(),
         }
     }
@@ -106,6 +125,14 @@ impl<'a, T: Copy> MatrixStore<'a, T> {
                     item_size: std::mem::size_of::<T>(),
                 }
             }
+            MatrixStore::VecStride { ptr, byte_stride, mr, .. } => {
+                debug_assert_eq!(right, 0);
+                PanelStore::VecStride {
+                    ptr: ((*ptr as isize) + (*byte_stride * (down * mr) as isize)) as *const T,
+                    byte_stride: *byte_stride,
+                    item_size: std::mem::size_of::<T>(),
+                }
+            }
             _ => unimplemented!(),
         }
     }
@@ -152,3 +179,40 @@ pub enum PanelStore<T: Copy> {
     OffsetsAndPtrs { row_byte_offsets: *const isize, col_ptrs: *const *const T },
     VecStride { ptr: *const T, byte_stride: isize, item_size: usize },
 }
+
+impl<T: Copy> PanelStore<T> {
+    /// Restrict a B panel to the rows listed in `ks`, copying packed or vector
+    /// rows to `buffer`, and selecting rows offsets into `row_byte_offsets`.
+    pub(super) unsafe fn gather_rows(
+        &self,
+        ks: &[usize],
+        nr: usize,
+        buffer: *mut T,
+        row_byte_offsets: &mut Vec<isize>,
+    ) -> PanelStore<T> {
+        match *self {
+            PanelStore::Packed { ptr } => {
+                for (ix, &k) in ks.iter().enumerate() {
+                    std::ptr::copy_nonoverlapping(ptr.add(k * nr), buffer.add(ix * nr), nr);
+                }
+                PanelStore::Packed { ptr: buffer }
+            }
+            PanelStore::VecStride { ptr, byte_stride, item_size } => {
+                for (ix, &k) in ks.iter().enumerate() {
+                    *buffer.add(ix) =
+                        *((ptr as *const u8).offset(k as isize * byte_stride) as *const T);
+                }
+                PanelStore::VecStride { ptr: buffer, byte_stride: item_size as isize, item_size }
+            }
+            PanelStore::OffsetsAndPtrs { row_byte_offsets: offsets, col_ptrs } => {
+                row_byte_offsets.clear();
+                row_byte_offsets.extend(ks.iter().map(|&k| *offsets.add(k)));
+                // repeat the last offset four times to simplify kernel loop unrolling
+                let pad = row_byte_offsets.last().cloned().unwrap_or(*offsets);
+                row_byte_offsets.extend(std::iter::repeat(pad).take(4));
+                PanelStore::OffsetsAndPtrs { row_byte_offsets: row_byte_offsets.as_ptr(), col_ptrs }
+            }
+            PanelStore::Strides { .. } => unimplemented!(),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Kernel selection cache.
+//!
+//! Decisions are keyed by operation name and (m, k, n), and stored in a plain
+//! text file, one decision per line:
+//!
+//! ```text
+//! mmm_f32 256 256 1 fma_64x1
+//! ```
+//!
+//! The cache and auto-tuning can be set up programmatically, or from the
+//! `TRACT_LINALG_TUNING_CACHE` (path of the cache file) and
+//! `TRACT_LINALG_AUTOTUNE` (`true` to micro-benchmark the candidates for
+//! shapes missing from the cache) environment variables.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::{env, fs, io};
+
+type Key = (String, usize, usize, usize);
+
+#[derive(Debug, Default)]
+struct Tuning {
+    auto_tune: bool,
+    path: Option<PathBuf>,
+    decisions: HashMap<Key, String>,
+}
+
+lazy_static::lazy_static! {
+    static ref TUNING: Mutex<Tuning> = {
+        let mut tuning = Tuning::default();
+        tuning.auto_tune = env::var("TRACT_LINALG_AUTOTUNE").map(|v| v == "true").unwrap_or(false);
+        if let Ok(path) = env::var("TRACT_LINALG_TUNING_CACHE") {
+            match load(&path) {
+                Ok(decisions) => tuning.decisions = decisions,
+                Err(e) => log::warn!("Could not load tuning cache {}: {}", path, e),
+            }
+            tuning.path = Some(path.into());
+        }
+        Mutex::new(tuning)
+    };
+}
+
+fn parse(content: &str) -> io::Result<HashMap<Key, String>> {
+    let mut decisions = HashMap::new();
+    for (ix, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let invalid =
+            || io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {:?}", ix + 1, line));
+        if tokens.len() != 5 {
+            return Err(invalid());
+        }
+        let dim = |s: &str| s.parse::<usize>().map_err(|_| invalid());
+        let key = (tokens[0].to_string(), dim(tokens[1])?, dim(tokens[2])?, dim(tokens[3])?);
+        decisions.insert(key, tokens[4].to_string());
+    }
+    Ok(decisions)
+}
+
+fn dump(decisions: &HashMap<Key, String>) -> String {
+    let mut lines: Vec<String> = decisions
+        .iter()
+        .map(|((op, m, k, n), kernel)| format!("{} {} {} {} {}\n", op, m, k, n, kernel))
+        .collect();
+    lines.sort();
+    lines.concat()
+}
+
+fn load(path: impl AsRef<Path>) -> io::Result<HashMap<Key, String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => parse(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Use the cache file at `path`, loading its decisions if it exists. With
+/// `auto_tune`, shapes missing from the cache are benchmarked and the
+/// decisions written back to the file.
+pub fn set_tuning_cache(path: impl AsRef<Path>, auto_tune: bool) -> io::Result<()> {
+    let decisions = load(path.as_ref())?;
+    let mut tuning = TUNING.lock().unwrap();
+    tuning.decisions = decisions;
+    tuning.path = Some(path.as_ref().to_path_buf());
+    tuning.auto_tune = auto_tune;
+    Ok(())
+}
+
+/// Turn auto-tuning on or off, keeping the current decisions in memory only.
+pub fn set_auto_tune(auto_tune: bool) {
+    TUNING.lock().unwrap().auto_tune = auto_tune;
+}
+
+/// Forget all decisions and go back to heuristic selection.
+pub fn reset_tuning() {
+    *TUNING.lock().unwrap() = Tuning::default();
+}
+
+pub(crate) fn auto_tune() -> bool {
+    TUNING.lock().unwrap().auto_tune
+}
+
+pub(crate) fn lookup(op: &str, m: usize, k: usize, n: usize) -> Option<String> {
+    TUNING.lock().unwrap().decisions.get(&(op.to_string(), m, k, n)).cloned()
+}
+
+pub(crate) fn record(op: &str, m: usize, k: usize, n: usize, kernel: &str) -> io::Result<()> {
+    let mut tuning = TUNING.lock().unwrap();
+    tuning.decisions.insert((op.to_string(), m, k, n), kernel.to_string());
+    if let Some(path) = &tuning.path {
+        fs::write(path, dump(&tuning.decisions))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let decisions =
+            parse("# comment\nmmm_f32 256 256 1 fma_64x1\n\nqmmm_i8_i8 8 8 8 generic_4x4\n")
+                .unwrap();
+        assert_eq!(decisions.len(), 2);
+        assert_eq!(decisions[&("mmm_f32".to_string(), 256, 256, 1)], "fma_64x1");
+        assert_eq!(parse(&dump(&decisions)).unwrap(), decisions);
+    }
+
+    #[test]
+    fn invalid_line() {
+        assert!(parse("mmm_f32 256 256 fma_64x1").is_err());
+        assert!(parse("mmm_f32 256 256 x fma_64x1").is_err());
+    }
+}
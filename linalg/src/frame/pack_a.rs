@@ -2,6 +2,8 @@ use num_traits::Zero;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use crate::frame::mmm::MatrixStoreSpec;
+
 #[derive(Clone, Debug, Eq, PartialEq, Educe)]
 #[educe(Hash)]
 pub struct PackA<T: Copy + Zero> {
@@ -20,6 +22,10 @@ impl<T: Copy + Zero + Debug> PackA<T> {
         self.alignment
     }
 
+    pub fn mr(&self) -> usize {
+        self.mr
+    }
+
     pub fn len(&self) -> usize {
         (self.m + self.mr - 1) / self.mr * self.mr * self.k
     }
@@ -67,3 +73,148 @@ impl<T: Copy + Zero + Debug> PackA<T> {
         }
     }
 }
+
+/// Block-sparse packing of a constant A.
+///
+/// Rows are grouped in panels of `mr` like `PackA` does, and each panel is cut
+/// along k in mr x 1 blocks. Only the blocks holding a non-zero value are
+/// stored, so a panel is a dense `PackA` panel over its own subset of k. Each
+/// panel starts on an aligned boundary.
+#[derive(Clone, Debug, Eq, PartialEq, Educe)]
+#[educe(Hash)]
+pub struct BlockSparsePackA<T: Copy + Zero> {
+    k: usize,
+    m: usize,
+    mr: usize,
+    alignment: usize,
+    panel_offsets: Vec<usize>,
+    panel_ks: Vec<usize>,
+    ks: Vec<usize>,
+    len: usize,
+    _boo: PhantomData<T>,
+}
+
+impl<T: Copy + Zero + Debug> BlockSparsePackA<T> {
+    /// Scan `a` (m x k, with row and column strides) for its non-zero blocks.
+    pub unsafe fn new(
+        k: usize,
+        m: usize,
+        mr: usize,
+        alignment: usize,
+        a: *const T,
+        rsa: isize,
+        csa: isize,
+    ) -> BlockSparsePackA<T> {
+        let panels = (m + mr - 1) / mr;
+        let align = alignment / std::mem::size_of::<T>().max(1);
+        let mut panel_offsets = Vec::with_capacity(panels);
+        let mut panel_ks = Vec::with_capacity(panels + 1);
+        let mut ks = vec![];
+        let mut len = 0;
+        panel_ks.push(0);
+        for p in 0..panels {
+            let rows = mr.min(m - p * mr);
+            for i in 0..k {
+                let col = a.offset(i as isize * csa + (p * mr) as isize * rsa);
+                if (0..rows).any(|j| !(*col.offset(j as isize * rsa)).is_zero()) {
+                    ks.push(i);
+                }
+            }
+            panel_offsets.push(len);
+            len += (ks.len() - panel_ks[p]) * mr;
+            if align > 1 {
+                len = (len + align - 1) / align * align;
+            }
+            panel_ks.push(ks.len());
+        }
+        BlockSparsePackA {
+            k,
+            m,
+            mr,
+            alignment,
+            panel_offsets,
+            panel_ks,
+            ks,
+            len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Number of stored (non-zero) blocks.
+    pub fn blocks(&self) -> usize {
+        self.ks.len()
+    }
+
+    /// Fraction of the mr x 1 blocks that are entirely zero.
+    pub fn sparsity(&self) -> f32 {
+        let total = self.panel_offsets.len() * self.k;
+        if total == 0 {
+            0.0
+        } else {
+            1.0 - self.blocks() as f32 / total as f32
+        }
+    }
+
+    pub fn storage(&self) -> MatrixStoreSpec {
+        MatrixStoreSpec::BlockSparse {
+            k: self.k,
+            panel_offsets: self.panel_offsets.clone(),
+            panel_ks: self.panel_ks.clone(),
+            ks: self.ks.clone(),
+        }
+    }
+
+    pub fn pack(&self, pa: *mut T, a: *const T, rsa: isize, csa: isize) {
+        let mr = self.mr;
+        assert!(pa as usize % self.alignment == 0);
+        unsafe {
+            for (p, &offset) in self.panel_offsets.iter().enumerate() {
+                let rows = mr.min(self.m - p * mr);
+                let ks = &self.ks[self.panel_ks[p]..self.panel_ks[p + 1]];
+                for (ix, &i) in ks.iter().enumerate() {
+                    let pa = pa.add(offset + ix * mr);
+                    for j in 0..mr {
+                        *pa.add(j) = if j < rows {
+                            *a.offset(i as isize * csa + (p * mr + j) as isize * rsa)
+                        } else {
+                            T::zero()
+                        };
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn block_sparse_pack() {
+        // 5x4, mr=4: second panel has a single row
+        #[rustfmt::skip]
+        let a: Vec<f32> = vec!(
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0,
+            0.0, 2.0, 0.0, 3.0,
+            0.0, 0.0, 0.0, 0.0,
+            4.0, 0.0, 0.0, 0.0,
+        );
+        let packer = unsafe { BlockSparsePackA::new(4, 5, 4, 4, a.as_ptr(), 4, 1) };
+        assert_eq!(packer.blocks(), 3);
+        assert_eq!(packer.sparsity(), 5.0 / 8.0);
+        assert_eq!(packer.len(), 12);
+        let mut pa = vec![-1.0f32; packer.len()];
+        packer.pack(pa.as_mut_ptr(), a.as_ptr(), 4, 1);
+        assert_eq!(pa, vec!(1.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0, 0.0, 4.0, 0.0, 0.0, 0.0));
+    }
+}
@@ -5,5 +5,6 @@ pub mod tanh;
 
 pub use self::lut::GenericLut8;
 pub use self::mmm::GenericMmm4x4;
+pub use self::mmm::GenericMmv4x1;
 pub use self::sigmoid::SSigmoid4;
 pub use self::tanh::STanh4;
@@ -40,6 +40,15 @@ impl PseudoRightShift for f32 {
     }
 }
 
+impl PseudoRightShift for f64 {
+    fn q_even(self, mult: Self, shift: usize) -> Self {
+        self * mult * 2f64.powi(-(shift as i32))
+    }
+    fn q_to_plus_inf(self, mult: Self, shift: usize) -> Self {
+        self * mult * 2f64.powi(-(shift as i32))
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct GenericMmm4x4<TA, TB, TC, TI>(PhantomData<(TA, TB, TC, TI)>)
 where
@@ -318,6 +327,13 @@ where
                             }
                         }
                     }
+                    FusedKerSpec::PerRowQTowardsPlusInf(mults, shifts) => {
+                        for i in 0..4 {
+                            for j in 0..4 {
+                                ab[i][j] = ab[i][j].q_to_plus_inf(*mults.add(i), *shifts.add(i));
+                            }
+                        }
+                    }
                 }
                 pnl = pnl.add(1);
             }
@@ -358,6 +374,254 @@ where
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct GenericMmv4x1<TA, TB, TC, TI>(PhantomData<(TA, TB, TC, TI)>)
+where
+    TA: Copy + fmt::Debug + AsPrimitive<TI>,
+    TB: Copy + fmt::Debug + AsPrimitive<TI>,
+    TC: Copy + fmt::Debug + AsPrimitive<TI> + 'static,
+    TI: Copy
+        + ops::AddAssign
+        + ops::Mul<Output = TI>
+        + ops::MulAssign
+        + PseudoRightShift
+        + PartialOrd
+        + Zero
+        + fmt::Debug
+        + fmt::Display
+        + AsPrimitive<TC>
+        + 'static;
+
+unsafe impl<TA, TB, TC, TI> Send for GenericMmv4x1<TA, TB, TC, TI>
+where
+    TA: Copy + fmt::Debug + AsPrimitive<TI>,
+    TB: Copy + fmt::Debug + AsPrimitive<TI>,
+    TC: Copy + fmt::Debug + AsPrimitive<TI> + 'static,
+    TI: Copy
+        + ops::AddAssign
+        + ops::Mul<Output = TI>
+        + ops::MulAssign
+        + PseudoRightShift
+        + PartialOrd
+        + Zero
+        + fmt::Debug
+        + fmt::Display
+        + AsPrimitive<TC>
+        + 'static,
+{
+}
+
+unsafe impl<TA, TB, TC, TI> Sync for GenericMmv4x1<TA, TB, TC, TI>
+where
+    TA: Copy + fmt::Debug + AsPrimitive<TI>,
+    TB: Copy + fmt::Debug + AsPrimitive<TI>,
+    TC: Copy + fmt::Debug + AsPrimitive<TI> + 'static,
+    TI: Copy
+        + ops::AddAssign
+        + ops::Mul<Output = TI>
+        + ops::MulAssign
+        + PseudoRightShift
+        + PartialOrd
+        + Zero
+        + fmt::Debug
+        + fmt::Display
+        + AsPrimitive<TC>
+        + 'static,
+{
+}
+
+impl<TA, TB, TC, TI> MatMatMulKer<TA, TB, TC, TI> for GenericMmv4x1<TA, TB, TC, TI>
+where
+    TA: Copy + fmt::Debug + AsPrimitive<TI>,
+    TB: Copy + fmt::Debug + AsPrimitive<TI>,
+    TC: Copy + fmt::Debug + AsPrimitive<TI> + 'static + Bounded,
+    TI: Copy
+        + ops::AddAssign
+        + ops::Mul<Output = TI>
+        + ops::MulAssign
+        + PseudoRightShift
+        + PartialOrd
+        + Zero
+        + Signed
+        + fmt::Debug
+        + fmt::Display
+        + AsPrimitive<TC>
+        + 'static,
+    usize: AsPrimitive<TI>,
+{
+    #[inline(always)]
+    fn name() -> &'static str {
+        "generic"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        4
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        1
+    }
+    #[inline(always)]
+    fn alignment_bytes_packed_a() -> usize {
+        std::mem::size_of::<TA>()
+    }
+    #[inline(always)]
+    fn alignment_bytes_packed_b() -> usize {
+        std::mem::size_of::<TB>()
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<TA, TB, TC, TI>) -> isize {
+        unsafe {
+            let mut ab = [TI::zero(); 4];
+            match (*spec.a, *spec.b, *spec.linear) {
+                (Packed { ptr: a }, Packed { ptr: b }, Mul { k }) => {
+                    for i in 0..k {
+                        let a = std::slice::from_raw_parts(a.offset(4 * i as isize), 4);
+                        let b = *b.offset(i as isize);
+                        ab[0] += a[0].as_() * b.as_();
+                        ab[1] += a[1].as_() * b.as_();
+                        ab[2] += a[2].as_() * b.as_();
+                        ab[3] += a[3].as_() * b.as_();
+                    }
+                }
+                (Packed { ptr: a }, OffsetsAndPtrs { row_byte_offsets, col_ptrs }, Mul { k }) => {
+                    let pb0 = *(col_ptrs.offset(0));
+                    for i in 0..k {
+                        let a = std::slice::from_raw_parts(a.offset(4 * i as isize), 4);
+                        let offset = *row_byte_offsets.offset(i as isize)
+                            / std::mem::size_of::<TB>() as isize;
+                        let b = *(pb0.offset(offset));
+                        ab[0] += a[0].as_() * b.as_();
+                        ab[1] += a[1].as_() * b.as_();
+                        ab[2] += a[2].as_() * b.as_();
+                        ab[3] += a[3].as_() * b.as_();
+                    }
+                }
+                (Packed { ptr: a }, VecStride { ptr: b, byte_stride, .. }, Mul { k }) => {
+                    for i in 0..k {
+                        let a = std::slice::from_raw_parts(a.offset(4 * i as isize), 4);
+                        let b = *b
+                            .offset(i as isize * byte_stride / std::mem::size_of::<TB>() as isize);
+                        ab[0] += a[0].as_() * b.as_();
+                        ab[1] += a[1].as_() * b.as_();
+                        ab[2] += a[2].as_() * b.as_();
+                        ab[3] += a[3].as_() * b.as_();
+                    }
+                }
+                _ => return 1,
+            }
+            let mut pnl = spec.non_linear;
+            loop {
+                if pnl.is_null() {
+                    break;
+                }
+                match *pnl {
+                    FusedKerSpec::Done => break,
+                    FusedKerSpec::AddC => match *spec.c {
+                        Strides { ptr: c, row_byte_stride, .. } => {
+                            let rsc = row_byte_stride as usize / std::mem::size_of::<TC>();
+                            let c = std::slice::from_raw_parts_mut(c, 1 + 3 * rsc);
+                            ab[0] += c[0 * rsc].as_();
+                            ab[1] += c[1 * rsc].as_();
+                            ab[2] += c[2 * rsc].as_();
+                            ab[3] += c[3 * rsc].as_();
+                        }
+                        VecStride { ptr: c, byte_stride, .. } => {
+                            let stride = byte_stride / std::mem::size_of::<TC>() as isize;
+                            ab[0] += (*c.offset(0 * stride)).as_();
+                            ab[1] += (*c.offset(1 * stride)).as_();
+                            ab[2] += (*c.offset(2 * stride)).as_();
+                            ab[3] += (*c.offset(3 * stride)).as_();
+                        }
+                        _ => return 1,
+                    },
+                    FusedKerSpec::PerRowMul(bias) => {
+                        for i in 0..4 {
+                            ab[i] *= *bias.offset(i as isize);
+                        }
+                    }
+                    FusedKerSpec::PerRowAdd(bias) => {
+                        for i in 0..4 {
+                            ab[i] += *bias.offset(i as isize);
+                        }
+                    }
+                    FusedKerSpec::PerColMul(bias) => {
+                        for i in 0..4 {
+                            ab[i] *= *bias;
+                        }
+                    }
+                    FusedKerSpec::PerColAdd(bias) => {
+                        for i in 0..4 {
+                            ab[i] += *bias;
+                        }
+                    }
+                    FusedKerSpec::Min(m) => {
+                        for i in 0..4 {
+                            ab[i] = if m < ab[i] { m } else { ab[i] }
+                        }
+                    }
+                    FusedKerSpec::Max(m) => {
+                        for i in 0..4 {
+                            ab[i] = if m > ab[i] { m } else { ab[i] }
+                        }
+                    }
+                    FusedKerSpec::AddRowColProducts(rows, cols) => {
+                        for i in 0..4 {
+                            ab[i] += *rows.offset(i as isize) * *cols;
+                        }
+                    }
+                    FusedKerSpec::ScalarAdd(a) => {
+                        for i in 0..4 {
+                            ab[i] += a;
+                        }
+                    }
+                    FusedKerSpec::ScalarMul(a) => {
+                        for i in 0..4 {
+                            ab[i] *= a;
+                        }
+                    }
+                    FusedKerSpec::QTowardsEven(mult, shift) => {
+                        for i in 0..4 {
+                            ab[i] = ab[i].q_even(mult, shift);
+                        }
+                    }
+                    FusedKerSpec::QTowardsPlusInf(mult, shift) => {
+                        for i in 0..4 {
+                            ab[i] = ab[i].q_to_plus_inf(mult, shift);
+                        }
+                    }
+                    FusedKerSpec::PerRowQTowardsPlusInf(mults, shifts) => {
+                        for i in 0..4 {
+                            ab[i] = ab[i].q_to_plus_inf(*mults.add(i), *shifts.add(i));
+                        }
+                    }
+                }
+                pnl = pnl.add(1);
+            }
+            match *spec.c {
+                Strides { ptr: c, row_byte_stride, .. } => {
+                    let rsc = row_byte_stride as usize / std::mem::size_of::<TC>();
+                    let c = std::slice::from_raw_parts_mut(c, 1 + 3 * rsc);
+                    c[0 * rsc] = ab[0].as_();
+                    c[1 * rsc] = ab[1].as_();
+                    c[2 * rsc] = ab[2].as_();
+                    c[3 * rsc] = ab[3].as_();
+                }
+                VecStride { ptr: c, byte_stride, .. } => {
+                    let stride = byte_stride / std::mem::size_of::<TC>() as isize;
+                    let c: *mut TC = c as _;
+                    *c.offset(0 * stride) = ab[0].as_();
+                    *c.offset(1 * stride) = ab[1].as_();
+                    *c.offset(2 * stride) = ab[2].as_();
+                    *c.offset(3 * stride) = ab[3].as_();
+                }
+                _ => return 1,
+            }
+        }
+        return 0;
+    }
+}
+
 #[cfg(test)]
 #[derive(Copy, Clone, Debug)]
 pub struct GenericMmmTest3x2<TA, TB, TC, TI>(PhantomData<(TA, TB, TC, TI)>)
@@ -596,6 +860,13 @@ where
                             }
                         }
                     }
+                    FusedKerSpec::PerRowQTowardsPlusInf(mults, shifts) => {
+                        for i in 0..3 {
+                            for j in 0..2 {
+                                ab[i][j] = ab[i][j].q_to_plus_inf(*mults.add(i), *shifts.add(i));
+                            }
+                        }
+                    }
                 }
                 pnl = pnl.add(1);
             }
@@ -626,10 +897,22 @@ where
 }
 
 test_mmm_kernel_f32!(crate::generic::mmm::GenericMmm4x4<f32, f32, f32, f32>, test_GenericMmm4x4_f32, true);
+test_mmm_kernel_f64!(crate::generic::mmm::GenericMmm4x4<f64, f64, f64, f64>, test_GenericMmm4x4_f64, true);
 test_mmm_kernel_i8!(crate::generic::mmm::GenericMmm4x4<i8, i8, i8, i32>, test_GenericMmm4x4_i8, true);
 test_mmm_kernel_u8!(crate::generic::mmm::GenericMmm4x4<u8, u8, u8, i32>, test_GenericMmm4x4_u8, true);
 test_mmm_kernel_i8_i32!(crate::generic::mmm::GenericMmm4x4<i8, i8, i32, i32>, test_GenericMmm4x4_i8_i32, true);
 
+test_qmmm_kernel_mixed!(crate::generic::mmm::GenericMmm4x4<i8, u8, u8, i32>, test_GenericMmm4x4_i8_u8_u8, true, i8, u8, u8);
+test_qmmm_kernel_mixed!(crate::generic::mmm::GenericMmm4x4<i8, u8, i32, i32>, test_GenericMmm4x4_i8_u8_i32, true, i8, u8, i32);
+test_qmmm_kernel_mixed!(crate::generic::mmm::GenericMmm4x4<u8, i8, u8, i32>, test_GenericMmm4x4_u8_i8_u8, true, u8, i8, u8);
+test_qmmm_kernel_mixed!(crate::generic::mmm::GenericMmm4x4<u8, i8, i32, i32>, test_GenericMmm4x4_u8_i8_i32, true, u8, i8, i32);
+
+test_mmm_kernel_f32!(crate::generic::mmm::GenericMmv4x1<f32, f32, f32, f32>, test_GenericMmv4x1_f32, true);
+test_mmm_kernel_f64!(crate::generic::mmm::GenericMmv4x1<f64, f64, f64, f64>, test_GenericMmv4x1_f64, true);
+test_mmm_kernel_i8!(crate::generic::mmm::GenericMmv4x1<i8, i8, i8, i32>, test_GenericMmv4x1_i8, true);
+test_mmm_kernel_u8!(crate::generic::mmm::GenericMmv4x1<u8, u8, u8, i32>, test_GenericMmv4x1_u8, true);
+test_mmm_kernel_i8_i32!(crate::generic::mmm::GenericMmv4x1<i8, i8, i32, i32>, test_GenericMmv4x1_i8_i32, true);
+
 test_mmm_kernel_f32!(crate::generic::mmm::GenericMmmTest3x2<f32, f32, f32, f32>, test_GenericMmmTest3x2_f32, true);
 test_mmm_kernel_i8!(crate::generic::mmm::GenericMmmTest3x2<i8, i8, i8, i32>, test_GenericMmmTest3x2_i8, true);
 test_mmm_kernel_u8!(crate::generic::mmm::GenericMmmTest3x2<u8, u8, u8, i32>, test_GenericMmmTest3x2_u8, true);
@@ -31,112 +31,223 @@ pub use self::frame::sigmoid;
 pub use self::frame::tanh;
 
 pub struct Ops {
-    pub mmm_f32: Box<
-        dyn Fn(usize, usize, usize) -> Box<dyn mmm::MatMatMul<f32, f32, f32, f32>> + Send + Sync,
-    >,
-    pub qmmm_i8_i32: Box<
-        dyn Fn(usize, usize, usize) -> Box<dyn mmm::QMatMatMul<i8, i8, i32, i32>> + Send + Sync,
-    >,
-    pub qmmm_u8_i32: Box<
-        dyn Fn(usize, usize, usize) -> Box<dyn mmm::QMatMatMul<u8, u8, i32, i32>> + Send + Sync,
-    >,
-    pub qmmm_u8_u8:
-        Box<dyn Fn(usize, usize, usize) -> Box<dyn mmm::QMatMatMul<u8, u8, u8, i32>> + Send + Sync>,
-    pub qmmm_i8_i8:
-        Box<dyn Fn(usize, usize, usize) -> Box<dyn mmm::QMatMatMul<i8, i8, i8, i32>> + Send + Sync>,
+    pub mmm_f32: mmm::MMMRegistry<dyn mmm::MatMatMul<f32, f32, f32, f32>>,
+    pub mmm_f64: mmm::MMMRegistry<dyn mmm::MatMatMul<f64, f64, f64, f64>>,
+    pub qmmm_i8_i32: mmm::MMMRegistry<dyn mmm::QMatMatMul<i8, i8, i32, i32>>,
+    pub qmmm_u8_i32: mmm::MMMRegistry<dyn mmm::QMatMatMul<u8, u8, i32, i32>>,
+    pub qmmm_u8_u8: mmm::MMMRegistry<dyn mmm::QMatMatMul<u8, u8, u8, i32>>,
+    pub qmmm_i8_i8: mmm::MMMRegistry<dyn mmm::QMatMatMul<i8, i8, i8, i32>>,
+    pub qmmm_i8_u8_u8: mmm::MMMRegistry<dyn mmm::QMatMatMul<i8, u8, u8, i32>>,
+    pub qmmm_i8_u8_i32: mmm::MMMRegistry<dyn mmm::QMatMatMul<i8, u8, i32, i32>>,
+    pub qmmm_u8_i8_u8: mmm::MMMRegistry<dyn mmm::QMatMatMul<u8, i8, u8, i32>>,
+    pub qmmm_u8_i8_i32: mmm::MMMRegistry<dyn mmm::QMatMatMul<u8, i8, i32, i32>>,
     pub sigmoid_f32: Box<dyn Fn() -> Box<dyn sigmoid::Sigmoid<f32>> + Send + Sync>,
     pub tanh_f32: Box<dyn Fn() -> Box<dyn tanh::Tanh<f32>> + Send + Sync>,
     pub lut_u8: Box<dyn Fn(&[u8]) -> Box<dyn lut::Lut> + Send + Sync>,
 }
 
+macro_rules! generic_qmmm {
+    ($op: expr, $ta: ty, $tb: ty, $tc: ty) => {{
+        let mut registry = mmm::MMMRegistry::new($op);
+        registry.register(qmmm_kernel!(
+            "generic_4x4",
+            generic::GenericMmm4x4<$ta, $tb, $tc, i32>,
+            $ta,
+            $tb,
+            $tc,
+            i32,
+            1.0
+        ));
+        registry.register(qmmm_kernel!(
+            "generic_4x1",
+            generic::GenericMmv4x1<$ta, $tb, $tc, i32>,
+            $ta,
+            $tb,
+            $tc,
+            i32,
+            0.5
+        ));
+        registry
+    }};
+}
+
 pub fn generic() -> Ops {
+    let mut mmm_f32 = mmm::MMMRegistry::new("mmm_f32");
+    mmm_f32.register(mmm_kernel!(
+        "generic_4x4",
+        generic::GenericMmm4x4<f32, f32, f32, f32>,
+        f32,
+        f32,
+        f32,
+        f32,
+        1.0
+    ));
+    mmm_f32.register(mmm_kernel!(
+        "generic_4x1",
+        generic::GenericMmv4x1<f32, f32, f32, f32>,
+        f32,
+        f32,
+        f32,
+        f32,
+        0.5
+    ));
+    let mut mmm_f64 = mmm::MMMRegistry::new("mmm_f64");
+    mmm_f64.register(mmm_kernel!(
+        "generic_4x4",
+        generic::GenericMmm4x4<f64, f64, f64, f64>,
+        f64,
+        f64,
+        f64,
+        f64,
+        1.0
+    ));
+    mmm_f64.register(mmm_kernel!(
+        "generic_4x1",
+        generic::GenericMmv4x1<f64, f64, f64, f64>,
+        f64,
+        f64,
+        f64,
+        f64,
+        0.5
+    ));
     Ops {
-        mmm_f32: Box::new(|m, k, n| {
-            Box::new(mmm::MatMatMulImpl::<
-                generic::GenericMmm4x4<f32, f32, f32, f32>,
+        mmm_f32,
+        mmm_f64,
+        qmmm_i8_i32: generic_qmmm!("qmmm_i8_i32", i8, i8, i32),
+        qmmm_u8_i32: generic_qmmm!("qmmm_u8_i32", u8, u8, i32),
+        qmmm_u8_u8: generic_qmmm!("qmmm_u8_u8", u8, u8, u8),
+        qmmm_i8_i8: generic_qmmm!("qmmm_i8_i8", i8, i8, i8),
+        qmmm_i8_u8_u8: generic_qmmm!("qmmm_i8_u8_u8", i8, u8, u8),
+        qmmm_i8_u8_i32: generic_qmmm!("qmmm_i8_u8_i32", i8, u8, i32),
+        qmmm_u8_i8_u8: generic_qmmm!("qmmm_u8_i8_u8", u8, i8, u8),
+        qmmm_u8_i8_i32: generic_qmmm!("qmmm_u8_i8_i32", u8, i8, i32),
+        sigmoid_f32: Box::new(|| Box::new(sigmoid::SigmoidImpl::<generic::SSigmoid4, f32>::new())),
+        tanh_f32: Box::new(|| Box::new(tanh::TanhImpl::<generic::STanh4, f32>::new())),
+        lut_u8: Box::new(|table: &[u8]| Box::new(lut::LutImpl::<generic::GenericLut8>::new(table))),
+    }
+}
+
+#[allow(unreachable_code, unused_mut)]
+pub fn best() -> Ops {
+    let mut ops = generic();
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("fma") {
+            ops.mmm_f32.register(mmm_kernel!(
+                "fma_16x6",
+                x86_64_fma::mmm::MatMatMulF32x16x6,
+                f32,
+                f32,
+                f32,
+                f32,
+                32.0
+            ));
+            ops.mmm_f32.register(mmm_kernel!(
+                "fma_64x1",
+                x86_64_fma::mmm::MatMatMulF32x64x1,
                 f32,
                 f32,
                 f32,
                 f32,
-            >::new(m, k, n))
-        }),
-        qmmm_i8_i32: Box::new(|m, k, n| {
-            Box::new(mmm::QMatMatMulImpl::from(mmm::MatMatMulImpl::<
-                generic::GenericMmm4x4<i8, i8, i32, i32>,
+                16.0
+            ));
+            log::info!("mmm_f32 x86_64/fma activated");
+            ops.mmm_f64.register(mmm_kernel!(
+                "fma_8x6",
+                x86_64_fma::mmm::MatMatMulF64x8x6,
+                f64,
+                f64,
+                f64,
+                f64,
+                16.0
+            ));
+            log::info!("mmm_f64 x86_64/fma activated");
+            ops.sigmoid_f32 = Box::new(|| {
+                Box::new(sigmoid::SigmoidImpl::<x86_64_fma::sigmoid::SigmoidF32, f32>::new())
+            });
+            ops.tanh_f32 =
+                Box::new(|| Box::new(tanh::TanhImpl::<x86_64_fma::tanh::TanhF32, f32>::new()));
+            log::info!("sigmoid_f32 and tanh_f32 x86_64/fma activated");
+        }
+        if is_x86_feature_detected!("avx2") {
+            ops.qmmm_i8_i8.register(qmmm_kernel!(
+                "avx2_8x8",
+                x86_64_fma::mmm::MatMatMulI8x8x8,
+                i8,
                 i8,
                 i8,
                 i32,
+                32.0
+            ));
+            ops.qmmm_i8_i8.register(qmmm_kernel!(
+                "avx2_32x1",
+                x86_64_fma::mmm::MatMatMulI8x32x1,
+                i8,
+                i8,
+                i8,
                 i32,
-            >::new(m, k, n)))
-        }),
-        qmmm_u8_i32: Box::new(|m, k, n| {
-            Box::new(mmm::QMatMatMulImpl::from(mmm::MatMatMulImpl::<
-                generic::GenericMmm4x4<u8, u8, i32, i32>,
-                u8,
-                u8,
+                16.0
+            ));
+            ops.qmmm_i8_i32.register(qmmm_kernel!(
+                "avx2_8x8",
+                x86_64_fma::mmm::MatMatMulI8xI32x8x8,
+                i8,
+                i8,
                 i32,
                 i32,
-            >::new(m, k, n)))
-        }),
-        qmmm_u8_u8: Box::new(|m, k, n| {
-            Box::new(mmm::QMatMatMulImpl::from(mmm::MatMatMulImpl::<
-                generic::GenericMmm4x4<u8, u8, u8, i32>,
-                u8,
+                32.0
+            ));
+            ops.qmmm_i8_i32.register(qmmm_kernel!(
+                "avx2_32x1",
+                x86_64_fma::mmm::MatMatMulI8xI32x32x1,
+                i8,
+                i8,
+                i32,
+                i32,
+                16.0
+            ));
+            log::info!("mmm_i8_i8 and mmm_i8_i32 x86_64/fma activated");
+            ops.qmmm_i8_u8_u8.register(qmmm_kernel!(
+                "avx2_8x8",
+                x86_64_fma::mmm::MatMatMulI8xU8x8x8,
+                i8,
                 u8,
                 u8,
                 i32,
-            >::new(m, k, n)))
-        }),
-        qmmm_i8_i8: Box::new(|m, k, n| {
-            Box::new(mmm::QMatMatMulImpl::from(mmm::MatMatMulImpl::<
-                generic::GenericMmm4x4<i8, i8, i8, i32>,
+                32.0
+            ));
+            ops.qmmm_i8_u8_i32.register(qmmm_kernel!(
+                "avx2_8x8",
+                x86_64_fma::mmm::MatMatMulI8xU8xI32x8x8,
                 i8,
+                u8,
+                i32,
+                i32,
+                32.0
+            ));
+            ops.qmmm_u8_i8_u8.register(qmmm_kernel!(
+                "avx2_8x8",
+                x86_64_fma::mmm::MatMatMulU8xI8x8x8,
+                u8,
                 i8,
+                u8,
+                i32,
+                32.0
+            ));
+            ops.qmmm_u8_i8_i32.register(qmmm_kernel!(
+                "avx2_8x8",
+                x86_64_fma::mmm::MatMatMulU8xI8xI32x8x8,
+                u8,
                 i8,
                 i32,
-            >::new(m, k, n)))
-        }),
-        sigmoid_f32: Box::new(|| Box::new(sigmoid::SigmoidImpl::<generic::SSigmoid4, f32>::new())),
-        tanh_f32: Box::new(|| Box::new(tanh::TanhImpl::<generic::STanh4, f32>::new())),
-        lut_u8: Box::new(|table: &[u8]| Box::new(lut::LutImpl::<generic::GenericLut8>::new(table))),
-    }
-}
-
-#[allow(unreachable_code, unused_mut)]
-pub fn best() -> Ops {
-    let mut ops = generic();
-    #[cfg(target_arch = "x86_64")]
-    {
-        if is_x86_feature_detected!("fma") {
-            ops.mmm_f32 = Box::new(|m, k, n| {
-                Box::new(
-                    mmm::MatMatMulImpl::<x86_64_fma::mmm::MatMatMulF32x16x6, f32, f32, f32, f32>::new(
-                        m, k, n,
-                    ),
-                )
-            });
-            log::info!("mmm_f32 x86_64/fma activated");
-        }
-        if is_x86_feature_detected!("avx2") {
-            ops.qmmm_i8_i8 = Box::new(|m, k, n| {
-                Box::new(mmm::QMatMatMulImpl::from(mmm::MatMatMulImpl::<
-                    x86_64_fma::mmm::MatMatMulI8x8x8,
-                    i8,
-                    i8,
-                    i8,
-                    i32,
-                >::new(m, k, n)))
-            });
-            ops.qmmm_i8_i32 = Box::new(|m, k, n| {
-                Box::new(mmm::QMatMatMulImpl::from(mmm::MatMatMulImpl::<
-                    x86_64_fma::mmm::MatMatMulI8xI32x8x8,
-                    i8,
-                    i8,
-                    i32,
-                    i32,
-                >::new(m, k, n)))
+                i32,
+                32.0
+            ));
+            log::info!("mixed signedness mmm_i8_u8 and mmm_u8_i8 x86_64/avx2 activated");
+            ops.lut_u8 = Box::new(|table: &[u8]| {
+                Box::new(lut::LutImpl::<x86_64_fma::lut::Lut8>::new(table))
             });
-            log::info!("mmm_i8_i8 and mmm_i8_i32 x86_64/fma activated");
+            log::info!("lut_u8 x86_64/avx2 activated");
         }
     }
     #[cfg(any(target_arch = "arm", target_arch = "armv7"))]
@@ -192,6 +303,15 @@ mod test {
         }
     }
 
+    impl Datum for f64 {
+        fn strat() -> BoxedStrategy<Self> {
+            (-1000isize..1000).prop_map(|i| i as f64 / 1000.0).boxed()
+        }
+        fn close(&self, other: &Self) -> bool {
+            (self - other).abs() < 0.000001
+        }
+    }
+
     impl Datum for i8 {
         fn strat() -> BoxedStrategy<Self> {
             any::<i8>().boxed()
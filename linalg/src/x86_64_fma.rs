@@ -1 +1,4 @@
+pub mod lut;
 pub mod mmm;
+pub mod sigmoid;
+pub mod tanh;
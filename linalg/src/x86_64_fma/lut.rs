@@ -0,0 +1,37 @@
+use crate::frame::lut::LutKer;
+
+extern "C" {
+    #[no_mangle]
+    fn fma_lut_u8(buf: *mut u8, len: usize, table: *const u8);
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Lut8;
+
+impl LutKer for Lut8 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "avx2"
+    }
+    #[inline(always)]
+    fn n() -> usize {
+        32
+    }
+    #[inline(always)]
+    fn input_alignment_bytes() -> usize {
+        32
+    }
+    #[inline(always)]
+    fn table_alignment_bytes() -> usize {
+        32
+    }
+    #[inline(never)]
+    fn run(buf: *mut u8, len: usize, table: *const u8) {
+        unsafe { fma_lut_u8(buf, len, table) }
+    }
+}
+
+#[cfg(test)]
+mod test_avx2 {
+    lut_frame_tests!(is_x86_feature_detected!("avx2"), crate::x86_64_fma::lut::Lut8);
+}
@@ -4,7 +4,17 @@ extern "C" {
     #[no_mangle]
     fn fma_mmm_f32_16x6(op: *const MatMatMulKerSpec<f32, f32, f32, f32>) -> isize;
     #[no_mangle]
+    fn fma_mmm_f64_8x6(op: *const MatMatMulKerSpec<f64, f64, f64, f64>) -> isize;
+    #[no_mangle]
     fn fma_mmm_i8_8x8(op: *const MatMatMulKerSpec<i8, i8, i8, i32>) -> isize;
+    #[no_mangle]
+    fn fma_mmm_i8u8_8x8(op: *const MatMatMulKerSpec<i8, u8, u8, i32>) -> isize;
+    #[no_mangle]
+    fn fma_mmm_u8i8_8x8(op: *const MatMatMulKerSpec<u8, i8, u8, i32>) -> isize;
+    #[no_mangle]
+    fn fma_mmv_f32_64x1(op: *const MatMatMulKerSpec<f32, f32, f32, f32>) -> isize;
+    #[no_mangle]
+    fn fma_mmv_i8_32x1(op: *const MatMatMulKerSpec<i8, i8, i8, i32>) -> isize;
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -35,6 +45,34 @@ impl MatMatMulKer<f32, f32, f32, f32> for MatMatMulF32x16x6 {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct MatMatMulF64x8x6;
+
+impl MatMatMulKer<f64, f64, f64, f64> for MatMatMulF64x8x6 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "fma"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        8
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        6
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        32
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        8
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<f64, f64, f64, f64>) -> isize {
+        unsafe { fma_mmm_f64_8x6(spec) }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct MatMatMulI8x8x8;
 
@@ -91,12 +129,214 @@ impl MatMatMulKer<i8, i8, i32, i32> for MatMatMulI8xI32x8x8 {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct MatMatMulI8xU8x8x8;
+
+impl MatMatMulKer<i8, u8, u8, i32> for MatMatMulI8xU8x8x8 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "avx2"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        8
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        8
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        32
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        4
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<i8, u8, u8, i32>) -> isize {
+        unsafe { fma_mmm_i8u8_8x8(spec) }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MatMatMulI8xU8xI32x8x8;
+
+impl MatMatMulKer<i8, u8, i32, i32> for MatMatMulI8xU8xI32x8x8 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "avx2"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        8
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        8
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        32
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        4
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<i8, u8, i32, i32>) -> isize {
+        unsafe { fma_mmm_i8u8_8x8(spec as *const _ as _) }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MatMatMulU8xI8x8x8;
+
+impl MatMatMulKer<u8, i8, u8, i32> for MatMatMulU8xI8x8x8 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "avx2"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        8
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        8
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        32
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        4
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<u8, i8, u8, i32>) -> isize {
+        unsafe { fma_mmm_u8i8_8x8(spec) }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MatMatMulU8xI8xI32x8x8;
+
+impl MatMatMulKer<u8, i8, i32, i32> for MatMatMulU8xI8xI32x8x8 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "avx2"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        8
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        8
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        32
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        4
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<u8, i8, i32, i32>) -> isize {
+        unsafe { fma_mmm_u8i8_8x8(spec as *const _ as _) }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MatMatMulF32x64x1;
+
+impl MatMatMulKer<f32, f32, f32, f32> for MatMatMulF32x64x1 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "fma"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        64
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        1
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        32
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        4
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<f32, f32, f32, f32>) -> isize {
+        unsafe { fma_mmv_f32_64x1(spec) }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MatMatMulI8x32x1;
+
+impl MatMatMulKer<i8, i8, i8, i32> for MatMatMulI8x32x1 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "avx2"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        32
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        1
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        32
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        1
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<i8, i8, i8, i32>) -> isize {
+        unsafe { fma_mmv_i8_32x1(spec) }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct MatMatMulI8xI32x32x1;
+
+impl MatMatMulKer<i8, i8, i32, i32> for MatMatMulI8xI32x32x1 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "avx2"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        32
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        1
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        32
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        1
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<i8, i8, i32, i32>) -> isize {
+        unsafe { fma_mmv_i8_32x1(spec as *const _ as _) }
+    }
+}
+
 test_mmm_kernel_f32!(
     crate::x86_64_fma::mmm::MatMatMulF32x16x6,
     test_MatMatMulF32x16x6,
     is_x86_feature_detected!("fma")
 );
 
+test_mmm_kernel_f64!(
+    crate::x86_64_fma::mmm::MatMatMulF64x8x6,
+    test_MatMatMulF64x8x6,
+    is_x86_feature_detected!("fma")
+);
+
 test_mmm_kernel_i8!(
     crate::x86_64_fma::mmm::MatMatMulI8x8x8,
     test_MatMatMulI8x8x8,
@@ -108,3 +348,57 @@ test_mmm_kernel_i8_i32!(
     test_MatMatMulI8xI32x8x8,
     is_x86_feature_detected!("avx2")
 );
+
+test_qmmm_kernel_mixed!(
+    crate::x86_64_fma::mmm::MatMatMulI8xU8x8x8,
+    test_MatMatMulI8xU8x8x8,
+    is_x86_feature_detected!("avx2"),
+    i8,
+    u8,
+    u8
+);
+
+test_qmmm_kernel_mixed!(
+    crate::x86_64_fma::mmm::MatMatMulI8xU8xI32x8x8,
+    test_MatMatMulI8xU8xI32x8x8,
+    is_x86_feature_detected!("avx2"),
+    i8,
+    u8,
+    i32
+);
+
+test_qmmm_kernel_mixed!(
+    crate::x86_64_fma::mmm::MatMatMulU8xI8x8x8,
+    test_MatMatMulU8xI8x8x8,
+    is_x86_feature_detected!("avx2"),
+    u8,
+    i8,
+    u8
+);
+
+test_qmmm_kernel_mixed!(
+    crate::x86_64_fma::mmm::MatMatMulU8xI8xI32x8x8,
+    test_MatMatMulU8xI8xI32x8x8,
+    is_x86_feature_detected!("avx2"),
+    u8,
+    i8,
+    i32
+);
+
+test_mmm_kernel_f32!(
+    crate::x86_64_fma::mmm::MatMatMulF32x64x1,
+    test_MatMatMulF32x64x1,
+    is_x86_feature_detected!("fma")
+);
+
+test_mmm_kernel_i8!(
+    crate::x86_64_fma::mmm::MatMatMulI8x32x1,
+    test_MatMatMulI8x32x1,
+    is_x86_feature_detected!("avx2")
+);
+
+test_mmm_kernel_i8_i32!(
+    crate::x86_64_fma::mmm::MatMatMulI8xI32x32x1,
+    test_MatMatMulI8xI32x32x1,
+    is_x86_feature_detected!("avx2")
+);
@@ -7,6 +7,13 @@ pub mod parse;
 pub struct ProtoModel {
     pub doc: Document,
     pub tensors: HashMap<String, Arc<Tensor>>,
+    pub quantization: Option<HashMap<String, QuantFormat>>,
+}
+
+/// Quantization of a tensor, as described in the graph.quant file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuantFormat {
+    Linear { zero_point: i32, scale: f32, bits: usize, signed: bool },
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -33,6 +33,22 @@ impl<'a> Dumper<'a> {
         Ok(())
     }
 
+    pub fn quantization(&mut self, quantization: &HashMap<String, QuantFormat>) -> TractResult<()> {
+        let mut ids: Vec<&String> = quantization.keys().collect();
+        ids.sort();
+        for id in ids {
+            write!(self.w, "\"{}\": ", id)?;
+            match quantization[id] {
+                QuantFormat::Linear { zero_point, scale, bits, signed } => writeln!(
+                    self.w,
+                    "zero_point_linear_quantize(zero_point = {}, scale = {:?}, bits = {}, signed = {}, symmetric = false);",
+                    zero_point, scale, bits, signed
+                )?,
+            }
+        }
+        Ok(())
+    }
+
     pub fn fragments(&mut self, defs: &[FragmentDef]) -> TractResult<()> {
         for fragment_def in defs {
             self.fragment_def(&fragment_def)?
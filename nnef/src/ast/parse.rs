@@ -31,6 +31,11 @@ pub fn parse_parameters(doc: &str) -> TractResult<Vec<Parameter>> {
     all_consuming(parameter_list)(doc).map(|pair| pair.1).map_err(translate_error)
 }
 
+#[inline(never)]
+pub fn parse_quantization(doc: &str) -> TractResult<Vec<(String, QuantFormat)>> {
+    all_consuming(many0(quantization))(doc).map(|pair| pair.1).map_err(translate_error)
+}
+
 // <document> ::= <version> <extension>* <fragmentdefinition>* <graph-definition>
 fn document(i: &str) -> IResult<&str, Document> {
     map(
@@ -344,6 +349,78 @@ fn loop_iters(i: &str) -> IResult<&str, Vec<(String, RValue)>> {
     separated_list(stag(","), separated_pair(identifier, stag("in"), rvalue))(i)
 }
 
+// QUANTIZATION
+
+// <quantization> ::= <string-literal> ":" <invocation> ";"
+fn quantization(i: &str) -> IResult<&str, (String, QuantFormat)> {
+    map_opt(
+        pair(terminated(spaced(string_literal), stag(":")), terminated(invocation, stag(";"))),
+        |(id, invocation)| quant_format(&invocation).map(|q| (id, q)),
+    )(i)
+}
+
+fn quant_format(invocation: &Invocation) -> Option<QuantFormat> {
+    let arg = |name: &str| -> Option<&Literal> {
+        invocation.arguments.iter().find(|arg| arg.id.as_deref() == Some(name)).and_then(|arg| {
+            if let RValue::Literal(lit) = &arg.rvalue {
+                Some(lit)
+            } else {
+                None
+            }
+        })
+    };
+    let number = |name: &str| -> Option<f32> {
+        if let Some(Literal::Numeric(n)) = arg(name) {
+            n.parse::<f32>().ok()
+        } else {
+            None
+        }
+    };
+    let logical = |name: &str| -> Option<bool> {
+        match arg(name) {
+            Some(Literal::Logical(b)) => Some(*b),
+            None => Some(false),
+            _ => None,
+        }
+    };
+    let bits = number("bits")?;
+    if !(1.0..=32.0).contains(&bits) {
+        return None;
+    }
+    let bits = bits as usize;
+    let valid_scale = |scale: f32| scale.is_finite() && scale > 0.0;
+    match &*invocation.id {
+        "zero_point_linear_quantize" => {
+            let scale = number("scale")?;
+            if !valid_scale(scale) {
+                return None;
+            }
+            Some(QuantFormat::Linear {
+                zero_point: number("zero_point")? as i32,
+                scale,
+                bits,
+                signed: logical("signed")?,
+            })
+        }
+        "linear_quantize" | "min_max_linear_quantize" => {
+            let (min, max) = (number("min")?, number("max")?);
+            let signed = logical("signed")?;
+            let scale = (max - min) / ((1u64 << bits) - 1) as f32;
+            if !valid_scale(scale) {
+                return None;
+            }
+            let offset = if signed { 1i64 << (bits - 1) } else { 0 };
+            Some(QuantFormat::Linear {
+                zero_point: ((-min / scale).round() as i64 - offset) as i32,
+                scale,
+                bits,
+                signed,
+            })
+        }
+        _ => None,
+    }
+}
+
 // TERMINALS
 
 // identifier: identifiers must consist of the following ASCII characters: _, [a-z], [A-Z], [0-9].
@@ -443,6 +520,44 @@ mod test {
         Result_ { id: s.into(), spec: t }
     }
 
+    #[test]
+    fn test_quantization() {
+        let quant = parse_quantization(
+            r#"
+            "conv_weights": zero_point_linear_quantize(zero_point = -3, scale = 0.5, bits = 8, signed = true, symmetric = false);
+            "input": linear_quantize(min = -1.0, max = 126.5, bits = 8);
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            quant[0],
+            (
+                "conv_weights".to_string(),
+                QuantFormat::Linear { zero_point: -3, scale: 0.5, bits: 8, signed: true }
+            )
+        );
+        assert_eq!(
+            quant[1],
+            (
+                "input".to_string(),
+                QuantFormat::Linear { zero_point: 2, scale: 0.5, bits: 8, signed: false }
+            )
+        );
+    }
+
+    #[test]
+    fn test_invalid_quantization() {
+        for spec in &[
+            r#""a": linear_quantize(min = 1.0, max = 1.0, bits = 8);"#,
+            r#""a": linear_quantize(min = -1.0, max = 1.0, bits = 0);"#,
+            r#""a": linear_quantize(min = -1.0, max = 1.0, bits = 33);"#,
+            r#""a": zero_point_linear_quantize(zero_point = 0, scale = 0.0, bits = 8);"#,
+            r#""a": zero_point_linear_quantize(zero_point = 0, scale = -0.5, bits = 8);"#,
+        ] {
+            assert!(parse_quantization(spec).is_err(), "{}", spec);
+        }
+    }
+
     #[test]
     fn test_type_spec() {
         assert_eq!(p(type_spec, "scalar"), Single(Scalar));
@@ -8,6 +8,9 @@ pub struct ModelBuilder<'a> {
     pub naming_scopes: Vec<String>,
     pub scopes: Vec<HashMap<String, Value>>,
     pub proto_model: &'a ProtoModel,
+    pub symbols: SymbolTable,
+    /// Integer wires holding quantized values, with their graph.quant format.
+    pub quantized: HashMap<OutletId, QuantFormat>,
 }
 
 impl<'mb> ModelBuilder<'mb> {
@@ -19,6 +22,8 @@ impl<'mb> ModelBuilder<'mb> {
             naming_scopes: vec![],
             scopes: vec![],
             proto_model,
+            symbols: framework.symbols.clone(),
+            quantized: HashMap::new(),
         }
     }
 
@@ -88,13 +93,61 @@ impl<'mb> ModelBuilder<'mb> {
             }
             self.model.node_mut(values[0].node).name = format!("{}", self.naming_scopes.join("."));
             for (id, outlet) in identifiers.iter().zip(values.iter()) {
-                self.scopes.last_mut().unwrap().insert(id.to_string(), Value::Wire(*outlet));
+                let outlet = if self.scopes.len() == 1 && !self.scopes[0].contains_key(*id) {
+                    self.wire_quantization(id, *outlet)?
+                } else {
+                    *outlet
+                };
+                self.scopes.last_mut().unwrap().insert(id.to_string(), Value::Wire(outlet));
             }
             self.naming_scopes.pop();
         }
         Ok(())
     }
 
+    /// Apply the graph.quant parameters of a top-level tensor, if any.
+    ///
+    /// Integer tensors hold the quantized values and are kept as is, float tensors get
+    /// quantized. Dequantization only happens where a quantized tensor is copied to a non
+    /// quantized one.
+    fn wire_quantization(&mut self, id: &str, outlet: OutletId) -> TractResult<OutletId> {
+        use tract_core::ops::quant::*;
+        let format =
+            if let Some(format) = self.proto_model.quantization.as_ref().and_then(|q| q.get(id)) {
+                *format
+            } else {
+                return Ok(outlet);
+            };
+        let QuantFormat::Linear { zero_point, scale, bits, signed } = format;
+        let mut wire = outlet;
+        if self.model.outlet_fact(outlet)?.datum_type.is_float() {
+            use std::convert::TryFrom;
+            let out_of_range = || format_err!("Zero point out of range for {}: {:?}", id, format);
+            let quant: Box<dyn TypedOp> = match (bits, signed) {
+                (8, false) => Box::new(quantize_linear_u8(
+                    scale.recip(),
+                    u8::try_from(zero_point).map_err(|_| out_of_range())?,
+                )),
+                (8, true) => Box::new(quantize_linear_i8(
+                    scale.recip(),
+                    i8::try_from(zero_point).map_err(|_| out_of_range())?,
+                )),
+                _ => bail!("Unsupported quantization for {}: {:?}", id, format),
+            };
+            wire = self.model.wire_node(format!("{}.quantize", id), quant, &[wire])?[0];
+        }
+        self.quantized.insert(wire, format);
+        Ok(wire)
+    }
+
+    /// Quantization format of the top-level tensor being wired, if any.
+    pub fn current_quantization(&self) -> Option<QuantFormat> {
+        if self.naming_scopes.len() != 1 {
+            return None;
+        }
+        self.proto_model.quantization.as_ref().and_then(|q| q.get(&self.naming_scopes[0])).cloned()
+    }
+
     pub fn wire_invocation(&mut self, invocation: &Invocation) -> TractResult<Value> {
         for frag in &self.proto_model.doc.fragments {
             if frag.decl.id == invocation.id && frag.body.is_some() {
@@ -386,9 +439,10 @@ impl CoerceFrom<Value> for i64 {
 }
 
 impl CoerceFrom<Value> for TDim {
-    fn coerce(_builder: &mut ModelBuilder, from: &Value) -> TractResult<Self> {
+    fn coerce(builder: &mut ModelBuilder, from: &Value) -> TractResult<Self> {
         match from {
             Value::Dim(d) => Ok(d.clone()),
+            Value::String(s) => parse_tdim(&mut builder.symbols, s),
             _ => bail!("Can not build a TDim from {:?}", from),
         }
     }
@@ -10,11 +10,22 @@ pub fn stdlib() -> Vec<FragmentDef> {
 pub struct Nnef {
     pub stdlib: Vec<FragmentDef>,
     pub registries: Vec<Registry>,
+    /// Symbols to use when loading symbolic dimensions, by name.
+    pub symbols: SymbolTable,
 }
 
 impl Nnef {
     pub fn new() -> Nnef {
-        Nnef { stdlib: stdlib(), registries: vec![crate::ops::tract_nnef()] }
+        Nnef {
+            stdlib: stdlib(),
+            registries: vec![crate::ops::tract_nnef()],
+            symbols: SymbolTable::default(),
+        }
+    }
+
+    pub fn with_symbol(mut self, symbol: Symbol) -> Nnef {
+        self.symbols = self.symbols.with(symbol);
+        self
     }
 
     pub fn with_registry(mut self, registry: Registry) -> Nnef {
@@ -54,6 +65,18 @@ impl Nnef {
         header.set_cksum();
         ar.append(&header, &mut &*graph_data)?;
 
+        if let Some(quantization) = &proto_model.quantization {
+            let mut quant_data = vec![];
+            crate::ast::dump::Dumper::new(&mut quant_data).quantization(quantization)?;
+            let mut header = tar::Header::new_gnu();
+            header.set_path("graph.quant")?;
+            header.set_size(quant_data.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(now.as_secs());
+            header.set_cksum();
+            ar.append(&header, &mut &*quant_data)?;
+        }
+
         for (label, t) in &proto_model.tensors {
             let label = label.to_string() + ".dat";
             let filename = std::path::Path::new(&label);
@@ -84,6 +107,10 @@ impl Nnef {
         std::fs::create_dir_all(path)?;
         let mut graph_nnef = std::fs::File::create(path.join("graph.nnef"))?;
         crate::ast::dump::Dumper::new(&mut graph_nnef).document(&proto_model.doc)?;
+        if let Some(quantization) = &proto_model.quantization {
+            let mut graph_quant = std::fs::File::create(path.join("graph.quant"))?;
+            crate::ast::dump::Dumper::new(&mut graph_quant).quantization(quantization)?;
+        }
         for (label, t) in &proto_model.tensors {
             let label = label.to_string() + ".dat";
             std::fs::create_dir_all(path.join(&label).parent().unwrap())?;
@@ -108,6 +135,7 @@ impl tract_core::prelude::Framework<ProtoModel, TypedModel> for Nnef {
             return self.proto_model_for_read(&mut f);
         }
         let mut text: Option<String> = None;
+        let mut quant: Option<String> = None;
         let mut tensors: std::collections::HashMap<String, Arc<Tensor>> = Default::default();
         for entry in walkdir::WalkDir::new(path) {
             let entry =
@@ -118,25 +146,34 @@ impl tract_core::prelude::Framework<ProtoModel, TypedModel> for Nnef {
                 .skip(path.components().count())
                 .collect::<std::path::PathBuf>();
             let mut stream = std::fs::File::open(entry.path())?;
-            read_stream(&subpath, &mut stream, &mut text, &mut tensors)?;
+            read_stream(&subpath, &mut stream, &mut text, &mut quant, &mut tensors)?;
         }
         let text = text.ok_or_else(|| format_err!("Model must contain graph.nnef at top level"))?;
         let doc = crate::ast::parse::parse_document(&text)?;
-        Ok(ProtoModel { doc, tensors })
+        let quantization = quant
+            .map(|q| crate::ast::parse::parse_quantization(&q))
+            .transpose()?
+            .map(|q| q.into_iter().collect());
+        Ok(ProtoModel { doc, tensors, quantization })
     }
 
     fn proto_model_for_read(&self, reader: &mut dyn std::io::Read) -> TractResult<ProtoModel> {
         let mut text: Option<String> = None;
+        let mut quant: Option<String> = None;
         let mut tensors: std::collections::HashMap<String, Arc<Tensor>> = Default::default();
         let mut tar = tar::Archive::new(reader);
         for entry in tar.entries()? {
             let mut entry = entry?;
             let path = entry.path()?.to_path_buf();
-            read_stream(&path, &mut entry, &mut text, &mut tensors)?;
+            read_stream(&path, &mut entry, &mut text, &mut quant, &mut tensors)?;
         }
         let text = text.ok_or_else(|| format_err!("Model must contain graph.nnef at top level"))?;
         let doc = crate::ast::parse::parse_document(&text)?;
-        Ok(ProtoModel { doc, tensors })
+        let quantization = quant
+            .map(|q| crate::ast::parse::parse_quantization(&q))
+            .transpose()?
+            .map(|q| q.into_iter().collect());
+        Ok(ProtoModel { doc, tensors, quantization })
     }
 
     fn model_for_proto_model(&self, proto: &ProtoModel) -> TractResult<TypedModel> {
@@ -148,12 +185,17 @@ fn read_stream<R: std::io::Read>(
     path: &std::path::Path,
     reader: &mut R,
     text: &mut Option<String>,
+    quant: &mut Option<String>,
     tensors: &mut HashMap<String, Arc<Tensor>>,
 ) -> TractResult<()> {
     if path.file_name().map(|n| n == "graph.nnef").unwrap_or(false) {
         let mut t = String::new();
         reader.read_to_string(&mut t)?;
         *text = Some(t);
+    } else if path.file_name().map(|n| n == "graph.quant").unwrap_or(false) {
+        let mut q = String::new();
+        reader.read_to_string(&mut q)?;
+        *quant = Some(q);
     } else if path.extension().map(|e| e == "dat").unwrap_or(false) {
         let mut path = path.to_path_buf();
         path.set_extension("");
@@ -1,13 +1,27 @@
 use crate::internal::*;
 use tract_core::ops;
 
+mod attention;
 mod broadcast;
 mod cast;
+mod cum_sum;
+mod depth_to_space;
 mod downsample;
+mod einsum;
 mod gather;
+mod gelu;
+mod layer_norm;
+mod prepacked;
+mod qparams;
+mod range;
 mod reduce;
+mod resize;
+mod reverse_sequence;
 mod scan;
+mod scatter;
 mod source;
+mod topk;
+mod trilu;
 
 pub fn register(registry: &mut Registry) {
     registry.register_unit_element_wise("tract_core_tan", &ops::math::Tan {});
@@ -20,15 +34,31 @@ pub fn register(registry: &mut Registry) {
     registry.register_unit_element_wise("tract_core_asinh", &ops::math::Asinh {});
     registry.register_unit_element_wise("tract_core_atanh", &ops::math::Atanh {});
 
+    registry.register_unit_element_wise("tract_core_erf", &ops::math::Erf {});
+
     registry.register_unit_element_wise("tract_core_round_even", &ops::math::RoundHalfToEven {});
 
     registry.register_binary("tract_core_xor", &ops::logic::Xor {});
 
+    attention::register(registry);
     broadcast::register(registry);
     cast::register(registry);
+    cum_sum::register(registry);
+    depth_to_space::register(registry);
     downsample::register(registry);
+    einsum::register(registry);
     gather::register(registry);
+    gelu::register(registry);
+    layer_norm::register(registry);
+    prepacked::register(registry);
+    qparams::register(registry);
+    range::register(registry);
     reduce::register(registry);
+    resize::register(registry);
+    reverse_sequence::register(registry);
     scan::register(registry);
+    scatter::register(registry);
     source::register(registry);
+    topk::register(registry);
+    trilu::register(registry);
 }
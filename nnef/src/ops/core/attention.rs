@@ -0,0 +1,47 @@
+use crate::deser::Value;
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::nn::ScaledDotProductAttention;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<ScaledDotProductAttention>(), ser_attention);
+    registry.register_primitive(
+        "tract_core_scaled_dot_product_attention",
+        &[
+            TypeName::Scalar.tensor().named("q"),
+            TypeName::Scalar.tensor().named("k"),
+            TypeName::Scalar.tensor().named("v"),
+            TypeName::Scalar.tensor().named("mask").default(0.0),
+            TypeName::Scalar.named("scale").default(1.0),
+        ],
+        de_attention,
+    );
+}
+
+fn ser_attention(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<ScaledDotProductAttention>().unwrap();
+    let inputs: TVec<Arc<RValue>> = node.inputs.iter().map(|i| ast.mapping[i].clone()).collect();
+    let mut named = vec![("scale", numeric(op.scale))];
+    if let Some(mask) = inputs.get(3) {
+        named.push(("mask", mask.as_ref().clone()));
+    }
+    Ok(Some(invocation("tract_core_scaled_dot_product_attention", &inputs[0..3], &named)))
+}
+
+fn de_attention(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let mut inputs: TVec<OutletId> = tvec!(
+        invocation.named_arg_as(builder, "q")?,
+        invocation.named_arg_as(builder, "k")?,
+        invocation.named_arg_as(builder, "v")?
+    );
+    // a scalar mask (the default is zero) shifts all scores alike: ignore it
+    match invocation.named_arg("mask")?.resolve(builder)? {
+        Value::Dim(_) | Value::Scalar(_) => (),
+        mask => inputs.push(mask.to::<OutletId>(builder)?),
+    }
+    let scale = invocation.named_arg_as(builder, "scale")?;
+    builder.wire(ScaledDotProductAttention { scale }, &inputs)
+}
@@ -0,0 +1,69 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::array::{DepthToSpace, DepthToSpaceMode, SpaceToDepth};
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<DepthToSpace>(), ser_depth_to_space);
+    registry.register_primitive(
+        "tract_core_depth_to_space",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Integer.named("block_size"),
+            TypeName::String.named("mode").default("dcr"),
+        ],
+        de_depth_to_space,
+    );
+    registry.register_dumper(TypeId::of::<SpaceToDepth>(), ser_space_to_depth);
+    registry.register_primitive(
+        "tract_core_space_to_depth",
+        &[TypeName::Scalar.tensor().named("input"), TypeName::Integer.named("block_size")],
+        de_space_to_depth,
+    );
+}
+
+fn ser_depth_to_space(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<DepthToSpace>().unwrap();
+    let input = ast.mapping[&node.inputs[0]].clone();
+    let mode = match op.mode {
+        DepthToSpaceMode::Dcr => "dcr",
+        DepthToSpaceMode::Crd => "crd",
+    };
+    Ok(Some(invocation(
+        "tract_core_depth_to_space",
+        &[input],
+        &[("block_size", numeric(op.block_size)), ("mode", string(mode))],
+    )))
+}
+
+fn de_depth_to_space(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input = invocation.named_arg_as(builder, "input")?;
+    let block_size = invocation.named_arg_as(builder, "block_size")?;
+    let mode = match &*invocation.named_arg_as::<String>(builder, "mode")? {
+        "dcr" => DepthToSpaceMode::Dcr,
+        "crd" => DepthToSpaceMode::Crd,
+        s => bail!("Unknown depth to space mode {}", s),
+    };
+    builder.wire(DepthToSpace { block_size, mode }, &[input])
+}
+
+fn ser_space_to_depth(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<SpaceToDepth>().unwrap();
+    let input = ast.mapping[&node.inputs[0]].clone();
+    Ok(Some(invocation(
+        "tract_core_space_to_depth",
+        &[input],
+        &[("block_size", numeric(op.block_size))],
+    )))
+}
+
+fn de_space_to_depth(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input = invocation.named_arg_as(builder, "input")?;
+    let block_size = invocation.named_arg_as(builder, "block_size")?;
+    builder.wire(SpaceToDepth { block_size }, &[input])
+}
@@ -0,0 +1,36 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::einsum::{EinSum, Expr};
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<EinSum>(), ser_einsum);
+    registry.register_primitive(
+        "tract_core_einsum",
+        &[TypeName::Scalar.tensor().array().named("inputs"), TypeName::String.named("expr")],
+        de_einsum,
+    );
+}
+
+fn ser_einsum(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<EinSum>().unwrap();
+    let inputs: TVec<RValue> =
+        node.inputs.iter().map(|i| ast.mapping[i].as_ref().clone()).collect();
+    Ok(Some(invocation(
+        "tract_core_einsum",
+        &[Arc::new(array(inputs))],
+        &[("expr", string(op.expr.to_string()))],
+    )))
+}
+
+fn de_einsum(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let inputs: TVec<OutletId> = invocation.named_arg_as(builder, "inputs")?;
+    let expr: String = invocation.named_arg_as(builder, "expr")?;
+    let ranks = inputs
+        .iter()
+        .map(|i| Ok(builder.model.outlet_fact(*i)?.rank()))
+        .collect::<TractResult<TVec<_>>>()?;
+    builder.wire(EinSum::new(Expr::parse(&expr, &ranks)?), &inputs)
+}
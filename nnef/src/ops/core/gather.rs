@@ -13,6 +13,26 @@ pub fn register(registry: &mut Registry) {
         ],
         de_gather,
     );
+    registry.register_dumper(TypeId::of::<ops::array::GatherElements>(), ser_gather_elements);
+    registry.register_primitive(
+        "tract_core_gather_elements",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Scalar.tensor().named("indices"),
+            TypeName::Integer.named("axis"),
+        ],
+        de_gather_elements,
+    );
+    registry.register_dumper(TypeId::of::<ops::array::GatherNd>(), ser_gather_nd);
+    registry.register_primitive(
+        "tract_core_gather_nd",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Scalar.tensor().named("indices"),
+            TypeName::Integer.named("batch_dims"),
+        ],
+        de_gather_nd,
+    );
 }
 
 fn ser_gather(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
@@ -31,3 +51,45 @@ fn de_gather(
     let axis = invocation.named_arg_as(builder, "axis")?;
     builder.wire(ops::array::Gather { axis }, &[wire, indices])
 }
+
+fn ser_gather_elements(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op().downcast_ref::<ops::array::GatherElements>().unwrap();
+    let wire = ast.mapping[&node.inputs[0]].clone();
+    let indices = ast.mapping[&node.inputs[1]].clone();
+    Ok(Some(invocation(
+        "tract_core_gather_elements",
+        &[wire, indices],
+        &[("axis", numeric(op.axis))],
+    )))
+}
+
+fn de_gather_elements(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let wire = invocation.named_arg_as(builder, "input")?;
+    let indices = invocation.named_arg_as(builder, "indices")?;
+    let axis = invocation.named_arg_as(builder, "axis")?;
+    builder.wire(ops::array::GatherElements { axis }, &[wire, indices])
+}
+
+fn ser_gather_nd(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op().downcast_ref::<ops::array::GatherNd>().unwrap();
+    let wire = ast.mapping[&node.inputs[0]].clone();
+    let indices = ast.mapping[&node.inputs[1]].clone();
+    Ok(Some(invocation(
+        "tract_core_gather_nd",
+        &[wire, indices],
+        &[("batch_dims", numeric(op.batch_dims))],
+    )))
+}
+
+fn de_gather_nd(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let wire = invocation.named_arg_as(builder, "input")?;
+    let indices = invocation.named_arg_as(builder, "indices")?;
+    let batch_dims = invocation.named_arg_as(builder, "batch_dims")?;
+    builder.wire(ops::array::GatherNd { batch_dims }, &[wire, indices])
+}
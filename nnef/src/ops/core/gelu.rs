@@ -0,0 +1,36 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::element_wise::ElementWiseOp;
+use tract_core::ops::nn::Gelu;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_element_wise(
+        "tract_core_gelu",
+        TypeId::of::<Gelu>(),
+        gelu_dump,
+        gelu_parameters(),
+        gelu_load,
+    );
+}
+
+fn gelu_parameters() -> Vec<Parameter> {
+    vec![
+        TypeName::Scalar.tensor().named("input"),
+        TypeName::Logical.named("approximate").default(false),
+    ]
+}
+
+fn gelu_dump(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<ElementWiseOp>().unwrap().0.downcast_ref::<Gelu>().unwrap();
+    let input = ast.mapping[&node.inputs[0]].clone();
+    Ok(Some(invocation("tract_core_gelu", &[input], &[("approximate", logical(op.approximate))])))
+}
+
+fn gelu_load(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input = invocation.named_arg_as(builder, "input")?;
+    let approximate = invocation.named_arg_as(builder, "approximate")?;
+    builder.wire(ElementWiseOp(Box::new(Gelu { approximate })), &[input])
+}
@@ -0,0 +1,264 @@
+use crate::ast::Literal;
+use crate::internal::*;
+use crate::ser::*;
+use std::collections::HashSet;
+use tract_core::ndarray::ArrayD;
+use tract_core::ops::cnn::ConvUnary;
+use tract_core::ops::konst::Const;
+use tract_core::ops::matmul::{MatMul, MatMulUnary, PackedA};
+use tract_core::tract_linalg::mmm::MatrixStoreSpec;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<ConvUnary>(), ser_prepacked);
+    registry.register_dumper(TypeId::of::<MatMulUnary>(), ser_prepacked);
+    registry.register_primitive(
+        "tract_core_prepacked",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Scalar.tensor().array().named("packed"),
+            TypeName::Integer.array().named("shape"),
+            TypeName::String.named("kernel"),
+            TypeName::Integer.named("m"),
+            TypeName::Integer.named("k"),
+            TypeName::String.named("storage"),
+            TypeName::Integer.named("panel_len").default(0),
+            TypeName::Integer.array().named("panel_offsets").default(Literal::Array(vec![])),
+            TypeName::Integer.array().named("panel_ks").default(Literal::Array(vec![])),
+            TypeName::Integer.array().named("ks").default(Literal::Array(vec![])),
+        ],
+        de_prepacked,
+    );
+    registry.register_primitive(
+        "tract_core_unpacked",
+        &[
+            TypeName::Scalar.tensor().array().named("packed"),
+            TypeName::Integer.array().named("shape"),
+            TypeName::String.named("kernel"),
+            TypeName::Integer.named("m"),
+            TypeName::Integer.named("k"),
+            TypeName::String.named("storage"),
+            TypeName::Integer.named("panel_len").default(0),
+            TypeName::Integer.array().named("panel_offsets").default(Literal::Array(vec![])),
+            TypeName::Integer.array().named("panel_ks").default(Literal::Array(vec![])),
+            TypeName::Integer.array().named("ks").default(Literal::Array(vec![])),
+            TypeName::Logical.named("transpose").default(false),
+            TypeName::Integer.array().named("output_shape"),
+            TypeName::String.named("datum_type"),
+        ],
+        de_unpacked,
+    );
+}
+
+/// Dumps the op as tract_nnef would without its packed weights, and tags its
+/// output with them.
+///
+/// The packed weights only help when the loading machine picks the same
+/// kernel, but ops still need the original ones to compute facts and to pack
+/// for other kernels. These are not dumped: a tract_core_unpacked invocation
+/// rebuilds them from the packed ones at load time.
+fn ser_prepacked(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let mut unpacked = node.clone();
+    unpacked.name = format!("{}_unpacked", node.name);
+    let (packed, transposed) = if let Some(op) = node.op_as::<ConvUnary>() {
+        unpacked.op = Box::new(ConvUnary { prepacked: None, ..op.clone() });
+        (&op.prepacked, false)
+    } else {
+        let op = node.op_as::<MatMulUnary>().unwrap();
+        unpacked.op = Box::new(MatMulUnary { prepacked: None, ..op.clone() });
+        (&op.prepacked, op.a_trans)
+    };
+    let packed = if let Some(packed) = packed {
+        packed
+    } else {
+        // not packed, so left to us only for its quantization parameters
+        return super::qparams::ser_qparams(ast, node);
+    };
+    let tensors: Vec<RValue> = packed
+        .packed
+        .iter()
+        .enumerate()
+        .map(|(ix, t)| {
+            ast.konst_variable(format!("{}_packed_{}", node.name, ix), t).as_ref().clone()
+        })
+        .collect();
+    let named = packed_args(packed, tensors)?;
+    let dumped: HashSet<String> = ast.tensors.keys().cloned().collect();
+    let wire = if let Some(wire) = super::qparams::ser_qparams(ast, &unpacked)? {
+        Some(wire)
+    } else if let Some(op) = unpacked.op_as::<ConvUnary>() {
+        crate::ops::nnef::ser::conv(ast, &unpacked, op)?
+    } else {
+        crate::ops::nnef::ser::matmul_unary(ast, &unpacked, unpacked.op_as().unwrap())?
+    };
+    let wire = if let Some(wire) = wire { wire } else { return Ok(None) };
+    unpack_weights(ast, packed, transposed, &named, &dumped)?;
+    Ok(Some(invocation("tract_core_prepacked", &[wire], &named)))
+}
+
+/// Arguments describing `packed`, whose tensors are dumped as `tensors`.
+fn packed_args(packed: &PackedA, tensors: Vec<RValue>) -> TractResult<Vec<(&'static str, RValue)>> {
+    let mut named = vec![
+        ("packed", array(tensors)),
+        ("shape", ints(packed.packed.shape())),
+        ("kernel", string(&packed.kernel)),
+        ("m", numeric(packed.m)),
+        ("k", numeric(packed.k)),
+    ];
+    match &packed.storage {
+        MatrixStoreSpec::Packed { panel_len } => {
+            named.push(("storage", string("packed")));
+            named.push(("panel_len", numeric(panel_len)));
+        }
+        MatrixStoreSpec::BlockSparse { panel_offsets, panel_ks, ks, .. } => {
+            named.push(("storage", string("block_sparse")));
+            named.push(("panel_offsets", ints(panel_offsets)));
+            named.push(("panel_ks", ints(panel_ks)));
+            named.push(("ks", ints(ks)));
+        }
+        _ => bail!("Unexpected storage for packed weights: {:?}", packed.storage),
+    }
+    Ok(named)
+}
+
+/// Replaces the variable holding the weights, among the tensors dumped since
+/// `dumped`, by their unpacking. The weights are only dropped when unpacking
+/// gives them back exactly.
+fn unpack_weights(
+    ast: &mut IntoAst,
+    packed: &PackedA,
+    transposed: bool,
+    packed_args: &[(&'static str, RValue)],
+    dumped: &HashSet<String>,
+) -> TractResult<()> {
+    let unpacked = transpose(packed.unpack()?, transposed)?;
+    let label = ast.tensors.iter().find_map(|(label, t)| {
+        if dumped.contains(label)
+            || t.datum_type() != unpacked.datum_type()
+            || t.len() != unpacked.len()
+        {
+            return None;
+        }
+        let mut reshaped = unpacked.clone();
+        reshaped.set_shape(t.shape()).ok()?;
+        Some(label.clone()).filter(|_| reshaped == **t)
+    });
+    let label = if let Some(label) = label { label } else { return Ok(()) };
+    let weights = ast.tensors.remove(&label).unwrap();
+    let mut named = packed_args.to_vec();
+    named.push(("transpose", logical(transposed)));
+    named.push(("output_shape", ints(weights.shape())));
+    named.push(("datum_type", super::qparams::datum_type(weights.datum_type())));
+    let unpack = invocation("tract_core_unpacked", &[], &named);
+    for assignment in &mut ast.body {
+        if let RValue::Invocation(inv) = &assignment.right {
+            if inv.id == "variable"
+                && inv
+                    .arguments
+                    .iter()
+                    .any(|arg| arg.id.as_deref() == Some("label") && arg.rvalue == string(&label))
+            {
+                assignment.right = unpack.as_ref().clone();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Swaps the two inner axes of `t` if `transposed`.
+fn transpose(t: Tensor, transposed: bool) -> TractResult<Tensor> {
+    if !transposed {
+        return Ok(t);
+    }
+    let rank = t.rank();
+    let mut permutation: Vec<usize> = (0..rank).collect();
+    permutation.swap(rank - 2, rank - 1);
+    t.permute_axes(&permutation)
+}
+
+/// Attaches the packed weights to the op producing `input`: a ConvUnary,
+/// possibly behind axis changes, or a MatMul with a constant operand, which
+/// is replaced by a MatMulUnary.
+fn de_prepacked(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input: OutletId = invocation.named_arg_as(builder, "input")?;
+    let packed = de_packed_a(builder, invocation)?;
+
+    let mut wire = input;
+    loop {
+        let node = builder.model.node(wire.node);
+        if node.op_is::<ConvUnary>() {
+            builder.model.node_mut(wire.node).op_as_mut::<ConvUnary>().unwrap().prepacked =
+                Some(packed);
+            return Ok(tvec!(input));
+        } else if let (true, Some(op)) = (wire == input, node.op_as::<MatMul>()) {
+            let inputs = node.inputs.clone();
+            let konst_ix = (0..2).find(|&ix| {
+                builder.model.outlet_fact(inputs[ix]).map(|f| f.konst.is_some()).unwrap_or(false)
+            });
+            if let Some(konst_ix) = konst_ix {
+                let konst = builder.model.outlet_fact(inputs[konst_ix])?.konst.clone().unwrap();
+                let op = MatMulUnary { prepacked: Some(packed), ..op.to_unary(konst_ix, konst) };
+                return builder.wire(op, &[inputs[1 - konst_ix]]);
+            }
+        } else if node.op_is::<AxisOp>() {
+            wire = node.inputs[0];
+            continue;
+        }
+        bail!("tract_core_prepacked expects a convolution or a matrix product, got {}", node)
+    }
+}
+
+/// Rebuilds weights dumped only in their packed form, see `unpack_weights`.
+fn de_unpacked(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let packed = de_packed_a(builder, invocation)?;
+    let transposed: bool = invocation.named_arg_as(builder, "transpose")?;
+    let shape: TVec<usize> = invocation.named_arg_as(builder, "output_shape")?;
+    let dt: DatumType = invocation.named_arg_as::<String>(builder, "datum_type")?.parse()?;
+    let mut weights = transpose(packed.unpack()?, transposed)?;
+    weights.set_shape(&shape)?;
+    // like variable, only keep integers for quantized tensors
+    let quantized = builder.current_quantization().is_some();
+    let dt = if dt == f32::datum_type() || (quantized && dt.is_integer()) {
+        dt
+    } else {
+        f32::datum_type()
+    };
+    let weights = weights.cast_to_dt(dt)?.into_owned();
+    builder.wire(Const::new(weights.into_arc_tensor()), &[])
+}
+
+fn de_packed_a(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<PackedA> {
+    let tensors: TVec<Arc<Tensor>> = invocation.named_arg_as(builder, "packed")?;
+    let shape: TVec<usize> = invocation.named_arg_as(builder, "shape")?;
+    let kernel = invocation.named_arg_as(builder, "kernel")?;
+    let m = invocation.named_arg_as(builder, "m")?;
+    let k = invocation.named_arg_as(builder, "k")?;
+    let storage: String = invocation.named_arg_as(builder, "storage")?;
+    let storage = match &*storage {
+        "packed" => {
+            MatrixStoreSpec::Packed { panel_len: invocation.named_arg_as(builder, "panel_len")? }
+        }
+        "block_sparse" => {
+            let panel_offsets: TVec<usize> = invocation.named_arg_as(builder, "panel_offsets")?;
+            let panel_ks: TVec<usize> = invocation.named_arg_as(builder, "panel_ks")?;
+            let ks: TVec<usize> = invocation.named_arg_as(builder, "ks")?;
+            MatrixStoreSpec::BlockSparse {
+                k,
+                panel_offsets: panel_offsets.into_vec(),
+                panel_ks: panel_ks.into_vec(),
+                ks: ks.into_vec(),
+            }
+        }
+        _ => bail!("Unknown storage for packed weights: {}", storage),
+    };
+    let packed = ArrayD::from_shape_vec(&*shape, tensors.into_vec())?;
+    Ok(PackedA { kernel, m, k, storage, packed })
+}
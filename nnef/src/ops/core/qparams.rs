@@ -0,0 +1,213 @@
+use crate::ast::Literal;
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::cnn::ConvUnary;
+use tract_core::ops::konst::Const;
+use tract_core::ops::matmul::{MatMul, MatMulUnary};
+use tract_core::ops::quant::QParams;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<MatMul>(), ser_qparams);
+    registry.register_primitive(
+        "tract_core_qparams",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::String.named("a_datum_type"),
+            TypeName::String.named("b_datum_type"),
+            TypeName::String.named("c_datum_type"),
+            TypeName::String.named("bias_datum_type").default(""),
+            TypeName::Integer.array().named("zero_point_a").default(Literal::Array(vec![])),
+            TypeName::Integer.array().named("zero_point_b").default(Literal::Array(vec![])),
+            TypeName::Integer.array().named("zero_point_c").default(Literal::Array(vec![])),
+            TypeName::Scalar.array().named("scale_factor").default(Literal::Array(vec![])),
+            TypeName::Scalar.array().named("scale_factor_rows").default(Literal::Array(vec![])),
+            TypeName::Scalar.array().named("scale_factor_cols").default(Literal::Array(vec![])),
+        ],
+        de_qparams,
+    );
+}
+
+pub(super) fn datum_type(dt: DatumType) -> RValue {
+    string(format!("{:?}", dt).to_lowercase())
+}
+
+fn zero_point(zp: &Option<Arc<Tensor>>) -> TractResult<RValue> {
+    let values = if let Some(zp) = zp {
+        zp.cast_to::<i64>()?.as_slice::<i64>()?.iter().map(numeric).collect()
+    } else {
+        vec![]
+    };
+    Ok(array(values))
+}
+
+fn scale_factors(factors: &Option<Arc<Tensor>>) -> TractResult<RValue> {
+    let values = if let Some(factors) = factors {
+        factors.cast_to::<f32>()?.as_slice::<f32>()?.iter().map(numeric).collect()
+    } else {
+        vec![]
+    };
+    Ok(array(values))
+}
+
+/// Dumps a quantized convolution or matrix product as tract_nnef would dump
+/// its float counterpart, and tags its output with the quantization
+/// parameters.
+pub(super) fn ser_qparams(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let mut unquantized = node.clone();
+    unquantized.name = format!("{}_unquantized", node.name);
+    let (q_params, a_dt, b_dt, bias_dt) = if let Some(op) = node.op_as::<ConvUnary>() {
+        let q_params = if let Some(q_params) = &op.q_params { q_params } else { return Ok(None) };
+        unquantized.op = Box::new(ConvUnary { q_params: None, ..op.clone() });
+        let b_dt = ast.model.outlet_fact(node.inputs[0])?.datum_type;
+        let bias_dt = op.bias.as_ref().map(|b| b.datum_type());
+        (q_params.clone(), op.kernel.datum_type(), b_dt, bias_dt)
+    } else if let Some(op) = node.op_as::<MatMulUnary>() {
+        let q_params = if let Some(q_params) = &op.q_params { q_params } else { return Ok(None) };
+        unquantized.op = Box::new(MatMulUnary { q_params: None, ..op.clone() });
+        let b_dt = ast.model.outlet_fact(node.inputs[0])?.datum_type;
+        // tract_nnef dumps a transposed product with its operands swapped
+        if op.c_trans {
+            (q_params.flipped(), b_dt, op.a.datum_type(), None)
+        } else {
+            (q_params.clone(), op.a.datum_type(), b_dt, None)
+        }
+    } else {
+        let op = node.op_as::<MatMul>().unwrap();
+        let q_params = if let Some(q_params) = &op.q_params { q_params } else { return Ok(None) };
+        unquantized.op = Box::new(MatMul { q_params: None, ..op.clone() });
+        let a_dt = ast.model.outlet_fact(node.inputs[0])?.datum_type;
+        let b_dt = ast.model.outlet_fact(node.inputs[1])?.datum_type;
+        if op.c_trans {
+            (q_params.flipped(), b_dt, a_dt, None)
+        } else {
+            (q_params.clone(), a_dt, b_dt, None)
+        }
+    };
+    let wire = if let Some(op) = unquantized.op_as::<ConvUnary>() {
+        crate::ops::nnef::ser::conv(ast, &unquantized, op)?
+    } else if let Some(op) = unquantized.op_as::<MatMulUnary>() {
+        crate::ops::nnef::ser::matmul_unary(ast, &unquantized, op)?
+    } else {
+        crate::ops::nnef::ser::matmul(ast, &unquantized, unquantized.op_as::<MatMul>().unwrap())?
+    };
+    let wire = if let Some(wire) = wire { wire } else { return Ok(None) };
+    let mut named = vec![
+        ("a_datum_type", datum_type(a_dt)),
+        ("b_datum_type", datum_type(b_dt)),
+        ("c_datum_type", datum_type(q_params.c_datum_type)),
+        ("zero_point_a", zero_point(&q_params.zero_point_a)?),
+        ("zero_point_b", zero_point(&q_params.zero_point_b)?),
+        ("zero_point_c", zero_point(&q_params.zero_point_c)?),
+        ("scale_factor", array(q_params.scale_factor.iter().map(numeric).collect::<Vec<_>>())),
+        ("scale_factor_rows", scale_factors(&q_params.scale_factor_rows)?),
+        ("scale_factor_cols", scale_factors(&q_params.scale_factor_cols)?),
+    ];
+    if let Some(bias_dt) = bias_dt {
+        named.push(("bias_datum_type", datum_type(bias_dt)));
+    }
+    Ok(Some(invocation("tract_core_qparams", &[wire], &named)))
+}
+
+/// Attaches the quantization parameters to the op producing `input`: a
+/// ConvUnary or a MatMul, possibly behind axis changes. The weights, reloaded
+/// as floats, get back their integer datum types.
+fn de_qparams(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input: OutletId = invocation.named_arg_as(builder, "input")?;
+    let a_dt: DatumType = invocation.named_arg_as::<String>(builder, "a_datum_type")?.parse()?;
+    let b_dt: DatumType = invocation.named_arg_as::<String>(builder, "b_datum_type")?.parse()?;
+    let c_dt: DatumType = invocation.named_arg_as::<String>(builder, "c_datum_type")?.parse()?;
+    let bias_dt: String = invocation.named_arg_as(builder, "bias_datum_type")?;
+    let mut q_params = QParams::new(c_dt);
+    if let Some(zp) = de_zero_point(builder, invocation, "zero_point_a", a_dt)? {
+        q_params.set_zero_point_a(&zp);
+    }
+    if let Some(zp) = de_zero_point(builder, invocation, "zero_point_b", b_dt)? {
+        q_params.set_zero_point_b(&zp);
+    }
+    if let Some(zp) = de_zero_point(builder, invocation, "zero_point_c", c_dt)? {
+        q_params.set_zero_point_c(&zp);
+    }
+    let scale_factor: TVec<f32> = invocation.named_arg_as(builder, "scale_factor")?;
+    q_params.scale_factor = scale_factor.get(0).cloned();
+    let rows: TVec<f32> = invocation.named_arg_as(builder, "scale_factor_rows")?;
+    if rows.len() > 0 {
+        q_params.scale_factor_rows = Some(rctensor1(&rows));
+    }
+    let cols: TVec<f32> = invocation.named_arg_as(builder, "scale_factor_cols")?;
+    if cols.len() > 0 {
+        q_params.scale_factor_cols = Some(rctensor1(&cols));
+    }
+
+    let mut path = vec![];
+    let mut wire = input;
+    loop {
+        let node = builder.model.node(wire.node);
+        if let Some(op) = node.op_as::<ConvUnary>() {
+            let mut op = op.clone();
+            op.kernel = op.kernel.cast_to_dt(a_dt)?.into_owned().into_arc_tensor();
+            if let Some(bias) = op.bias.clone() {
+                op.bias = Some(bias.cast_to_dt(bias_dt.parse()?)?.into_owned().into_arc_tensor());
+            }
+            op.q_params = Some(q_params);
+            builder.model.node_mut(wire.node).op = Box::new(op);
+            break;
+        } else if let Some(op) = node.op_as::<MatMul>() {
+            let op = MatMul { q_params: Some(q_params), ..op.clone() };
+            let inputs = node.inputs.clone();
+            for (input, dt) in inputs.into_iter().zip([a_dt, b_dt].iter()) {
+                cast_konst(builder, input, *dt)?;
+            }
+            builder.model.node_mut(wire.node).op = Box::new(op);
+            break;
+        } else if node.op_is::<AxisOp>() {
+            path.push(wire.node);
+            wire = node.inputs[0];
+        } else {
+            bail!("tract_core_qparams expects a convolution or a matrix product, got {}", node)
+        }
+    }
+    path.push(wire.node);
+    // datum types changed from the op down to the input
+    for &node in path.iter().rev() {
+        let inputs =
+            builder.model.node_input_facts(node)?.into_iter().cloned().collect::<TVec<_>>();
+        let facts =
+            builder.model.node(node).op.output_facts(&inputs.iter().collect::<TVec<_>>())?;
+        builder.model.node_mut(node).outputs[0].fact = facts[0].clone();
+    }
+    Ok(tvec!(input))
+}
+
+fn de_zero_point(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+    name: &str,
+    dt: DatumType,
+) -> TractResult<Option<Arc<Tensor>>> {
+    let values: TVec<i64> = invocation.named_arg_as(builder, name)?;
+    let zp = match values.len() {
+        0 => return Ok(None),
+        1 => tensor0(values[0]),
+        _ => tensor1(&values),
+    };
+    Ok(Some(zp.cast_to_dt(dt)?.into_owned().into_arc_tensor()))
+}
+
+/// Casts a constant operand, reloaded as a float, back to its datum type.
+fn cast_konst(builder: &mut ModelBuilder, outlet: OutletId, dt: DatumType) -> TractResult<()> {
+    if builder.model.outlet_fact(outlet)?.datum_type == dt {
+        return Ok(());
+    }
+    let node = builder.model.node_mut(outlet.node);
+    if let Some(konst) = node.op_as::<Const>() {
+        let konst = konst.0.cast_to_dt(dt)?.into_owned().into_arc_tensor();
+        node.outputs[0].fact = TypedFact::from(konst.clone());
+        node.op = Box::new(Const(konst));
+        Ok(())
+    } else {
+        bail!("Quantized matrix product expects {:?} operands, got {}", dt, node)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::array::Range;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<Range>(), ser_range);
+    registry.register_primitive(
+        "tract_core_range",
+        &[
+            TypeName::Scalar.tensor().named("start"),
+            TypeName::Scalar.tensor().named("limit"),
+            TypeName::Scalar.tensor().named("delta"),
+        ],
+        de_range,
+    );
+}
+
+fn ser_range(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let wires: TVec<_> = node.inputs.iter().map(|i| ast.mapping[i].clone()).collect();
+    Ok(Some(invocation("tract_core_range", &wires, &[])))
+}
+
+fn de_range(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let start = invocation.named_arg_as(builder, "start")?;
+    let limit = invocation.named_arg_as(builder, "limit")?;
+    let delta = invocation.named_arg_as(builder, "delta")?;
+    builder.wire(Range, &[start, limit, delta])
+}
@@ -0,0 +1,121 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::array::{CoordTransformer, Interpolator, Nearest, Resize, ResizeAxis};
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<Resize>(), ser_resize);
+    registry.register_primitive(
+        "tract_core_resize",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Integer.array().named("axes"),
+            TypeName::Integer.array().named("lens"),
+            TypeName::Scalar.array().named("scales"),
+            TypeName::Scalar.array().named("roi_starts"),
+            TypeName::Scalar.array().named("roi_ends"),
+            TypeName::String.named("coord_transformer").default("half_pixel"),
+            TypeName::String.named("interpolator").default("nearest"),
+            TypeName::String.named("nearest").default("round_prefer_floor"),
+            TypeName::Scalar.named("cubic_coeff_a").default(-0.75),
+            TypeName::Logical.named("exclude_outside").default(false),
+            TypeName::Scalar.named("extrapolation_value").default(0.0),
+        ],
+        de_resize,
+    );
+}
+
+fn ser_resize(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<Resize>().unwrap();
+    let input = ast.mapping[&node.inputs[0]].clone();
+    let floats = |f: &dyn Fn(&ResizeAxis) -> f32| {
+        array(op.axes.iter().map(|a| numeric(f(a))).collect::<TVec<_>>())
+    };
+    let coord_transformer = match op.coord_transformer {
+        CoordTransformer::HalfPixel => "half_pixel",
+        CoordTransformer::PytorchHalfPixel => "pytorch_half_pixel",
+        CoordTransformer::AlignCorners => "align_corners",
+        CoordTransformer::Asymmetric => "asymmetric",
+        CoordTransformer::TfHalfPixelForNn => "tf_half_pixel_for_nn",
+        CoordTransformer::TfCropAndResize => "tf_crop_and_resize",
+    };
+    let interpolator = match op.interpolator {
+        Interpolator::Nearest => "nearest",
+        Interpolator::Linear => "linear",
+        Interpolator::Cubic => "cubic",
+    };
+    let nearest = match op.nearest {
+        Nearest::Floor => "floor",
+        Nearest::Ceil => "ceil",
+        Nearest::RoundPreferFloor => "round_prefer_floor",
+        Nearest::RoundPreferCeil => "round_prefer_ceil",
+    };
+    Ok(Some(invocation(
+        "tract_core_resize",
+        &[input],
+        &[
+            ("axes", ints(&op.axes.iter().map(|a| a.axis).collect::<TVec<_>>())),
+            ("lens", ints(&op.axes.iter().map(|a| a.len).collect::<TVec<_>>())),
+            ("scales", floats(&|a| a.scale)),
+            ("roi_starts", floats(&|a| a.roi_start)),
+            ("roi_ends", floats(&|a| a.roi_end)),
+            ("coord_transformer", string(coord_transformer)),
+            ("interpolator", string(interpolator)),
+            ("nearest", string(nearest)),
+            ("cubic_coeff_a", numeric(op.cubic_coeff_a)),
+            ("exclude_outside", logical(op.exclude_outside)),
+            ("extrapolation_value", numeric(op.extrapolation_value)),
+        ],
+    )))
+}
+
+fn de_resize(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input = invocation.named_arg_as(builder, "input")?;
+    let axes: TVec<usize> = invocation.named_arg_as(builder, "axes")?;
+    let lens: TVec<usize> = invocation.named_arg_as(builder, "lens")?;
+    let scales: TVec<f32> = invocation.named_arg_as(builder, "scales")?;
+    let roi_starts: TVec<f32> = invocation.named_arg_as(builder, "roi_starts")?;
+    let roi_ends: TVec<f32> = invocation.named_arg_as(builder, "roi_ends")?;
+    if [lens.len(), scales.len(), roi_starts.len(), roi_ends.len()].iter().any(|l| *l != axes.len())
+    {
+        bail!("tract_core_resize expects axes, lens, scales, roi_starts and roi_ends of the same length")
+    }
+    let axes = (0..axes.len())
+        .map(|i| ResizeAxis::new(axes[i], lens[i], scales[i], roi_starts[i], roi_ends[i]))
+        .collect();
+    let coord_transformer =
+        match &*invocation.named_arg_as::<String>(builder, "coord_transformer")? {
+            "half_pixel" => CoordTransformer::HalfPixel,
+            "pytorch_half_pixel" => CoordTransformer::PytorchHalfPixel,
+            "align_corners" => CoordTransformer::AlignCorners,
+            "asymmetric" => CoordTransformer::Asymmetric,
+            "tf_half_pixel_for_nn" => CoordTransformer::TfHalfPixelForNn,
+            "tf_crop_and_resize" => CoordTransformer::TfCropAndResize,
+            s => bail!("Unknown coordinate transformer {}", s),
+        };
+    let interpolator = match &*invocation.named_arg_as::<String>(builder, "interpolator")? {
+        "nearest" => Interpolator::Nearest,
+        "linear" => Interpolator::Linear,
+        "cubic" => Interpolator::Cubic,
+        s => bail!("Unknown interpolator {}", s),
+    };
+    let nearest = match &*invocation.named_arg_as::<String>(builder, "nearest")? {
+        "floor" => Nearest::Floor,
+        "ceil" => Nearest::Ceil,
+        "round_prefer_floor" => Nearest::RoundPreferFloor,
+        "round_prefer_ceil" => Nearest::RoundPreferCeil,
+        s => bail!("Unknown nearest mode {}", s),
+    };
+    let op = Resize {
+        axes,
+        coord_transformer,
+        interpolator,
+        nearest,
+        cubic_coeff_a: invocation.named_arg_as(builder, "cubic_coeff_a")?,
+        exclude_outside: invocation.named_arg_as(builder, "exclude_outside")?,
+        extrapolation_value: invocation.named_arg_as(builder, "extrapolation_value")?,
+    };
+    builder.wire(op, &[input])
+}
@@ -0,0 +1,38 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::array::ReverseSequence;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<ReverseSequence>(), ser_reverse_sequence);
+    registry.register_primitive(
+        "tract_core_reverse_sequence",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Scalar.tensor().named("sequence_lens"),
+            TypeName::Integer.named("batch_axis"),
+            TypeName::Integer.named("time_axis"),
+        ],
+        de_reverse_sequence,
+    );
+}
+
+fn ser_reverse_sequence(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<ReverseSequence>().unwrap();
+    let wires: TVec<_> = node.inputs.iter().map(|i| ast.mapping[i].clone()).collect();
+    Ok(Some(invocation(
+        "tract_core_reverse_sequence",
+        &wires,
+        &[("batch_axis", numeric(op.batch_axis)), ("time_axis", numeric(op.time_axis))],
+    )))
+}
+
+fn de_reverse_sequence(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input = invocation.named_arg_as(builder, "input")?;
+    let lens = invocation.named_arg_as(builder, "sequence_lens")?;
+    let batch_axis = invocation.named_arg_as(builder, "batch_axis")?;
+    let time_axis = invocation.named_arg_as(builder, "time_axis")?;
+    builder.wire(ReverseSequence { batch_axis, time_axis }, &[input, lens])
+}
@@ -0,0 +1,59 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<ops::array::ScatterElements>(), ser_scatter_elements);
+    registry.register_primitive(
+        "tract_core_scatter_elements",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Scalar.tensor().named("indices"),
+            TypeName::Scalar.tensor().named("updates"),
+            TypeName::Integer.named("axis"),
+        ],
+        de_scatter_elements,
+    );
+    registry.register_dumper(TypeId::of::<ops::array::ScatterNd>(), ser_scatter_nd);
+    registry.register_primitive(
+        "tract_core_scatter_nd",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Scalar.tensor().named("indices"),
+            TypeName::Scalar.tensor().named("updates"),
+        ],
+        de_scatter_nd,
+    );
+}
+
+fn ser_scatter_elements(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op().downcast_ref::<ops::array::ScatterElements>().unwrap();
+    let wires: TVec<_> = node.inputs.iter().map(|i| ast.mapping[i].clone()).collect();
+    Ok(Some(invocation("tract_core_scatter_elements", &wires, &[("axis", numeric(op.axis))])))
+}
+
+fn de_scatter_elements(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let wire = invocation.named_arg_as(builder, "input")?;
+    let indices = invocation.named_arg_as(builder, "indices")?;
+    let updates = invocation.named_arg_as(builder, "updates")?;
+    let axis = invocation.named_arg_as(builder, "axis")?;
+    builder.wire(ops::array::ScatterElements { axis }, &[wire, indices, updates])
+}
+
+fn ser_scatter_nd(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let wires: TVec<_> = node.inputs.iter().map(|i| ast.mapping[i].clone()).collect();
+    Ok(Some(invocation("tract_core_scatter_nd", &wires, &[])))
+}
+
+fn de_scatter_nd(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let wire = invocation.named_arg_as(builder, "input")?;
+    let indices = invocation.named_arg_as(builder, "indices")?;
+    let updates = invocation.named_arg_as(builder, "updates")?;
+    builder.wire(ops::array::ScatterNd, &[wire, indices, updates])
+}
@@ -13,7 +13,7 @@ fn external_dump(_ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc
         "tract_core_external",
         &[],
         &[
-            ("shape", ints(&op.fact.shape.as_finite().unwrap())),
+            ("shape", tdims(&op.fact.shape)),
             ("datum_type", string(format!("{:?}", op.fact.datum_type))),
         ],
     )))
@@ -27,7 +27,7 @@ fn external_load(
     builder: &mut ModelBuilder,
     invocation: &ResolvedInvocation,
 ) -> TractResult<TVec<OutletId>> {
-    let shape: TVec<usize> = invocation.named_arg_as(builder, "shape")?;
+    let shape: TVec<TDim> = invocation.named_arg_as(builder, "shape")?;
     let dt = invocation.named_arg_as::<String>(builder, "datum_type")?.parse()?;
     let fact = TypedFact::dt_shape(dt, &*shape)?;
     Ok(tvec!(builder.model.add_source("", fact)?))
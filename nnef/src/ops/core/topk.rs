@@ -0,0 +1,38 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::array::TopK;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<TopK>(), ser_topk);
+    registry.register_primitive(
+        "tract_core_topk",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Integer.named("axis"),
+            TypeName::Integer.named("k"),
+            TypeName::Logical.named("largest").default(true),
+        ],
+        de_topk,
+    );
+}
+
+fn ser_topk(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<TopK>().unwrap();
+    let input = ast.mapping[&node.inputs[0]].clone();
+    Ok(Some(invocation(
+        "tract_core_topk",
+        &[input],
+        &[("axis", numeric(op.axis)), ("k", numeric(op.k)), ("largest", logical(op.largest))],
+    )))
+}
+
+fn de_topk(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input = invocation.named_arg_as(builder, "input")?;
+    let axis = invocation.named_arg_as(builder, "axis")?;
+    let k = invocation.named_arg_as(builder, "k")?;
+    let largest = invocation.named_arg_as(builder, "largest")?;
+    builder.wire(TopK { axis, k, largest }, &[input])
+}
@@ -0,0 +1,36 @@
+use crate::internal::*;
+use crate::ser::*;
+use tract_core::ops::array::Trilu;
+
+pub fn register(registry: &mut Registry) {
+    registry.register_dumper(TypeId::of::<Trilu>(), ser_trilu);
+    registry.register_primitive(
+        "tract_core_trilu",
+        &[
+            TypeName::Scalar.tensor().named("input"),
+            TypeName::Logical.named("upper").default(true),
+            TypeName::Integer.named("k").default(0),
+        ],
+        de_trilu,
+    );
+}
+
+fn ser_trilu(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<Trilu>().unwrap();
+    let input = ast.mapping[&node.inputs[0]].clone();
+    Ok(Some(invocation(
+        "tract_core_trilu",
+        &[input],
+        &[("upper", logical(op.upper)), ("k", numeric(op.k))],
+    )))
+}
+
+fn de_trilu(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input = invocation.named_arg_as(builder, "input")?;
+    let upper = invocation.named_arg_as(builder, "upper")?;
+    let k = invocation.named_arg_as(builder, "k")?;
+    builder.wire(Trilu { upper, k }, &[input])
+}
@@ -44,7 +44,11 @@ pub fn variable(
             shape
         );
     }
-    let tensor = if tensor.datum_type() == f32::datum_type() {
+    // quantized tensors keep their integer values
+    let quantized = builder.current_quantization().is_some();
+    let tensor = if tensor.datum_type() == f32::datum_type()
+        || (quantized && tensor.datum_type().is_integer())
+    {
         tensor.clone()
     } else {
         tensor.cast_to::<f32>()?.into_owned().into_arc_tensor()
@@ -181,6 +185,7 @@ pub fn pad(
         "constant" => PadMode::Constant(value.into_arc_tensor()),
         "replicated" => PadMode::Edge,
         "reflect" => PadMode::Reflect,
+        "reflect-even" => PadMode::Symmetric,
         _ => bail!("unsupported padding mode {}", border),
     };
     builder.wire(Pad { pads: padding, mode }, &wire)
@@ -424,3 +429,17 @@ pub fn select(
     let inputs = crate::registry::multicast(builder, &[cond, true_value, false_value])?;
     builder.wire(ops::logic::Iff {}, &inputs)
 }
+
+// fragment copy<?>( x: tensor<?> ) -> ( y: tensor<?> );
+pub fn copy(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let x = invocation.named_arg_as(builder, "x")?;
+    // copying a quantized tensor to a non quantized one dequantizes it
+    if let (Some(format), None) = (builder.quantized.get(&x), builder.current_quantization()) {
+        let QuantFormat::Linear { zero_point, scale, .. } = *format;
+        return builder.wire(ops::quant::DequantizeLinearF32::new(scale, zero_point), &[x]);
+    }
+    builder.wire(ops::identity::Identity, &[x])
+}
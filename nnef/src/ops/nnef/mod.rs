@@ -1,7 +1,7 @@
 use crate::internal::*;
 
 mod deser;
-mod ser;
+pub(crate) mod ser;
 
 pub fn tract_nnef() -> Registry {
     use tract_core::ops;
@@ -111,6 +111,24 @@ pub fn tract_nnef() -> Registry {
     primitive(&mut registry, "box", deser::sum_pool);
     dumper!(ops::cnn::SumPool, ser::sum_pool);
 
+    // quantization parameters go to graph.quant, the quantized tensor itself is a copy
+    let copy = stdlib.iter().find(|f| f.decl.id == "copy").unwrap().decl.parameters.clone();
+    registry.register_element_wise(
+        "copy",
+        TypeId::of::<ops::quant::QuantizeLinearU8>(),
+        ser::quantize_linear,
+        copy.clone(),
+        deser::copy,
+    );
+    registry.register_element_wise(
+        "copy",
+        TypeId::of::<ops::quant::QuantizeLinearI8>(),
+        ser::quantize_linear,
+        copy,
+        deser::copy,
+    );
+    dumper!(ops::quant::DequantizeLinearF32, ser::dequantize_linear);
+
     for frag in stdlib {
         if frag.body.is_some() {
             registry.register_fragment(frag);
@@ -1,3 +1,4 @@
+use crate::ast::QuantFormat;
 use crate::internal::*;
 use crate::ser::*;
 use tract_core::ops;
@@ -8,12 +9,8 @@ pub fn source(
     _node: &TypedNode,
     op: &ops::source::TypedSource,
 ) -> TractResult<Option<Arc<RValue>>> {
-    if op.fact.datum_type == DatumType::F32 {
-        Ok(Some(invocation(
-            "external",
-            &[],
-            &[("shape", ints(&*op.fact.shape.as_finite().unwrap()))],
-        )))
+    if let (DatumType::F32, Some(shape)) = (op.fact.datum_type, op.fact.shape.as_finite()) {
+        Ok(Some(invocation("external", &[], &[("shape", ints(&*shape))])))
     } else {
         Ok(None)
     }
@@ -94,6 +91,7 @@ pub fn pad(
             "constant"
         }
         PadMode::Reflect => "reflect",
+        PadMode::Symmetric => "reflect-even",
         PadMode::Edge => "replicated",
     };
     params.push(("border", string(border)));
@@ -170,6 +168,10 @@ pub fn conv(
     op: &ops::cnn::conv::ConvUnary,
 ) -> TractResult<Option<Arc<RValue>>> {
     use tract_core::ops::cnn::PaddingSpec;
+    if op.prepacked.is_some() || op.q_params.is_some() {
+        // left to tract_core, along with the packed weights or quantization parameters
+        return Ok(None);
+    }
     let ci = op
         .pool_spec
         .data_format
@@ -346,10 +348,7 @@ pub fn axis_op(
             "reshape",
             &[wire],
             &[
-                (
-                    "shape",
-                    ints(&*to.iter().map(|d| d.to_usize().unwrap()).collect::<Vec<_>>()),
-                ),
+                ("shape", tdims(to)),
                 ("axis_start", numeric(start)),
                 ("axis_count", numeric(from.len())),
             ],
@@ -380,6 +379,10 @@ pub fn matmul(
     node: &TypedNode,
     op: &ops::matmul::MatMul,
 ) -> TractResult<Option<Arc<RValue>>> {
+    if op.q_params.is_some() {
+        // left to tract_core, along with the quantization parameters
+        return Ok(None);
+    }
     let a = ast.force_assign(format!("{}_a", node.name), &ast.mapping[&node.inputs[0]].clone());
     let b = ast.force_assign(format!("{}_b", node.name), &ast.mapping[&node.inputs[1]].clone());
     let c = if op.c_trans {
@@ -403,6 +406,10 @@ pub fn matmul_unary(
     node: &TypedNode,
     op: &ops::matmul::MatMulUnary,
 ) -> TractResult<Option<Arc<RValue>>> {
+    if op.prepacked.is_some() || op.q_params.is_some() {
+        // left to tract_core, along with the packed weights or quantization parameters
+        return Ok(None);
+    }
     let a = ast.konst(format!("{}_a", node.name), &op.a);
     let b = ast.force_assign(format!("{}_b", node.name), &ast.mapping[&node.inputs[0]].clone());
     let c = if op.c_trans {
@@ -432,3 +439,49 @@ pub fn select(
         &[],
     )))
 }
+
+pub fn quantize_linear(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = &node.op_as::<ops::element_wise::ElementWiseOp>().unwrap().0;
+    let (scale, zero_point, signed) =
+        if let Some(op) = op.downcast_ref::<ops::quant::QuantizeLinearU8>() {
+            (op.scale, op.zero_point as i32, false)
+        } else if let Some(op) = op.downcast_ref::<ops::quant::QuantizeLinearI8>() {
+            (op.scale, op.zero_point as i32, true)
+        } else {
+            return Ok(None);
+        };
+    let format = QuantFormat::Linear { zero_point, scale: scale.recip(), bits: 8, signed };
+    ast.quantize(ast.scoped_id(&node.name), format)?;
+    let input = ast.mapping[&node.inputs[0]].clone();
+    Ok(Some(invocation("copy", &[input], &[])))
+}
+
+pub fn dequantize_linear(
+    ast: &mut IntoAst,
+    node: &TypedNode,
+    op: &ops::quant::DequantizeLinearF32,
+) -> TractResult<Option<Arc<RValue>>> {
+    let fact = ast.model.outlet_fact(node.inputs[0])?.clone();
+    let (bits, signed) = match fact.datum_type {
+        DatumType::U8 => (8, false),
+        DatumType::I8 => (8, true),
+        DatumType::I32 => (32, true),
+        _ => return Ok(None),
+    };
+    let input = match &fact.konst {
+        // uniform constants are dumped as float literals, lost their integer type
+        Some(k) if k.is_uniform()? => ast.konst_variable(format!("{}.quantized", node.name), k),
+        _ => ast.mapping[&node.inputs[0]].clone(),
+    };
+    let id = if let RValue::Identifier(id) = input.as_ref() {
+        id.clone()
+    } else {
+        return Ok(None);
+    };
+    ast.quantize(
+        id,
+        QuantFormat::Linear { zero_point: op.zero_point, scale: op.scale, bits, signed },
+    )?;
+    // a copy of a quantized tensor to a non quantized one is its dequantization
+    Ok(Some(invocation("copy", &[input], &[])))
+}
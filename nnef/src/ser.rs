@@ -28,6 +28,7 @@ pub struct IntoAst<'a> {
     pub results: Vec<String>,
     pub mapping: HashMap<OutletId, Arc<RValue>>,
     pub tensors: HashMap<String, Arc<Tensor>>,
+    pub quantization: HashMap<String, QuantFormat>,
     pub fragments: HashMap<String, FragmentDef>,
     pub body: Vec<Assignment>,
 }
@@ -50,6 +51,7 @@ impl<'a> IntoAst<'a> {
             results: vec![],
             mapping: Default::default(),
             tensors: Default::default(),
+            quantization: Default::default(),
             fragments: Default::default(),
             body: vec![],
             parent: None,
@@ -158,7 +160,9 @@ impl<'a> IntoAst<'a> {
             self.konst("tract_nnef_format_version", &rctensor0("alpha1".to_string())).as_ref().clone(),
         ));
         let properties: Assignment = assignment("properties", Arc::new(array(properties)));
-        let IntoAst { prefix, mut fragments, body, tensors, parameters, results, .. } = self;
+        let IntoAst {
+            prefix, mut fragments, body, tensors, quantization, parameters, results, ..
+        } = self;
         let mut id = prefix
             .map(|p| p.trim_end_matches(&['-', '/', '.'][..]).replace(&['-', '/', '.'][..], "_"))
             .unwrap_or("network".into());
@@ -191,7 +195,8 @@ impl<'a> IntoAst<'a> {
             fragments: fragments.into_iter().map(|(_, v)| v).collect(),
             graph_def: GraphDef { id, parameters, results, body },
         };
-        Ok(ProtoModel { doc, tensors })
+        let quantization = if quantization.len() > 0 { Some(quantization) } else { None };
+        Ok(ProtoModel { doc, tensors, quantization })
     }
 
     fn node(&mut self, node: &TypedNode) -> TractResult<TVec<Arc<RValue>>> {
@@ -250,6 +255,26 @@ impl<'a> IntoAst<'a> {
         }
     }
 
+    /// Record the quantization of a top-level tensor, to be written in graph.quant.
+    pub fn quantize(&mut self, id: impl Into<String>, format: QuantFormat) -> TractResult<()> {
+        let id = id.into();
+        if self.parent.is_some() {
+            bail!("Quantized tensors ({}) are only supported in the main graph", id);
+        }
+        if let Some(previous) = self.quantization.get(&id) {
+            let (
+                QuantFormat::Linear { zero_point: z0, scale: s0, .. },
+                QuantFormat::Linear { zero_point: z1, scale: s1, .. },
+            ) = (previous, &format);
+            if z0 != z1 || (s0 - s1).abs() > s0.abs() * 1e-6 {
+                bail!("Conflicting quantization for {}: {:?} and {:?}", id, previous, format);
+            }
+            return Ok(());
+        }
+        self.quantization.insert(id, format);
+        Ok(())
+    }
+
     pub fn konst(&mut self, name: impl Into<String>, tensor: &Arc<Tensor>) -> Arc<RValue> {
         self.do_konst(name, tensor, false)
     }
@@ -303,6 +328,19 @@ pub fn ints(shape: &[usize]) -> RValue {
     RValue::Array(shape.iter().map(|s| RValue::Literal(Literal::Numeric(s.to_string()))).collect())
 }
 
+/// Integers for the known dimensions, strings for the symbolic ones.
+pub fn tdims(shape: &[TDim]) -> RValue {
+    RValue::Array(
+        shape
+            .iter()
+            .map(|d| match d.to_i64() {
+                Ok(v) => RValue::Literal(Literal::Numeric(v.to_string())),
+                Err(_) => RValue::Literal(Literal::String(d.to_string())),
+            })
+            .collect(),
+    )
+}
+
 pub fn string(s: impl Into<String>) -> RValue {
     RValue::Literal(Literal::String(s.into()))
 }
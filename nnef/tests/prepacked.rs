@@ -0,0 +1,67 @@
+use tract_core::internal::*;
+use tract_core::ndarray::{Array2, Array4};
+use tract_core::ops::cnn::{ConvUnary, KernelFormat, PaddingSpec, PoolSpec};
+use tract_core::ops::matmul::MatMulUnary;
+use tract_core::ops::nn::DataFormat;
+
+fn model() -> TractResult<TypedModel> {
+    let mut model = TypedModel::default();
+    let x =
+        model.add_source("x", TypedFact::dt_shape(f32::datum_type(), &[1usize, 2, 5, 5][..])?)?;
+    let kernel = Array4::from_shape_fn((3, 2, 3, 3), |(o, i, h, w)| (o + 2 * i + h * w) as f32);
+    let pool_spec =
+        PoolSpec::new(DataFormat::NCHW, tvec!(3, 3), PaddingSpec::Valid, None, None, Some(3));
+    let conv =
+        ConvUnary::new(pool_spec, KernelFormat::OIHW, kernel.into_arc_tensor(), 1, None, None);
+    let y = model.wire_node("y", conv, &[x])?;
+    let a = Array2::from_shape_fn((4, 3), |(i, j)| (i * 3 + j) as f32 - 5.0)
+        .into_shape((1, 1, 4, 3))?
+        .into_arc_tensor();
+    let z = model.wire_node("z", MatMulUnary::new(a, false, false, false, None), &y)?;
+    model.set_output_outlets(&z)?;
+    Ok(model)
+}
+
+#[test]
+fn prepacked_round_trip() -> TractResult<()> {
+    let model = model()?;
+    let prepacked = model.prepack()?;
+    let nnef = tract_nnef::nnef().with_tract_core();
+    let buffer = nnef.write_to_tar(&prepacked, vec![])?;
+    let reloaded = nnef.model_for_read(&mut &*buffer)?;
+    assert!(reloaded
+        .nodes()
+        .iter()
+        .any(|n| n.op_as::<ConvUnary>().map(|op| op.prepacked.is_some()) == Some(true)));
+    assert!(reloaded
+        .nodes()
+        .iter()
+        .any(|n| n.op_as::<MatMulUnary>().map(|op| op.prepacked.is_some()) == Some(true)));
+
+    let input = Array4::from_shape_fn((1, 2, 5, 5), |(_, c, h, w)| (c * 25 + h * 5 + w) as f32);
+    let expected = SimplePlan::new(&model)?.run(tvec!(input.clone().into_tensor()))?;
+    let found = reloaded.optimize()?.into_runnable()?.run(tvec!(input.into_tensor()))?;
+    found[0].close_enough(&expected[0], true)?;
+    Ok(())
+}
+
+#[test]
+fn prepacked_dump_holds_packed_weights_only() -> TractResult<()> {
+    let model = model()?;
+    let nnef = tract_nnef::nnef().with_tract_core();
+    let buffer = nnef.write_to_tar(&model.prepack()?, vec![])?;
+    let proto = nnef.proto_model_for_read(&mut &*buffer)?;
+    assert!(!proto.tensors.is_empty());
+    assert!(proto.tensors.keys().all(|label| label.contains("_packed_")));
+
+    let reloaded = nnef.model_for_proto_model(&proto)?;
+    let kernel = |model: &TypedModel| -> Arc<Tensor> {
+        model.nodes().iter().find_map(|n| n.op_as::<ConvUnary>()).unwrap().kernel.clone()
+    };
+    let a = |model: &TypedModel| -> Arc<Tensor> {
+        model.nodes().iter().find_map(|n| n.op_as::<MatMulUnary>()).unwrap().a.clone()
+    };
+    assert_eq!(kernel(&reloaded).as_slice::<f32>()?, kernel(&model).as_slice::<f32>()?);
+    assert_eq!(a(&reloaded).as_slice::<f32>()?, a(&model).as_slice::<f32>()?);
+    Ok(())
+}
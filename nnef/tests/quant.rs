@@ -0,0 +1,129 @@
+use tract_core::internal::*;
+use tract_core::ndarray::{Array2, Array4};
+use tract_core::ops::cnn::{ConvUnary, KernelFormat, PaddingSpec, PoolSpec};
+use tract_core::ops::matmul::{MatMul, MatMulUnary};
+use tract_core::ops::nn::DataFormat;
+use tract_core::ops::{math, quant};
+use tract_nnef::ast::QuantFormat;
+
+fn quantized_model() -> TractResult<TypedModel> {
+    let mut model = TypedModel::default();
+    let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), &[2usize, 3][..])?)?;
+    let x = model.wire_node("x_q", quant::quantize_linear_u8(10.0, 5), &[x])?;
+    let x = model.wire_node("x_dq", quant::DequantizeLinearF32::new(0.1, 5), &[x[0]])?;
+    let w = model.add_const("w", tensor2(&[[1i8, -2, 3], [-4, 5, -6]]))?;
+    let w = model.wire_node("w_dq", quant::DequantizeLinearF32::new(0.5, 0), &[w])?;
+    let y = model.wire_node("y", math::add::bin_typed(), &[x[0], w[0]])?;
+    model.set_output_outlets(&y)?;
+    Ok(model)
+}
+
+#[test]
+fn quantized_model_round_trip() -> TractResult<()> {
+    let model = quantized_model()?;
+    let nnef = tract_nnef::nnef();
+    let buffer = nnef.write_to_tar(&model, vec![])?;
+    let proto = nnef.proto_model_for_read(&mut &*buffer)?;
+    let quantization = proto.quantization.as_ref().unwrap();
+    assert_eq!(
+        quantization["x_q"],
+        QuantFormat::Linear { zero_point: 5, scale: 0.1, bits: 8, signed: false }
+    );
+    assert_eq!(
+        quantization["w"],
+        QuantFormat::Linear { zero_point: 0, scale: 0.5, bits: 8, signed: true }
+    );
+    let reloaded = nnef.model_for_proto_model(&proto)?;
+    assert_eq!(reloaded.outlet_fact(reloaded.output_outlets()?[0])?.datum_type, f32::datum_type());
+    let x_q = reloaded.node_by_name("x_q.quantize")?;
+    assert_eq!(reloaded.outlet_fact(x_q.id.into())?.datum_type, u8::datum_type());
+    let w = reloaded.node_by_name("w")?;
+    assert_eq!(reloaded.outlet_fact(w.id.into())?.datum_type, i8::datum_type());
+
+    let input = tensor2(&[[0.12f32, -0.3, 1.0], [2.0, 30.0, -1.0]]);
+    let expected = SimplePlan::new(&model)?.run(tvec!(input.clone()))?;
+    let found = SimplePlan::new(&reloaded)?.run(tvec!(input))?;
+    found[0].close_enough(&expected[0], true)?;
+    Ok(())
+}
+
+#[test]
+fn out_of_range_zero_point_is_an_error() -> TractResult<()> {
+    let nnef = tract_nnef::nnef();
+    let buffer = nnef.write_to_tar(&quantized_model()?, vec![])?;
+    let mut proto = nnef.proto_model_for_read(&mut &*buffer)?;
+    proto.quantization.as_mut().unwrap().insert(
+        "x_q".to_string(),
+        QuantFormat::Linear { zero_point: 300, scale: 0.1, bits: 8, signed: false },
+    );
+    assert!(nnef.model_for_proto_model(&proto).is_err());
+    Ok(())
+}
+
+/// Runs `model` and its reload from NNEF on `input`, and returns the reloaded model.
+fn check_int8_round_trip(model: &TypedModel, input: Tensor) -> TractResult<TypedModel> {
+    let nnef = tract_nnef::nnef().with_tract_core();
+    let buffer = nnef.write_to_tar(model, vec![])?;
+    let reloaded = nnef.model_for_read(&mut &*buffer)?;
+    let expected = SimplePlan::new(model)?.run(tvec!(input.clone()))?;
+    let found = SimplePlan::new(&reloaded)?.run(tvec!(input))?;
+    found[0].close_enough(&expected[0], false)?;
+    let y_dq = reloaded.node_by_name("y_dq")?;
+    assert_eq!(reloaded.outlet_fact(y_dq.inputs[0])?.datum_type, i32::datum_type());
+    Ok(reloaded)
+}
+
+#[test]
+fn int8_matmul_round_trip() -> TractResult<()> {
+    let mut model = TypedModel::default();
+    let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), &[3usize, 4][..])?)?;
+    let x = model.wire_node("x_q", quant::quantize_linear_u8(4.0, 128), &[x])?;
+    let a = Array2::from_shape_fn((2, 3), |(i, j)| (i * 3 + j) as i8 - 3).into_arc_tensor();
+    let q_params = quant::QParams::new(i32::datum_type()).with_zero_point_b(&rctensor0(128u8));
+    let y = model.wire_node("y", MatMulUnary::new(a, false, false, false, Some(q_params)), &x)?;
+    let y = model.wire_node("y_dq", quant::DequantizeLinearF32::new(0.25, 0), &y)?;
+    model.set_output_outlets(&y)?;
+
+    let input = Array2::from_shape_fn((3, 4), |(i, j)| (i * 4 + j) as f32 - 6.0);
+    let reloaded = check_int8_round_trip(&model, input.into_tensor())?;
+    let op = reloaded.node_by_name("y_unquantized")?.op_as::<MatMul>().unwrap();
+    assert_eq!(op.q_params.as_ref().unwrap().zero_point_b, Some(rctensor0(128u8)));
+    assert_eq!(
+        reloaded.outlet_fact(reloaded.node_by_name("x_q.quantize")?.id.into())?.datum_type,
+        u8::datum_type()
+    );
+    Ok(())
+}
+
+#[test]
+fn int8_conv_round_trip() -> TractResult<()> {
+    let mut model = TypedModel::default();
+    let x =
+        model.add_source("x", TypedFact::dt_shape(f32::datum_type(), &[1usize, 2, 5, 5][..])?)?;
+    let x = model.wire_node("x_q", quant::quantize_linear_i8(4.0, 0), &[x])?;
+    let kernel = Array4::from_shape_fn((3, 2, 3, 3), |(o, i, h, w)| (o + 2 * i + h * w) as i8 - 4);
+    let bias = rctensor1(&[1i32, -2, 3]);
+    let pool_spec =
+        PoolSpec::new(DataFormat::NCHW, tvec!(3, 3), PaddingSpec::Valid, None, None, Some(3));
+    let q_params = quant::QParams::new(i32::datum_type());
+    let conv = ConvUnary::new(
+        pool_spec,
+        KernelFormat::OIHW,
+        kernel.into_arc_tensor(),
+        1,
+        Some(bias),
+        Some(q_params),
+    );
+    let y = model.wire_node("y", conv, &x)?;
+    let y = model.wire_node("y_dq", quant::DequantizeLinearF32::new(0.25, 0), &y)?;
+    model.set_output_outlets(&y)?;
+
+    let input =
+        Array4::from_shape_fn((1, 2, 5, 5), |(_, c, h, w)| (c * 25 + h * 5 + w) as f32 / 8.0);
+    let reloaded = check_int8_round_trip(&model, input.into_tensor())?;
+    let op = reloaded.node_by_name("y_unquantized")?.op_as::<ConvUnary>().unwrap();
+    assert_eq!(op.kernel.datum_type(), i8::datum_type());
+    assert_eq!(op.bias.as_ref().unwrap().datum_type(), i32::datum_type());
+    assert!(op.q_params.is_some());
+    Ok(())
+}
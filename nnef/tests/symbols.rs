@@ -0,0 +1,31 @@
+use tract_core::internal::*;
+
+#[test]
+fn symbolic_shapes_round_trip() -> TractResult<()> {
+    let mut table = SymbolTable::default();
+    let b: TDim = table.sym('B').into();
+    let s: TDim = table.sym('S').into();
+    let bs = b.maybe_mul(&s)?;
+    let mut model = TypedModel::default();
+    let x = model.add_source(
+        "x",
+        TypedFact::dt_shape(f32::datum_type(), &[b.clone(), s.clone(), 3.to_dim()][..])?,
+    )?;
+    let y = model.wire_node(
+        "y",
+        AxisOp::Reshape(0, tvec!(b.clone(), s.clone()), tvec!(bs.clone())),
+        &[x],
+    )?;
+    model.set_output_outlets(&y)?;
+
+    let nnef = tract_nnef::nnef().with_tract_core().with_symbol(table.sym('B'));
+    let buffer = nnef.write_to_tar(&model, vec![])?;
+    let reloaded = nnef.model_for_read(&mut &*buffer)?;
+    let input = reloaded.input_fact(0)?;
+    assert_eq!(input.shape[0], b);
+    assert_eq!(input.shape[1].symbols().len(), 1);
+    assert_ne!(input.shape[1], s);
+    let s = input.shape[1].clone();
+    assert_eq!(reloaded.output_fact(0)?.shape.to_tvec(), tvec!(b.maybe_mul(&s)?, 3.to_dim()));
+    Ok(())
+}
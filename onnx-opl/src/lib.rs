@@ -10,7 +10,9 @@ pub mod erf;
 pub mod is_inf;
 pub mod is_nan;
 pub mod lrn;
+pub mod non_max_suppression;
 pub mod one_hot;
+pub mod roi_align;
 
 pub trait WithOnnx {
     fn with_onnx(self) -> Self;
@@ -37,7 +39,18 @@ fn onnx_opl_registry() -> Registry {
     registry.register_unit_element_wise("tract_onnx_is_nan", &is_nan::IsNan {});
     registry.register_dumper(TypeId::of::<lrn::Lrn>(), lrn::dump);
     registry.register_primitive("tract_onnx_lrn", &lrn::parameters(), lrn::load);
+    registry.register_dumper(
+        TypeId::of::<non_max_suppression::NonMaxSuppression>(),
+        non_max_suppression::dump,
+    );
+    registry.register_primitive(
+        "tract_onnx_non_max_suppression",
+        &non_max_suppression::parameters(),
+        non_max_suppression::load,
+    );
     registry.register_dumper(TypeId::of::<one_hot::OneHot>(), one_hot::dump);
     registry.register_primitive("tract_onnx_one_hot", &one_hot::parameters(), one_hot::load);
+    registry.register_dumper(TypeId::of::<roi_align::RoiAlign>(), roi_align::dump);
+    registry.register_primitive("tract_onnx_roi_align", &roi_align::parameters(), roi_align::load);
     registry
 }
@@ -0,0 +1,287 @@
+use tract_nnef::internal::*;
+
+/// How boxes are encoded in the last axis of the boxes input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoxRepr {
+    /// [y1, x1, y2, x2], with any pair of diagonal corners.
+    TwoPoints,
+    /// [x_center, y_center, width, height]
+    CenterWidthHeight,
+}
+
+impl BoxRepr {
+    /// Box as (y_min, x_min, y_max, x_max).
+    fn corners(&self, b: &[f32]) -> [f32; 4] {
+        match self {
+            BoxRepr::TwoPoints => [b[0].min(b[2]), b[1].min(b[3]), b[0].max(b[2]), b[1].max(b[3])],
+            BoxRepr::CenterWidthHeight => {
+                [b[1] - b[3] / 2.0, b[0] - b[2] / 2.0, b[1] + b[3] / 2.0, b[0] + b[2] / 2.0]
+            }
+        }
+    }
+}
+
+/// Total order on scores, NaN being the lowest.
+fn score_order(a: f32, b: f32) -> std::cmp::Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => std::cmp::Ordering::Equal,
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+fn iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    let area_a = (a[2] - a[0]) * (a[3] - a[1]);
+    let area_b = (b[2] - b[0]) * (b[3] - b[1]);
+    if area_a <= 0.0 || area_b <= 0.0 {
+        return 0.0;
+    }
+    let height = (a[2].min(b[2]) - a[0].max(b[0])).max(0.0);
+    let width = (a[3].min(b[3]) - a[1].max(b[1])).max(0.0);
+    let intersection = height * width;
+    intersection / (area_a + area_b - intersection)
+}
+
+/// Greedy non-maximum suppression, per batch and class.
+///
+/// Inputs are boxes [batch, boxes, 4], scores [batch, classes, boxes],
+/// max_output_boxes_per_class, iou_threshold and score_threshold scalars. The
+/// output is a [selected, 3] tensor of (batch, class, box) indices: its first
+/// dimension depends on the data, and is represented by a symbol that gets
+/// resolved in the session at run time.
+#[derive(Debug, Clone, Hash)]
+pub struct NonMaxSuppression {
+    pub center_point_box: BoxRepr,
+    pub num_selected_indices_symbol: Symbol,
+    pub has_score_threshold: bool,
+}
+
+tract_linalg::impl_dyn_hash!(NonMaxSuppression);
+
+impl NonMaxSuppression {
+    fn select(&self, inputs: &[Arc<Tensor>]) -> TractResult<Tensor> {
+        let boxes = inputs[0].cast_to::<f32>()?;
+        let boxes = boxes.to_array_view::<f32>()?;
+        let scores = inputs[1].cast_to::<f32>()?;
+        let scores = scores.to_array_view::<f32>()?;
+        let max_output_boxes = inputs[2].cast_to_scalar::<i64>()?.max(0) as usize;
+        let iou_threshold = inputs[3].cast_to_scalar::<f32>()?;
+        let score_threshold = inputs[4].cast_to_scalar::<f32>()?;
+        if boxes.ndim() != 3 || boxes.shape()[2] != 4 {
+            bail!("Expected boxes of shape [batch, boxes, 4], got {:?}", boxes.shape())
+        }
+        let (batches, num_boxes) = (boxes.shape()[0], boxes.shape()[1]);
+        if scores.shape() != [batches, scores.shape()[1], num_boxes] {
+            bail!("Inconsistent boxes {:?} and scores {:?}", boxes.shape(), scores.shape())
+        }
+        let mut selected: Vec<i64> = vec![];
+        for batch in 0..batches {
+            let corners: Vec<[f32; 4]> = (0..num_boxes)
+                .map(|b| {
+                    let b = boxes.slice(tract_ndarray::s![batch, b, ..]);
+                    self.center_point_box.corners(b.as_slice().unwrap())
+                })
+                .collect();
+            for class in 0..scores.shape()[1] {
+                let scores = scores.slice(tract_ndarray::s![batch, class, ..]);
+                let mut candidates: Vec<usize> = (0..num_boxes)
+                    .filter(|&b| !self.has_score_threshold || scores[b] > score_threshold)
+                    .collect();
+                // best scores first, NaN scores last
+                candidates.sort_by(|&a, &b| score_order(scores[b], scores[a]));
+                let mut kept: Vec<usize> = vec![];
+                for candidate in candidates {
+                    if kept.len() >= max_output_boxes {
+                        break;
+                    }
+                    if kept.iter().all(|&k| iou(&corners[k], &corners[candidate]) <= iou_threshold)
+                    {
+                        kept.push(candidate);
+                        selected.extend(&[batch as i64, class as i64, candidate as i64]);
+                    }
+                }
+            }
+        }
+        let len = selected.len() / 3;
+        Ok(tract_ndarray::Array2::from_shape_vec((len, 3), selected)?.into_tensor())
+    }
+}
+
+impl Op for NonMaxSuppression {
+    fn name(&self) -> Cow<str> {
+        "NonMaxSuppression".into()
+    }
+
+    op_onnx!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for NonMaxSuppression {
+    fn is_stateless(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        Ok(tvec!(self.select(&inputs)?.into_arc_tensor()))
+    }
+
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(NonMaxSuppressionState)))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct NonMaxSuppressionState;
+
+impl OpState for NonMaxSuppressionState {
+    fn eval(
+        &mut self,
+        session: &mut SessionState,
+        op: &dyn Op,
+        inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let op = op.downcast_ref::<NonMaxSuppression>().unwrap();
+        let selected = op.select(&inputs)?;
+        session.resolved_symbols[op.num_selected_indices_symbol] = Some(selected.shape()[0] as i64);
+        Ok(tvec!(selected.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for NonMaxSuppression {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs.len() != 5 {
+            bail!("Expected 5 inputs, got {}", inputs.len())
+        }
+        let shape = tvec!(self.num_selected_indices_symbol.into(), 3.to_dim());
+        Ok(tvec!(TypedFact::dt_shape(i64::datum_type(), &*shape)?))
+    }
+}
+
+pub fn parameters() -> Vec<Parameter> {
+    vec![
+        TypeName::Scalar.tensor().named("boxes"),
+        TypeName::Scalar.tensor().named("scores"),
+        TypeName::Integer.tensor().named("max_output_boxes_per_class"),
+        TypeName::Scalar.tensor().named("iou_threshold"),
+        TypeName::Scalar.tensor().named("score_threshold"),
+        TypeName::Integer.named("center_point_box").default(0),
+        TypeName::Logical.named("has_score_threshold").default(true),
+    ]
+}
+
+pub fn dump(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<NonMaxSuppression>().unwrap();
+    let inputs: TVec<Arc<RValue>> = node.inputs.iter().map(|i| ast.mapping[i].clone()).collect();
+    let center_point_box = match op.center_point_box {
+        BoxRepr::TwoPoints => 0,
+        BoxRepr::CenterWidthHeight => 1,
+    };
+    Ok(Some(invocation(
+        "tract_onnx_non_max_suppression",
+        &inputs,
+        &[
+            ("center_point_box", numeric(center_point_box)),
+            ("has_score_threshold", logical(op.has_score_threshold)),
+        ],
+    )))
+}
+
+pub fn load(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let boxes = invocation.named_arg_as(builder, "boxes")?;
+    let scores = invocation.named_arg_as(builder, "scores")?;
+    let max_output_boxes_per_class =
+        invocation.named_arg_as(builder, "max_output_boxes_per_class")?;
+    let iou_threshold = invocation.named_arg_as(builder, "iou_threshold")?;
+    let score_threshold = invocation.named_arg_as(builder, "score_threshold")?;
+    let center_point_box = match invocation.named_arg_as::<i64>(builder, "center_point_box")? {
+        0 => BoxRepr::TwoPoints,
+        1 => BoxRepr::CenterWidthHeight,
+        other => bail!("Unsupported center_point_box: {}", other),
+    };
+    let has_score_threshold = invocation.named_arg_as(builder, "has_score_threshold")?;
+    // a new symbol, so that it does not clash with the ones named in the graph
+    let op = NonMaxSuppression {
+        center_point_box,
+        num_selected_indices_symbol: builder.symbols.new_symbol('n'),
+        has_score_threshold,
+    };
+    builder.wire(op, &[boxes, scores, max_output_boxes_per_class, iou_threshold, score_threshold])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppress_by_iou() {
+        let op = NonMaxSuppression {
+            center_point_box: BoxRepr::TwoPoints,
+            num_selected_indices_symbol: Symbol::new('n'),
+            has_score_threshold: false,
+        };
+        let boxes = rctensor3(&[[
+            [0.0f32, 0.0, 1.0, 1.0],
+            [0.0, 0.1, 1.0, 1.1],
+            [0.0, -0.1, 1.0, 0.9],
+            [0.0, 10.0, 1.0, 11.0],
+        ]]);
+        let scores = rctensor3(&[[[0.9f32, 0.75, 0.6, 0.95]]]);
+        let output = op
+            .eval(tvec!(boxes, scores, rctensor0(3i64), rctensor0(0.5f32), rctensor0(0f32)))
+            .unwrap();
+        assert_eq!(*output[0], tensor2(&[[0i64, 0, 3], [0, 0, 0]]));
+    }
+
+    #[test]
+    fn selection_count_resolved_in_session() -> TractResult<()> {
+        use tract_nnef::tract_core::ops::identity::Identity;
+        let op = NonMaxSuppression {
+            center_point_box: BoxRepr::TwoPoints,
+            num_selected_indices_symbol: Symbol::new('n'),
+            has_score_threshold: false,
+        };
+        let mut model = TypedModel::default();
+        let boxes = model
+            .add_source("boxes", TypedFact::dt_shape(f32::datum_type(), &[1usize, 2, 4][..])?)?;
+        let scores = model
+            .add_source("scores", TypedFact::dt_shape(f32::datum_type(), &[1usize, 1, 2][..])?)?;
+        let max = model.add_const("max", rctensor0(2i64))?;
+        let iou = model.add_const("iou", rctensor0(0.5f32))?;
+        let score = model.add_const("score", rctensor0(0f32))?;
+        let selected = model.wire_node("nms", op, &[boxes, scores, max, iou, score])?;
+        // a successor, so that the selection is checked against its fact
+        let output = model.wire_node("output", Identity, &selected)?;
+        model.set_output_outlets(&output)?;
+        let boxes = tensor3(&[[[0.0f32, 0.0, 1.0, 1.0], [0.0, 0.1, 1.0, 1.1]]]);
+        let scores = tensor3(&[[[0.9f32, 0.75]]]);
+        let output = SimplePlan::new(&model)?.run(tvec!(boxes, scores))?;
+        assert_eq!(*output[0], tensor2(&[[0i64, 0, 0]]));
+        Ok(())
+    }
+
+    #[test]
+    fn nan_scores_come_last() {
+        let op = NonMaxSuppression {
+            center_point_box: BoxRepr::TwoPoints,
+            num_selected_indices_symbol: Symbol::new('n'),
+            has_score_threshold: false,
+        };
+        let boxes =
+            rctensor3(&[[[0.0f32, 0.0, 1.0, 1.0], [0.0, 10.0, 1.0, 11.0], [0.0, 20.0, 1.0, 21.0]]]);
+        let scores = rctensor3(&[[[std::f32::NAN, 0.5, 0.9]]]);
+        let output = op
+            .eval(tvec!(boxes, scores, rctensor0(3i64), rctensor0(0.5f32), rctensor0(0f32)))
+            .unwrap();
+        assert_eq!(*output[0], tensor2(&[[0i64, 0, 2], [0, 0, 1], [0, 0, 0]]));
+    }
+}
@@ -66,8 +66,12 @@ impl OneHot {
         for icoord in tract_ndarray::indices_of(&input) {
             let mut ocoord: Vec<usize> = icoord.slice().into();
             let coord = input[&icoord];
-            let coord = if coord < 0 { coord + self.dim as i32 } else { coord } as usize;
-            ocoord.insert(self.axis, coord);
+            let coord = if coord < 0 { coord + self.dim as i32 } else { coord };
+            // out of range indices get an all-off representation
+            if coord < 0 || coord as usize >= self.dim {
+                continue;
+            }
+            ocoord.insert(self.axis, coord as usize);
             array[&*ocoord] = on.clone();
         }
         Ok(())
@@ -0,0 +1,229 @@
+use tract_ndarray::prelude::*;
+use tract_nnef::internal::*;
+use tract_nnef::ser::string;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoiAlignMode {
+    Avg,
+    Max,
+}
+
+/// Region of interest pooling with bilinear sampling, on NCHW inputs.
+///
+/// Inputs are the feature map, rois [rois, 4] as (x1, y1, x2, y2) in input
+/// image coordinates, and the batch index of each roi.
+#[derive(Debug, Clone, Educe)]
+#[educe(Hash)]
+pub struct RoiAlign {
+    pub mode: RoiAlignMode,
+    pub output_height: usize,
+    pub output_width: usize,
+    pub sampling_ratio: usize,
+    #[educe(Hash(method = "hash_f32"))]
+    pub spatial_scale: f32,
+}
+
+tract_linalg::impl_dyn_hash!(RoiAlign);
+
+impl RoiAlign {
+    /// Bilinear sample of a single channel plane, as (value, weight) pairs.
+    fn sample(plane: &ArrayView2<f32>, y: f32, x: f32) -> [(f32, f32); 4] {
+        let (height, width) = (plane.shape()[0], plane.shape()[1]);
+        if y < -1.0 || y > height as f32 || x < -1.0 || x > width as f32 {
+            return [(0.0, 0.0); 4];
+        }
+        let (mut y, mut x) = (y.max(0.0), x.max(0.0));
+        let (mut y_low, mut x_low) = (y as usize, x as usize);
+        let (y_high, x_high);
+        if y_low >= height - 1 {
+            y_low = height - 1;
+            y_high = y_low;
+            y = y_low as f32;
+        } else {
+            y_high = y_low + 1;
+        }
+        if x_low >= width - 1 {
+            x_low = width - 1;
+            x_high = x_low;
+            x = x_low as f32;
+        } else {
+            x_high = x_low + 1;
+        }
+        let (ly, lx) = (y - y_low as f32, x - x_low as f32);
+        let (hy, hx) = (1.0 - ly, 1.0 - lx);
+        [
+            (plane[(y_low, x_low)], hy * hx),
+            (plane[(y_low, x_high)], hy * lx),
+            (plane[(y_high, x_low)], ly * hx),
+            (plane[(y_high, x_high)], ly * lx),
+        ]
+    }
+
+    fn eval_f32(
+        &self,
+        input: ArrayView4<f32>,
+        rois: ArrayView2<f32>,
+        batch_indices: &[i64],
+    ) -> TractResult<Array4<f32>> {
+        let channels = input.shape()[1];
+        let mut output =
+            Array4::zeros((rois.shape()[0], channels, self.output_height, self.output_width));
+        for (ix, roi) in rois.outer_iter().enumerate() {
+            let batch = batch_indices[ix];
+            if batch < 0 || batch as usize >= input.shape()[0] {
+                bail!("Batch index {} out of bounds", batch)
+            }
+            let start_x = roi[0] * self.spatial_scale;
+            let start_y = roi[1] * self.spatial_scale;
+            let roi_width = (roi[2] * self.spatial_scale - start_x).max(1.0);
+            let roi_height = (roi[3] * self.spatial_scale - start_y).max(1.0);
+            let bin_height = roi_height / self.output_height as f32;
+            let bin_width = roi_width / self.output_width as f32;
+            let grid_h = if self.sampling_ratio > 0 {
+                self.sampling_ratio
+            } else {
+                bin_height.ceil() as usize
+            };
+            let grid_w = if self.sampling_ratio > 0 {
+                self.sampling_ratio
+            } else {
+                bin_width.ceil() as usize
+            };
+            let count = (grid_h * grid_w).max(1) as f32;
+            for c in 0..channels {
+                let plane = input.slice(s![batch as usize, c, .., ..]);
+                for ph in 0..self.output_height {
+                    for pw in 0..self.output_width {
+                        let mut acc: Option<f32> = None;
+                        for iy in 0..grid_h {
+                            let y = start_y
+                                + ph as f32 * bin_height
+                                + (iy as f32 + 0.5) * bin_height / grid_h as f32;
+                            for ix in 0..grid_w {
+                                let x = start_x
+                                    + pw as f32 * bin_width
+                                    + (ix as f32 + 0.5) * bin_width / grid_w as f32;
+                                let samples = Self::sample(&plane, y, x);
+                                let value = match self.mode {
+                                    RoiAlignMode::Avg => samples.iter().map(|(v, w)| v * w).sum(),
+                                    RoiAlignMode::Max => samples
+                                        .iter()
+                                        .map(|(v, w)| v * w)
+                                        .fold(std::f32::MIN, f32::max),
+                                };
+                                acc = Some(match (self.mode, acc) {
+                                    (_, None) => value,
+                                    (RoiAlignMode::Avg, Some(acc)) => acc + value,
+                                    (RoiAlignMode::Max, Some(acc)) => acc.max(value),
+                                });
+                            }
+                        }
+                        let acc = acc.unwrap_or(0.0);
+                        output[(ix, c, ph, pw)] =
+                            if self.mode == RoiAlignMode::Avg { acc / count } else { acc };
+                    }
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+impl Op for RoiAlign {
+    fn name(&self) -> Cow<str> {
+        "RoiAlign".into()
+    }
+
+    op_onnx!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for RoiAlign {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (input, rois, batch_indices) = args_3!(inputs);
+        let dt = input.datum_type();
+        let input = input.cast_to::<f32>()?;
+        let rois = rois.cast_to::<f32>()?;
+        let batch_indices = batch_indices.cast_to::<i64>()?;
+        let output = self.eval_f32(
+            input.to_array_view::<f32>()?.into_dimensionality()?,
+            rois.to_array_view::<f32>()?.into_dimensionality()?,
+            batch_indices.as_slice::<i64>()?,
+        )?;
+        Ok(tvec!(output.into_tensor().cast_to_dt(dt)?.into_owned().into_arc_tensor()))
+    }
+}
+
+impl TypedOp for RoiAlign {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs[0].rank() != 4 || inputs[1].rank() != 2 {
+            bail!("Expected NCHW input and [rois, 4] rois, got {:?} and {:?}", inputs[0], inputs[1])
+        }
+        let shape = tvec!(
+            inputs[1].shape[0].clone(),
+            inputs[0].shape[1].clone(),
+            self.output_height.to_dim(),
+            self.output_width.to_dim()
+        );
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+}
+
+pub fn parameters() -> Vec<Parameter> {
+    vec![
+        TypeName::Scalar.tensor().named("input"),
+        TypeName::Scalar.tensor().named("rois"),
+        TypeName::Integer.tensor().named("batch_indices"),
+        TypeName::String.named("mode").default("avg"),
+        TypeName::Integer.named("output_height").default(1),
+        TypeName::Integer.named("output_width").default(1),
+        TypeName::Integer.named("sampling_ratio").default(0),
+        TypeName::Scalar.named("spatial_scale").default(1.0),
+    ]
+}
+
+pub fn dump(ast: &mut IntoAst, node: &TypedNode) -> TractResult<Option<Arc<RValue>>> {
+    let op = node.op_as::<RoiAlign>().unwrap();
+    let inputs: TVec<Arc<RValue>> = node.inputs.iter().map(|i| ast.mapping[i].clone()).collect();
+    let mode = match op.mode {
+        RoiAlignMode::Avg => "avg",
+        RoiAlignMode::Max => "max",
+    };
+    Ok(Some(invocation(
+        "tract_onnx_roi_align",
+        &inputs,
+        &[
+            ("mode", string(mode)),
+            ("output_height", numeric(op.output_height)),
+            ("output_width", numeric(op.output_width)),
+            ("sampling_ratio", numeric(op.sampling_ratio)),
+            ("spatial_scale", numeric(op.spatial_scale)),
+        ],
+    )))
+}
+
+pub fn load(
+    builder: &mut ModelBuilder,
+    invocation: &ResolvedInvocation,
+) -> TractResult<TVec<OutletId>> {
+    let input = invocation.named_arg_as(builder, "input")?;
+    let rois = invocation.named_arg_as(builder, "rois")?;
+    let batch_indices = invocation.named_arg_as(builder, "batch_indices")?;
+    let mode = match &*invocation.named_arg_as::<String>(builder, "mode")? {
+        "avg" => RoiAlignMode::Avg,
+        "max" => RoiAlignMode::Max,
+        s => bail!("Unknown RoiAlign mode {}", s),
+    };
+    let output_height = invocation.named_arg_as(builder, "output_height")?;
+    let output_width = invocation.named_arg_as(builder, "output_width")?;
+    let sampling_ratio = invocation.named_arg_as(builder, "sampling_ratio")?;
+    let spatial_scale = invocation.named_arg_as(builder, "spatial_scale")?;
+    let op = RoiAlign { mode, output_height, output_width, sampling_ratio, spatial_scale };
+    builder.wire(op, &[input, rois, batch_indices])
+}
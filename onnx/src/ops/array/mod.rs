@@ -3,6 +3,8 @@ mod nonzero;
 mod one_hot;
 mod pad;
 mod slice;
+mod topk;
+mod trilu;
 
 use tract_hir::internal::*;
 use tract_hir::ops::array;
@@ -16,18 +18,29 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("ConstantLike", constant_like);
     reg.insert("ConstantOfShape", constant_of_shape);
     reg.insert("Expand", |_, _| Ok((expand(array::MultiBroadcastTo::default()), vec![])));
+    reg.insert("DepthToSpace", depth_to_space);
     reg.insert("EyeLike", eye_like);
     reg.insert("Flatten", flatten);
     reg.insert("Gather", gather);
+    reg.insert("GatherElements", gather_elements);
+    reg.insert("GatherND", gather_nd);
     reg.insert("NonZero", |_, _| Ok((Box::new(nonzero::NonZero), vec![])));
     reg.insert("OneHot", one_hot::one_hot);
     reg.insert("Pad", pad::pad);
+    reg.insert("Range", |_, _| Ok((Box::new(array::Range), vec![])));
     reg.insert("Reshape", |_, _| Ok((expand(array::Reshape::default()), vec![])));
+    reg.insert("ReverseSequence", reverse_sequence);
+    reg.insert("Scatter", scatter_elements);
+    reg.insert("ScatterElements", scatter_elements);
+    reg.insert("ScatterND", |_, _| Ok((Box::new(array::ScatterNd), vec![])));
     reg.insert("Shape", |_, _| Ok((expand(array::Shape::new(DatumType::I64)), vec![])));
     reg.insert("Size", |_, _| Ok((expand(array::Size::new(DatumType::I64)), vec![])));
     reg.insert("Transpose", transpose);
     reg.insert("Tile", |_, _| Ok((expand(array::Tile::default()), vec![])));
+    reg.insert("TopK", topk::topk);
+    reg.insert("Trilu", trilu::trilu);
     reg.insert("Slice", slice::slice);
+    reg.insert("SpaceToDepth", space_to_depth);
     reg.insert("Split", split);
     reg.insert("Squeeze", squeeze);
     reg.insert("Unsqueeze", unsqueeze);
@@ -74,6 +87,19 @@ pub fn constant_of_shape(
     Ok((expand(array::ConstantOfShape::new(value)), vec![]))
 }
 
+pub fn depth_to_space(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let block_size = node.get_attr("blocksize")?;
+    let mode = match node.get_attr_opt("mode")?.unwrap_or("DCR") {
+        "DCR" => array::DepthToSpaceMode::Dcr,
+        "CRD" => array::DepthToSpaceMode::Crd,
+        mode => bail!("Unsupported DepthToSpace mode {}", mode),
+    };
+    Ok((Box::new(array::DepthToSpace::new(block_size, mode)), vec![]))
+}
+
 pub fn eye_like(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -99,6 +125,47 @@ pub fn gather(
     Ok((Box::new(array::Gather::new(axis)), vec![]))
 }
 
+pub fn gather_elements(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let axis = node.get_attr_opt("axis")?.unwrap_or(0i64);
+    Ok((expand(array::GatherElements::new(axis)), vec![]))
+}
+
+pub fn gather_nd(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let batch_dims = node.get_attr_opt("batch_dims")?.unwrap_or(0);
+    Ok((Box::new(array::GatherNd::new(batch_dims)), vec![]))
+}
+
+pub fn reverse_sequence(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let batch_axis = node.get_attr_opt("batch_axis")?.unwrap_or(1);
+    let time_axis = node.get_attr_opt("time_axis")?.unwrap_or(0);
+    Ok((Box::new(array::ReverseSequence::new(batch_axis, time_axis)), vec![]))
+}
+
+pub fn scatter_elements(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let axis = node.get_attr_opt("axis")?.unwrap_or(0i64);
+    Ok((expand(array::ScatterElements::new(axis)), vec![]))
+}
+
+pub fn space_to_depth(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let block_size = node.get_attr("blocksize")?;
+    Ok((Box::new(array::SpaceToDepth::new(block_size)), vec![]))
+}
+
 pub fn split(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -0,0 +1,84 @@
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::array;
+
+use crate::model::ParsingContext;
+use crate::pb::NodeProto;
+
+pub fn topk(
+    ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let axis = node.get_attr_opt("axis")?.unwrap_or(-1);
+    let largest = node.get_attr_opt("largest")?.unwrap_or(true);
+    let k = if ctx.onnx_operator_set_version < 10 { Some(node.get_attr("k")?) } else { None };
+    Ok((expand(TopK::new(axis, largest, k)), vec![]))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+struct TopK {
+    axis: i64,
+    largest: bool,
+    k: Option<usize>,
+}
+
+tract_linalg::impl_dyn_hash!(TopK);
+
+impl Expansion for TopK {
+    fn name(&self) -> Cow<str> {
+        "TopK".into()
+    }
+
+    op_onnx!();
+
+    fn nboutputs(&self) -> TractResult<usize> {
+        Ok(2)
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let rank = model.outlet_fact(inputs[0])?.rank() as i64;
+        let axis = if self.axis < 0 { self.axis + rank } else { self.axis } as usize;
+        let k = if let Some(k) = self.k {
+            k
+        } else if let Some(k) = &model.outlet_fact(inputs[1])?.konst {
+            k.cast_to::<i64>()?.as_slice::<i64>()?[0] as usize
+        } else {
+            bail!("TopK expects k to be a constant")
+        };
+        model.wire_node(prefix, array::TopK::new(axis, k, self.largest), &[inputs[0]])
+    }
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, if self.k.is_some() { 1 } else { 2 })?;
+        check_output_arity(&outputs, 2)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&outputs[1].datum_type, i64::datum_type())?;
+        s.equals(&inputs[0].rank, &outputs[0].rank)?;
+        s.equals(&inputs[0].rank, &outputs[1].rank)?;
+        s.given(&inputs[0].rank, move |s, rank| {
+            let axis = if self.axis < 0 { self.axis + rank } else { self.axis } as usize;
+            for ix in (0..rank as usize).filter(|&ix| ix != axis) {
+                s.equals(&inputs[0].shape[ix], &outputs[0].shape[ix])?;
+                s.equals(&inputs[0].shape[ix], &outputs[1].shape[ix])?;
+            }
+            s.equals(&outputs[0].shape[axis], &outputs[1].shape[axis])?;
+            if let Some(k) = self.k {
+                s.equals(&outputs[0].shape[axis], k.to_dim())
+            } else {
+                s.given(&inputs[1].value, move |s, k| {
+                    let k = k.cast_to::<i64>()?.as_slice::<i64>()?[0];
+                    s.equals(&outputs[0].shape[axis], k.to_dim())
+                })
+            }
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::array;
+
+use crate::model::ParsingContext;
+use crate::pb::NodeProto;
+
+pub fn trilu(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let upper = node.get_attr_opt("upper")?.unwrap_or(true);
+    Ok((expand(Trilu::new(upper)), vec![]))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+struct Trilu {
+    upper: bool,
+}
+
+tract_linalg::impl_dyn_hash!(Trilu);
+
+impl Expansion for Trilu {
+    fn name(&self) -> Cow<str> {
+        "Trilu".into()
+    }
+
+    op_onnx!();
+
+    fn wire(
+        &self,
+        prefix: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let k = if let Some(k) = inputs.get(1) {
+            if let Some(k) = &model.outlet_fact(*k)?.konst {
+                k.cast_to_scalar::<i64>()?
+            } else {
+                bail!("Trilu expects k to be a constant")
+            }
+        } else {
+            0
+        };
+        model.wire_node(prefix, array::Trilu::new(self.upper, k), &[inputs[0]])
+    }
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        if inputs.len() != 1 && inputs.len() != 2 {
+            bail!("Trilu expects one or two inputs, got {}", inputs.len())
+        }
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        if inputs.len() == 2 {
+            s.equals(&inputs[1].rank, 0)?;
+        }
+        Ok(())
+    }
+}
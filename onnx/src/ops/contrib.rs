@@ -0,0 +1,359 @@
+//! Operators from the com.microsoft domain emitted by onnxruntime's
+//! transformer optimizers.
+use crate::model::{optional_inputs, OnnxOpRegister, ParsingContext};
+use crate::pb::NodeProto;
+use tract_hir::internal::*;
+use tract_hir::ops;
+use tract_hir::ops::binary::wire_rank_broadcast;
+use tract_hir::tract_core::ops::cast::cast;
+use tract_hir::tract_core::ops::nn as core_nn;
+
+use super::nn::layer_norm::wire_layer_norm;
+
+pub fn register_all_ops(reg: &mut OnnxOpRegister) {
+    reg.insert("Attention", attention);
+    reg.insert("BiasGelu", |_, _| Ok((expand(BiasGelu::new(false)), vec![])));
+    reg.insert("FastGelu", fast_gelu);
+    reg.insert("Gelu", gelu);
+    reg.insert("SkipLayerNormalization", skip_layer_normalization);
+}
+
+fn check_single_output(node: &NodeProto) -> TractResult<()> {
+    if node.output.iter().skip(1).any(|s| !s.is_empty()) {
+        bail!("Only the first output of {} is supported", node.op_type)
+    }
+    Ok(())
+}
+
+fn gelu(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let approximate = node.get_attr_opt("approximate")?.unwrap_or("none");
+    let approximate = node.check_value(
+        "approximate",
+        match approximate {
+            "none" => Ok(false),
+            "tanh" => Ok(true),
+            _ => Err(approximate),
+        },
+    )?;
+    Ok((Box::new(core_nn::gelu(approximate)), vec![]))
+}
+
+fn fast_gelu(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    if optional_inputs(node).nth(1).unwrap().is_some() {
+        Ok((expand(BiasGelu::new(true)), vec![]))
+    } else {
+        Ok((Box::new(core_nn::gelu(true)), vec![]))
+    }
+}
+
+/// Gelu applied to the sum of the input and a bias.
+#[derive(Debug, Clone, new, Hash)]
+pub struct BiasGelu {
+    approximate: bool,
+}
+
+tract_linalg::impl_dyn_hash!(BiasGelu);
+
+impl Expansion for BiasGelu {
+    fn name(&self) -> Cow<str> {
+        "BiasGelu".into()
+    }
+
+    op_onnx!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(inputs, 2)?;
+        check_output_arity(outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[1].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        Ok(())
+    }
+
+    fn wire(
+        &self,
+        name: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let wires = wire_rank_broadcast(name, model, inputs)?;
+        let wire =
+            model.wire_node(format!("{}.bias", name), ops::math::add::bin_typed(), &wires)?;
+        model.wire_node(name, core_nn::gelu(self.approximate), &wire)
+    }
+}
+
+fn skip_layer_normalization(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    check_single_output(node)?;
+    let epsilon = node.get_attr_opt("epsilon")?.unwrap_or(1e-12);
+    let mut options = optional_inputs(node).skip(3);
+    let op = SkipLayerNorm::new(epsilon, options.next().unwrap(), options.next().unwrap());
+    Ok((expand(op), vec![]))
+}
+
+/// Layer normalization over the last axis of `input + skip (+ bias)`.
+#[derive(Debug, Clone, new, Educe)]
+#[educe(Hash)]
+pub struct SkipLayerNorm {
+    #[educe(Hash(method = "hash_f32"))]
+    epsilon: f32,
+    optional_beta_input: Option<usize>,
+    optional_bias_input: Option<usize>,
+}
+
+tract_linalg::impl_dyn_hash!(SkipLayerNorm);
+
+impl Expansion for SkipLayerNorm {
+    fn name(&self) -> Cow<str> {
+        "SkipLayerNorm".into()
+    }
+
+    op_onnx!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(
+            inputs,
+            3 + self.optional_beta_input.is_some() as usize
+                + self.optional_bias_input.is_some() as usize,
+        )?;
+        check_output_arity(outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+        }
+        s.equals(&inputs[0].shape, &inputs[1].shape)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        Ok(())
+    }
+
+    fn wire(
+        &self,
+        name: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let rank = model.outlet_fact(inputs[0])?.rank();
+        let mut wire = model.wire_node(
+            format!("{}.skip", name),
+            ops::math::add::bin_typed(),
+            &inputs[0..2],
+        )?[0];
+        if let Some(bias) = self.optional_bias_input {
+            let wires =
+                wire_rank_broadcast(&format!("{}.input-bias", name), model, &[wire, inputs[bias]])?;
+            wire = model.wire_node(
+                format!("{}.input-bias", name),
+                ops::math::add::bin_typed(),
+                &wires,
+            )?[0];
+        }
+        let beta = self.optional_beta_input.map(|ix| inputs[ix]);
+        Ok(tvec!(wire_layer_norm(
+            name,
+            model,
+            wire,
+            rank - 1,
+            self.epsilon,
+            Some(inputs[2]),
+            beta
+        )?))
+    }
+}
+
+fn attention(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    check_single_output(node)?;
+    if node.input.iter().skip(4).any(|s| !s.is_empty()) {
+        bail!("Attention with past state or extra inputs is not supported")
+    }
+    if node.get_attr_opt("unidirectional")?.unwrap_or(0i64) != 0 {
+        bail!("Unidirectional Attention is not supported")
+    }
+    if node.get_attr_opt_vec::<i64>("qkv_hidden_sizes")?.is_some() {
+        bail!("Attention with qkv_hidden_sizes is not supported")
+    }
+    let num_heads = node.get_attr::<i64>("num_heads")?;
+    node.expect_attr("num_heads", num_heads > 0, "strictly positive")?;
+    let scale = node.get_attr_opt("scale")?;
+    let mask = optional_inputs(node).nth(3).unwrap();
+    Ok((expand(Attention::new(num_heads as usize, scale, mask)), vec![]))
+}
+
+/// Multi-head self attention with packed query, key and value weights.
+///
+/// Inputs are `input [B, S, H]`, `weights [H, 3H]`, `bias [3H]` and an
+/// optional `mask_index` of ones and zeros, `[B, S]` or `[B, S, S]`.
+#[derive(Debug, Clone, new, Educe)]
+#[educe(Hash)]
+pub struct Attention {
+    num_heads: usize,
+    #[educe(Hash(method = "hash_opt_f32"))]
+    scale: Option<f32>,
+    optional_mask_input: Option<usize>,
+}
+
+tract_linalg::impl_dyn_hash!(Attention);
+
+impl Expansion for Attention {
+    fn name(&self) -> Cow<str> {
+        "Attention".into()
+    }
+
+    op_onnx!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(inputs, 3 + self.optional_mask_input.is_some() as usize)?;
+        check_output_arity(outputs, 1)?;
+        for input in &inputs[0..3] {
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+        }
+        s.equals(&inputs[0].rank, 3)?;
+        s.equals(&inputs[1].rank, 2)?;
+        s.equals(&inputs[2].rank, 1)?;
+        s.equals(&inputs[0].shape[2], &inputs[1].shape[0])?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        Ok(())
+    }
+
+    fn wire(
+        &self,
+        name: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        use tract_hir::tract_core::ops::matmul::MatMul;
+        let fact = model.outlet_fact(inputs[0])?.clone();
+        let hidden = fact.shape[2].to_usize()?;
+        let weights = model.outlet_fact(inputs[1])?.shape.to_tvec();
+        if weights[1] != (3 * hidden).to_dim() {
+            bail!(
+                "Attention expects weights of shape [{}, {}], got {:?}",
+                hidden,
+                3 * hidden,
+                weights
+            )
+        }
+        if hidden % self.num_heads != 0 {
+            bail!("Hidden size {} is not a multiple of num_heads {}", hidden, self.num_heads)
+        }
+        let head = hidden / self.num_heads;
+
+        let w = model.wire_node(format!("{}.weights", name), AxisOp::Add(0), &inputs[1..2])?;
+        let qkv =
+            model.wire_node(format!("{}.qkv", name), MatMul::default(), &[inputs[0], w[0]])?;
+        let mut bias = inputs[2];
+        for i in 0..2 {
+            bias =
+                model.wire_node(format!("{}.bias-axis-{}", name, i), AxisOp::Add(0), &[bias])?[0];
+        }
+        let qkv = model.wire_node(
+            format!("{}.qkv-bias", name),
+            ops::math::add::bin_typed(),
+            &[qkv[0], bias],
+        )?[0];
+
+        let mut heads = tvec!();
+        for (ix, part) in ["q", "k", "v"].iter().enumerate() {
+            let wire = model.wire_node(
+                format!("{}.{}", name, part),
+                ops::array::Slice::new(2, ix * hidden, (ix + 1) * hidden),
+                &[qkv],
+            )?;
+            let wire = model.wire_node(
+                format!("{}.{}-split-heads", name, part),
+                AxisOp::Reshape(
+                    2,
+                    tvec!(hidden.to_dim()),
+                    tvec!(self.num_heads.to_dim(), head.to_dim()),
+                ),
+                &wire,
+            )?;
+            let wire = model.wire_node(
+                format!("{}.{}-heads-first", name, part),
+                AxisOp::Move(2, 1),
+                &wire,
+            )?;
+            heads.push(wire[0]);
+        }
+
+        if let Some(mask) = self.optional_mask_input {
+            let mask_rank = model.outlet_fact(inputs[mask])?.rank();
+            if mask_rank != 2 && mask_rank != 3 {
+                bail!("Attention supports mask_index of rank 2 or 3, got rank {}", mask_rank)
+            }
+            let konst = |v: f32| -> TractResult<Arc<Tensor>> {
+                Ok(tensor0(v)
+                    .cast_to_dt(fact.datum_type)?
+                    .into_owned()
+                    .broadcast_into_rank(mask_rank)?
+                    .into_arc_tensor())
+            };
+            let mut wire = model.wire_node(
+                format!("{}.mask-cast", name),
+                cast(fact.datum_type),
+                &[inputs[mask]],
+            )?[0];
+            // ones are attended to, zeros get a large negative score
+            wire = model.wire_node(
+                format!("{}.mask-shift", name),
+                ops::math::add::unary(konst(-1.0)?),
+                &[wire],
+            )?[0];
+            wire = model.wire_node(
+                format!("{}.mask-scale", name),
+                ops::math::mul::unary(konst(10000.0)?),
+                &[wire],
+            )?[0];
+            for i in mask_rank..4 {
+                wire = model.wire_node(
+                    format!("{}.mask-axis-{}", name, i),
+                    AxisOp::Add(1),
+                    &[wire],
+                )?[0];
+            }
+            heads.push(wire);
+        }
+
+        let scale = self.scale.unwrap_or((head as f32).sqrt().recip());
+        let wire = model.wire_node(
+            format!("{}.attention", name),
+            core_nn::ScaledDotProductAttention::new(scale),
+            &heads,
+        )?;
+        let wire = model.wire_node(format!("{}.heads-last", name), AxisOp::Move(1, 2), &wire)?;
+        model.wire_node(
+            name,
+            AxisOp::Reshape(
+                2,
+                tvec!(self.num_heads.to_dim(), head.to_dim()),
+                tvec!(hidden.to_dim()),
+            ),
+            &wire,
+        )
+    }
+}
@@ -6,6 +6,8 @@ use tract_hir::ops;
 use tract_hir::ops::binary::Nary;
 
 mod clip;
+mod cum_sum;
+mod einsum;
 mod gemm;
 mod mat_mul_integer;
 mod pow;
@@ -43,7 +45,7 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("Asinh", |_, _| Ok((Box::new(ops::math::asinh()), vec![])));
     reg.insert("Atanh", |_, _| Ok((Box::new(ops::math::atanh()), vec![])));
 
-    reg.insert("Erf", |_, _| Ok((Box::new(tract_onnx_opl::erf::erf()), vec![])));
+    reg.insert("Erf", |_, _| Ok((Box::new(ops::math::erf()), vec![])));
     reg.insert("Exp", |_, _| Ok((Box::new(ops::math::exp()), vec![])));
     reg.insert("Log", |_, _| Ok((Box::new(ops::math::ln()), vec![])));
     reg.insert("Sqrt", |_, _| Ok((Box::new(ops::math::sqrt()), vec![])));
@@ -57,6 +59,8 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
 
     reg.insert("Pow", pow::pow);
 
+    reg.insert("CumSum", cum_sum::cum_sum);
+    reg.insert("Einsum", einsum::einsum);
     reg.insert("MatMul", |_, _| Ok((expand(ops::matmul::MatMulInference::default()), vec![])));
     reg.insert("MatMulInteger", mat_mul_integer::mat_mul_integer);
     reg.insert("QLinearMatMul", mat_mul_integer::q_linear_mat_mul);
@@ -0,0 +1,60 @@
+use tract_hir::internal::*;
+
+use crate::model::ParsingContext;
+use crate::pb::NodeProto;
+
+pub fn cum_sum(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let exclusive = node.get_attr_opt::<i64>("exclusive")?.unwrap_or(0) == 1;
+    let reverse = node.get_attr_opt::<i64>("reverse")?.unwrap_or(0) == 1;
+    Ok((expand(CumSum::new(exclusive, reverse)), vec![]))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+struct CumSum {
+    exclusive: bool,
+    reverse: bool,
+}
+
+tract_linalg::impl_dyn_hash!(CumSum);
+
+impl Expansion for CumSum {
+    fn name(&self) -> Cow<str> {
+        "CumSum".into()
+    }
+
+    op_onnx!();
+
+    fn wire(
+        &self,
+        prefix: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let axis = if let Some(axis) = &model.outlet_fact(inputs[1])?.konst {
+            axis.cast_to_scalar::<i64>()?
+        } else {
+            bail!("CumSum expects axis to be a constant")
+        };
+        let rank = model.outlet_fact(inputs[0])?.rank() as i64;
+        let axis = if axis < 0 { axis + rank } else { axis } as usize;
+        let op = tract_hir::ops::math::CumSum::new(axis, self.exclusive, self.reverse);
+        model.wire_node(prefix, op, &[inputs[0]])
+    }
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        s.equals(&inputs[1].rank, 0)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::einsum;
+
+use crate::model::ParsingContext;
+use crate::pb::NodeProto;
+
+pub fn einsum(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let expr = node.get_attr::<String>("equation")?;
+    Ok((expand(EinSum::new(expr)), vec![]))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+struct EinSum {
+    expr: String,
+}
+
+tract_linalg::impl_dyn_hash!(EinSum);
+
+impl Expansion for EinSum {
+    fn name(&self) -> Cow<str> {
+        "EinSum".into()
+    }
+
+    op_onnx!();
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![self.expr.clone()])
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let ranks = inputs
+            .iter()
+            .map(|i| Ok(model.outlet_fact(*i)?.rank()))
+            .collect::<TractResult<TVec<_>>>()?;
+        let expr = einsum::Expr::parse(&self.expr, &ranks)?;
+        model.wire_node(prefix, einsum::EinSum::new(expr), inputs)
+    }
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+        }
+        s.given_all(inputs.iter().map(|i| &i.shape), move |s, shapes: Vec<TVec<TDim>>| {
+            let ranks = shapes.iter().map(|s| s.len()).collect::<TVec<_>>();
+            let expr = einsum::Expr::parse(&self.expr, &ranks)?;
+            let shapes = shapes.iter().map(|s| &**s).collect::<TVec<_>>();
+            s.equals(&outputs[0].shape, expr.output_shape(&shapes)?)
+        })
+    }
+}
@@ -171,11 +171,13 @@ impl Expansion for QLinearMatMul {
         if let Some(zp) = cleanup_zero_point(konsts.pop().unwrap())? {
             qp = qp.with_zero_point_c(&zp.into_arc_tensor());
         }
-        let scale = konsts.pop().unwrap().to_scalar::<f32>()?
-            * konsts.pop().unwrap().to_scalar::<f32>()?
-            / konsts.pop().unwrap().to_scalar::<f32>()?;
-
-        qp = qp.with_scale_factor(scale);
+        let a_scale = konsts.pop().unwrap();
+        let b_scale = konsts.pop().unwrap();
+        let y_scale = konsts.pop().unwrap();
+        qp = qp.with_scale_factor(1.0 / y_scale.to_scalar::<f32>()?);
+        // per-row scales for a, per-column scales for b
+        qp = qp.with_scale_factor_rows(&a_scale)?;
+        qp = qp.with_scale_factor_cols(&b_scale)?;
         let op = tract_hir::ops::matmul::MatMul::default().with_q_params(qp);
         let inputs = tract_hir::ops::binary::wire_rank_broadcast(prefix, target, &[inputs[0], inputs[3]])?;
         target.wire_node(prefix, op, &inputs)
@@ -14,6 +14,7 @@ macro_rules! op_onnx {
 mod array;
 mod cast;
 mod category_mapper;
+mod contrib;
 mod logic;
 mod math;
 mod resize;
@@ -26,8 +27,10 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("Constant", konst);
     reg.insert("Identity", |_, _| Ok((Box::new(ops::identity::Identity::default()), vec![])));
     reg.insert("Resize", resize::resize);
+    reg.insert("Upsample", resize::resize);
     array::register_all_ops(reg);
     category_mapper::register_all_ops(reg);
+    contrib::register_all_ops(reg);
     logic::register_all_ops(reg);
     math::register_all_ops(reg);
     nn::register_all_ops(reg);
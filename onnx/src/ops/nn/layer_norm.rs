@@ -0,0 +1,105 @@
+use crate::model::ParsingContext;
+use crate::pb::NodeProto;
+use tract_hir::internal::*;
+use tract_hir::ops::binary::wire_rank_broadcast;
+use tract_hir::ops::math;
+
+pub fn layer_normalization(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let axis = node.get_attr_opt("axis")?.unwrap_or(-1);
+    let epsilon = node.get_attr_opt("epsilon")?.unwrap_or(1e-5);
+    if node.output.iter().filter(|s| !s.is_empty()).count() > 1 {
+        bail!("Mean and InvStdDev outputs of LayerNormalization are not supported")
+    }
+    let bias = crate::model::optional_inputs(node).skip(2).next().unwrap();
+    Ok((expand(LayerNormalization::new(axis, epsilon, bias)), vec![]))
+}
+
+#[derive(Debug, Clone, new, Default, Educe)]
+#[educe(Hash)]
+pub struct LayerNormalization {
+    axis: i64,
+    #[educe(Hash(method = "hash_f32"))]
+    epsilon: f32,
+    optional_bias_input: Option<usize>,
+}
+
+tract_linalg::impl_dyn_hash!(LayerNormalization);
+
+impl Expansion for LayerNormalization {
+    fn name(&self) -> Cow<str> {
+        "LayerNormalization".into()
+    }
+
+    op_onnx!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(inputs, 2 + self.optional_bias_input.is_some() as usize)?;
+        check_output_arity(outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+        }
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        Ok(())
+    }
+
+    fn wire(
+        &self,
+        name: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let rank = model.outlet_fact(inputs[0])?.rank() as i64;
+        let axis = if self.axis < 0 { self.axis + rank } else { self.axis };
+        if axis < 0 || axis >= rank {
+            bail!("Invalid axis {} for input of rank {}", self.axis, rank)
+        }
+        let bias = self.optional_bias_input.map(|ix| inputs[ix]);
+        let wire = wire_layer_norm(
+            name,
+            model,
+            inputs[0],
+            axis as usize,
+            self.epsilon,
+            Some(inputs[1]),
+            bias,
+        )?;
+        Ok(tvec!(wire))
+    }
+}
+
+/// Normalizes over axes `axis..` then applies the optional scale and bias,
+/// broadcast from the innermost axes.
+pub fn wire_layer_norm(
+    name: &str,
+    model: &mut TypedModel,
+    input: OutletId,
+    axis: usize,
+    epsilon: f32,
+    scale: Option<OutletId>,
+    bias: Option<OutletId>,
+) -> TractResult<OutletId> {
+    let rank = model.outlet_fact(input)?.rank();
+    let axes = (axis..rank).collect();
+    let mut wire = model.wire_node(
+        format!("{}.norm", name),
+        tract_hir::tract_core::ops::nn::LayerNorm::new(axes, epsilon),
+        &[input],
+    )?[0];
+    if let Some(scale) = scale {
+        let wires = wire_rank_broadcast(&format!("{}.scale", name), model, &[wire, scale])?;
+        wire = model.wire_node(format!("{}.scaled", name), math::mul::bin_typed(), &wires)?[0];
+    }
+    if let Some(bias) = bias {
+        let wires = wire_rank_broadcast(&format!("{}.bias", name), model, &[wire, bias])?;
+        wire = model.wire_node(format!("{}.biased", name), math::add::bin_typed(), &wires)?[0];
+    }
+    Ok(wire)
+}
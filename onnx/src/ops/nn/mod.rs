@@ -9,7 +9,10 @@ use crate::pb_helpers::OptionExt;
 mod batch_norm;
 mod dropout;
 mod instance_norm;
+pub mod layer_norm;
 mod lrn;
+mod non_max_suppression;
+mod roi_align;
 
 pub fn arg_max_min(
     _ctx: &ParsingContext,
@@ -46,10 +49,12 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("Hardmax", layer_hard_max);
     reg.insert("HardSigmoid", hard_sigmoid);
     reg.insert("InstanceNormalization", instance_norm::instance_normalization);
+    reg.insert("LayerNormalization", layer_norm::layer_normalization);
     reg.insert("LeakyRelu", leaky_relu);
     reg.insert("LogSoftmax", layer_log_soft_max);
     reg.insert("LRN", lrn::lrn);
     reg.insert("MaxPool", max_pool);
+    reg.insert("NonMaxSuppression", non_max_suppression::non_max_suppression);
     reg.insert("ParametricSoftplus", parametric_softplus);
     reg.insert("QLinearConv", conv_qlinear);
     reg.insert("PRelu", |_, _| Ok((expand(Prelu), vec![])));
@@ -64,6 +69,7 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("ReduceSum", |_, node| reduce(node, nn::Reducer::Sum));
     reg.insert("ReduceSumSquare", |_, node| reduce(node, nn::Reducer::SumSquare));
     reg.insert("Relu", |_, _| Ok((expand(ops::activations::Clip::new(Some(0.0), None)), vec![])));
+    reg.insert("RoiAlign", roi_align::roi_align);
     reg.insert("ScaledTanh", scaled_tanh);
     reg.insert("Shrink", shrink);
     reg.insert("ThresholdedRelu", thresholded_relu);
@@ -0,0 +1,102 @@
+use tract_hir::internal::*;
+
+use crate::model::ParsingContext;
+use crate::pb::NodeProto;
+
+use tract_onnx_opl::non_max_suppression::BoxRepr;
+
+pub fn non_max_suppression(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let center_point_box = match node.get_attr_opt("center_point_box")?.unwrap_or(0i64) {
+        0 => BoxRepr::TwoPoints,
+        1 => BoxRepr::CenterWidthHeight,
+        other => bail!("Unsupported center_point_box: {}", other),
+    };
+    let mut options = crate::model::optional_inputs(node).skip(2);
+    let op = NonMaxSuppression {
+        center_point_box,
+        optional_max_output_boxes_per_class_input: options.next().unwrap(),
+        optional_iou_threshold_input: options.next().unwrap(),
+        optional_score_threshold_input: options.next().unwrap(),
+        num_selected_indices_symbol: Symbol::new('n'),
+    };
+    Ok((expand(op), vec![]))
+}
+
+#[derive(Debug, Clone, Hash)]
+struct NonMaxSuppression {
+    center_point_box: BoxRepr,
+    optional_max_output_boxes_per_class_input: Option<usize>,
+    optional_iou_threshold_input: Option<usize>,
+    optional_score_threshold_input: Option<usize>,
+    /// Number of selected boxes, one symbol per node.
+    num_selected_indices_symbol: Symbol,
+}
+
+tract_linalg::impl_dyn_hash!(NonMaxSuppression);
+
+impl Expansion for NonMaxSuppression {
+    fn name(&self) -> Cow<str> {
+        "NonMaxSuppression".into()
+    }
+
+    op_onnx!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(
+            &inputs,
+            2 + self.optional_max_output_boxes_per_class_input.is_some() as usize
+                + self.optional_iou_threshold_input.is_some() as usize
+                + self.optional_score_threshold_input.is_some() as usize,
+        )?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].rank, 3)?;
+        s.equals(&inputs[0].shape[2], 4.to_dim())?;
+        s.equals(&inputs[1].rank, 3)?;
+        s.equals(&inputs[0].shape[0], &inputs[1].shape[0])?;
+        s.equals(&inputs[0].shape[1], &inputs[1].shape[2])?;
+        s.equals(&outputs[0].datum_type, i64::datum_type())?;
+        s.equals(&outputs[0].rank, 2)?;
+        s.equals(&outputs[0].shape[0], TDim::from(self.num_selected_indices_symbol))?;
+        s.equals(&outputs[0].shape[1], 3.to_dim())?;
+        Ok(())
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let max_output_boxes_per_class = match self.optional_max_output_boxes_per_class_input {
+            Some(input) => inputs[input],
+            None => model
+                .add_const(format!("{}.max_output_boxes_per_class", prefix), rctensor0(0i64))?,
+        };
+        let iou_threshold = match self.optional_iou_threshold_input {
+            Some(input) => inputs[input],
+            None => model.add_const(format!("{}.iou_threshold", prefix), rctensor0(0f32))?,
+        };
+        let score_threshold = match self.optional_score_threshold_input {
+            Some(input) => inputs[input],
+            None => model.add_const(format!("{}.score_threshold", prefix), rctensor0(0f32))?,
+        };
+        let op = tract_onnx_opl::non_max_suppression::NonMaxSuppression {
+            center_point_box: self.center_point_box,
+            num_selected_indices_symbol: self.num_selected_indices_symbol,
+            has_score_threshold: self.optional_score_threshold_input.is_some(),
+        };
+        model.wire_node(
+            prefix,
+            op,
+            &[inputs[0], inputs[1], max_output_boxes_per_class, iou_threshold, score_threshold],
+        )
+    }
+}
@@ -1,4 +1,5 @@
 use crate::model::OnnxOpRegister;
+use tract_hir::internal::*;
 
 pub mod gru;
 pub mod lstm;
@@ -11,3 +12,30 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("RNN", rnn::rnn);
     reg.insert("Scan", scan::scan);
 }
+
+/// Wire X*(W^T) for all time steps and all directions, out of the scan loops.
+///
+/// X is [seq_length, batch_size, input_size], W is [num_directions,
+/// gates*hidden_size, input_size]. The result is [seq_length, batch_size,
+/// num_directions*gates*hidden_size]: each direction slices its part on the
+/// last axis and scans it, so the loop bodies are only left with the
+/// recurrent products.
+fn wire_input_projection(
+    prefix: &str,
+    target: &mut TypedModel,
+    x: OutletId,
+    w: OutletId,
+) -> TractResult<OutletId> {
+    let w_shape = target.outlet_fact(w)?.shape.to_tvec();
+    let rows = w_shape[0].to_usize()? * w_shape[1].to_usize()?;
+    let w = target.wire_node(
+        format!("{}.w_all_directions", prefix),
+        AxisOp::Reshape(0, w_shape[0..2].iter().cloned().collect(), tvec!(1.to_dim(), rows.to_dim())),
+        &[w],
+    )?[0];
+    Ok(target.wire_node(
+        format!("{}.input_projection", prefix),
+        tract_hir::ops::matmul::MatMul::default().with_b_trans(true),
+        &[x, w],
+    )?[0])
+}
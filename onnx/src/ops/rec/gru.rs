@@ -130,10 +130,11 @@ impl Expansion for GRU {
         inputs: &[OutletId],
     ) -> TractResult<TVec<OutletId>> {
         use tract_hir::tract_core::ops::array::TypedConcat;
-        let fore = self.wire_one_side(prefix, target, inputs, 0)?;
+        let x_wt = super::wire_input_projection(prefix, target, inputs[0], inputs[1])?;
+        let fore = self.wire_one_side(prefix, target, inputs, x_wt, 0)?;
         let w_fact = target.outlet_fact(inputs[1])?;
         if w_fact.shape[0] == 2.into() {
-            let back = self.wire_one_side(&format!("{}.back", prefix), target, inputs, 1)?;
+            let back = self.wire_one_side(&format!("{}.back", prefix), target, inputs, x_wt, 1)?;
             let mut outputs = tvec!(0.into(); self.nboutputs()?);
             if let Some(ix) = self.optional_y_output {
                 outputs[ix] = target.wire_node(
@@ -163,6 +164,7 @@ impl GRU {
         prefix: &str,
         target: &mut TypedModel,
         inputs: &[OutletId],
+        x_wt: OutletId,
         dir: usize,
     ) -> TractResult<TVec<OutletId>> {
         use tract_hir::ops::{array, math, matmul, scan};
@@ -195,38 +197,36 @@ impl GRU {
             }
         };
 
-        // X: onnx interface: [seq_length, batch_size, input_size]
-        // scan outer interface: idem
-        // scann inner interface: [chunk=1, batch_size, input_size]
-        // onnx inner interface: [batch_size, input_size]
-        outer_inputs.push(inputs[0]);
+        // X*(W^T): [seq_length, batch_size, num_directions*3*hidden_size]
+        // scan outer interface: [seq_length, batch_size, 3*hidden_size]
+        // scan inner interface: [chunk=1, batch_size, 3*hidden_size]
+        // onnx inner interface: [batch_size, 3*hidden_size]
+        // W (onnx input 1) is consumed by the projection, so the outer slots
+        // of the following inputs are shifted by one.
+        target_wire!(
+            x_wt_dir = array::Slice::new(2, dir * 3 * h_size, (dir + 1) * 3 * h_size),
+            x_wt
+        );
+        outer_inputs.push(x_wt_dir);
         input_mapping.push(scan::InputMapping::Scan { slot: 0, axis: 0, chunk });
-        let mut x_source_fact = x_fact.without_value();
-        x_source_fact.shape[0] = 1.to_dim();
-        let x_source = body.add_source("x_source", x_source_fact)?.into();
-        wire!(Xt = AxisOp::Rm(0), x_source);
-
-        // W: onnx interface: [num_directions, 3*hidden_size, input_size]
-        // scan interfaces: [3*hidden_size, input_size]
-        target_wire!(w_dir = array::Slice::new(0, dir, dir + 1), inputs[1]);
-        target_wire!(w = AxisOp::Rm(0), w_dir);
-        outer_inputs.push(w);
-        input_mapping.push(scan::InputMapping::Full { slot: 1 });
-        let W = body.add_source("w", target.outlet_fact(w)?.clone())?.into();
+        let mut x_wt_source_fact = target.outlet_fact(x_wt_dir)?.without_value();
+        x_wt_source_fact.shape[0] = 1.to_dim();
+        let x_wt_source = body.add_source("x_wt_source", x_wt_source_fact)?.into();
+        wire!(Xt_WT = AxisOp::Rm(0), x_wt_source);
 
         // R: onnx interface: [num_directions, 3*hidden_size, hidden_size]
         // scan interfaces: [3*hidden_size, hidden_size]
         target_wire!(r_dir = array::Slice::new(0, dir, dir + 1), inputs[2]);
         target_wire!(r = AxisOp::Rm(0), r_dir);
         outer_inputs.push(r);
-        input_mapping.push(scan::InputMapping::Full { slot: 2 });
+        input_mapping.push(scan::InputMapping::Full { slot: 1 });
         let R = body.add_source("r", target.outlet_fact(r)?.clone())?.into();
 
         // B: onnx interface: [num_directions, 6*hidden_size]
         let b = if let Some(slot) = self.optional_bias_input {
             target_wire!(b_dir = array::Slice::new(0, dir, dir + 1), inputs[slot]);
             outer_inputs.push(b_dir);
-            input_mapping.push(scan::InputMapping::Full { slot });
+            input_mapping.push(scan::InputMapping::Full { slot: slot - 1 });
             let b = body.add_source("b", target.outlet_fact(b_dir)?.clone())?.into();
             Some(b)
         } else {
@@ -245,7 +245,7 @@ impl GRU {
             target_wire!(h = AxisOp::Rm(0), inputs[initial_h_input]);
             target_wire!(h_chunk = AxisOp::Add(0), h);
             outer_inputs.push(h_chunk);
-            scan::StateInitializer::FromInput(initial_h_input)
+            scan::StateInitializer::FromInput(initial_h_input - 1)
         } else {
             scan::StateInitializer::Value(
                 tract_ndarray::Array3::<f32>::zeros((1, b_size, h_size)).into_arc_tensor(),
@@ -265,12 +265,11 @@ impl GRU {
         wire!(Rr = array::Slice::new(0, 1 * h_size, 2 * h_size), R);
         wire!(Rh = array::Slice::new(0, 2 * h_size, 3 * h_size), R);
 
-        wire!(Wz = array::Slice::new(0, 0 * h_size, 1 * h_size), W);
-        wire!(Wr = array::Slice::new(0, 1 * h_size, 2 * h_size), W);
-        wire!(Wh = array::Slice::new(0, 2 * h_size, 3 * h_size), W);
+        wire!(Xt_WzT = array::Slice::new(1, 0 * h_size, 1 * h_size), Xt_WT);
+        wire!(Xt_WrT = array::Slice::new(1, 1 * h_size, 2 * h_size), Xt_WT);
+        wire!(Xt_WhT = array::Slice::new(1, 2 * h_size, 3 * h_size), Xt_WT);
 
         // zt = f(Xt*(Wz^T) + Ht-1*(Rz^T) + Wbz + Rbz)
-        wire!(Xt_WzT = matmul::MatMul::default().with_b_trans(true), Xt, Wz);
         wire!(Ht_1_RzT = matmul::MatMul::default().with_b_trans(true), Ht_1, Rz);
         wire!(zt0 = math::add::bin_typed(), Xt_WzT, Ht_1_RzT);
         let mut zt0 = zt0;
@@ -284,7 +283,6 @@ impl GRU {
         wire!(zt = self.f.clone(), zt0);
 
         // rt = f(Xt*(Wr^T) + Ht-1*(Rr^T) + Wbr + Rbr)
-        wire!(Xt_WrT = matmul::MatMul::default().with_b_trans(true), Xt, Wr);
         wire!(Ht_1_RrT = matmul::MatMul::default().with_b_trans(true), Ht_1, Rr);
         wire!(rt0 = math::add::bin_typed(), Xt_WrT, Ht_1_RrT);
         let mut rt0 = rt0;
@@ -299,7 +297,6 @@ impl GRU {
 
         // ht = g(Xt*(Wh^T) + (rt (.) Ht-1)*(Rh^T) + Rbh + Wbh) # default, when linear_before_reset = 0
         // ht = g(Xt*(Wh^T) + (rt (.) (Ht-1*(Rh^T) + Rbh)) + Wbh) # when linear_before_reset != 0
-        wire!(Xt_WhT = matmul::MatMul::default().with_b_trans(true), Xt, Wh);
         let rt_Ht_1_RhT = if self.linear_before_reset {
             wire!(Ht_1_RhT = matmul::MatMul::default().with_b_trans(true), Ht_1, Rh);
             wire!(rt_Ht_1_RhT = math::mul::bin_typed(), rt, Ht_1_RhT);
@@ -345,7 +342,7 @@ impl GRU {
                 body,
                 input_mapping,
                 vec![output_mapping],
-                self.optional_sequence_lens_input,
+                self.optional_sequence_lens_input.map(|slot| slot - 1),
                 0,
             )?,
             &outer_inputs,
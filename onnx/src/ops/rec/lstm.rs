@@ -164,10 +164,11 @@ impl Expansion for LSTM {
         inputs: &[OutletId],
     ) -> TractResult<TVec<OutletId>> {
         use tract_hir::tract_core::ops::array::TypedConcat;
-        let fore = self.wire_one_side(prefix, target, inputs, 0)?;
+        let x_wt = super::wire_input_projection(prefix, target, inputs[0], inputs[1])?;
+        let fore = self.wire_one_side(prefix, target, inputs, x_wt, 0)?;
         let w_fact = target.outlet_fact(inputs[1])?;
         if w_fact.shape[0] == 2.into() {
-            let back = self.wire_one_side(&format!("{}.back", prefix), target, inputs, 1)?;
+            let back = self.wire_one_side(&format!("{}.back", prefix), target, inputs, x_wt, 1)?;
             let mut outputs = tvec!(0.into(); self.nboutputs()?);
             if let Some(ix) = self.optional_y_output {
                 outputs[ix] = target.wire_node(
@@ -204,6 +205,7 @@ impl LSTM {
         prefix: &str,
         target: &mut TypedModel,
         inputs: &[OutletId],
+        x_wt: OutletId,
         dir: usize,
     ) -> TractResult<TVec<OutletId>> {
         use tract_hir::ops::{array, math, matmul, scan};
@@ -236,38 +238,36 @@ impl LSTM {
 
         let chunk = if dir == 0 { 1 } else { -1 };
 
-        // X: onnx interface: [seq_length, batch_size, input_size]
-        // scan outer interface: idem
-        // scann inner interface: [chunk=1, batch_size, input_size]
-        // onnx inner interface: [batch_size, input_size]
-        outer_inputs.push(inputs[0]);
+        // X*(W^T): [seq_length, batch_size, num_directions*4*hidden_size]
+        // scan outer interface: [seq_length, batch_size, 4*hidden_size]
+        // scan inner interface: [chunk=1, batch_size, 4*hidden_size]
+        // onnx inner interface: [batch_size, 4*hidden_size]
+        // W (onnx input 1) is consumed by the projection, so the outer slots
+        // of the following inputs are shifted by one.
+        target_wire!(
+            x_wt_dir = array::Slice::new(2, dir * 4 * h_size, (dir + 1) * 4 * h_size),
+            x_wt
+        );
+        outer_inputs.push(x_wt_dir);
         input_mapping.push(scan::InputMapping::Scan { slot: 0, axis: 0, chunk });
-        let mut x_source_fact = x_fact.without_value();
-        x_source_fact.shape[0] = 1.to_dim();
-        let x_source = body.add_source("x_source", x_source_fact)?.into();
-        wire!(Xt = AxisOp::Rm(0), x_source);
-
-        // W: onnx interface: [num_directions, 4*hidden_size, input_size]
-        // scan interfaces: [4*hidden_size, input_size]
-        target_wire!(w_dir = array::Slice::new(0, dir, dir + 1), inputs[1]);
-        target_wire!(w = AxisOp::Rm(0), w_dir);
-        outer_inputs.push(w);
-        input_mapping.push(scan::InputMapping::Full { slot: 1 });
-        let W = body.add_source("w", target.outlet_fact(w)?.clone())?.into();
+        let mut x_wt_source_fact = target.outlet_fact(x_wt_dir)?.without_value();
+        x_wt_source_fact.shape[0] = 1.to_dim();
+        let x_wt_source = body.add_source("x_wt_source", x_wt_source_fact)?.into();
+        wire!(Xt_WT = AxisOp::Rm(0), x_wt_source);
 
         // R: onnx interface: [num_directions, 4*hidden_size, hidden_size]
         // scan interfaces: [4*hidden_size, hidden_size]
         target_wire!(r_dir = array::Slice::new(0, dir, dir + 1), inputs[2]);
         target_wire!(r = AxisOp::Rm(0), r_dir);
         outer_inputs.push(r);
-        input_mapping.push(scan::InputMapping::Full { slot: 2 });
+        input_mapping.push(scan::InputMapping::Full { slot: 1 });
         let R = body.add_source("r", target.outlet_fact(r)?.clone())?.into();
 
         // B: onnx interface: [num_directions, 8*hidden_size]
         let b = if let Some(slot) = self.optional_bias_input {
             target_wire!(b = array::Slice::new(0, dir, dir + 1), inputs[slot]);
             outer_inputs.push(b);
-            input_mapping.push(scan::InputMapping::Full { slot });
+            input_mapping.push(scan::InputMapping::Full { slot: slot - 1 });
             let b = body.add_source("b", target.outlet_fact(b)?.clone())?.into();
             Some(b)
         } else {
@@ -287,7 +287,7 @@ impl LSTM {
             target_wire!(h = AxisOp::Rm(0), h_dir);
             target_wire!(h_chunk = AxisOp::Add(0), h);
             outer_inputs.push(h_chunk);
-            scan::StateInitializer::FromInput(initial_h_input)
+            scan::StateInitializer::FromInput(initial_h_input - 1)
         } else {
             scan::StateInitializer::Value(
                 tract_ndarray::Array3::<f32>::zeros((1, b_size, h_size)).into_arc_tensor(),
@@ -306,7 +306,7 @@ impl LSTM {
             target_wire!(c = AxisOp::Rm(0), c_dir);
             target_wire!(c_chunk = AxisOp::Add(0), c);
             outer_inputs.push(c_chunk);
-            scan::StateInitializer::FromInput(initial_c_input)
+            scan::StateInitializer::FromInput(initial_c_input - 1)
         } else {
             scan::StateInitializer::Value(
                 tract_ndarray::Array3::<f32>::zeros((1, b_size, h_size)).into_arc_tensor(),
@@ -324,7 +324,7 @@ impl LSTM {
         let p = if let Some(slot) = self.optional_p_input {
             target_wire!(p = array::Slice::new(0, dir, dir + 1), inputs[slot]);
             outer_inputs.push(p);
-            input_mapping.push(scan::InputMapping::Full { slot });
+            input_mapping.push(scan::InputMapping::Full { slot: slot - 1 });
             let p = body.add_source("p", target.outlet_fact(p)?.clone())?.into();
             Some(p)
         } else {
@@ -334,10 +334,10 @@ impl LSTM {
         wire!(Ht_1 = AxisOp::Rm(0), h_source);
         wire!(Ct_1 = AxisOp::Rm(0), c_source);
 
-        wire!(Wi = array::Slice::new(0, 0 * h_size, 1 * h_size), W);
-        wire!(Wo = array::Slice::new(0, 1 * h_size, 2 * h_size), W);
-        wire!(Wf = array::Slice::new(0, 2 * h_size, 3 * h_size), W);
-        wire!(Wc = array::Slice::new(0, 3 * h_size, 4 * h_size), W);
+        wire!(Xt_WiT = array::Slice::new(1, 0 * h_size, 1 * h_size), Xt_WT);
+        wire!(Xt_WoT = array::Slice::new(1, 1 * h_size, 2 * h_size), Xt_WT);
+        wire!(Xt_WfT = array::Slice::new(1, 2 * h_size, 3 * h_size), Xt_WT);
+        wire!(Xt_WcT = array::Slice::new(1, 3 * h_size, 4 * h_size), Xt_WT);
 
         wire!(Ri = array::Slice::new(0, 0 * h_size, 1 * h_size), R);
         wire!(Ro = array::Slice::new(0, 1 * h_size, 2 * h_size), R);
@@ -375,7 +375,6 @@ impl LSTM {
         };
 
         // it = f(Xt*(Wi^T) + Ht-1*(Ri^T) + Pi (.) Ct-1 + Wbi + Rbi)
-        wire!(Xt_WiT = matmul::MatMul::default().with_b_trans(true), Xt, Wi);
         wire!(Ht_1_RiT = matmul::MatMul::default().with_b_trans(true), Ht_1, Ri);
         wire!(it0 = math::add::bin_typed(), Xt_WiT, Ht_1_RiT);
         let mut it0 = it0;
@@ -391,7 +390,6 @@ impl LSTM {
         wire!(it = self.f.clone(), it0);
 
         // ft = f(Xt*(Wf^T) + Ht-1*(Rf^T) + Pf (.) Ct-1 + Wbf + Rbf)
-        wire!(Xt_WfT = matmul::MatMul::default().with_b_trans(true), Xt, Wf);
         wire!(Ht_1_RfT = matmul::MatMul::default().with_b_trans(true), Ht_1, Rf);
         wire!(ft0 = math::add::bin_typed(), Xt_WfT, Ht_1_RfT);
         let mut ft0 = ft0;
@@ -407,7 +405,6 @@ impl LSTM {
         wire!(ft = self.f.clone(), ft0);
 
         // ct = g(Xt*(Wc^T) + Ht-1*(Rc^T) + Wbc + Rbc)
-        wire!(Xt_WcT = matmul::MatMul::default().with_b_trans(true), Xt, Wc);
         wire!(Ht_1_RcT = matmul::MatMul::default().with_b_trans(true), Ht_1, Rc);
         wire!(ct0 = math::add::bin_typed(), Xt_WcT, Ht_1_RcT);
         let mut ct0 = ct0;
@@ -423,7 +420,6 @@ impl LSTM {
         wire!(Ct = math::add::bin_typed(), ft_Ct_1, it_ct);
 
         // ot = f(Xt*(Wo^T) + Ht-1*(Ro^T) + Po (.) Ct + Wbo + Rbo)
-        wire!(Xt_WoT = matmul::MatMul::default().with_b_trans(true), Xt, Wo);
         wire!(Ht_1_RoT = matmul::MatMul::default().with_b_trans(true), Ht_1, Ro);
         wire!(ot0 = math::add::bin_typed(), Xt_WoT, Ht_1_RoT);
         let mut ot0 = ot0;
@@ -469,7 +465,7 @@ impl LSTM {
                 body,
                 input_mapping,
                 vec![h_mapping, c_mapping],
-                self.optional_sequence_lens_input,
+                self.optional_sequence_lens_input.map(|slot| slot - 1),
                 0,
             )?,
             &outer_inputs,
@@ -128,10 +128,11 @@ impl Expansion for RNN {
         inputs: &[OutletId],
     ) -> TractResult<TVec<OutletId>> {
         use tract_hir::tract_core::ops::array::TypedConcat;
-        let fore = self.wire_one_side(prefix, target, inputs, 0)?;
+        let x_wt = super::wire_input_projection(prefix, target, inputs[0], inputs[1])?;
+        let fore = self.wire_one_side(prefix, target, inputs, x_wt, 0)?;
         let w_fact = target.outlet_fact(inputs[1])?;
         if w_fact.shape[0] == 2.into() {
-            let back = self.wire_one_side(&format!("{}.back", prefix), target, inputs, 1)?;
+            let back = self.wire_one_side(&format!("{}.back", prefix), target, inputs, x_wt, 1)?;
             let mut outputs = tvec!(0.into(); self.nboutputs()?);
             if let Some(ix) = self.optional_y_output {
                 outputs[ix] = target.wire_node(
@@ -161,6 +162,7 @@ impl RNN {
         prefix: &str,
         target: &mut TypedModel,
         inputs: &[OutletId],
+        x_wt: OutletId,
         dir: usize,
     ) -> TractResult<TVec<OutletId>> {
         use tract_hir::ops::{array, math, matmul, scan};
@@ -193,37 +195,33 @@ impl RNN {
             }
         };
 
-        // X: onnx interface: [seq_length, batch_size, input_size]
-        // scan outer interface: idem
-        // scann inner interface: [chunk=1, batch_size, input_size]
-        // onnx inner interface: [batch_size, input_size]
-        outer_inputs.push(inputs[0]);
+        // X*(W^T): [seq_length, batch_size, num_directions*hidden_size]
+        // scan outer interface: [seq_length, batch_size, hidden_size]
+        // scan inner interface: [chunk=1, batch_size, hidden_size]
+        // onnx inner interface: [batch_size, hidden_size]
+        // W (onnx input 1) is consumed by the projection, so the outer slots
+        // of the following inputs are shifted by one.
+        target_wire!(x_wt_dir = array::Slice::new(2, dir * h_size, (dir + 1) * h_size), x_wt);
+        outer_inputs.push(x_wt_dir);
         input_mapping.push(scan::InputMapping::Scan { slot: 0, axis: 0, chunk });
-        let mut x_source_fact = x_fact.without_value();
-        x_source_fact.shape[0] = 1.to_dim();
-        let x_source = body.add_source("x_source", x_source_fact)?.into();
-        wire!(Xt = AxisOp::Rm(0), x_source);
-
-        // W: onnx interface: [num_directions, 3*hidden_size, input_size]
-        // scan interfaces: [3*hidden_size, input_size]
-        target_wire!(w = AxisOp::Rm(0), inputs[1]);
-        outer_inputs.push(w);
-        input_mapping.push(scan::InputMapping::Full { slot: 1 });
-        let W = body.add_source("w", target.outlet_fact(w)?.clone())?.into();
+        let mut x_wt_source_fact = target.outlet_fact(x_wt_dir)?.without_value();
+        x_wt_source_fact.shape[0] = 1.to_dim();
+        let x_wt_source = body.add_source("x_wt_source", x_wt_source_fact)?.into();
+        wire!(Xt_WiT = AxisOp::Rm(0), x_wt_source);
 
         // R: onnx interface: [num_directions, 3*hidden_size, hidden_size]
         // scan interfaces: [3*hidden_size, hidden_size]
         target_wire!(r_dir = array::Slice::new(0, dir, dir + 1), inputs[2]);
         target_wire!(r = AxisOp::Rm(0), r_dir);
         outer_inputs.push(r);
-        input_mapping.push(scan::InputMapping::Full { slot: 2 });
+        input_mapping.push(scan::InputMapping::Full { slot: 1 });
         let R = body.add_source("r", target.outlet_fact(r)?.clone())?.into();
 
         // B: onnx interface: [num_directions, 6*hidden_size]
         let b = if let Some(slot) = self.optional_bias_input {
             target_wire!(b_dir = array::Slice::new(0, dir, dir + 1), inputs[slot]);
             outer_inputs.push(b_dir);
-            input_mapping.push(scan::InputMapping::Full { slot });
+            input_mapping.push(scan::InputMapping::Full { slot: slot - 1 });
             let b = body.add_source("b", target.outlet_fact(b_dir)?.clone())?.into();
             Some(b)
         } else {
@@ -239,11 +237,11 @@ impl RNN {
         // scan inner: [chunk=1, batch_size, hidden_size]
         // onnx inner: [batch_size, hidden_size]
         let initializer = if let Some(initial_h_input) = self.optional_initial_h_input {
-            target_wire!(h_dir = array::Slice::new(0, dir, dir+1), inputs[initial_h_input]);
+            target_wire!(h_dir = array::Slice::new(0, dir, dir + 1), inputs[initial_h_input]);
             target_wire!(h = AxisOp::Rm(0), h_dir);
             target_wire!(h_chunk = AxisOp::Add(0), h);
             outer_inputs.push(h_chunk);
-            scan::StateInitializer::FromInput(initial_h_input)
+            scan::StateInitializer::FromInput(initial_h_input - 1)
         } else {
             scan::StateInitializer::Value(
                 tract_ndarray::Array3::<f32>::zeros((1, b_size, h_size)).into_arc_tensor(),
@@ -269,7 +267,6 @@ impl RNN {
         };
 
         // Ht = f(Xt*(Wi^T) + Ht-1*(Ri^T) + Wbi + Rbi)
-        wire!(Xt_WiT = matmul::MatMul::default().with_b_trans(true), Xt, W);
         wire!(Ht_1_RiT = matmul::MatMul::default().with_b_trans(true), Ht_1, R);
 
         wire!(ht0 = math::add::bin_typed(), Xt_WiT, Ht_1_RiT);
@@ -278,7 +275,8 @@ impl RNN {
             wire!(ht_bias = math::add::bin_typed(), ht0, bias);
             ht0 = ht_bias;
         }
-        wire!(Ht = self.fore.clone(), ht0);
+        let f = if dir == 0 { self.fore.clone() } else { self.back.clone() };
+        wire!(Ht = f, ht0);
 
         wire!(y_h = AxisOp::Add(0), Ht);
         body.set_output_outlets(&[y_h])?;
@@ -298,7 +296,7 @@ impl RNN {
                 body,
                 input_mapping,
                 vec![output_mapping],
-                self.optional_sequence_lens_input,
+                self.optional_sequence_lens_input.map(|slot| slot - 1),
                 0,
             )?,
             &outer_inputs,
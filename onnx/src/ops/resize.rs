@@ -1,200 +1,214 @@
 use crate::model::ParsingContext;
 use crate::pb::*;
-use std::hash::Hash;
 use tract_hir::internal::*;
+use tract_hir::tract_core::ops::array::{
+    CoordTransformer, Interpolator, Nearest, Resize as CoreResize, ResizeAxis,
+};
 
 pub fn resize(
-    _ctx: &ParsingContext,
+    ctx: &ParsingContext,
     node: &NodeProto,
 ) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let interpolator = match node.get_attr_opt("mode")?.unwrap_or("nearest") {
+        "nearest" => Interpolator::Nearest,
+        "linear" | "bilinear" => Interpolator::Linear,
+        "cubic" if node.op_type == "Resize" => Interpolator::Cubic,
+        s => node.bail_attr("mode", &format!("unsupported mode {}", s))?,
+    };
+    if node.op_type == "Upsample" || ctx.onnx_operator_set_version < 11 {
+        // Upsample and Resize-10 only take scales, and use asymmetric
+        // coordinates with floor rounding
+        let params = CoreResize {
+            coord_transformer: CoordTransformer::Asymmetric,
+            interpolator,
+            nearest: Nearest::Floor,
+            ..CoreResize::default()
+        };
+        return Ok((expand(Resize::new(params, None, Some(1), None)), vec![]));
+    }
     let coord_transformer =
         match node.get_attr_opt("coordinate_transformation_mode")?.unwrap_or("half_pixel") {
-            "align_corners" => CoordTransformer::AlignCorners,
             "half_pixel" => CoordTransformer::HalfPixel,
-            s => todo!("coordinate_transformation_mode: {}", s),
+            "pytorch_half_pixel" => CoordTransformer::PytorchHalfPixel,
+            "align_corners" => CoordTransformer::AlignCorners,
+            "asymmetric" => CoordTransformer::Asymmetric,
+            "tf_half_pixel_for_nn" => CoordTransformer::TfHalfPixelForNn,
+            "tf_crop_and_resize" => CoordTransformer::TfCropAndResize,
+            s => node
+                .bail_attr("coordinate_transformation_mode", &format!("unsupported mode {}", s))?,
         };
-    let interpolator = match node.get_attr("mode")? {
-        "linear" => Interpolator::Linear,
-        s => todo!("mode: {}", s),
-    };
     let nearest = match node.get_attr_opt("nearest_mode")?.unwrap_or("round_prefer_floor") {
         "floor" => Nearest::Floor,
+        "ceil" => Nearest::Ceil,
         "round_prefer_floor" => Nearest::RoundPreferFloor,
-        s => todo!("nearest_mode: {}", s),
+        "round_prefer_ceil" => Nearest::RoundPreferCeil,
+        s => node.bail_attr("nearest_mode", &format!("unsupported mode {}", s))?,
     };
-    let mut options = crate::model::optional_inputs(node).skip(2);
+    let params = CoreResize {
+        axes: tvec!(),
+        coord_transformer,
+        interpolator,
+        nearest,
+        cubic_coeff_a: node.get_attr_opt("cubic_coeff_a")?.unwrap_or(-0.75),
+        exclude_outside: node.get_attr_opt("exclude_outside")?.unwrap_or(false),
+        extrapolation_value: node.get_attr_opt("extrapolation_value")?.unwrap_or(0.0),
+    };
+    let mut options = crate::model::optional_inputs(node).skip(1);
     Ok((
-        Box::new(Resize {
-            optional_scales_input: options.next().unwrap(),
-            optional_sizes_input: options.next().unwrap(),
-            coord_transformer,
-            interpolator,
-            nearest,
-        }),
+        expand(Resize::new(
+            params,
+            options.next().unwrap(),
+            options.next().unwrap(),
+            options.next().unwrap(),
+        )),
         vec![],
     ))
 }
 
-#[derive(Clone, Debug, Hash)]
-enum CoordTransformer {
-    HalfPixel,
-    AlignCorners,
-}
-
-impl CoordTransformer {
-    fn transform(&self, x_out: usize, scale: f32, len_in: usize, len_out: usize) -> f32 {
-        match self {
-            CoordTransformer::HalfPixel => (x_out as f32 + 0.5) * scale - 0.5,
-            CoordTransformer::AlignCorners => {
-                (x_out as f32 * (len_in as f32 - 1.0)) / (len_out as f32 - 1.0)
-            }
-        }
-    }
-}
-
-#[derive(Clone, Debug, Hash)]
-enum Interpolator {
-    Linear,
-}
-
-impl Interpolator {
-    fn interpolate(&self, y_left: f32, y_right: f32, x_ratio: f32) -> f32 {
-        match self {
-            Interpolator::Linear => y_left * (1.0 - x_ratio) + y_right * x_ratio,
-        }
-    }
-}
-
-#[derive(Clone, Debug, Hash)]
-enum Nearest {
-    Floor,
-    RoundPreferFloor,
-}
-
 #[derive(Clone, new, Debug, Hash)]
 struct Resize {
-    coord_transformer: CoordTransformer,
-    interpolator: Interpolator,
-    nearest: Nearest,
+    /// Interpolation parameters, the axes are filled from the inputs.
+    params: CoreResize,
+    optional_roi_input: Option<usize>,
     optional_scales_input: Option<usize>,
     optional_sizes_input: Option<usize>,
 }
 
 tract_linalg::impl_dyn_hash!(Resize);
 
-impl Op for Resize {
-    fn name(&self) -> Cow<str> {
-        "Resize".into()
-    }
-
-    op_onnx!();
-    op_as_typed_op!();
-}
-
 impl Resize {
-    fn compute_output_shape(
+    fn core_op(
         &self,
-        input_shape: &[usize],
-        input_scale: Option<&Tensor>,
-        input_sizes: Option<&Tensor>,
-    ) -> TractResult<TVec<usize>> {
-        if let Some(scale) = input_scale {
-            if scale.len() == input_shape.len() {
-                let scales = scale.cast_to::<f32>()?;
-                return Ok(input_shape
-                    .iter()
-                    .zip(scales.as_slice::<f32>()?.iter())
-                    .map(|(input, scale)| ((*input as f32) * scale) as usize)
-                    .collect());
+        input_shape: &[TDim],
+        roi: Option<&Tensor>,
+        scales: Option<&Tensor>,
+        sizes: Option<&Tensor>,
+    ) -> TractResult<CoreResize> {
+        let rank = input_shape.len();
+        let crop = self.params.coord_transformer == CoordTransformer::TfCropAndResize;
+        let roi: TVec<f32> = match roi.filter(|roi| roi.len() > 0) {
+            Some(roi) if roi.len() == 2 * rank => roi.cast_to::<f32>()?.as_slice::<f32>()?.into(),
+            Some(roi) => bail!("Expected roi of length {}, got {:?}", 2 * rank, roi),
+            None => {
+                std::iter::repeat(0.0).take(rank).chain(std::iter::repeat(1.0).take(rank)).collect()
             }
-        }
-        if let Some(sizes) = input_sizes {
-            if sizes.len() == input_shape.len() {
-                let size = sizes.cast_to::<i64>()?;
-                return Ok(size.as_slice::<i64>()?.iter().map(|i| *i as usize).collect());
+        };
+        let full_roi = |ax: usize| !crop || (roi[ax] == 0.0 && roi[rank + ax] == 1.0);
+        let mut axes = tvec!();
+        if let Some(scales) = scales.filter(|s| s.len() > 0) {
+            if scales.len() != rank {
+                bail!("Expected {} scales, got {:?}", rank, scales)
+            }
+            let scales = scales.cast_to::<f32>()?;
+            for (ax, &scale) in scales.as_slice::<f32>()?.iter().enumerate() {
+                if scale == 1.0 && full_roi(ax) {
+                    continue;
+                }
+                let len_in = input_shape[ax].to_usize()?;
+                let extent = if crop { roi[rank + ax] - roi[ax] } else { 1.0 };
+                let len = (len_in as f32 * extent * scale).floor() as usize;
+                axes.push(ResizeAxis::new(ax, len, scale, roi[ax], roi[rank + ax]));
             }
+        } else if let Some(sizes) = sizes {
+            if sizes.len() != rank {
+                bail!("Expected {} sizes, got {:?}", rank, sizes)
+            }
+            let sizes = sizes.cast_to::<i64>()?;
+            for (ax, &len) in sizes.as_slice::<i64>()?.iter().enumerate() {
+                if input_shape[ax] == len.to_dim() && full_roi(ax) {
+                    continue;
+                }
+                let len_in = input_shape[ax].to_usize()?;
+                let scale = len as f32 / len_in as f32;
+                axes.push(ResizeAxis::new(ax, len as usize, scale, roi[ax], roi[rank + ax]));
+            }
+        } else {
+            bail!("Resize needs either scales or sizes")
         }
-        bail!("Neither shape not scale makes sense: input_shape: {:?}, scale: {:?}, sizes: {:?}")
+        Ok(CoreResize { axes, ..self.params.clone() })
+    }
+
+    fn output_shape(
+        &self,
+        input_shape: &[TDim],
+        roi: Option<&Tensor>,
+        scales: Option<&Tensor>,
+        sizes: Option<&Tensor>,
+    ) -> TractResult<TVec<TDim>> {
+        let op = self.core_op(input_shape, roi, scales, sizes)?;
+        let mut shape: TVec<TDim> = input_shape.into();
+        for axis in &op.axes {
+            shape[axis.axis] = axis.len.to_dim();
+        }
+        Ok(shape)
     }
 }
 
-impl EvalOp for Resize {
-    fn is_stateless(&self) -> bool {
-        true
+impl Expansion for Resize {
+    fn name(&self) -> Cow<str> {
+        "Resize".into()
     }
 
-    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
-        let scales = self.optional_scales_input.and_then(|ix| inputs.get(ix));
-        let sizes = self.optional_sizes_input.and_then(|ix| inputs.get(ix));
-        let output_shape = self.compute_output_shape(
-            inputs[0].shape(),
-            scales.map(|t| &**t),
-            sizes.map(|t| &**t),
-        )?;
-        let mut data = inputs.remove(0).into_tensor().into_array::<f32>()?;
-        for axis in 0..data.ndim() {
-            if output_shape[axis] == data.shape()[axis] {
-                continue;
-            } else if output_shape[axis] > data.shape()[axis] {
-                let scale = output_shape[axis] as f32 / data.shape()[axis] as f32;
-                let mut new_shape: TVec<usize> = data.shape().into();
-                new_shape[axis] = output_shape[axis];
-                data = tract_ndarray::ArrayD::from_shape_fn(&*new_shape, |co_o| -> f32 {
-                    let x_out = co_o[axis];
-                    let x_in = self.coord_transformer.transform(
-                        x_out,
-                        scale,
-                        data.shape()[axis],
-                        new_shape[axis],
-                    );
-                    let mut co_i = co_o.clone();
-                    let x_left = (x_in as usize).min(data.shape()[axis] - 1).max(0);
-                    co_i[axis] = x_left;
-                    let y_left = data[&co_i];
-                    let x_right = (x_left + 1).min(data.shape()[axis] - 1);
-                    co_i[axis] = x_right;
-                    let y_right = data[&co_i];
-                    let x_frac = x_in - x_left as f32;
-                    self.interpolator.interpolate(y_left, y_right, x_frac)
-                })
-            }
-        }
-        Ok(tvec!(data.into_arc_tensor()))
+    fn validation(&self) -> Validation {
+        Validation::Rounding
     }
-}
 
-impl InferenceRulesOp for Resize {
+    op_onnx!();
+
     fn rules<'r, 'p: 'r, 's: 'r>(
         &'s self,
         s: &mut Solver<'r>,
         inputs: &'p [TensorProxy],
         outputs: &'p [TensorProxy],
     ) -> InferenceResult {
-        check_output_arity(&outputs, 1)?;
+        check_output_arity(outputs, 1)?;
         s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
         s.equals(&inputs[0].rank, &outputs[0].rank)?;
-        if inputs.len() == 3 && self.optional_scales_input == Some(2) {
-            rules_with_scales(self, s, inputs, outputs)
-        } else if inputs.len() == 3 && self.optional_sizes_input == Some(2) {
-            rules_with_sizes(self, s, inputs, outputs)
-        } else {
-            // bogus 4 inputs case
-            s.given_2(
-                &inputs[0].rank,
-                &inputs[self.optional_scales_input.unwrap()].shape,
-                move |s, input_rank, scale_shape| {
-                    if scale_shape.len() == 0 || scale_shape[0] != input_rank.to_dim() {
-                        rules_with_sizes(self, s, inputs, outputs)
-                    } else {
-                        rules_with_scales(self, s, inputs, outputs)
-                    }
-                },
-            )
+        match (self.optional_scales_input, self.optional_sizes_input) {
+            (Some(_), None) => rules_with_scales(self, s, inputs, outputs),
+            (None, Some(_)) => rules_with_sizes(self, s, inputs, outputs),
+            (Some(scales), Some(_)) => {
+                // scales may be given as an empty tensor when sizes are used
+                s.given_2(
+                    &inputs[0].rank,
+                    &inputs[scales].shape,
+                    move |s, input_rank, scale_shape| {
+                        if scale_shape.is_empty() || scale_shape[0] != input_rank.to_dim() {
+                            rules_with_sizes(self, s, inputs, outputs)
+                        } else {
+                            rules_with_scales(self, s, inputs, outputs)
+                        }
+                    },
+                )
+            }
+            (None, None) => bail!("Resize needs either scales or sizes"),
         }
     }
 
-    as_op!();
-    to_typed!();
+    fn wire(
+        &self,
+        prefix: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let konst = |ix: Option<usize>| -> TractResult<Option<Arc<Tensor>>> {
+            if let Some(ix) = ix {
+                if let Some(k) = model.outlet_fact(inputs[ix])?.konst.clone() {
+                    Ok(Some(k))
+                } else {
+                    bail!("Resize only supports constant roi, scales and sizes")
+                }
+            } else {
+                Ok(None)
+            }
+        };
+        let roi = konst(self.optional_roi_input)?;
+        let scales = konst(self.optional_scales_input)?;
+        let sizes = konst(self.optional_sizes_input)?;
+        let input_shape = model.outlet_fact(inputs[0])?.shape.to_tvec();
+        let op = self.core_op(&input_shape, roi.as_deref(), scales.as_deref(), sizes.as_deref())?;
+        model.wire_node(prefix, op, &[inputs[0]])
+    }
 }
 
 fn rules_with_scales<'r, 'p: 'r, 's: 'r>(
@@ -207,20 +221,27 @@ fn rules_with_scales<'r, 'p: 'r, 's: 'r>(
     s.equals(&scales.datum_type, f32::datum_type())?;
     s.equals(&scales.rank, 1)?;
     s.equals(&scales.shape[0], inputs[0].rank.bex().to_dim())?;
-    s.given_2(
-        &inputs[0].shape,
-        &inputs[op.optional_scales_input.unwrap()].value,
-        move |s, input_shape, scales| {
-            let input_shape =
-                input_shape.iter().map(|d| d.to_usize()).collect::<TractResult<TVec<usize>>>()?;
-            let output_size = op.compute_output_shape(&input_shape, Some(scales.as_ref()), None)?;
-            let rank = input_shape.len();
-            for i in 0..rank {
-                s.equals(&outputs[0].shape[i], output_size[i].to_dim())?;
-            }
-            Ok(())
-        },
-    )
+    let wire_shape = move |s: &mut Solver<'r>,
+                           shape: TVec<TDim>,
+                           roi: Option<Arc<Tensor>>,
+                           scales: Arc<Tensor>| {
+        let output_shape = op.output_shape(&shape, roi.as_deref(), Some(&scales), None)?;
+        for (i, d) in output_shape.into_iter().enumerate() {
+            s.equals(&outputs[0].shape[i], d)?;
+        }
+        Ok(())
+    };
+    match op.optional_roi_input {
+        Some(roi) if op.params.coord_transformer == CoordTransformer::TfCropAndResize => s.given_3(
+            &inputs[0].shape,
+            &inputs[roi].value,
+            &scales.value,
+            move |s, shape, roi, scales| wire_shape(s, shape, Some(roi), scales),
+        ),
+        _ => s.given_2(&inputs[0].shape, &scales.value, move |s, shape, scales| {
+            wire_shape(s, shape, None, scales)
+        }),
+    }
 }
 
 fn rules_with_sizes<'r, 'p: 'r, 's: 'r>(
@@ -239,31 +260,3 @@ fn rules_with_sizes<'r, 'p: 'r, 's: 'r>(
         Ok(())
     })
 }
-
-impl TypedOp for Resize {
-    as_op!();
-
-    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
-        let input_shape = if let Some(s) = inputs[0].shape.as_finite() {
-            s
-        } else {
-            bail!("Only constant input shape are supported in Resize")
-        };
-        let scales = self.optional_scales_input.and_then(|ix| inputs.get(ix));
-        let sizes = self.optional_sizes_input.and_then(|ix| inputs.get(ix));
-        let output_shape = self.compute_output_shape(
-            &*input_shape,
-            scales.and_then(|f| f.konst.as_deref()),
-            sizes.and_then(|f| f.konst.as_deref()),
-        )?;
-        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*output_shape)?))
-    }
-
-    fn declutter(
-        &self,
-        _model: &TypedModel,
-        _node: &TypedNode,
-    ) -> TractResult<Option<TypedModelPatch>> {
-        Ok(None)
-    }
-}
@@ -1,6 +1,6 @@
-use tract_nnef::internal::*;
 use tract_core::ndarray::*;
 use tract_core::ops::array::PadMode;
+use tract_nnef::internal::*;
 
 #[derive(Debug, Clone, Default, Hash)]
 struct PulsePadOpState {
@@ -52,6 +52,58 @@ impl PulsePadOpState {
         }
     }
 
+    /// Builds the output pulse from the input window, which holds
+    /// `op.overlap()` frames of past input. Input frame `k` sits at `k +
+    /// begin_input + overlap` in the window stream, output frame `o` at `o +
+    /// begin_input + before` in the output stream.
+    fn reflect(
+        &mut self,
+        op: &PulsePad,
+        input: &Tensor,
+        pulse_begin: usize,
+        end_input: usize,
+        after: usize,
+    ) -> TractResult<Tensor> {
+        let overlap = (2 * (op.before + after)) as isize;
+        let before = op.before as isize;
+        let len = end_input.saturating_sub(op.begin_input).min(std::isize::MAX as usize / 4) as isize;
+        let output_begin = (op.begin_input + op.before) as isize;
+        let indices: Vec<usize> = (0..op.pulse as isize)
+            .map(|ix| {
+                let o = pulse_begin as isize + ix - output_begin;
+                // outside of the padded signal, or right in the input
+                let default = ix + 2 * after as isize;
+                let k = if o < 0 || o >= before + len + after as isize {
+                    return default as usize;
+                } else if o < before {
+                    before - o
+                } else if o < before + len {
+                    o - before
+                } else {
+                    2 * (len - 1) + before - o
+                };
+                let ix = k + op.begin_input as isize + overlap - pulse_begin as isize;
+                if ix < 0 || ix >= op.pulse as isize + overlap {
+                    default as usize
+                } else {
+                    ix as usize
+                }
+            })
+            .collect();
+        unsafe {
+            Ok(tract_core::dispatch_copy_by_size!(Self::select(input.datum_type())(
+                input, op.axis, &indices
+            )))
+        }
+    }
+
+    unsafe fn select<T: Datum + Copy>(input: &Tensor, axis: usize, indices: &[usize]) -> Tensor {
+        let mut output =
+            input.to_array_view_unchecked::<T>().select(Axis(axis), indices).into_tensor();
+        output.set_datum_type(input.datum_type());
+        output
+    }
+
     fn pad(
         &mut self,
         session: &mut SessionState,
@@ -65,6 +117,10 @@ impl PulsePadOpState {
             op.end_input.eval(&session.resolved_symbols).to_usize().unwrap_or(std::usize::MAX);
         let after = op.after.eval(&session.resolved_symbols).to_usize().unwrap_or(std::usize::MAX);
 
+        if op.mode == PadMode::Reflect {
+            return self.reflect(op, &input, pulse_begin, end_input, after);
+        }
+
         if let PadMode::Edge = op.mode {
             if after != 0 && pulse_begin < end_input {
                 let latest_valid_frame = (end_input - pulse_begin).min(op.pulse) - 1;
@@ -162,6 +218,19 @@ pub struct PulsePad {
 
 tract_linalg::impl_dyn_hash!(PulsePad);
 
+impl PulsePad {
+    /// Frames of past input needed in the input window: reflect padding reads
+    /// up to 2 * before frames ahead and 2 * after frames behind the
+    /// padded frame.
+    pub fn overlap(&self) -> TractResult<usize> {
+        if self.mode == PadMode::Reflect {
+            Ok(2 * (self.before + self.after.to_usize()?))
+        } else {
+            Ok(0)
+        }
+    }
+}
+
 impl Op for PulsePad {
     fn name(&self) -> Cow<str> {
         "PulsePad".into()
@@ -194,7 +263,9 @@ impl EvalOp for PulsePad {
 
 impl TypedOp for PulsePad {
     fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
-        Ok(tvec!(inputs[0].clone()))
+        let mut shape = inputs[0].shape.to_tvec();
+        shape[self.axis] = self.pulse.to_dim();
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
     }
 
     as_op!();
@@ -28,7 +28,7 @@ pub trait WithPulse {
 
 impl WithPulse for tract_nnef::framework::Nnef {
     fn with_pulse(mut self) -> Self {
-        self = self.with_tract_core();
+        self = self.with_tract_core().with_symbol(stream_symbol());
         self.registries.push(tract_nnef_registry());
         self
     }
@@ -98,4 +98,33 @@ mod tests {
             TypedFact::dt_shape(DatumType::F32, &*vec!(4, 2, 3)).unwrap()
         );
     }
+
+    #[test]
+    fn test_const_output_is_an_error() {
+        let mut model = TypedModel::default();
+        let a = model
+            .add_source(
+                "a",
+                TypedFact::dt_shape(f32::datum_type(), [stream_dim()].as_ref()).unwrap(),
+            )
+            .unwrap();
+        let b = model.add_const("b", rctensor1(&[1f32])).unwrap();
+        model.set_output_outlets(&[a, b]).unwrap();
+        assert!(PulsedModel::new(&model, 4).is_err());
+    }
+
+    #[test]
+    fn test_unary_op_on_const_is_an_error() {
+        let mut model = TypedModel::default();
+        let _a = model
+            .add_source(
+                "a",
+                TypedFact::dt_shape(f32::datum_type(), [stream_dim()].as_ref()).unwrap(),
+            )
+            .unwrap();
+        let b = model.add_const("b", rctensor1(&[-1f32])).unwrap();
+        let abs = model.wire_node("abs", tract_core::ops::math::abs(), &[b]).unwrap();
+        model.set_output_outlets(&abs).unwrap();
+        assert!(PulsedModel::new(&model, 4).is_err());
+    }
 }
@@ -14,18 +14,45 @@ fn pulsify(
     mapping: &HashMap<OutletId, OutletId>,
     _pulse: usize,
 ) -> TractResult<TVec<OutletId>> {
-    if node.inputs.len() > 1 {
-        bail!("Pulsification not implemented for more than one input to Concat")
-    }
-
-    let input = mapping[&node.inputs[0]];
+    let input = crate::ops::pulsed_input(node, 0, mapping)?;
     let fact = target.outlet_fact(input)?;
 
     if fact.axis == op.axis {
         pulsify_along_concat_axis(op, source, node, target, mapping)
     } else {
-        bail!("Pulsify for Concat on a separate axis is not implemented (but possible)");
+        pulsify_across_concat_axis(op, node, target, mapping)
+    }
+}
+
+fn pulsify_across_concat_axis(
+    op: &TypedConcat,
+    node: &TypedNode,
+    target: &mut PulsedModel,
+    mapping: &HashMap<OutletId, OutletId>,
+) -> TractResult<TVec<OutletId>> {
+    if op.slices.iter().any(|s| !s.is_var()) {
+        bail!("Pulsify for Concat of constants on a separate axis is not implemented")
     }
+    let axis = target.outlet_fact(crate::ops::pulsed_input(node, 0, mapping)?)?.axis;
+    for ix in 0..node.inputs.len() {
+        if target.outlet_fact(crate::ops::pulsed_input(node, ix, mapping)?)?.axis != axis {
+            bail!("Concat inputs must be streamed along the same axis")
+        }
+    }
+    let inputs = crate::ops::binary::sync_inputs(node, target, mapping)?;
+    target.wire_node(&*node.name, op.clone(), &*inputs)
+}
+
+impl PulsedOp for TypedConcat {
+    fn pulsed_output_facts(&self, inputs: &[&PulsedFact]) -> TractResult<TVec<PulsedFact>> {
+        let mut fact = inputs[0].clone();
+        fact.shape[self.axis] =
+            inputs.iter().fold(0.to_dim(), |acc, input| acc + &input.shape[self.axis]);
+        Ok(tvec!(fact))
+    }
+
+    as_op!();
+    pulsed_op_to_typed_op!();
 }
 
 fn pulsify_along_concat_axis(
@@ -38,7 +65,7 @@ fn pulsify_along_concat_axis(
     if node.inputs.len() > 1 {
         bail!("Concat can not pulse more than on input on concat axis")
     }
-    let mut input = mapping[&node.inputs[0]];
+    let mut input = crate::ops::pulsed_input(node, 0, mapping)?;
     let fact = target.outlet_fact(input)?.clone();
     assert_eq!(fact.axis, op.axis);
     let var_index = op.slices.iter().position(|s| s.is_var()).unwrap();
@@ -249,3 +276,33 @@ fn range_in_range(needle: &Range<usize>, haystack: &Range<usize>) -> RangeInRang
         RangeInRange::Inside(needle.start - haystack.start)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tract_core::ops::array::ConcatSlice;
+
+    #[test]
+    fn concat_across_pulse_axis() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let a = model.add_source(
+            "a",
+            TypedFact::dt_shape(f32::datum_type(), [stream_dim(), 2.to_dim()].as_ref())?,
+        )?;
+        let b = model.add_source(
+            "b",
+            TypedFact::dt_shape(f32::datum_type(), [stream_dim(), 1.to_dim()].as_ref())?,
+        )?;
+        let concat = TypedConcat::new(1, tvec!(ConcatSlice::Var, ConcatSlice::Var));
+        let c = model.wire_node("c", concat, &[a, b])?;
+        model.set_output_outlets(&c)?;
+
+        let pulsed = PulsedModel::new(&model, 2)?;
+        assert_eq!(pulsed.output_fact(0)?.axis, 0);
+        let plan = SimplePlan::new(pulsed.into_typed()?)?;
+        let outputs =
+            plan.run(tvec!(tensor2(&[[1f32, 2.], [3., 4.]]), tensor2(&[[5f32], [6.]])))?;
+        assert_eq!(*outputs[0], tensor2(&[[1f32, 2., 5.], [3., 4., 6.]]));
+        Ok(())
+    }
+}
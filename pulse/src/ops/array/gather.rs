@@ -0,0 +1,100 @@
+use crate::internal::*;
+use tract_core::ops::array::Gather;
+
+submit_op_pulsifier!(Gather, pulsify);
+
+fn pulsify(
+    op: &Gather,
+    source: &TypedModel,
+    node: &TypedNode,
+    target: &mut PulsedModel,
+    mapping: &HashMap<OutletId, OutletId>,
+    _pulse: usize,
+) -> TractResult<TVec<OutletId>> {
+    let input = crate::ops::pulsed_input(node, 0, mapping)?;
+    if target.outlet_fact(input)?.axis == op.axis {
+        bail!("Can not gather along streaming axis");
+    }
+    let indices = if let Some(indices) = source.outlet_fact(node.inputs[1])?.konst.clone() {
+        indices
+    } else {
+        bail!("Gather pulsification requires constant indices")
+    };
+    target.wire_node(&*node.name, PulsedGather { axis: op.axis, indices }, &[input])
+}
+
+/// Gather with constant indices folded in, as constants are not part of the
+/// pulsed model. Decluttered back to a regular Gather.
+#[derive(Debug, Clone, Hash)]
+pub struct PulsedGather {
+    pub axis: usize,
+    pub indices: Arc<Tensor>,
+}
+
+tract_linalg::impl_dyn_hash!(PulsedGather);
+
+impl PulsedGather {
+    fn output_shape(&self, input_shape: &[TDim]) -> TractResult<TVec<TDim>> {
+        let indices_shape: TVec<TDim> = self.indices.shape().iter().map(|d| d.to_dim()).collect();
+        Gather::new(self.axis).compute_output_shape(input_shape, &indices_shape)
+    }
+}
+
+impl Op for PulsedGather {
+    fn name(&self) -> Cow<str> {
+        "PulsedGather".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {} indices: {:?}", self.axis, self.indices)])
+    }
+
+    op_pulse!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for PulsedGather {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        Gather::new(self.axis).eval(tvec!(inputs[0].clone(), self.indices.clone()))
+    }
+}
+
+impl TypedOp for PulsedGather {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = self.output_shape(&inputs[0].shape.to_tvec())?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        let mut patch = TypedModelPatch::default();
+        let input = patch.tap_model(model, node.inputs[0])?;
+        let indices = patch.add_const(format!("{}.indices", node.name), self.indices.clone())?;
+        let wire = patch.wire_node(&*node.name, Gather::new(self.axis), &[input, indices])?[0];
+        patch.shunt_outside(model, node.id.into(), wire)?;
+        Ok(Some(patch))
+    }
+}
+
+impl PulsedOp for PulsedGather {
+    fn pulsed_output_facts(&self, inputs: &[&PulsedFact]) -> TractResult<TVec<PulsedFact>> {
+        let mut fact = inputs[0].clone();
+        fact.shape = self.output_shape(&inputs[0].shape)?;
+        if fact.axis > self.axis {
+            fact.axis = fact.axis + self.indices.rank() - 1;
+        }
+        Ok(tvec!(fact))
+    }
+
+    as_op!();
+    pulsed_op_to_typed_op!();
+}
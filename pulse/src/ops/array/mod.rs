@@ -1,3 +1,5 @@
 mod concat;
+mod gather;
 mod pad;
 mod slice;
+mod tile;
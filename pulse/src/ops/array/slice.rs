@@ -12,7 +12,7 @@ fn pulsify<D: DimLike + ToDim + Hash>(
     mapping: &HashMap<OutletId, OutletId>,
     _pulse: usize,
 ) -> TractResult<TVec<OutletId>> {
-    let input = mapping[&node.inputs[0]];
+    let input = crate::ops::pulsed_input(node, 0, mapping)?;
     let fact = target.outlet_fact(input)?.clone();
     let op: Box<dyn PulsedOp> = if op.axis == fact.axis {
         let skip = op.start.to_usize()?;
@@ -7,20 +7,17 @@ submit_op_pulsifier!(UnaryOp, pulsify_un);
 submit_op_pulsifier!(TypedBinOp, pulsify_bin);
 submit_op_pulsifier!(Iff, pulsify_iff);
 
-fn sync_inputs(
+pub(crate) fn sync_inputs(
     node: &TypedNode,
     target: &mut PulsedModel,
     mapping: &HashMap<OutletId, OutletId>,
 ) -> TractResult<TVec<OutletId>> {
-    let delay = node
-        .inputs
-        .iter()
-        .map(|input| target.outlet_fact(mapping[input]).unwrap().delay)
-        .max()
-        .unwrap();
+    let pulsed = (0..node.inputs.len())
+        .map(|ix| crate::ops::pulsed_input(node, ix, mapping))
+        .collect::<TractResult<TVec<_>>>()?;
+    let delay = pulsed.iter().map(|input| target.outlet_fact(*input).unwrap().delay).max().unwrap();
     let mut inputs = tvec!();
-    for input in &node.inputs {
-        let mut input = mapping[input];
+    for mut input in pulsed {
         let fact = target.outlet_fact(input)?.clone();
         if fact.delay < delay {
             let add_delay = delay - fact.delay;
@@ -37,12 +34,23 @@ fn sync_inputs(
 
 fn pulsify_bin(
     op: &TypedBinOp,
-    _source: &TypedModel,
+    source: &TypedModel,
     node: &TypedNode,
     target: &mut PulsedModel,
     mapping: &HashMap<OutletId, OutletId>,
     _pulse: usize,
 ) -> TractResult<TVec<OutletId>> {
+    // constants are not in the pulsed model, they get folded in the op
+    if let Some(a) = source.outlet_fact(node.inputs[0])?.konst.clone() {
+        let input = crate::ops::pulsed_input(node, 1, mapping)?;
+        return target.wire_node(&*node.name, UnaryOp::new(op.0.clone(), a), &[input]);
+    }
+    if let Some(b) = source.outlet_fact(node.inputs[1])?.konst.clone() {
+        if let Some(unary) = op.0.unary_with_b_const(&b) {
+            let input = crate::ops::pulsed_input(node, 0, mapping)?;
+            return target.wire_node(&*node.name, unary, &[input]);
+        }
+    }
     let inputs = &*sync_inputs(node, target, mapping)?;
     target.wire_node(&*node.name, op.clone(), &inputs)
 }
@@ -70,7 +78,7 @@ fn pulsify_un(
     mapping: &HashMap<OutletId, OutletId>,
     _pulse: usize,
 ) -> TractResult<TVec<OutletId>> {
-    let input = mapping[&node.inputs[0]];
+    let input = crate::ops::pulsed_input(node, 0, mapping)?;
     target.wire_node(&*node.name, op.clone(), &[input])
 }
 
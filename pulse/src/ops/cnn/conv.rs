@@ -14,7 +14,7 @@ fn pulsify(
     fn zero<D: Datum>() -> Tensor {
         tensor0(D::default())
     }
-    let fact = target.outlet_fact(mapping[&node.inputs[0]])?;
+    let fact = target.outlet_fact(crate::ops::pulsed_input(node, 0, mapping)?)?;
     let zero = dispatch_numbers!(zero(fact.datum_type)());
     let (wire, pool_spec) = super::pools::pulsify(&op.pool_spec, source, node, target, mapping, Some(zero))?;
     target.wire_node(&node.name, ConvUnary { pool_spec, ..op.clone() }, &[wire])
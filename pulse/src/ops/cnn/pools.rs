@@ -16,7 +16,7 @@ fn pulsify_max_pool(
     fn min_value<D: Datum + tract_core::num_traits::Bounded>() -> Tensor {
         tensor0(D::min_value())
     }
-    let fact = target.outlet_fact(mapping[&node.inputs[0]])?;
+    let fact = target.outlet_fact(crate::ops::pulsed_input(node, 0, mapping)?)?;
     let min = dispatch_numbers!(min_value(fact.datum_type)());
     let (wire, pool_spec) = pulsify(&op.pool_spec, source, node, target, mapping, Some(min))?;
     target.wire_node(&node.name, MaxPool { pool_spec, ..op.clone() }, &[wire])
@@ -94,7 +94,7 @@ pub fn pulsify(
     mapping: &HashMap<OutletId, OutletId>,
     padding_value: Option<Tensor>,
 ) -> TractResult<(OutletId, PoolSpec)> {
-    let mut wire = mapping[&node.inputs[0]];
+    let mut wire = crate::ops::pulsed_input(node, 0, mapping)?;
     let mut fact: PulsedFact = target.outlet_fact(wire)?.clone();
     let input_shape = spec.data_format.shape(fact.shape.clone())?;
     if Some(fact.axis) == input_shape.n_axis() {
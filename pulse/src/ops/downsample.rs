@@ -11,7 +11,7 @@ fn pulsify(
     mapping: &HashMap<OutletId, OutletId>,
     _pulse: usize,
 ) -> TractResult<TVec<OutletId>> {
-    let input = mapping[&node.inputs[0]];
+    let input = crate::ops::pulsed_input(node, 0, mapping)?;
     let pulse = target.outlet_fact(input)?.pulse();
     let stride = if op.stride > 0 {
         op.stride as usize
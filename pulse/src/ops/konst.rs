@@ -0,0 +1,20 @@
+use crate::internal::*;
+use tract_core::ops::konst::Const;
+
+submit_op_pulsifier!(Const, pulsify);
+
+/// Constants are not streamed: they do not appear in the pulsed model, and
+/// the ops using them fetch their values from the source model.
+fn pulsify(
+    _op: &Const,
+    source: &TypedModel,
+    node: &TypedNode,
+    _target: &mut PulsedModel,
+    _mapping: &HashMap<OutletId, OutletId>,
+    _pulse: usize,
+) -> TractResult<TVec<OutletId>> {
+    if source.output_outlets()?.contains(&node.id.into()) {
+        bail!("Can not pulsify {}: constant outputs do not stream", node)
+    }
+    Ok(tvec!())
+}
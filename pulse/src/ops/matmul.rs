@@ -11,7 +11,7 @@ fn pulsify(
     mapping: &HashMap<OutletId, OutletId>,
     _pulse: usize,
 ) -> TractResult<TVec<OutletId>> {
-    let input = mapping[&node.inputs[0]];
+    let input = crate::ops::pulsed_input(node, 0, mapping)?;
     let fact = target.outlet_fact(input)?;
     if fact.axis >= fact.shape.len() - op.b_trans as usize {
         bail!("Can not pulsify MatMulUnaryA on the k dimension");
@@ -8,6 +8,7 @@ pub mod delay;
 pub mod downsample;
 pub mod dummy;
 pub mod element_wise;
+pub mod konst;
 pub mod matmul;
 pub mod nn;
 pub mod quant;
@@ -27,6 +28,18 @@ pub struct OpPulsifier {
     ) -> TractResult<TVec<OutletId>>,
 }
 
+/// The pulsed counterpart of the `ix`-th input of `node`. Constants are left
+/// out of pulsed models, so ops must fold their constant inputs in.
+pub(crate) fn pulsed_input(
+    node: &TypedNode,
+    ix: usize,
+    mapping: &HashMap<OutletId, OutletId>,
+) -> TractResult<OutletId> {
+    mapping.get(&node.inputs[ix]).cloned().ok_or_else(|| {
+        format_err!("Can not pulsify {}: its input #{} is a constant it can not fold", node, ix)
+    })
+}
+
 pub trait PulsedOp:
     Op
     + fmt::Debug
@@ -11,7 +11,7 @@ fn pulsify(
     mapping: &HashMap<OutletId, OutletId>,
     _pulse: usize,
 ) -> TractResult<TVec<OutletId>> {
-    let input = mapping[&node.inputs[0]];
+    let input = crate::ops::pulsed_input(node, 0, mapping)?;
     let axis = target.outlet_fact(input)?.axis;
     if op.axes.contains(&axis) {
         bail!("Can not reduce over streaming axis");
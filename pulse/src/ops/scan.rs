@@ -12,23 +12,32 @@ fn pulsify(
     _pulse: usize,
 ) -> TractResult<TVec<OutletId>> {
     for input_id in 0..node.inputs.len() {
-        let input = mapping[&node.inputs[input_id]];
+        let input = crate::ops::pulsed_input(node, input_id, mapping)?;
         let input_fact = target.outlet_fact(input)?;
-        let (_slot, axis, chunk) = op
+        let (_slot, axis, chunk) = if let Some(scan) = op
             .input_mapping
             .iter()
             .filter_map(InputMapping::as_scan)
             .find(|mapping| mapping.0 == input_id)
-            .unwrap();
+        {
+            scan
+        } else {
+            continue;
+        };
         if chunk < 0 {
-            bail!("Can not pulsify a backward scan.")
+            bail!(
+                "Can not pulsify a backward scan (like the reverse half of a bidirectional \
+                 recurrent network): it needs the end of the sequence to start."
+            )
         }
         if input_fact.axis != axis {
             bail!("Scan pulsification limited to scanning axis");
         }
     }
 
-    let pulse_inputs = node.inputs.iter().map(|i| mapping[i]).collect::<TVec<_>>();
+    let pulse_inputs = (0..node.inputs.len())
+        .map(|ix| crate::ops::pulsed_input(node, ix, mapping))
+        .collect::<TractResult<TVec<_>>>()?;
 
     let mut op = op.clone();
     op.skip = target.outlet_fact(pulse_inputs[0])?.delay;
@@ -43,7 +52,9 @@ impl PulsedOp for Scan {
             .iter()
             .enumerate()
             .find(|(_ix, om)| om.full_slot == Some(0))
-            .ok_or_else(|| format_err!("Expects output 0 to be the full stream (and no other output)"))?;
+            .ok_or_else(|| {
+                format_err!("Expects output 0 to be the full stream (and no other output)")
+            })?;
         let output_body_fact = self.body.output_fact(output_body_ix)?;
         let shape = output_body_fact
             .shape
@@ -72,3 +83,45 @@ impl PulsedOp for Scan {
     as_op!();
     pulsed_op_to_typed_op!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tract_core::ops::scan::OutputMapping;
+
+    #[test]
+    fn backward_scan_is_not_pulsifiable() {
+        let mut body = TypedModel::default();
+        let x = body
+            .add_source("x", TypedFact::dt_shape(f32::datum_type(), [1, 2].as_ref()).unwrap())
+            .unwrap();
+        body.set_output_outlets(&[x]).unwrap();
+        let scan = Scan::new(
+            body,
+            vec![InputMapping::Scan { slot: 0, axis: 0, chunk: -1 }],
+            vec![OutputMapping {
+                full_slot: Some(0),
+                axis: 0,
+                chunk: -1,
+                full_dim_hint: None,
+                last_value_slot: None,
+                state: false,
+            }],
+            None,
+            0,
+        )
+        .unwrap();
+        let mut model = TypedModel::default();
+        let source = model
+            .add_source(
+                "source",
+                TypedFact::dt_shape(f32::datum_type(), [stream_dim(), 2.to_dim()].as_ref())
+                    .unwrap(),
+            )
+            .unwrap();
+        model.wire_node("scan", scan, &[source]).unwrap();
+        model.auto_outputs().unwrap();
+        let err = PulsedModel::new(&model, 4).unwrap_err();
+        assert!(format!("{:?}", err).contains("backward scan"));
+    }
+}
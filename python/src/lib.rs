@@ -0,0 +1,273 @@
+//! Python bindings for tract.
+//!
+//! The module exposes the model loading, optimisation and execution pipeline
+//! with the same steps as the Rust API:
+//!
+//! ```python
+//! import numpy, tract
+//!
+//! model = (
+//!     tract.onnx()
+//!     .model_for_path("mobilenetv2-1.0.onnx")
+//!     .with_input_fact(0, "1x3x224x224xf32")
+//!     .into_optimized()
+//!     .into_runnable()
+//! )
+//! [result] = model.run([numpy.zeros((1, 3, 224, 224), dtype=numpy.float32)])
+//! ```
+
+use pyo3::exceptions::{PyIndexError, PyRuntimeError};
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+use tract_hir::internal::*;
+use tract_nnef::internal::Nnef as NnefFramework;
+use tract_pulse::internal::PulsedModelExt;
+
+mod tensor;
+
+pub(crate) trait ToPyResult<T> {
+    fn to_py(self) -> PyResult<T>;
+}
+
+impl<T, E: std::fmt::Debug> ToPyResult<T> for Result<T, E> {
+    fn to_py(self) -> PyResult<T> {
+        self.map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))
+    }
+}
+
+/// ONNX loader, as returned by `tract.onnx()`.
+#[pyclass(module = "tract")]
+struct Onnx(tract_onnx::Onnx);
+
+#[pymethods]
+impl Onnx {
+    /// Load an ONNX model from a file.
+    fn model_for_path(&self, path: std::path::PathBuf) -> PyResult<InferenceModel> {
+        Ok(InferenceModel(self.0.model_for_path(path).to_py()?))
+    }
+}
+
+/// NNEF loader and writer, as returned by `tract.nnef()`.
+#[pyclass(module = "tract")]
+struct Nnef(NnefFramework);
+
+#[pymethods]
+impl Nnef {
+    /// Enable tract-core operators extensions.
+    fn with_tract_core(mut slf: PyRefMut<Self>) -> PyRefMut<Self> {
+        let fw = std::mem::replace(&mut slf.0, tract_nnef::nnef());
+        slf.0 = fw.with_tract_core();
+        slf
+    }
+
+    /// Enable tract-onnx operators extensions.
+    fn with_onnx(mut slf: PyRefMut<Self>) -> PyRefMut<Self> {
+        use tract_onnx::WithOnnx;
+        let fw = std::mem::replace(&mut slf.0, tract_nnef::nnef());
+        slf.0 = fw.with_onnx();
+        slf
+    }
+
+    /// Enable tract-pulse operators extensions.
+    fn with_pulse(mut slf: PyRefMut<Self>) -> PyRefMut<Self> {
+        use tract_pulse::WithPulse;
+        let fw = std::mem::replace(&mut slf.0, tract_nnef::nnef());
+        slf.0 = fw.with_pulse();
+        slf
+    }
+
+    /// Load a NNEF model from a directory or an (uncompressed) tar file.
+    fn model_for_path(&self, path: std::path::PathBuf) -> PyResult<TypedModel> {
+        Ok(TypedModel(self.0.model_for_path(path).to_py()?))
+    }
+
+    /// Dump a model to a directory, in NNEF format.
+    fn write_model_to_dir(&self, model: &TypedModel, path: std::path::PathBuf) -> PyResult<()> {
+        self.0.write_to_dir(&model.0, path).to_py()
+    }
+
+    /// Dump a model to a tar file, in NNEF format.
+    fn write_model_to_tar(&self, model: &TypedModel, path: std::path::PathBuf) -> PyResult<()> {
+        let file = std::fs::File::create(path)?;
+        self.0.write_to_tar(&model.0, file).to_py()?;
+        Ok(())
+    }
+}
+
+/// A model as loaded by a framework, before analysis. Facts (types and shapes)
+/// may be partially known.
+#[pyclass(module = "tract")]
+#[derive(Clone)]
+struct InferenceModel(tract_hir::prelude::InferenceModel);
+
+#[pymethods]
+impl InferenceModel {
+    fn input_count(&self) -> PyResult<usize> {
+        Ok(self.0.input_outlets().to_py()?.len())
+    }
+
+    fn output_count(&self) -> PyResult<usize> {
+        Ok(self.0.output_outlets().to_py()?.len())
+    }
+
+    /// Return a copy of the model with the input fact set, using the tract
+    /// command line syntax: `1x3x224x224xf32`, `1xSx40xf32`.
+    fn with_input_fact(&self, input: usize, fact: &str) -> PyResult<InferenceModel> {
+        let fact = tensor::parse_fact(fact).to_py()?;
+        Ok(InferenceModel(self.0.clone().with_input_fact(input, fact).to_py()?))
+    }
+
+    /// Return a copy of the model with the output fact set, using the tract
+    /// command line syntax.
+    fn with_output_fact(&self, output: usize, fact: &str) -> PyResult<InferenceModel> {
+        let fact = tensor::parse_fact(fact).to_py()?;
+        Ok(InferenceModel(self.0.clone().with_output_fact(output, fact).to_py()?))
+    }
+
+    /// Return a copy of the model with outputs set to the named nodes.
+    fn with_output_names(&self, names: Vec<String>) -> PyResult<InferenceModel> {
+        let mut model = self.0.clone();
+        model.set_output_names(&names).to_py()?;
+        Ok(InferenceModel(model))
+    }
+
+    /// Run type and shape analysis, and translate to a TypedModel.
+    fn into_typed(&self) -> PyResult<TypedModel> {
+        Ok(TypedModel(self.0.clone().into_typed().to_py()?))
+    }
+
+    /// Translate to a TypedModel and declutter it.
+    fn into_decluttered(&self) -> PyResult<TypedModel> {
+        Ok(TypedModel(self.0.clone().into_typed().to_py()?.declutter().to_py()?))
+    }
+
+    /// Translate to a TypedModel and optimize it.
+    fn into_optimized(&self) -> PyResult<TypedModel> {
+        Ok(TypedModel(self.0.clone().into_optimized().to_py()?))
+    }
+
+    /// Make a runnable plan without performing any optimisation.
+    fn into_runnable(&self) -> PyResult<Runnable> {
+        self.into_typed()?.into_runnable()
+    }
+}
+
+/// A model with fully determined types and shapes.
+#[pyclass(module = "tract")]
+#[derive(Clone)]
+struct TypedModel(tract_hir::prelude::TypedModel);
+
+#[pymethods]
+impl TypedModel {
+    fn input_count(&self) -> PyResult<usize> {
+        Ok(self.0.input_outlets().to_py()?.len())
+    }
+
+    fn output_count(&self) -> PyResult<usize> {
+        Ok(self.0.output_outlets().to_py()?.len())
+    }
+
+    /// Return the type and shape of an input, like `1x3x224x224xF32`, in the
+    /// syntax accepted by `with_input_fact`. Constant values are not shown.
+    fn input_fact(&self, input: usize) -> PyResult<String> {
+        let outlets = self.0.input_outlets().to_py()?;
+        let outlet = outlets.get(input).ok_or_else(|| {
+            PyIndexError::new_err(format!("No input {}, model has {}", input, outlets.len()))
+        })?;
+        Ok(format!("{:?}", self.0.outlet_fact(*outlet).to_py()?.without_value()))
+    }
+
+    /// Return the type and shape of an output, like `1x1000xF32`, in the
+    /// syntax accepted by `with_output_fact`. Constant values are not shown.
+    fn output_fact(&self, output: usize) -> PyResult<String> {
+        let outlets = self.0.output_outlets().to_py()?;
+        let outlet = outlets.get(output).ok_or_else(|| {
+            PyIndexError::new_err(format!("No output {}, model has {}", output, outlets.len()))
+        })?;
+        Ok(format!("{:?}", self.0.outlet_fact(*outlet).to_py()?.without_value()))
+    }
+
+    /// Return a model property (like `pulse.delay`) as a numpy array.
+    fn property<'py>(&self, py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+        let prop = self
+            .0
+            .properties
+            .get(name)
+            .ok_or_else(|| PyRuntimeError::new_err(format!("No property named {}", name)))?;
+        tensor::to_numpy(py, prop)
+    }
+
+    fn declutter(&self) -> PyResult<TypedModel> {
+        Ok(TypedModel(self.0.declutter().to_py()?))
+    }
+
+    fn optimize(&self) -> PyResult<TypedModel> {
+        Ok(TypedModel(self.0.clone().optimize().to_py()?))
+    }
+
+    fn into_optimized(&self) -> PyResult<TypedModel> {
+        Ok(TypedModel(self.0.clone().into_optimized().to_py()?))
+    }
+
+    /// Pulsify the model along its streaming axis, with the given pulse size.
+    ///
+    /// The resulting model output delay is available as the `pulse.delay`
+    /// property.
+    fn pulse(&self, pulse: usize) -> PyResult<TypedModel> {
+        let pulsed = tract_pulse::internal::PulsedModel::new(&self.0, pulse).to_py()?;
+        Ok(TypedModel(pulsed.into_typed().to_py()?))
+    }
+
+    /// Make a runnable plan.
+    fn into_runnable(&self) -> PyResult<Runnable> {
+        Ok(Runnable(Arc::new(SimplePlan::new(self.0.clone()).to_py()?)))
+    }
+}
+
+/// A runnable plan. Plans are stateless: each call to run is independent.
+#[pyclass(module = "tract")]
+struct Runnable(Arc<TypedSimplePlan<tract_hir::prelude::TypedModel>>);
+
+#[pymethods]
+impl Runnable {
+    /// Run the model on a list of numpy arrays, and return the list of
+    /// outputs as numpy arrays.
+    fn run<'py>(
+        &self,
+        py: Python<'py>,
+        inputs: Vec<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let inputs = inputs.iter().map(tensor::from_numpy).collect::<PyResult<TVec<Tensor>>>()?;
+        let plan = self.0.clone();
+        let outputs = py.detach(move || plan.run(inputs)).to_py()?;
+        let outputs =
+            outputs.iter().map(|t| tensor::to_numpy(py, t)).collect::<PyResult<Vec<_>>>()?;
+        PyList::new(py, outputs)
+    }
+}
+
+/// Create an ONNX loader.
+#[pyfunction]
+fn onnx() -> Onnx {
+    Onnx(tract_onnx::onnx())
+}
+
+/// Create a NNEF loader and writer.
+#[pyfunction]
+fn nnef() -> Nnef {
+    Nnef(tract_nnef::nnef())
+}
+
+#[pymodule]
+#[pyo3(name = "tract")]
+fn tract_python(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<Onnx>()?;
+    m.add_class::<Nnef>()?;
+    m.add_class::<InferenceModel>()?;
+    m.add_class::<TypedModel>()?;
+    m.add_class::<Runnable>()?;
+    m.add_function(wrap_pyfunction!(onnx, m)?)?;
+    m.add_function(wrap_pyfunction!(nnef, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Conversions between numpy arrays and tract tensors.
+//!
+//! numpy and tract do not share the same ndarray version, so data crosses the
+//! boundary as a flat vector plus a shape.
+
+use numpy::{PyArray1, PyArrayDyn, PyArrayMethods, PyReadonlyArrayDyn, PyUntypedArrayMethods};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use tract_hir::internal::*;
+
+use crate::ToPyResult;
+
+fn tensor_from_numpy<T: Datum + numpy::Element + Copy>(
+    array: &Bound<PyAny>,
+) -> PyResult<Option<Tensor>> {
+    let array = match array.cast::<PyArrayDyn<T>>() {
+        Ok(array) => array.readonly(),
+        Err(_) => return Ok(None),
+    };
+    let array: PyReadonlyArrayDyn<T> = array;
+    let shape = array.shape().to_vec();
+    let data = array.as_array().iter().copied().collect::<Vec<T>>();
+    let tensor = tract_ndarray::ArrayD::from_shape_vec(shape, data).to_py()?.into_tensor();
+    Ok(Some(tensor))
+}
+
+/// Convert a numpy array to a tract tensor, copying its content.
+pub fn from_numpy(array: &Bound<PyAny>) -> PyResult<Tensor> {
+    macro_rules! try_types {
+        ($($t: ty),*) => {
+            $(
+                if let Some(t) = tensor_from_numpy::<$t>(array)? {
+                    return Ok(t);
+                }
+            )*
+        }
+    }
+    try_types!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, bool);
+    Err(PyTypeError::new_err(format!(
+        "Expected a numpy array of a numeric or boolean type, got {}",
+        array.get_type().name()?
+    )))
+}
+
+fn numpy_from_tensor<'py, T: Datum + numpy::Element>(
+    py: Python<'py>,
+    tensor: &Tensor,
+) -> PyResult<Bound<'py, PyAny>> {
+    let flat = PyArray1::<T>::from_slice(py, tensor.as_slice::<T>().to_py()?);
+    let array: Bound<'py, PyArrayDyn<T>> = flat.reshape(tensor.shape().to_vec())?;
+    Ok(array.into_any())
+}
+
+/// Convert a tract tensor to a numpy array, copying its content.
+pub fn to_numpy<'py>(py: Python<'py>, tensor: &Tensor) -> PyResult<Bound<'py, PyAny>> {
+    use DatumType::*;
+    match tensor.datum_type() {
+        F32 => numpy_from_tensor::<f32>(py, tensor),
+        F64 => numpy_from_tensor::<f64>(py, tensor),
+        I8 => numpy_from_tensor::<i8>(py, tensor),
+        I16 => numpy_from_tensor::<i16>(py, tensor),
+        I32 => numpy_from_tensor::<i32>(py, tensor),
+        I64 => numpy_from_tensor::<i64>(py, tensor),
+        U8 => numpy_from_tensor::<u8>(py, tensor),
+        U16 => numpy_from_tensor::<u16>(py, tensor),
+        U32 => numpy_from_tensor::<u32>(py, tensor),
+        U64 => numpy_from_tensor::<u64>(py, tensor),
+        Bool => numpy_from_tensor::<bool>(py, tensor),
+        F16 => {
+            let as_f32 = tensor.cast_to::<f32>().to_py()?;
+            numpy_from_tensor::<f32>(py, &as_f32)
+        }
+        dt => Err(PyTypeError::new_err(format!("Can not convert {:?} tensor to numpy", dt))),
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref SYMBOLS: std::sync::Mutex<SymbolTable> = std::sync::Mutex::new(
+        SymbolTable::default().with(tract_pulse::internal::stream_symbol())
+    );
+}
+
+/// Parse a fact specification, using the same syntax as the command line
+/// interface: `1x3x224x224xf32`, `1xSx40xf32` (streaming) or `_x10xi64`.
+/// Symbols with the same name are the same across calls.
+pub fn parse_fact(spec: &str) -> TractResult<InferenceFact> {
+    InferenceFact::parse(&mut SYMBOLS.lock().unwrap(), spec)
+}
@@ -0,0 +1,39 @@
+import numpy
+import pytest
+import tract
+
+GRAPH = """
+version 1.0;
+
+graph network(input) -> (output)
+{
+    input = external(shape = [1, 3]);
+    output = relu(input);
+}
+"""
+
+
+@pytest.fixture
+def model(tmp_path):
+    (tmp_path / "graph.nnef").write_text(GRAPH)
+    return tract.nnef().model_for_path(tmp_path)
+
+
+def test_facts(model):
+    assert model.input_count() == 1
+    assert model.output_count() == 1
+    assert model.input_fact(0) == "1x3xF32"
+    assert model.output_fact(0) == "1x3xF32"
+
+
+def test_fact_index_out_of_range(model):
+    with pytest.raises(IndexError):
+        model.input_fact(1)
+    with pytest.raises(IndexError):
+        model.output_fact(1)
+
+
+def test_run(model):
+    input = numpy.array([[-1.0, 0.0, 2.0]], dtype=numpy.float32)
+    [output] = model.into_optimized().into_runnable().run([input])
+    assert numpy.array_equal(output, numpy.array([[0.0, 0.0, 2.0]], dtype=numpy.float32))
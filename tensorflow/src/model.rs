@@ -228,6 +228,21 @@ impl Tensorflow {
         }
         model.set_input_outlets(&*inputs)?;
         model.auto_outputs()?;
+        if cf::lower_while_loops(&mut model)? {
+            // drop the loop frames nodes, control inputs are tracked by name
+            // as compaction renumbers the nodes
+            let names: Vec<(String, String)> = control_inputs
+                .iter()
+                .map(|&(node, prec)| (model.node(node).name.clone(), model.node(prec).name.clone()))
+                .collect();
+            model = model.compact()?;
+            control_inputs = names
+                .iter()
+                .filter_map(|(node, prec)| {
+                    Some((model.node_id_by_name(node).ok()?, model.node_id_by_name(prec).ok()?))
+                })
+                .collect();
+        }
         let extensions = TfModelExtensions { control_inputs, initializing_nodes: vec![] };
         Ok(TfModelAndExtensions(model, extensions))
     }
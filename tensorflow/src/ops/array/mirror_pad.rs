@@ -0,0 +1,68 @@
+use tract_hir::internal::*;
+use tract_hir::ops::array::PadMode;
+
+use crate::model::ParsingContext;
+use crate::tfpb::tensorflow::NodeDef;
+
+pub fn mirror_pad(_ctx: &ParsingContext, pb: &NodeDef) -> TractResult<Box<dyn InferenceOp>> {
+    let mode = match pb.get_attr_raw_str("mode")? {
+        b"REFLECT" => PadMode::Reflect,
+        b"SYMMETRIC" => PadMode::Symmetric,
+        s => bail!("unsupported MirrorPad mode {}", String::from_utf8_lossy(s)),
+    };
+    Ok(expand(MirrorPad::new(mode)))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+pub struct MirrorPad {
+    mode: PadMode,
+}
+
+tract_linalg::impl_dyn_hash!(MirrorPad);
+
+impl Expansion for MirrorPad {
+    fn name(&self) -> Cow<str> {
+        "MirrorPad".into()
+    }
+
+    op_tf!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].rank, &outputs[0].rank)?;
+        s.equals(&inputs[1].rank, 2)?;
+        s.equals(&inputs[1].shape[0], inputs[0].rank.bex().to_dim())?;
+        s.equals(&inputs[1].shape[1], 2.to_dim())?;
+        s.given_2(&inputs[0].shape, &inputs[1].value, move |s, shape, pads| {
+            let pads = pads.cast_to::<i64>()?;
+            let pads = pads.as_slice::<i64>()?;
+            for (ix, d) in shape.iter().enumerate() {
+                s.equals(&outputs[0].shape[ix], d.clone() + pads[2 * ix] + pads[2 * ix + 1])?;
+            }
+            Ok(())
+        })
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        if let Some(ref pads) = target.outlet_fact(inputs[1])?.konst {
+            let pads = pads.cast_to::<i64>()?;
+            let pads = pads.as_slice::<i64>()?.chunks(2).map(|p| (p[0] as usize, p[1] as usize));
+            let op = tract_hir::ops::array::Pad::new(pads.collect(), self.mode.clone());
+            target.wire_node(prefix, op, &[inputs[0]])
+        } else {
+            bail!("Need paddings to be const")
+        }
+    }
+}
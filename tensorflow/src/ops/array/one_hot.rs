@@ -0,0 +1,75 @@
+use tract_hir::internal::*;
+
+use crate::model::ParsingContext;
+use crate::tfpb::tensorflow::NodeDef;
+
+pub fn one_hot(_ctx: &ParsingContext, pb: &NodeDef) -> TractResult<Box<dyn InferenceOp>> {
+    let axis = pb.get_attr_opt_int("axis")?.unwrap_or(-1);
+    Ok(expand(OneHot::new(axis)))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+pub struct OneHot {
+    axis: i64,
+}
+
+tract_linalg::impl_dyn_hash!(OneHot);
+
+impl OneHot {
+    fn resolve_axis(&self, input_rank: usize) -> usize {
+        if self.axis < 0 {
+            (self.axis + input_rank as i64 + 1) as usize
+        } else {
+            self.axis as usize
+        }
+    }
+}
+
+impl Expansion for OneHot {
+    fn name(&self) -> Cow<str> {
+        "OneHot".into()
+    }
+
+    op_tf!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 4)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[1].rank, 0)?;
+        s.equals(&inputs[2].rank, 0)?;
+        s.equals(&inputs[3].rank, 0)?;
+        s.equals(&inputs[2].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[3].datum_type, &outputs[0].datum_type)?;
+        s.equals(inputs[0].rank.bex() + 1, &outputs[0].rank)?;
+        s.given_2(&inputs[0].shape, &inputs[1].value, move |s, shape, depth| {
+            let mut shape = shape.clone();
+            let axis = self.resolve_axis(shape.len());
+            shape.insert(axis, depth.cast_to_scalar::<i64>()?.to_dim());
+            s.equals(&outputs[0].shape, shape)
+        })
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let depth = target.outlet_fact(inputs[1])?.konst.clone();
+        let on = target.outlet_fact(inputs[2])?.konst.clone();
+        let off = target.outlet_fact(inputs[3])?.konst.clone();
+        if let (Some(depth), Some(on), Some(off)) = (depth, on, off) {
+            let axis = self.resolve_axis(target.outlet_fact(inputs[0])?.rank());
+            let dim = depth.cast_to_scalar::<i64>()? as usize;
+            let op = tract_onnx_opl::one_hot::OneHot { axis, dim, off, on };
+            target.wire_node(prefix, op, &[inputs[0]])
+        } else {
+            bail!("Need depth, on and off values to be const")
+        }
+    }
+}
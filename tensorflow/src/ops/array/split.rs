@@ -0,0 +1,172 @@
+use tract_hir::internal::*;
+use tract_hir::ops::array::Split as HirSplit;
+
+use crate::model::ParsingContext;
+use crate::tfpb::tensorflow::NodeDef;
+
+pub fn split(_ctx: &ParsingContext, pb: &NodeDef) -> TractResult<Box<dyn InferenceOp>> {
+    let num_split = pb.get_attr_int("num_split")?;
+    Ok(expand(Split::new(num_split)))
+}
+
+pub fn split_v(_ctx: &ParsingContext, pb: &NodeDef) -> TractResult<Box<dyn InferenceOp>> {
+    let num_split = pb.get_attr_int("num_split")?;
+    Ok(expand(SplitV::new(num_split)))
+}
+
+fn resolve_axis(axis: &Tensor, rank: usize) -> TractResult<usize> {
+    let axis = axis.cast_to_scalar::<i64>()?;
+    Ok(if axis < 0 { axis + rank as i64 } else { axis } as usize)
+}
+
+/// Split in `num_split` equal parts. Inputs are axis and value.
+#[derive(Debug, Clone, new, Hash)]
+pub struct Split {
+    num_split: usize,
+}
+
+tract_linalg::impl_dyn_hash!(Split);
+
+impl Expansion for Split {
+    fn name(&self) -> Cow<str> {
+        "Split".into()
+    }
+
+    op_tf!();
+
+    fn nboutputs(&self) -> TractResult<usize> {
+        Ok(self.num_split)
+    }
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, self.num_split)?;
+        s.equals(&inputs[0].rank, 0)?;
+        for output in outputs {
+            s.equals(&inputs[1].datum_type, &output.datum_type)?;
+            s.equals(&inputs[1].rank, &output.rank)?;
+        }
+        s.given_2(&inputs[1].shape, &inputs[0].value, move |s, shape, axis| {
+            let axis = resolve_axis(&axis, shape.len())?;
+            for output in outputs {
+                let mut shape = shape.clone();
+                shape[axis] = shape[axis].clone() / self.num_split;
+                s.equals(&output.shape, shape)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        if let Some(ref axis) = target.outlet_fact(inputs[0])?.konst {
+            let axis = resolve_axis(axis, target.outlet_fact(inputs[1])?.rank())?;
+            HirSplit::new(axis as isize, self.num_split, None).wire(prefix, target, &[inputs[1]])
+        } else {
+            bail!("Need axis to be const")
+        }
+    }
+}
+
+/// Split in parts of explicit sizes, one of them possibly -1. Inputs are
+/// value, size_splits and axis.
+#[derive(Debug, Clone, new, Hash)]
+pub struct SplitV {
+    num_split: usize,
+}
+
+tract_linalg::impl_dyn_hash!(SplitV);
+
+impl SplitV {
+    fn split_dims(&self, dim: &TDim, sizes: &Tensor) -> TractResult<TVec<TDim>> {
+        let sizes = sizes.cast_to::<i64>()?;
+        let sizes = sizes.as_slice::<i64>()?;
+        if sizes.len() != self.num_split {
+            bail!("Expected {} split sizes, got {:?}", self.num_split, sizes)
+        }
+        let known: i64 = sizes.iter().filter(|&&s| s >= 0).sum();
+        Ok(sizes.iter().map(|&s| if s >= 0 { s.to_dim() } else { dim.clone() - known }).collect())
+    }
+}
+
+impl Expansion for SplitV {
+    fn name(&self) -> Cow<str> {
+        "SplitV".into()
+    }
+
+    op_tf!();
+
+    fn nboutputs(&self) -> TractResult<usize> {
+        Ok(self.num_split)
+    }
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, self.num_split)?;
+        s.equals(&inputs[1].rank, 1)?;
+        s.equals(&inputs[2].rank, 0)?;
+        for output in outputs {
+            s.equals(&inputs[0].datum_type, &output.datum_type)?;
+            s.equals(&inputs[0].rank, &output.rank)?;
+        }
+        s.given_3(
+            &inputs[0].shape,
+            &inputs[1].value,
+            &inputs[2].value,
+            move |s, shape, sizes, axis| {
+                let axis = resolve_axis(&axis, shape.len())?;
+                let dims = self.split_dims(&shape[axis], &sizes)?;
+                for (output, dim) in outputs.iter().zip(dims) {
+                    let mut shape = shape.clone();
+                    shape[axis] = dim;
+                    s.equals(&output.shape, shape)?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let sizes = target.outlet_fact(inputs[1])?.konst.clone();
+        let axis = target.outlet_fact(inputs[2])?.konst.clone();
+        if let (Some(sizes), Some(axis)) = (sizes, axis) {
+            let input = target.outlet_fact(inputs[0])?.clone();
+            let axis = resolve_axis(&axis, input.rank())?;
+            let mut outputs = tvec!();
+            let mut current = 0.to_dim();
+            for len in self.split_dims(&input.shape[axis], &sizes)? {
+                let end = current.clone() + len;
+                outputs.push(
+                    target.wire_node(
+                        format!("{}.axis_{}_{}..{}", prefix, axis, current, end),
+                        tract_hir::ops::array::Slice::new(axis, current, end.clone()),
+                        &[inputs[0]],
+                    )?[0],
+                );
+                current = end;
+            }
+            Ok(outputs)
+        } else {
+            bail!("Need size_splits and axis to be const")
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use std::collections::HashSet;
+
 use tract_hir::internal::*;
+use tract_hir::ops::scan::InferenceWhile;
 
 use crate::model::TfOpRegister;
+use crate::ops::logic::{Merge, Switch};
 
 pub fn register_all_ops(reg: &mut TfOpRegister) {
     reg.insert("Enter", |_, node| {
@@ -91,7 +95,7 @@ impl EvalOp for NextIteration {
         _state: &mut SessionState,
         _id: usize,
     ) -> TractResult<Option<Box<dyn OpState>>> {
-        unimplemented!();
+        bail!("NextIteration {} is not part of a while loop that could be lowered", self.name)
     }
 }
 
@@ -117,3 +121,326 @@ impl InferenceRulesOp for NextIteration {
 
     as_op!();
 }
+
+fn enter_frame(node: &InferenceNode) -> Option<&str> {
+    if let Some(LoopGate(LoopGateRole::Enter(frame))) = node.op_as::<LoopGate>() {
+        Some(&**frame)
+    } else {
+        None
+    }
+}
+
+fn is_exit(node: &InferenceNode) -> bool {
+    if let Some(LoopGate(LoopGateRole::Exit)) = node.op_as::<LoopGate>() {
+        true
+    } else {
+        false
+    }
+}
+
+/// Replaces TensorFlow while frames (Enter, Merge, Switch, LoopCond,
+/// NextIteration and Exit nodes) by InferenceWhile ops, outermost frames
+/// first. Nested frames are lowered in the body of their parent.
+///
+/// The frame nodes are left in the graph as dummies, so the model must be
+/// compacted if this returns true.
+///
+/// Only loops over plain tensors are supported: TensorArray ops are not
+/// translated, so loops accumulating their outputs in a TensorArray (like
+/// dynamic_rnn) can not run. The resulting While is not pulsified either.
+pub fn lower_while_loops(model: &mut InferenceModel) -> TractResult<bool> {
+    let mut lowered = false;
+    while let Some((frame, nodes)) = outermost_frame(model) {
+        lower_frame(model, &frame, &nodes)
+            .with_context(|| format!("Lowering while loop {}", frame))?;
+        lowered = true;
+    }
+    Ok(lowered)
+}
+
+/// Nodes of a frame: everything downstream of its Enter nodes, up to (and
+/// excluding) its Exit nodes.
+fn frame_nodes(model: &InferenceModel, frame: &str) -> HashSet<usize> {
+    let mut nodes = HashSet::new();
+    let mut todo: Vec<(usize, usize)> =
+        model.nodes().iter().filter(|n| enter_frame(n) == Some(frame)).map(|n| (n.id, 0)).collect();
+    while let Some((node, depth)) = todo.pop() {
+        if !nodes.insert(node) {
+            continue;
+        }
+        for succ in model.node(node).outputs.iter().flat_map(|o| o.successors.iter()) {
+            let succ_node = model.node(succ.node);
+            let depth = if enter_frame(succ_node).is_some() {
+                depth + 1
+            } else if is_exit(succ_node) {
+                if depth == 0 {
+                    continue;
+                }
+                depth - 1
+            } else {
+                depth
+            };
+            todo.push((succ.node, depth));
+        }
+    }
+    nodes
+}
+
+fn outermost_frame(model: &InferenceModel) -> Option<(String, HashSet<usize>)> {
+    let mut frames: Vec<String> = vec![];
+    for node in model.nodes() {
+        if let Some(frame) = enter_frame(node) {
+            if !frames.iter().any(|f| f == frame) {
+                frames.push(frame.to_string());
+            }
+        }
+    }
+    let sets: Vec<HashSet<usize>> = frames.iter().map(|f| frame_nodes(model, f)).collect();
+    (0..frames.len())
+        .find(|&ix| {
+            model
+                .nodes()
+                .iter()
+                .filter(|n| enter_frame(n) == Some(&*frames[ix]))
+                .all(|n| (0..frames.len()).all(|other| other == ix || !sets[other].contains(&n.id)))
+        })
+        .map(|ix| (frames[ix].clone(), sets[ix].clone()))
+}
+
+#[derive(Debug)]
+struct LoopVar {
+    enter: usize,
+    merge: usize,
+    switch: usize,
+    next: usize,
+    next_value: OutletId,
+    exit: Option<usize>,
+}
+
+fn loop_var(model: &InferenceModel, enter: usize) -> TractResult<Option<LoopVar>> {
+    let successors = |node: usize, slot: usize| {
+        model.node(node).outputs[slot].successors.iter().map(|s| s.node).collect::<Vec<_>>()
+    };
+    let merge = if let Some(merge) =
+        successors(enter, 0).into_iter().find(|&n| model.node(n).op_is::<Merge>())
+    {
+        merge
+    } else {
+        return Ok(None);
+    };
+    let next = model
+        .node(merge)
+        .inputs
+        .iter()
+        .map(|i| i.node)
+        .find(|&n| model.node(n).op_is::<NextIteration>())
+        .ok_or_else(|| format_err!("Merge {} has no NextIteration input", model.node(merge)))?;
+    let next_value = model.node_by_name(format!("{}-Sink", model.node(next).name))?.inputs[0];
+    let switch = successors(merge, 0)
+        .into_iter()
+        .find(|&n| model.node(n).op_is::<Switch>())
+        .ok_or_else(|| format_err!("Merge {} is not followed by a Switch", model.node(merge)))?;
+    let exit = successors(switch, 0).into_iter().find(|&n| is_exit(model.node(n)));
+    Ok(Some(LoopVar { enter, merge, switch, next, next_value, exit }))
+}
+
+/// Copies the body of a frame to a new model, pulling nodes from the outputs
+/// of the loop (the condition and the next iteration values).
+struct BodyBuilder<'a> {
+    model: &'a InferenceModel,
+    frame: &'a str,
+    nodes: &'a HashSet<usize>,
+    body: InferenceModel,
+    mapping: HashMap<OutletId, OutletId>,
+    invariants: Vec<(OutletId, OutletId)>,
+    next_iterations: Vec<String>,
+}
+
+impl<'a> BodyBuilder<'a> {
+    fn invariant(&mut self, outer: OutletId) -> TractResult<OutletId> {
+        if let Some((_, inner)) = self.invariants.iter().find(|(o, _)| *o == outer) {
+            return Ok(*inner);
+        }
+        let name = format!("{}.invariant-{}", self.frame, self.invariants.len());
+        let inner = self.body.add_source(name, InferenceFact::default())?;
+        self.invariants.push((outer, inner));
+        Ok(inner)
+    }
+
+    fn pull(&mut self, outlet: OutletId) -> TractResult<OutletId> {
+        if let Some(wire) = self.mapping.get(&outlet) {
+            return Ok(*wire);
+        }
+        let node = self.model.node(outlet.node);
+        let wire = if enter_frame(node) == Some(self.frame) {
+            self.invariant(node.inputs[0])?
+        } else if !self.nodes.contains(&node.id)
+            && (!node.inputs.is_empty() || InferenceModel::is_source(&node.op))
+        {
+            self.invariant(outlet)?
+        } else {
+            let inputs =
+                node.inputs.iter().map(|i| self.pull(*i)).collect::<TractResult<TVec<_>>>()?;
+            let facts = node.outputs.iter().map(|o| o.fact.clone()).collect();
+            let id = self.body.add_node(&*node.name, node.op.clone(), facts)?;
+            for (ix, input) in inputs.iter().enumerate() {
+                self.body.add_edge(*input, InletId::new(id, ix))?;
+            }
+            for slot in 0..node.outputs.len() {
+                self.mapping.insert(OutletId::new(node.id, slot), OutletId::new(id, slot));
+            }
+            if node.op_is::<NextIteration>() {
+                self.next_iterations.push(node.name.clone());
+            }
+            OutletId::new(id, outlet.slot)
+        };
+        self.mapping.insert(outlet, wire);
+        Ok(wire)
+    }
+
+    /// NextIteration sinks of the nested frames are not reachable from the
+    /// body outputs, they must be copied along with their sources.
+    fn pull_next_iteration_sinks(&mut self) -> TractResult<()> {
+        while let Some(name) = self.next_iterations.pop() {
+            let sink = self.model.node_by_name(format!("{}-Sink", name))?;
+            let input = self.pull(sink.inputs[0])?;
+            let id = self.body.add_node(&*sink.name, sink.op.clone(), tvec!())?;
+            self.body.add_edge(input, InletId::new(id, 0))?;
+        }
+        Ok(())
+    }
+}
+
+fn lower_frame(model: &mut InferenceModel, frame: &str, nodes: &HashSet<usize>) -> TractResult<()> {
+    let mut enters: Vec<usize> =
+        nodes.iter().cloned().filter(|&n| enter_frame(model.node(n)) == Some(frame)).collect();
+    enters.sort();
+    let mut vars = vec![];
+    for enter in enters {
+        if let Some(var) = loop_var(model, enter)? {
+            vars.push(var);
+        }
+    }
+    if vars.is_empty() {
+        bail!("No loop variable found")
+    }
+    let loop_cond = model.node(vars[0].switch).inputs[1].node;
+    let cond = model.node(loop_cond).inputs[0];
+
+    let mut builder = BodyBuilder {
+        model,
+        frame,
+        nodes,
+        body: InferenceModel::default(),
+        mapping: HashMap::new(),
+        invariants: vec![],
+        next_iterations: vec![],
+    };
+    let mut states = tvec!();
+    for var in &vars {
+        let state =
+            builder.body.add_source(&*model.node(var.merge).name, InferenceFact::default())?;
+        builder.mapping.insert(OutletId::new(var.merge, 0), state);
+        builder.mapping.insert(OutletId::new(var.switch, 1), state);
+        states.push(state);
+    }
+    let mut outputs = tvec!(builder.pull(cond)?);
+    for var in &vars {
+        outputs.push(builder.pull(var.next_value)?);
+    }
+    builder.pull_next_iteration_sinks()?;
+    let BodyBuilder { mut body, invariants, .. } = builder;
+    let body_inputs: TVec<OutletId> =
+        states.iter().cloned().chain(invariants.iter().map(|(_, inner)| *inner)).collect();
+    body.set_input_outlets(&body_inputs)?;
+    body.set_output_outlets(&outputs)?;
+    if lower_while_loops(&mut body)? {
+        body = body.compact()?;
+    }
+
+    let inputs: TVec<OutletId> = vars
+        .iter()
+        .map(|var| model.node(var.enter).inputs[0])
+        .chain(invariants.iter().map(|(outer, _)| *outer))
+        .collect();
+    let op = InferenceWhile::new(body, vars.len());
+    let wires = model.wire_node(frame, op, &inputs)?;
+
+    let mut dead: HashSet<usize> = nodes.clone();
+    for (var, wire) in vars.iter().zip(wires.iter()) {
+        dead.insert(var.next);
+        if let Some(exit) = var.exit {
+            dead.insert(exit);
+            let exit = OutletId::new(exit, 0);
+            for succ in model.outlet_successors(exit).to_vec() {
+                model.add_edge(*wire, succ)?;
+            }
+            for output in model.outputs.iter_mut().filter(|o| **o == exit) {
+                *output = *wire;
+            }
+        }
+    }
+    model.outputs.retain(|o| !dead.contains(&o.node));
+    for node in dead {
+        let dummy = model.create_dummy();
+        model.node_mut(node).op = dummy;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tfpb;
+    use crate::tfpb::tensorflow::{DataType, TensorProto};
+    use std::convert::TryFrom;
+
+    fn konst(name: &str, t: Tensor, dt: DataType) -> tfpb::tensorflow::NodeDef {
+        let t = TensorProto::try_from(&t).unwrap();
+        tfpb::node().name(name).op("Const").attr("dtype", dt).attr("value", t)
+    }
+
+    fn node(name: &str, op: &str, inputs: &[&str]) -> tfpb::tensorflow::NodeDef {
+        inputs.iter().fold(tfpb::node().name(name).op(op), |n, i| n.input(i))
+    }
+
+    #[test]
+    fn lower_counter_loop() -> TractResult<()> {
+        // i = 0; while i < 3 { i = i + 1; acc = acc * 2 }
+        let enter =
+            |name: &str, input: &str| node(name, "Enter", &[input]).attr("frame_name", "while");
+        let graph = tfpb::graph()
+            .node(tfpb::node().name("x").op("Placeholder").attr("dtype", DataType::DtFloat))
+            .node(konst("zero", tensor0(0i32), DataType::DtInt32))
+            .node(konst("n", tensor0(3i32), DataType::DtInt32))
+            .node(enter("while/i_enter", "zero"))
+            .node(enter("while/acc_enter", "x"))
+            .node(enter("while/n_enter", "n"))
+            .node(node("while/i_merge", "Merge", &["while/i_enter", "while/i_next"]).attr("N", 2))
+            .node(
+                node("while/acc_merge", "Merge", &["while/acc_enter", "while/acc_next"])
+                    .attr("N", 2),
+            )
+            .node(node("while/less", "Less", &["while/i_merge", "while/n_enter"]))
+            .node(node("while/cond", "LoopCond", &["while/less"]))
+            .node(node("while/i_switch", "Switch", &["while/i_merge", "while/cond"]))
+            .node(node("while/acc_switch", "Switch", &["while/acc_merge", "while/cond"]))
+            .node(node("while/i_exit", "Exit", &["while/i_switch"]))
+            .node(node("while/acc_exit", "Exit", &["while/acc_switch"]))
+            .node(konst("while/one", tensor0(1i32), DataType::DtInt32).input("^while/i_merge"))
+            .node(konst("while/two", tensor0(2f32), DataType::DtFloat).input("^while/i_merge"))
+            .node(node("while/i_add", "Add", &["while/i_switch:1", "while/one"]))
+            .node(node("while/acc_mul", "Mul", &["while/acc_switch:1", "while/two"]))
+            .node(node("while/i_next", "NextIteration", &["while/i_add"]))
+            .node(node("while/acc_next", "NextIteration", &["while/acc_mul"]))
+            .node(node("y", "Identity", &["while/acc_exit"]));
+        let mut model = crate::tensorflow().model_for_proto_model(&graph)?;
+        model.set_output_names(&["y"])?;
+        model.set_input_fact(0, InferenceFact::dt_shape(f32::datum_type(), shapefactoid!(2)))?;
+        let model = model.into_optimized()?;
+        let plan = SimplePlan::new(&model)?;
+        let output = plan.run(tvec!(tensor1(&[1f32, 2.0])))?;
+        assert_eq!(output[0], rctensor1(&[8f32, 16.0]));
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::array::{
+    CoordTransformer, Interpolator, Nearest, Resize as CoreResize, ResizeAxis,
+};
+
+use tract_onnx_opl::non_max_suppression::{BoxRepr, NonMaxSuppression as NonMaxSuppressionOpl};
+
+use crate::model::ParsingContext;
+use crate::model::TfOpRegister;
+use crate::tfpb::tensorflow::NodeDef;
+
+pub fn register_all_ops(reg: &mut TfOpRegister) {
+    reg.insert("NonMaxSuppressionV3", non_max_suppression);
+    reg.insert("NonMaxSuppressionV4", non_max_suppression);
+    reg.insert("ResizeBilinear", resize);
+    reg.insert("ResizeNearestNeighbor", resize);
+}
+
+fn resize(_ctx: &ParsingContext, pb: &NodeDef) -> TractResult<Box<dyn InferenceOp>> {
+    let align_corners = pb.get_attr_opt_bool("align_corners")?.unwrap_or(false);
+    let half_pixel_centers = pb.get_attr_opt_bool("half_pixel_centers")?.unwrap_or(false);
+    if align_corners && half_pixel_centers {
+        bail!("align_corners and half_pixel_centers can not be both set")
+    }
+    let params = if pb.op == "ResizeBilinear" {
+        let coord_transformer = if align_corners {
+            CoordTransformer::AlignCorners
+        } else if half_pixel_centers {
+            CoordTransformer::HalfPixel
+        } else {
+            CoordTransformer::Asymmetric
+        };
+        CoreResize {
+            coord_transformer,
+            interpolator: Interpolator::Linear,
+            ..CoreResize::default()
+        }
+    } else {
+        let (coord_transformer, nearest) = if align_corners {
+            (CoordTransformer::AlignCorners, Nearest::RoundPreferCeil)
+        } else if half_pixel_centers {
+            (CoordTransformer::TfHalfPixelForNn, Nearest::Floor)
+        } else {
+            (CoordTransformer::Asymmetric, Nearest::Floor)
+        };
+        CoreResize { coord_transformer, nearest, ..CoreResize::default() }
+    };
+    Ok(expand(Resize(params)))
+}
+
+/// TensorFlow image resizing, NHWC with a constant (height, width) size
+/// input. Bilinear resizing always outputs f32.
+#[derive(Debug, Clone, Hash)]
+pub struct Resize(CoreResize);
+
+tract_linalg::impl_dyn_hash!(Resize);
+
+impl Resize {
+    fn output_dt(&self, input_dt: DatumType) -> DatumType {
+        if self.0.interpolator == Interpolator::Nearest {
+            input_dt
+        } else {
+            DatumType::F32
+        }
+    }
+}
+
+impl Expansion for Resize {
+    fn name(&self) -> Cow<str> {
+        "Resize".into()
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_tf!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(inputs, 2)?;
+        check_output_arity(outputs, 1)?;
+        s.equals(&inputs[0].rank, 4)?;
+        s.equals(&outputs[0].rank, 4)?;
+        s.equals(&inputs[1].datum_type, DatumType::I32)?;
+        s.equals(&inputs[1].shape, shapefactoid!(2))?;
+        s.given(&inputs[0].datum_type, move |s, dt| {
+            s.equals(&outputs[0].datum_type, self.output_dt(dt))
+        })?;
+        s.equals(&inputs[0].shape[0], &outputs[0].shape[0])?;
+        s.equals(&inputs[0].shape[3], &outputs[0].shape[3])?;
+        s.given(&inputs[1].value, move |s, size| {
+            let size = size.cast_to::<i64>()?;
+            let size = size.as_slice::<i64>()?;
+            s.equals(&outputs[0].shape[1], size[0].to_dim())?;
+            s.equals(&outputs[0].shape[2], size[1].to_dim())
+        })
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let size = if let Some(size) = model.outlet_fact(inputs[1])?.konst.clone() {
+            size.cast_to::<i64>()?.into_owned()
+        } else {
+            bail!("Resize needs a constant size input")
+        };
+        let input_fact = model.outlet_fact(inputs[0])?.clone();
+        let mut axes = tvec!();
+        for (ix, &len) in size.as_slice::<i64>()?.iter().enumerate() {
+            let len_in = input_fact.shape[ix + 1].to_usize()?;
+            let scale = len as f32 / len_in as f32;
+            axes.push(ResizeAxis::new(ix + 1, len as usize, scale, 0.0, 1.0));
+        }
+        let mut wire = inputs[0];
+        let dt = self.output_dt(input_fact.datum_type);
+        if dt != input_fact.datum_type {
+            wire =
+                model.wire_node(format!("{}.cast", prefix), tract_hir::ops::cast(dt), &[wire])?[0];
+        }
+        model.wire_node(prefix, CoreResize { axes, ..self.0.clone() }, &[wire])
+    }
+}
+
+fn non_max_suppression(_ctx: &ParsingContext, pb: &NodeDef) -> TractResult<Box<dyn InferenceOp>> {
+    let pad_to_max_output_size = if pb.op == "NonMaxSuppressionV4" {
+        Some(pb.get_attr_opt_bool("pad_to_max_output_size")?.unwrap_or(false))
+    } else {
+        None
+    };
+    Ok(expand(NonMaxSuppression {
+        pad_to_max_output_size,
+        num_selected_indices_symbol: Symbol::new('n'),
+    }))
+}
+
+/// TensorFlow NonMaxSuppressionV3 and V4, mapped to the ONNX operator with a
+/// single batch and class. V4 has a second output with the number of valid
+/// indices, and can pad the selection to max_output_size.
+#[derive(Debug, Clone, Hash)]
+pub struct NonMaxSuppression {
+    pad_to_max_output_size: Option<bool>,
+    /// Number of selected boxes, one symbol per node.
+    num_selected_indices_symbol: Symbol,
+}
+
+tract_linalg::impl_dyn_hash!(NonMaxSuppression);
+
+impl Expansion for NonMaxSuppression {
+    fn name(&self) -> Cow<str> {
+        "NonMaxSuppression".into()
+    }
+
+    op_tf!();
+
+    fn nboutputs(&self) -> TractResult<usize> {
+        Ok(1 + self.pad_to_max_output_size.is_some() as usize)
+    }
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(inputs, 5)?;
+        check_output_arity(outputs, self.nboutputs()?)?;
+        s.equals(&inputs[0].rank, 2)?;
+        s.equals(&inputs[0].shape[1], 4.to_dim())?;
+        s.equals(&inputs[1].rank, 1)?;
+        s.equals(&inputs[0].shape[0], &inputs[1].shape[0])?;
+        s.equals(&outputs[0].datum_type, DatumType::I32)?;
+        s.equals(&outputs[0].rank, 1)?;
+        if let Some(pad) = self.pad_to_max_output_size {
+            s.equals(&outputs[1].datum_type, DatumType::I32)?;
+            s.equals(&outputs[1].rank, 0)?;
+            if pad {
+                s.given(&inputs[2].value, move |s, max| {
+                    let max = max.cast_to_scalar::<i64>()?;
+                    s.equals(&outputs[0].shape[0], max.to_dim())
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        model: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let pad_to = if self.pad_to_max_output_size == Some(true) {
+            if let Some(max) = &model.outlet_fact(inputs[2])?.konst {
+                Some(max.cast_to_scalar::<i64>()?.max(0) as usize)
+            } else {
+                bail!("NonMaxSuppressionV4 padding needs a constant max_output_size")
+            }
+        } else {
+            None
+        };
+        let boxes = model.wire_node(format!("{}.boxes", prefix), AxisOp::Add(0), &[inputs[0]])?;
+        let mut scores = inputs[1];
+        for axis in 0..2 {
+            scores = model.wire_node(
+                format!("{}.scores.axis-{}", prefix, axis),
+                AxisOp::Add(0),
+                &[scores],
+            )?[0];
+        }
+        let op = NonMaxSuppressionOpl {
+            center_point_box: BoxRepr::TwoPoints,
+            num_selected_indices_symbol: self.num_selected_indices_symbol,
+            has_score_threshold: true,
+        };
+        let selected = model.wire_node(
+            format!("{}.nms", prefix),
+            op,
+            &[boxes[0], scores, inputs[2], inputs[3], inputs[4]],
+        )?;
+        let mut outputs = model.wire_node(prefix, SelectedBoxes { pad_to }, &selected)?;
+        outputs.truncate(self.nboutputs()?);
+        Ok(outputs)
+    }
+}
+
+/// Extract the box indices from a [selected, 3] NonMaxSuppression output,
+/// optionally zero-padded to a fixed length, and count them.
+#[derive(Debug, Clone, Hash)]
+struct SelectedBoxes {
+    pad_to: Option<usize>,
+}
+
+tract_linalg::impl_dyn_hash!(SelectedBoxes);
+
+impl Op for SelectedBoxes {
+    fn name(&self) -> Cow<str> {
+        "SelectedBoxes".into()
+    }
+
+    op_tf!();
+    op_as_typed_op!();
+}
+
+impl EvalOp for SelectedBoxes {
+    fn is_stateless(&self) -> bool {
+        true
+    }
+
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let selected = args_1!(inputs);
+        let selected = selected.to_array_view::<i64>()?;
+        let mut indices: Vec<i32> = selected.outer_iter().map(|s| s[2] as i32).collect();
+        let valid = indices.len() as i32;
+        if let Some(len) = self.pad_to {
+            indices.resize(len, 0);
+        }
+        Ok(tvec!(rctensor1(&indices), rctensor0(valid)))
+    }
+}
+
+impl TypedOp for SelectedBoxes {
+    as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let len = self.pad_to.map(|len| len.to_dim()).unwrap_or(inputs[0].shape[0].clone());
+        Ok(tvec!(
+            TypedFact::dt_shape(DatumType::I32, &[len][..])?,
+            TypedFact::dt_shape(DatumType::I32, ())?
+        ))
+    }
+}
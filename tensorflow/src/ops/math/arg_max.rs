@@ -0,0 +1,69 @@
+use tract_hir::internal::*;
+use tract_hir::ops::nn;
+
+use crate::model::ParsingContext;
+use crate::tfpb::tensorflow::NodeDef;
+
+pub fn arg_max(_ctx: &ParsingContext, pb: &NodeDef) -> TractResult<Box<dyn InferenceOp>> {
+    let output_type = pb.get_attr_opt_datum_type("output_type")?.unwrap_or(DatumType::I64);
+    Ok(expand(ArgMax::new(output_type)))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+pub struct ArgMax {
+    output_type: DatumType,
+}
+
+tract_linalg::impl_dyn_hash!(ArgMax);
+
+impl Expansion for ArgMax {
+    fn name(&self) -> Cow<str> {
+        "ArgMax".into()
+    }
+
+    op_tf!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&outputs[0].datum_type, self.output_type)?;
+        s.equals(&inputs[1].rank, 0)?;
+        s.equals(inputs[0].rank.bex() - 1, &outputs[0].rank)?;
+        s.given_2(&inputs[0].shape, &inputs[1].value, move |s, shape, axis| {
+            let axis = axis.cast_to_scalar::<i64>()?;
+            let axis = if axis < 0 { axis + shape.len() as i64 } else { axis } as usize;
+            let mut shape = shape.clone();
+            shape.remove(axis);
+            s.equals(&outputs[0].shape, shape)
+        })
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        if let Some(ref axis) = target.outlet_fact(inputs[1])?.konst {
+            let axis = axis.cast_to_scalar::<i64>()?;
+            let op = nn::Reduce::new(Some(vec![axis]), false, nn::Reducer::ArgMax(false));
+            let wire = op.wire(prefix, target, &[inputs[0]])?;
+            if self.output_type == DatumType::I64 {
+                Ok(wire)
+            } else {
+                target.wire_node(
+                    format!("{}.cast", prefix),
+                    tract_hir::ops::cast(self.output_type),
+                    &wire,
+                )
+            }
+        } else {
+            bail!("Need dimension to be const")
+        }
+    }
+}
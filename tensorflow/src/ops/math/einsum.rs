@@ -0,0 +1,61 @@
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::einsum;
+
+use crate::model::ParsingContext;
+use crate::tfpb::tensorflow::NodeDef;
+
+pub fn einsum(_ctx: &ParsingContext, pb: &NodeDef) -> TractResult<Box<dyn InferenceOp>> {
+    let equation = pb.get_attr_str("equation")?;
+    Ok(expand(Einsum::new(equation)))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+pub struct Einsum {
+    equation: String,
+}
+
+tract_linalg::impl_dyn_hash!(Einsum);
+
+impl Expansion for Einsum {
+    fn name(&self) -> Cow<str> {
+        "Einsum".into()
+    }
+
+    op_tf!();
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![self.equation.clone()])
+    }
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+        }
+        s.given_all(inputs.iter().map(|i| &i.shape), move |s, shapes: Vec<TVec<TDim>>| {
+            let ranks = shapes.iter().map(|s| s.len()).collect::<TVec<_>>();
+            let expr = einsum::Expr::parse(&self.equation, &ranks)?;
+            let shapes = shapes.iter().map(|s| &**s).collect::<TVec<_>>();
+            s.equals(&outputs[0].shape, expr.output_shape(&shapes)?)
+        })
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let ranks = inputs
+            .iter()
+            .map(|i| Ok(target.outlet_fact(*i)?.rank()))
+            .collect::<TractResult<TVec<_>>>()?;
+        let expr = einsum::Expr::parse(&self.equation, &ranks)?;
+        target.wire_node(prefix, einsum::EinSum::new(expr), inputs)
+    }
+}
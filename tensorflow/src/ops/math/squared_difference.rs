@@ -0,0 +1,46 @@
+use tract_hir::internal::*;
+use tract_hir::ops;
+use tract_hir::tract_core::ops::binary::BinMiniOp;
+
+use crate::model::ParsingContext;
+use crate::tfpb::tensorflow::NodeDef;
+
+pub fn squared_difference(
+    _ctx: &ParsingContext,
+    _pb: &NodeDef,
+) -> TractResult<Box<dyn InferenceOp>> {
+    Ok(expand(SquaredDifference))
+}
+
+#[derive(Debug, Clone, new, Hash)]
+pub struct SquaredDifference;
+
+tract_linalg::impl_dyn_hash!(SquaredDifference);
+
+impl Expansion for SquaredDifference {
+    fn name(&self) -> Cow<str> {
+        "SquaredDifference".into()
+    }
+
+    op_tf!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        ops::binary::rules(s, inputs, outputs, move |a, b| ops::math::Sub.result_datum_type(a, b))
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let sub = ops::binary::InferenceBinOp(Box::new(ops::math::Sub));
+        let diff = sub.wire(&format!("{}.sub", prefix), target, inputs)?;
+        target.wire_node(format!("{}.square", prefix), ops::math::square(), &diff)
+    }
+}
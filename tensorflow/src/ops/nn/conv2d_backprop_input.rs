@@ -0,0 +1,109 @@
+use tract_hir::internal::*;
+use tract_hir::ops::cnn::{Deconv, KernelFormat, PaddingSpec};
+use tract_hir::ops::nn::DataFormat;
+
+use crate::model::ParsingContext;
+use crate::tfpb::tensorflow::NodeDef;
+
+pub fn conv2d_backprop_input(
+    _ctx: &ParsingContext,
+    pb: &NodeDef,
+) -> TractResult<Box<dyn InferenceOp>> {
+    let data_format = super::data_format(pb)?;
+    let strides = super::strides(pb)?;
+    let dilations = pb.get_attr_opt_list_int("dilations")?.unwrap_or(vec![1, 1, 1, 1]);
+    let padding = super::padding(pb)?;
+    let geo = data_format.shape(tvec!(0, 1, 2, 3))?.hw_axes();
+    Ok(expand(Conv2DBackpropInput::new(
+        data_format,
+        padding,
+        strides[geo.clone()].into(),
+        dilations[geo].into(),
+    )))
+}
+
+/// Gradient of Conv2D with respect to its input, a.k.a. transposed
+/// convolution. Inputs are input_sizes, filter and out_backprop.
+#[derive(Debug, Clone, new, Hash)]
+pub struct Conv2DBackpropInput {
+    data_format: DataFormat,
+    padding: PaddingSpec,
+    strides: TVec<usize>,
+    dilations: TVec<usize>,
+}
+
+tract_linalg::impl_dyn_hash!(Conv2DBackpropInput);
+
+impl Expansion for Conv2DBackpropInput {
+    fn name(&self) -> Cow<str> {
+        "Conv2DBackpropInput".into()
+    }
+
+    op_tf!();
+
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].rank, 1)?;
+        s.equals(&inputs[0].shape[0], 4.to_dim())?;
+        s.equals(&inputs[1].rank, 4)?;
+        s.equals(&inputs[2].rank, 4)?;
+        s.equals(&inputs[1].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[2].datum_type, &outputs[0].datum_type)?;
+        s.equals(&outputs[0].rank, 4)?;
+        s.given(&inputs[0].value, move |s, sizes| {
+            let sizes = sizes.cast_to::<TDim>()?;
+            s.equals(
+                &outputs[0].shape,
+                sizes.as_slice::<TDim>()?.iter().cloned().collect::<TVec<_>>(),
+            )
+        })
+    }
+
+    fn wire(
+        &self,
+        prefix: &str,
+        target: &mut TypedModel,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let sizes = target.outlet_fact(inputs[0])?.konst.clone();
+        let filter = target.outlet_fact(inputs[1])?.shape.as_finite();
+        let input = target.outlet_fact(inputs[2])?.shape.as_finite();
+        let (sizes, filter, input) = if let (Some(s), Some(f), Some(i)) = (sizes, filter, input) {
+            (s, f, i)
+        } else {
+            bail!("Need input_sizes to be const, and filter and out_backprop shapes to be known")
+        };
+        let sizes = sizes.cast_to::<i64>()?;
+        let sizes: TVec<usize> = sizes.as_slice::<i64>()?.iter().map(|&d| d as usize).collect();
+        let output_shape = self.data_format.shape(&sizes)?;
+        let input_shape = self.data_format.shape(&input)?;
+        let output_spatial: TVec<usize> = output_shape.hw_dims().into();
+        let pads_before = (0..2)
+            .map(|geo| match self.padding {
+                PaddingSpec::Valid => Ok(0),
+                PaddingSpec::SameUpper => {
+                    let needed = (input_shape.hw_dims()[geo] - 1) * self.strides[geo]
+                        + (filter[geo] - 1) * self.dilations[geo]
+                        + 1;
+                    Ok(needed.saturating_sub(output_spatial[geo]) / 2)
+                }
+                ref p => bail!("Unsupported padding {:?}", p),
+            })
+            .collect::<TractResult<TVec<usize>>>()?;
+        let op = Deconv::new(
+            self.data_format,
+            KernelFormat::HWIO,
+            self.strides.clone(),
+            self.dilations.clone(),
+            pads_before,
+            output_spatial,
+        );
+        target.wire_node(prefix, op, &[inputs[2], inputs[1]])
+    }
+}
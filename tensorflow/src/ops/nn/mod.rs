@@ -6,6 +6,7 @@ use crate::model::TfOpRegister;
 use crate::tfpb::tensorflow::NodeDef;
 
 pub mod conv2d;
+pub mod conv2d_backprop_input;
 pub mod dw_conv2d;
 pub mod fused_batch_norm;
 pub mod pools;
@@ -14,8 +15,14 @@ pub mod s2b;
 pub fn register_all_ops(reg: &mut TfOpRegister) {
     reg.insert("AvgPool", pools::avgpool);
     reg.insert("Conv2D", conv2d::conv2d);
+    reg.insert("Conv2DBackpropInput", conv2d_backprop_input::conv2d_backprop_input);
     reg.insert("DepthwiseConv2dNative", dw_conv2d::depthwise_conv2d);
     reg.insert("FusedBatchNorm", fused_batch_norm::fused_batch_norm);
+    reg.insert("FusedBatchNormV3", fused_batch_norm::fused_batch_norm);
+    reg.insert("LeakyRelu", |_, pb| {
+        let alpha = pb.get_attr_opt_float("alpha")?.unwrap_or(0.2);
+        Ok(expand(tract_hir::ops::activations::LeakyRelu(alpha)))
+    });
     reg.insert("MaxPool", pools::maxpool);
     reg.insert("Relu", |_, _| Ok(expand(tract_hir::ops::activations::Clip::new(Some(0.0), None))));
     reg.insert("Relu6", |_, _| {
@@ -23,6 +30,7 @@ pub fn register_all_ops(reg: &mut TfOpRegister) {
     });
     reg.insert("Sigmoid", |_, _| Ok(Box::new(tract_hir::ops::nn::sigmoid())));
     reg.insert("Softmax", |_, _| Ok(expand(LayerSoftmax::new(1))));
+    reg.insert("Softplus", |_, _| Ok(expand(tract_hir::ops::activations::Softplus)));
     reg.insert("SpaceToBatchND", s2b::space_to_batch_nd);
     reg.insert("BatchToSpaceND", s2b::batch_to_space_nd);
 }
@@ -0,0 +1,70 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::{DtFloat, DtInt32};
+
+fn mirror_pad_pb(pads: &Tensor, mode: &str) -> Result<Vec<u8>> {
+    let pad = tfpb::node()
+        .name("op")
+        .op("MirrorPad")
+        .input("data")
+        .input("pads")
+        .attr("T", DtFloat)
+        .attr("Tpaddings", DtInt32)
+        .attr("mode", mode);
+    let graph = tfpb::graph().node(placeholder_f32("data")).node(const_i32("pads", pads)).node(pad);
+    Ok(graph.write_to_bytes()?)
+}
+
+/// Input and paddings: reflect needs paddings strictly smaller than the
+/// dimensions, symmetric accepts paddings up to the dimensions.
+fn problem() -> BoxedStrategy<(Tensor, Tensor, bool)> {
+    (::proptest::collection::vec(2usize..5, 1..4), any::<bool>())
+        .prop_flat_map(|(shape, symmetric)| {
+            let len = shape.iter().product::<usize>();
+            let pads = shape
+                .iter()
+                .map(|&d| {
+                    let max = if symmetric { d + 1 } else { d };
+                    (0..max as i32, 0..max as i32)
+                })
+                .collect::<Vec<_>>();
+            (
+                Just(
+                    tract_ndarray::ArrayD::from_shape_vec(
+                        shape.clone(),
+                        (0..len).map(|i| i as f32).collect(),
+                    )
+                    .unwrap()
+                    .into(),
+                ),
+                pads.prop_map(|pads| {
+                    let flat = pads.iter().flat_map(|p| vec![p.0, p.1]).collect::<Vec<_>>();
+                    tract_ndarray::Array::from_shape_vec((pads.len(), 2), flat).unwrap().into()
+                }),
+                Just(symmetric),
+            )
+        })
+        .boxed()
+}
+
+proptest! {
+    #[test]
+    fn mirror_pad((ref i, ref pads, symmetric) in problem()) {
+        let model = mirror_pad_pb(pads, if symmetric { "SYMMETRIC" } else { "REFLECT" }).unwrap();
+        compare(&model, vec!(("data", i.clone())), "op")?;
+    }
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::{DtFloat, DtInt32};
+
+fn one_hot_pb(depth: i32, axis: i64) -> Result<Vec<u8>> {
+    let one_hot = tfpb::node()
+        .name("op")
+        .op("OneHot")
+        .input("indices")
+        .input("depth")
+        .input("on")
+        .input("off")
+        .attr("T", DtFloat)
+        .attr("TI", DtInt32)
+        .attr("axis", axis);
+    let graph = tfpb::graph()
+        .node(placeholder_i32("indices"))
+        .node(const_i32("depth", &tensor0(depth)))
+        .node(const_f32("on", &tensor0(5f32)))
+        .node(const_f32("off", &tensor0(-1f32)))
+        .node(one_hot);
+    Ok(graph.write_to_bytes()?)
+}
+
+fn indices() -> BoxedStrategy<(Tensor, i32, i64)> {
+    (::proptest::collection::vec(1usize..4, 0..3), 1i32..5)
+        .prop_flat_map(|(shape, depth)| {
+            let len = shape.iter().product::<usize>();
+            let rank = shape.len() as i64;
+            (
+                ::proptest::collection::vec(0..depth + 1, len..len + 1).prop_map(move |v| {
+                    tract_ndarray::ArrayD::from_shape_vec(shape.clone(), v).unwrap().into()
+                }),
+                Just(depth),
+                -1..rank + 1,
+            )
+        })
+        .boxed()
+}
+
+proptest! {
+    #[test]
+    fn one_hot((ref i, depth, axis) in indices()) {
+        let model = one_hot_pb(depth, axis).unwrap();
+        compare(&model, vec!(("indices", i.clone())), "op")?;
+    }
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::DtInt32;
+
+fn input(shape: &[usize]) -> Tensor {
+    let len = shape.iter().product::<usize>() as i32;
+    tract_ndarray::ArrayD::from_shape_vec(shape, (0..len).collect()).unwrap().into()
+}
+
+/// Picks one of the outputs of the op under test.
+fn output(ix: usize) -> tfpb::tensorflow::NodeDef {
+    tfpb::node().name("output").op("Identity").input(format!("op:{}", ix)).attr("T", DtInt32)
+}
+
+fn split_pb(axis: i32, num_split: usize, ix: usize) -> Result<Vec<u8>> {
+    let split = tfpb::node()
+        .name("op")
+        .op("Split")
+        .input("axis")
+        .input("data")
+        .attr("T", DtInt32)
+        .attr("num_split", num_split as i64);
+    let graph = tfpb::graph()
+        .node(const_i32("axis", &tensor0(axis)))
+        .node(placeholder_i32("data"))
+        .node(split)
+        .node(output(ix));
+    Ok(graph.write_to_bytes()?)
+}
+
+fn split_v_pb(sizes: &[i32], axis: i32, ix: usize) -> Result<Vec<u8>> {
+    let split = tfpb::node()
+        .name("op")
+        .op("SplitV")
+        .input("data")
+        .input("sizes")
+        .input("axis")
+        .attr("T", DtInt32)
+        .attr("num_split", sizes.len() as i64);
+    let graph = tfpb::graph()
+        .node(placeholder_i32("data"))
+        .node(const_i32("sizes", &tensor1(sizes)))
+        .node(const_i32("axis", &tensor0(axis)))
+        .node(split)
+        .node(output(ix));
+    Ok(graph.write_to_bytes()?)
+}
+
+fn unpack_pb(num: usize, axis: i32, ix: usize) -> Result<Vec<u8>> {
+    let unpack = tfpb::node()
+        .name("op")
+        .op("Unpack")
+        .input("data")
+        .attr("T", DtInt32)
+        .attr("num", num as i64)
+        .attr("axis", axis as i64);
+    let graph = tfpb::graph().node(placeholder_i32("data")).node(unpack).node(output(ix));
+    Ok(graph.write_to_bytes()?)
+}
+
+proptest! {
+    #[test]
+    fn split((num_split, axis, ix) in (1usize..4, -3i32..3, 0usize..4)) {
+        prop_assume!(ix < num_split);
+        let model = split_pb(axis, num_split, ix).unwrap();
+        compare(&model, vec!(("data", input(&[num_split * 2, num_split, num_split * 3]))), "output")?;
+    }
+
+    #[test]
+    fn unpack((num, axis, ix) in (1usize..4, -3i32..3, 0usize..4)) {
+        prop_assume!(ix < num);
+        let model = unpack_pb(num, axis, ix).unwrap();
+        compare(&model, vec!(("data", input(&[num, num, num]))), "output")?;
+    }
+}
+
+#[test]
+fn split_v() {
+    for ix in 0..3 {
+        let model = split_v_pb(&[1, 3, 2], 1, ix).unwrap();
+        compare(&model, vec![("data", input(&[2, 6, 3]))], "output").unwrap();
+    }
+}
+
+#[test]
+fn split_v_inferred_size() {
+    for ix in 0..3 {
+        let model = split_v_pb(&[2, -1, 1], -1, ix).unwrap();
+        compare(&model, vec![("data", input(&[2, 3, 7]))], "output").unwrap();
+    }
+}
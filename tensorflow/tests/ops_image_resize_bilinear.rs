@@ -0,0 +1,65 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::DtFloat;
+
+fn resize_pb(
+    size: (usize, usize),
+    align_corners: bool,
+    half_pixel_centers: bool,
+) -> Result<Vec<u8>> {
+    let size: Tensor = tract_ndarray::arr1(&[size.0 as i32, size.1 as i32]).into();
+    let resize = tfpb::node()
+        .name("op")
+        .op("ResizeBilinear")
+        .input("data")
+        .input("size")
+        .attr("T", DtFloat)
+        .attr("align_corners", align_corners)
+        .attr("half_pixel_centers", half_pixel_centers);
+    let graph =
+        tfpb::graph().node(placeholder_f32("data")).node(const_i32("size", &size)).node(resize);
+    Ok(graph.write_to_bytes()?)
+}
+
+fn problem() -> BoxedStrategy<(Tensor, (usize, usize), bool, bool)> {
+    (1usize..5, 1usize..5, 1usize..3, 1usize..8, 1usize..8, 0usize..3)
+        .prop_flat_map(|(h, w, c, oh, ow, mode)| {
+            let len = h * w * c;
+            (
+                ::proptest::collection::vec(-9i32..9, len..len + 1).prop_map(move |v| {
+                    tract_ndarray::Array::from_shape_vec(
+                        (1, h, w, c),
+                        v.into_iter().map(|i| i as f32).collect(),
+                    )
+                    .unwrap()
+                    .into()
+                }),
+                Just((oh, ow)),
+                Just(mode == 1),
+                Just(mode == 2),
+            )
+        })
+        .boxed()
+}
+
+proptest! {
+    #[test]
+    fn resize_bilinear((ref i, size, align_corners, half_pixel_centers) in problem()) {
+        let model = resize_pb(size, align_corners, half_pixel_centers).unwrap();
+        compare(&model, vec!(("data", i.clone())), "op")?;
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::{DtBool, DtFloat};
+
+fn select_pb(op: &str) -> Result<Vec<u8>> {
+    let select =
+        tfpb::node().name("op").op(op).input("cond").input("a").input("b").attr("T", DtFloat);
+    let graph = tfpb::graph()
+        .node(placeholder("cond", DtBool, None))
+        .node(placeholder_f32("a"))
+        .node(placeholder_f32("b"))
+        .node(select);
+    Ok(graph.write_to_bytes()?)
+}
+
+fn operands(shape: Vec<usize>, cond_shape: Vec<usize>) -> BoxedStrategy<(Tensor, Tensor, Tensor)> {
+    let len = shape.iter().product::<usize>();
+    let cond_len = cond_shape.iter().product::<usize>();
+    (
+        ::proptest::collection::vec(any::<bool>(), cond_len..cond_len + 1),
+        ::proptest::collection::vec(-9i32..9, len..len + 1),
+        ::proptest::collection::vec(-9i32..9, len..len + 1),
+    )
+        .prop_map(move |(c, a, b)| {
+            let f = |v: Vec<i32>| -> Tensor {
+                tract_ndarray::ArrayD::from_shape_vec(
+                    shape.clone(),
+                    v.into_iter().map(|i| i as f32).collect(),
+                )
+                .unwrap()
+                .into()
+            };
+            (
+                tract_ndarray::ArrayD::from_shape_vec(cond_shape.clone(), c).unwrap().into(),
+                f(a),
+                f(b),
+            )
+        })
+        .boxed()
+}
+
+proptest! {
+    #[test]
+    fn select_same_shape((ref c, ref a, ref b) in ::proptest::collection::vec(1usize..4, 0..4)
+                         .prop_flat_map(|s| operands(s.clone(), s))) {
+        let model = select_pb("Select").unwrap();
+        compare(&model, vec!(("cond", c.clone()), ("a", a.clone()), ("b", b.clone())), "op")?;
+    }
+
+    #[test]
+    fn select_rows((ref c, ref a, ref b) in ::proptest::collection::vec(1usize..4, 2..4)
+                   .prop_flat_map(|s| operands(s.clone(), vec!(s[0])))) {
+        let model = select_pb("Select").unwrap();
+        compare(&model, vec!(("cond", c.clone()), ("a", a.clone()), ("b", b.clone())), "op")?;
+    }
+
+    #[test]
+    fn select_v2_broadcast((ref c, ref a, ref b) in ::proptest::collection::vec(1usize..4, 1..4)
+                           .prop_flat_map(|s| operands(s.clone(), vec!(s[s.len() - 1])))) {
+        let model = select_pb("SelectV2").unwrap();
+        compare(&model, vec!(("cond", c.clone()), ("a", a.clone()), ("b", b.clone())), "op")?;
+    }
+}
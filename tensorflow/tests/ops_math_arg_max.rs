@@ -0,0 +1,43 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::{self, DtFloat, DtInt32};
+
+fn arg_max_pb(axis: i32, output_type: DataType) -> Result<Vec<u8>> {
+    let arg_max = tfpb::node()
+        .name("op")
+        .op("ArgMax")
+        .input("data")
+        .input("axis")
+        .attr("T", DtFloat)
+        .attr("Tidx", DtInt32)
+        .attr("output_type", output_type);
+    let graph = tfpb::graph()
+        .node(placeholder_f32("data"))
+        .node(const_i32("axis", &tensor0(axis)))
+        .node(arg_max);
+    Ok(graph.write_to_bytes()?)
+}
+
+proptest! {
+    #[test]
+    fn arg_max((ref i, axis) in ::proptest::collection::vec(1usize..4, 1..4)
+               .prop_flat_map(|s| { let r = s.len() as i32; (tensor(s), -r..r) }),
+               int32 in any::<bool>()) {
+        let model = arg_max_pb(axis, if int32 { DtInt32 } else { DataType::DtInt64 }).unwrap();
+        compare(&model, vec!(("data", i.clone())), "op")?;
+    }
+}
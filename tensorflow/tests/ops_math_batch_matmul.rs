@@ -0,0 +1,62 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::DtFloat;
+
+fn batch_mat_mul_pb(op: &str, adj_x: bool, adj_y: bool) -> Result<Vec<u8>> {
+    let mm = tfpb::node()
+        .name("op")
+        .op(op)
+        .input("a")
+        .input("b")
+        .attr("T", DtFloat)
+        .attr("adj_x", adj_x)
+        .attr("adj_y", adj_y);
+    let graph = tfpb::graph().node(placeholder_f32("a")).node(placeholder_f32("b")).node(mm);
+    Ok(graph.write_to_bytes()?)
+}
+
+fn operands() -> BoxedStrategy<(Tensor, Tensor, bool, bool)> {
+    (1usize..3, 1usize..3, 1usize..4, 1usize..4, 1usize..4, any::<bool>(), any::<bool>())
+        .prop_flat_map(|(b1, b2, m, k, n, adj_x, adj_y)| {
+            let a = if adj_x { vec![b1, b2, k, m] } else { vec![b1, b2, m, k] };
+            let b = if adj_y { vec![b2, n, k] } else { vec![b2, k, n] };
+            (integer_tensor(a), integer_tensor(b), Just(adj_x), Just(adj_y))
+        })
+        .boxed()
+}
+
+proptest! {
+    #[test]
+    fn batch_mat_mul_v2((ref a, ref b, adj_x, adj_y) in operands()) {
+        let model = batch_mat_mul_pb("BatchMatMulV2", adj_x, adj_y).unwrap();
+        compare(&model, vec!(("a", a.clone()), ("b", b.clone())), "op")?;
+    }
+}
+
+#[test]
+fn batch_mat_mul_v1() {
+    let a: Tensor =
+        tract_ndarray::Array::from_shape_vec((2, 2, 3), (0..12).map(|i| i as f32).collect())
+            .unwrap()
+            .into();
+    let b: Tensor =
+        tract_ndarray::Array::from_shape_vec((2, 3, 1), (0..6).map(|i| i as f32).collect())
+            .unwrap()
+            .into();
+    let model = batch_mat_mul_pb("BatchMatMul", false, false).unwrap();
+    compare(&model, vec![("a", a), ("b", b)], "op").unwrap();
+}
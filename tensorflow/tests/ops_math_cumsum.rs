@@ -0,0 +1,62 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::{DtFloat, DtInt32};
+
+fn cumsum_pb(axis: i32, exclusive: bool, reverse: bool) -> Result<Vec<u8>> {
+    let cumsum = tfpb::node()
+        .name("op")
+        .op("Cumsum")
+        .input("data")
+        .input("axis")
+        .attr("T", DtFloat)
+        .attr("Tidx", DtInt32)
+        .attr("exclusive", exclusive)
+        .attr("reverse", reverse);
+    let graph = tfpb::graph()
+        .node(placeholder_f32("data"))
+        .node(const_i32("axis", &tensor0(axis)))
+        .node(cumsum);
+    Ok(graph.write_to_bytes()?)
+}
+
+fn problem() -> BoxedStrategy<(Tensor, i32)> {
+    ::proptest::collection::vec(1usize..5, 1..4)
+        .prop_flat_map(|shape| {
+            let len = shape.iter().product::<usize>();
+            let rank = shape.len() as i32;
+            (
+                ::proptest::collection::vec(-9i32..9, len..len + 1).prop_map(move |v| {
+                    tract_ndarray::ArrayD::from_shape_vec(
+                        shape.clone(),
+                        v.into_iter().map(|i| i as f32).collect(),
+                    )
+                    .unwrap()
+                    .into()
+                }),
+                -rank..rank,
+            )
+        })
+        .boxed()
+}
+
+proptest! {
+    #[test]
+    fn cumsum((ref i, axis) in problem(), exclusive in any::<bool>(), reverse in any::<bool>()) {
+        let model = cumsum_pb(axis, exclusive, reverse).unwrap();
+        compare(&model, vec!(("data", i.clone())), "op")?;
+    }
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::DtFloat;
+
+fn einsum_pb(equation: &str, inputs: usize) -> Result<Vec<u8>> {
+    let mut einsum = tfpb::node()
+        .name("op")
+        .op("Einsum")
+        .attr("T", DtFloat)
+        .attr("N", inputs as i64)
+        .attr("equation", equation);
+    let mut graph = tfpb::graph();
+    for ix in 0..inputs {
+        let name = format!("input-{}", ix);
+        graph = graph.node(placeholder_f32(&name));
+        einsum = einsum.input(name);
+    }
+    Ok(graph.node(einsum).write_to_bytes()?)
+}
+
+fn input(shape: &[usize]) -> Tensor {
+    let len = shape.iter().product::<usize>();
+    tract_ndarray::ArrayD::from_shape_vec(shape, (0..len).map(|i| i as f32 - 3.0).collect())
+        .unwrap()
+        .into()
+}
+
+fn check(equation: &str, shapes: &[&[usize]]) {
+    let model = einsum_pb(equation, shapes.len()).unwrap();
+    let inputs = shapes
+        .iter()
+        .enumerate()
+        .map(|(ix, shape)| (format!("input-{}", ix), input(shape)))
+        .collect();
+    compare(&model, inputs, "op").unwrap();
+}
+
+#[test]
+fn einsum_matmul() {
+    check("ij,jk->ik", &[&[2, 3], &[3, 4]]);
+}
+
+#[test]
+fn einsum_batch_matmul() {
+    check("bij,bjk->bik", &[&[2, 2, 3], &[2, 3, 4]]);
+}
+
+#[test]
+fn einsum_transpose() {
+    check("ij->ji", &[&[2, 3]]);
+}
+
+#[test]
+fn einsum_reduce() {
+    check("ijk->j", &[&[2, 3, 4]]);
+}
+
+#[test]
+fn einsum_ellipsis() {
+    check("...ij,...jk->...ik", &[&[2, 2, 3], &[2, 3, 1]]);
+}
+
+#[test]
+fn einsum_attention() {
+    check("bqhd,bkhd->bhqk", &[&[1, 3, 2, 4], &[1, 5, 2, 4]]);
+}
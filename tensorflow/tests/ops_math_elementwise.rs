@@ -0,0 +1,61 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::DtFloat;
+
+fn unary_pb(op: &str) -> Result<Vec<u8>> {
+    let unary = tfpb::node().name("op").op(op).input("data").attr("T", DtFloat);
+    let graph = tfpb::graph().node(placeholder_f32("data")).node(unary);
+    Ok(graph.write_to_bytes()?)
+}
+
+fn binary_pb(op: &str) -> Result<Vec<u8>> {
+    let binary = tfpb::node().name("op").op(op).input("a").input("b").attr("T", DtFloat);
+    let graph = tfpb::graph().node(placeholder_f32("a")).node(placeholder_f32("b")).node(binary);
+    Ok(graph.write_to_bytes()?)
+}
+
+proptest! {
+    #[test]
+    fn exp(ref i in shape().prop_flat_map(tensor)) {
+        compare(&unary_pb("Exp").unwrap(), vec!(("data", i.clone())), "op")?;
+    }
+
+    #[test]
+    fn sqrt(ref i in shape().prop_flat_map(tensor)) {
+        let i = i.to_array_view::<f32>().unwrap().mapv(f32::abs).into_tensor();
+        compare(&unary_pb("Sqrt").unwrap(), vec!(("data", i)), "op")?;
+    }
+
+    #[test]
+    fn square(ref i in shape().prop_flat_map(tensor)) {
+        compare(&unary_pb("Square").unwrap(), vec!(("data", i.clone())), "op")?;
+    }
+
+    #[test]
+    fn squared_difference((ref a, ref b) in shape().prop_flat_map(|s| (tensor(s.clone()), tensor(s)))) {
+        let model = binary_pb("SquaredDifference").unwrap();
+        compare(&model, vec!(("a", a.clone()), ("b", b.clone())), "op")?;
+    }
+}
+
+#[test]
+fn squared_difference_broadcast() {
+    let a: Tensor = tract_ndarray::arr2(&[[1f32, 2., 3.], [4., 5., 6.]]).into();
+    let b: Tensor = tract_ndarray::arr1(&[0.5f32, -1., 2.]).into();
+    let model = binary_pb("SquaredDifference").unwrap();
+    compare(&model, vec![("a", a), ("b", b)], "op").unwrap();
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::DtFloat;
+
+proptest! {
+    #[test]
+    fn leaky_relu(ref i in shape().prop_flat_map(tensor), alpha in 0f32..1.0) {
+        let op = tfpb::node().name("op").op("LeakyRelu").input("data").attr("T", DtFloat).attr("alpha", alpha);
+        let graph = tfpb::graph().node(placeholder_f32("data")).node(op).write_to_bytes().unwrap();
+        compare(&graph, vec!(("data", i.clone())), "op")?;
+    }
+
+    #[test]
+    fn softplus(ref i in shape().prop_flat_map(tensor)) {
+        let op = tfpb::node().name("op").op("Softplus").input("data").attr("T", DtFloat);
+        let graph = tfpb::graph().node(placeholder_f32("data")).node(op).write_to_bytes().unwrap();
+        compare(&graph, vec!(("data", i.clone())), "op")?;
+    }
+}
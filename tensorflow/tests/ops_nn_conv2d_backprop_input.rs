@@ -0,0 +1,87 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::DtFloat;
+
+fn deconvolution_pb(
+    output_shape: &[usize],
+    stride: usize,
+    valid: bool,
+    kernel: &Tensor,
+) -> Result<Vec<u8>> {
+    let sizes: Tensor =
+        tract_ndarray::Array::from(output_shape.iter().map(|&d| d as i32).collect::<Vec<_>>())
+            .into();
+    let deconv = tfpb::node()
+        .name("op")
+        .op("Conv2DBackpropInput")
+        .input("sizes")
+        .input("kernel")
+        .input("data")
+        .attr("strides", vec![1, stride as i64, stride as i64, 1])
+        .attr("padding", if valid { "VALID" } else { "SAME" })
+        .attr("T", DtFloat);
+    let graph = tfpb::graph()
+        .node(const_i32("sizes", &sizes))
+        .node(const_f32("kernel", kernel))
+        .node(placeholder_f32("data"))
+        .node(deconv);
+    Ok(graph.write_to_bytes()?)
+}
+
+/// (output shape, kernel, input, stride, valid), with output shape being the
+/// input shape of the matching forward convolution.
+fn problem() -> BoxedStrategy<(Vec<usize>, Tensor, Tensor, usize, bool)> {
+    (1usize..4, 1usize..4, 1usize..4, 1usize..4, 1usize..3, 1usize..8, 1usize..8, any::<bool>())
+        .prop_filter("kernel must fit in valid mode", |&(_, _, kh, kw, _, h, w, valid)| {
+            !valid || (h >= kh && w >= kw)
+        })
+        .prop_flat_map(|(ic, oc, kh, kw, stride, h, w, valid)| {
+            let (ih, iw) = if valid {
+                ((h - kh) / stride + 1, (w - kw) / stride + 1)
+            } else {
+                ((h + stride - 1) / stride, (w + stride - 1) / stride)
+            };
+            (
+                Just(vec![1, h, w, oc]),
+                integer_tensor(vec![kh, kw, oc, ic]),
+                integer_tensor(vec![1, ih, iw, ic]),
+                Just(stride),
+                Just(valid),
+            )
+        })
+        .boxed()
+}
+
+proptest! {
+    #[test]
+    fn deconv_compare((ref shape, ref k, ref i, stride, valid) in problem()) {
+        let model = deconvolution_pb(shape, stride, valid, k).unwrap();
+        compare(&model, vec!(("data", i.clone())), "op")?;
+    }
+}
+
+#[test]
+fn deconv_same_stride_2() {
+    let k: Tensor =
+        tract_ndarray::Array::from_shape_vec((3, 3, 1, 1), (0..9).map(|i| i as f32).collect())
+            .unwrap()
+            .into();
+    let i: Tensor =
+        tract_ndarray::Array::from_shape_vec((1, 2, 2, 1), vec![1f32, 2., 3., 4.]).unwrap().into();
+    let model = deconvolution_pb(&[1, 4, 4, 1], 2, false, &k).unwrap();
+    compare(&model, vec![("data", i)], "op").unwrap();
+}
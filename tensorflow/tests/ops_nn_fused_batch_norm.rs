@@ -0,0 +1,85 @@
+#![cfg(feature = "conform")]
+#![allow(non_snake_case)]
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate proptest;
+extern crate tract_tensorflow;
+
+mod utils;
+
+use crate::utils::*;
+use proptest::prelude::*;
+use tract_tensorflow::conform::*;
+use tract_tensorflow::prelude::*;
+use tract_tensorflow::tfpb;
+use tract_tensorflow::tfpb::tensorflow::DataType::DtFloat;
+
+fn fused_batch_norm_pb(op: &str, params: &[Tensor; 4], epsilon: f32) -> Result<Vec<u8>> {
+    let bn = tfpb::node()
+        .name("op")
+        .op(op)
+        .input("data")
+        .input("scale")
+        .input("offset")
+        .input("mean")
+        .input("variance")
+        .attr("T", DtFloat)
+        .attr("U", DtFloat)
+        .attr("epsilon", epsilon)
+        .attr("data_format", "NHWC")
+        .attr("is_training", false);
+    let graph = tfpb::graph()
+        .node(placeholder_f32("data"))
+        .node(const_f32("scale", &params[0]))
+        .node(const_f32("offset", &params[1]))
+        .node(const_f32("mean", &params[2]))
+        .node(const_f32("variance", &params[3]))
+        .node(bn);
+    Ok(graph.write_to_bytes()?)
+}
+
+fn vector(len: usize, positive: bool) -> BoxedStrategy<Tensor> {
+    let range = if positive { 1i32..20 } else { -20i32..20 };
+    ::proptest::collection::vec(range, len..len + 1)
+        .prop_map(|v| {
+            tract_ndarray::Array::from(v.into_iter().map(|i| i as f32 / 10.0).collect::<Vec<_>>())
+                .into()
+        })
+        .boxed()
+}
+
+fn problem() -> BoxedStrategy<(Tensor, [Tensor; 4])> {
+    (1usize..3, 1usize..4, 1usize..4, 1usize..4)
+        .prop_flat_map(|(n, h, w, c)| {
+            let len = n * h * w * c;
+            (
+                ::proptest::collection::vec(-20i32..20, len..len + 1).prop_map(move |v| {
+                    tract_ndarray::Array::from_shape_vec(
+                        (n, h, w, c),
+                        v.into_iter().map(|i| i as f32 / 10.0).collect(),
+                    )
+                    .unwrap()
+                    .into()
+                }),
+                (vector(c, false), vector(c, false), vector(c, false), vector(c, true))
+                    .prop_map(|(s, o, m, v)| [s, o, m, v]),
+            )
+        })
+        .boxed()
+}
+
+proptest! {
+    #[test]
+    fn fused_batch_norm((ref i, ref params) in problem()) {
+        let model = fused_batch_norm_pb("FusedBatchNorm", params, 0.001).unwrap();
+        compare(&model, vec!(("data", i.clone())), "op")?;
+    }
+
+    #[test]
+    fn fused_batch_norm_v3((ref i, ref params) in problem()) {
+        let model = fused_batch_norm_pb("FusedBatchNormV3", params, 0.001).unwrap();
+        compare(&model, vec!(("data", i.clone())), "op")?;
+    }
+}
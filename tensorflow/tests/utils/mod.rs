@@ -1,3 +1,4 @@
+use proptest::prelude::{BoxedStrategy, Strategy};
 use tract_tensorflow::prelude::*;
 
 fn setup_test_logger() {
@@ -137,3 +138,40 @@ pub fn infer<S: AsRef<str>>(
 
     Ok(())
 }
+
+/// f32 tensors of the given shape, with values from -10 to 10 by steps of 0.1.
+#[allow(dead_code)]
+pub fn tensor(shape: Vec<usize>) -> BoxedStrategy<Tensor> {
+    f32_tensor(shape, -100..100, 10.0)
+}
+
+/// f32 tensors of the given shape, with integer values from -9 to 8, so that
+/// sums of products are exact.
+#[allow(dead_code)]
+pub fn integer_tensor(shape: Vec<usize>) -> BoxedStrategy<Tensor> {
+    f32_tensor(shape, -9..9, 1.0)
+}
+
+fn f32_tensor(
+    shape: Vec<usize>,
+    range: std::ops::Range<i32>,
+    divisor: f32,
+) -> BoxedStrategy<Tensor> {
+    let len = shape.iter().product::<usize>();
+    ::proptest::collection::vec(range, len..len + 1)
+        .prop_map(move |v| {
+            tract_ndarray::ArrayD::from_shape_vec(
+                shape.clone(),
+                v.into_iter().map(|i| i as f32 / divisor).collect(),
+            )
+            .unwrap()
+            .into()
+        })
+        .boxed()
+}
+
+/// Shapes of rank 0 to 3, with dimensions from 1 to 3.
+#[allow(dead_code)]
+pub fn shape() -> BoxedStrategy<Vec<usize>> {
+    ::proptest::collection::vec(1usize..4, 0..4).boxed()
+}
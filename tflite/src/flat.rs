@@ -0,0 +1,162 @@
+//! Minimal, bounds-checked flatbuffers reader.
+//!
+//! TFLite models are flatbuffers. We only need read access to a handful of
+//! tables, so instead of generated code we walk the buffer with a few safe
+//! primitives. Every access is checked, so a corrupted model results in an
+//! error instead of undefined behaviour.
+
+use tract_hir::internal::*;
+
+pub trait Scalar: Sized + Copy {
+    const SIZE: usize;
+    fn from_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! scalar {
+    ($t: ty, $size: expr) => {
+        impl Scalar for $t {
+            const SIZE: usize = $size;
+            fn from_le(bytes: &[u8]) -> Self {
+                let mut buf = [0u8; $size];
+                buf.copy_from_slice(&bytes[0..$size]);
+                <$t>::from_le_bytes(buf)
+            }
+        }
+    };
+}
+
+scalar!(u8, 1);
+scalar!(i8, 1);
+scalar!(u16, 2);
+scalar!(i16, 2);
+scalar!(u32, 4);
+scalar!(i32, 4);
+scalar!(u64, 8);
+scalar!(i64, 8);
+scalar!(f32, 4);
+
+impl Scalar for bool {
+    const SIZE: usize = 1;
+    fn from_le(bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+}
+
+fn read<T: Scalar>(buf: &[u8], pos: usize) -> TractResult<T> {
+    if pos.checked_add(T::SIZE).map(|end| end > buf.len()).unwrap_or(true) {
+        bail!("Truncated flatbuffer: reading {} bytes at {} in {}", T::SIZE, pos, buf.len());
+    }
+    Ok(T::from_le(&buf[pos..]))
+}
+
+/// Follow an unsigned offset stored at `pos`.
+fn follow(buf: &[u8], pos: usize) -> TractResult<usize> {
+    let offset = read::<u32>(buf, pos)? as usize;
+    pos.checked_add(offset).ok_or_else(|| format_err!("Invalid flatbuffer offset at {}", pos))
+}
+
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    vtable: usize,
+    vtable_len: usize,
+}
+
+impl<'a> std::fmt::Debug for Table<'a> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "Table@{}", self.pos)
+    }
+}
+
+impl<'a> Table<'a> {
+    /// Access the root table of a buffer.
+    pub fn root(buf: &'a [u8]) -> TractResult<Table<'a>> {
+        Table::at(buf, follow(buf, 0)?)
+    }
+
+    fn at(buf: &'a [u8], pos: usize) -> TractResult<Table<'a>> {
+        let soffset = read::<i32>(buf, pos)? as isize;
+        let vtable = pos as isize - soffset;
+        if vtable < 0 {
+            bail!("Invalid flatbuffer vtable offset for table at {}", pos)
+        }
+        let vtable = vtable as usize;
+        let vtable_len = read::<u16>(buf, vtable)? as usize;
+        Ok(Table { buf, pos, vtable, vtable_len })
+    }
+
+    fn field(&self, slot: usize) -> TractResult<Option<usize>> {
+        let entry = 4 + 2 * slot;
+        if entry + 2 > self.vtable_len {
+            return Ok(None);
+        }
+        let offset = read::<u16>(self.buf, self.vtable + entry)? as usize;
+        Ok(if offset == 0 { None } else { Some(self.pos + offset) })
+    }
+
+    pub fn has(&self, slot: usize) -> TractResult<bool> {
+        Ok(self.field(slot)?.is_some())
+    }
+
+    pub fn scalar<T: Scalar>(&self, slot: usize, default: T) -> TractResult<T> {
+        match self.field(slot)? {
+            Some(pos) => read(self.buf, pos),
+            None => Ok(default),
+        }
+    }
+
+    pub fn table(&self, slot: usize) -> TractResult<Option<Table<'a>>> {
+        match self.field(slot)? {
+            Some(pos) => Ok(Some(Table::at(self.buf, follow(self.buf, pos)?)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn vector_pos(&self, slot: usize, item_size: usize) -> TractResult<Option<(usize, usize)>> {
+        let pos = if let Some(pos) = self.field(slot)? {
+            follow(self.buf, pos)?
+        } else {
+            return Ok(None);
+        };
+        let len = read::<u32>(self.buf, pos)? as usize;
+        let start = pos + 4;
+        if len
+            .checked_mul(item_size)
+            .and_then(|l| l.checked_add(start))
+            .map(|end| end > self.buf.len())
+            .unwrap_or(true)
+        {
+            bail!("Truncated flatbuffer: vector of {} items at {}", len, pos)
+        }
+        Ok(Some((start, len)))
+    }
+
+    pub fn bytes(&self, slot: usize) -> TractResult<Option<&'a [u8]>> {
+        Ok(self.vector_pos(slot, 1)?.map(|(start, len)| &self.buf[start..][..len]))
+    }
+
+    pub fn string(&self, slot: usize) -> TractResult<Option<&'a str>> {
+        self.bytes(slot)?
+            .map(|b| std::str::from_utf8(b).map_err(|e| format_err!("Invalid string: {}", e)))
+            .transpose()
+    }
+
+    /// A vector of scalars, copied out of the buffer. Absent vectors are empty.
+    pub fn scalars<T: Scalar>(&self, slot: usize) -> TractResult<Vec<T>> {
+        if let Some((start, len)) = self.vector_pos(slot, T::SIZE)? {
+            (0..len).map(|i| read(self.buf, start + i * T::SIZE)).collect()
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// A vector of tables. Absent vectors are empty.
+    pub fn tables(&self, slot: usize) -> TractResult<Vec<Table<'a>>> {
+        if let Some((start, len)) = self.vector_pos(slot, 4)? {
+            (0..len).map(|i| Table::at(self.buf, follow(self.buf, start + i * 4)?)).collect()
+        } else {
+            Ok(vec![])
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! TensorFlow Lite loader for tract.
+//!
+//! Translates .tflite flatbuffer models to tract TypedModel. Quantized models
+//! are supported by dequantizing weights at load time and emulating
+//! activation quantization in float.
+#[macro_use]
+extern crate log;
+
+pub mod flat;
+pub mod model;
+pub mod ops;
+pub mod schema;
+
+pub use model::Tflite;
+
+pub use tract_hir::tract_core;
+
+pub mod prelude {
+    pub use crate::tflite;
+    pub use tract_hir::tract_core::prelude::*;
+}
+
+pub fn tflite() -> Tflite {
+    let mut ops = crate::model::TfliteOpRegister::default();
+    ops::register_all_ops(&mut ops);
+    Tflite { op_register: ops }
+}
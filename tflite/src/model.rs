@@ -0,0 +1,686 @@
+use std::{fs, path};
+
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::quant::{
+    quantize_linear_i8, quantize_linear_u8, DequantizeLinearF32,
+};
+
+use crate::schema::{self, Activation, Operator, Quantization, TensorDef};
+
+pub type OpBuilder = fn(&mut ModelBuilder, &Operator) -> TractResult<TVec<OutletId>>;
+
+#[derive(Clone, Default)]
+pub struct TfliteOpRegister(pub HashMap<i32, OpBuilder>);
+
+impl TfliteOpRegister {
+    pub fn insert(&mut self, code: i32, builder: OpBuilder) {
+        self.0.insert(code, builder);
+    }
+}
+
+pub struct Tflite {
+    pub op_register: TfliteOpRegister,
+}
+
+/// The raw flatbuffer content of a .tflite file.
+#[derive(Clone)]
+pub struct TfliteProtoModel(pub Vec<u8>);
+
+impl std::fmt::Debug for TfliteProtoModel {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "TfliteProtoModel ({} bytes)", self.0.len())
+    }
+}
+
+impl TfliteProtoModel {
+    pub fn model(&self) -> TractResult<schema::Model<'_>> {
+        schema::Model::root(&self.0)
+    }
+}
+
+/// Model translation state.
+///
+/// Quantized activations are kept as dequantized floats in the tract model,
+/// while each quantized output goes through a quantize/dequantize pair, so
+/// the network reproduces TFLite rounding. Quantized constants (weights and
+/// biases, per-tensor or per-channel) are dequantized at load time.
+pub struct ModelBuilder<'a> {
+    pub model: TypedModel,
+    pub tensors: Vec<TensorDef<'a>>,
+    pub buffers: Vec<schema::Buffer<'a>>,
+    pub outlets: HashMap<usize, OutletId>,
+    pub op_name: String,
+}
+
+impl<'a> ModelBuilder<'a> {
+    fn tensor(&self, ix: i32) -> TractResult<&TensorDef<'a>> {
+        if ix < 0 {
+            bail!("Missing optional tensor input");
+        }
+        self.tensors.get(ix as usize).ok_or_else(|| format_err!("No tensor #{}", ix))
+    }
+
+    pub fn tensor_name(&self, ix: i32) -> TractResult<String> {
+        Ok(self
+            .tensor(ix)?
+            .name()?
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("tensor_{}", ix)))
+    }
+
+    /// The value of a constant tensor, dequantized to f32 if needed.
+    pub fn konst(&self, ix: i32) -> TractResult<Option<Arc<Tensor>>> {
+        let tensor = self.tensor(ix)?;
+        let buffer = tensor.buffer()? as usize;
+        let data = if let Some(data) =
+            self.buffers.get(buffer).ok_or_else(|| format_err!("No buffer #{}", buffer))?.data()?
+        {
+            data
+        } else {
+            return Ok(None);
+        };
+        let dt = tensor.datum_type()?;
+        let shape = tensor.shape()?.iter().map(|&d| d as usize).collect::<TVec<usize>>();
+        let len = shape.iter().product::<usize>();
+        if data.len() != len * dt.size_of() {
+            bail!(
+                "Buffer size mismatch for {}: {:?} {:?} expects {} bytes, got {}",
+                self.tensor_name(ix)?,
+                dt,
+                shape,
+                len * dt.size_of(),
+                data.len()
+            )
+        }
+        let value = if len == 0 {
+            Tensor::zero_dt(dt, &shape)?
+        } else {
+            unsafe { Tensor::from_raw_dt(dt, &shape, data)? }
+        };
+        if let Some(q) = tensor.quantization()? {
+            Ok(Some(dequantize(&value, &q)?.into_arc_tensor()))
+        } else {
+            Ok(Some(value.into_arc_tensor()))
+        }
+    }
+
+    /// The value of a constant tensor input, or an error.
+    pub fn konst_input(&self, op: &Operator, input: usize) -> TractResult<Arc<Tensor>> {
+        let ix = *op.inputs()?.get(input).ok_or_else(|| format_err!("Missing input #{}", input))?;
+        if let Some(konst) = self.konst(ix)? {
+            Ok(konst)
+        } else {
+            bail!("Expected input #{} ({}) to be a constant", input, self.tensor_name(ix)?)
+        }
+    }
+
+    /// The value of an optional constant tensor input.
+    pub fn opt_konst_input(&self, op: &Operator, input: usize) -> TractResult<Option<Arc<Tensor>>> {
+        match op.inputs()?.get(input) {
+            Some(&ix) if ix >= 0 => self.konst(ix),
+            _ => Ok(None),
+        }
+    }
+
+    /// Wire for an operator input, adding constants to the model as needed.
+    pub fn input(&mut self, op: &Operator, input: usize) -> TractResult<OutletId> {
+        let ix = *op.inputs()?.get(input).ok_or_else(|| format_err!("Missing input #{}", input))?;
+        self.tensor_wire(ix)
+    }
+
+    pub fn tensor_wire(&mut self, ix: i32) -> TractResult<OutletId> {
+        if let Some(outlet) = self.outlets.get(&(ix as usize)) {
+            return Ok(*outlet);
+        }
+        let konst = self.konst(ix)?.ok_or_else(|| {
+            format_err!("Tensor #{} is neither a constant nor computed by a previous operator", ix)
+        })?;
+        let name = self.tensor_name(ix)?;
+        let outlet = self.model.add_const(name, konst)?;
+        self.outlets.insert(ix as usize, outlet);
+        Ok(outlet)
+    }
+
+    pub fn wire(
+        &mut self,
+        suffix: &str,
+        op: impl Into<Box<dyn TypedOp>>,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let name = if suffix.len() == 0 {
+            self.op_name.clone()
+        } else {
+            format!("{}.{}", self.op_name, suffix)
+        };
+        self.model.wire_node(name, op, inputs)
+    }
+
+    /// Add leading axes to inputs so their ranks match (numpy broadcasting).
+    pub fn multicast(&mut self, inputs: &[OutletId]) -> TractResult<TVec<OutletId>> {
+        let ranks = inputs
+            .iter()
+            .map(|&i| Ok(self.model.outlet_fact(i)?.rank()))
+            .collect::<TractResult<Vec<usize>>>()?;
+        let max_rank = ranks.iter().copied().max().unwrap_or(0);
+        let mut wires = tvec!();
+        for (ix, (&i, &r)) in inputs.iter().zip(ranks.iter()).enumerate() {
+            let mut wire = i;
+            for axis in 0..(max_rank - r) {
+                wire =
+                    self.wire(&format!("input_{}.add_axis_{}", ix, axis), AxisOp::Add(0), &[wire])?
+                        [0];
+            }
+            wires.push(wire);
+        }
+        Ok(wires)
+    }
+
+    /// Apply a fused activation function.
+    pub fn activation(&mut self, wire: OutletId, activation: Activation) -> TractResult<OutletId> {
+        use tract_hir::tract_core::ops::math;
+        let rank = self.model.outlet_fact(wire)?.rank();
+        let scalar = |v: f32| -> TractResult<Arc<Tensor>> {
+            Ok(tensor0(v).broadcast_into_rank(rank)?.into_arc_tensor())
+        };
+        let wire = match activation {
+            Activation::None => wire,
+            Activation::Relu => self.wire("relu", math::max::unary(scalar(0.0)?), &[wire])?[0],
+            Activation::Relu6 => {
+                let wire = self.wire("relu6.low", math::max::unary(scalar(0.0)?), &[wire])?[0];
+                self.wire("relu6.high", math::min::unary(scalar(6.0)?), &[wire])?[0]
+            }
+            Activation::ReluN1To1 => {
+                let wire = self.wire("relu1.low", math::max::unary(scalar(-1.0)?), &[wire])?[0];
+                self.wire("relu1.high", math::min::unary(scalar(1.0)?), &[wire])?[0]
+            }
+            Activation::Tanh => self.wire("tanh", math::tanh(), &[wire])?[0],
+        };
+        Ok(wire)
+    }
+
+    /// Emulate quantization of a computed output tensor.
+    fn fake_quant(&mut self, ix: i32, wire: OutletId) -> TractResult<OutletId> {
+        let tensor = self.tensor(ix)?.clone();
+        if let Some(q) = tensor.quantization()? {
+            if self.model.outlet_fact(wire)?.datum_type != f32::datum_type() {
+                return Ok(wire);
+            }
+            let dt = tensor.datum_type()?;
+            if !q.is_per_tensor() && (dt == DatumType::U8 || dt == DatumType::I8) {
+                let name = self.tensor_name(ix)?;
+                let wire = self.quantize_per_channel(&name, wire, &q, dt)?;
+                return self.dequantize_per_channel(&name, wire, &q);
+            }
+            let (scale, zero_point) = (q.scale[0], q.zero_point(0));
+            let quant = match dt {
+                DatumType::U8 => quantize_linear_u8(scale.recip(), zero_point as u8),
+                DatumType::I8 => quantize_linear_i8(scale.recip(), zero_point as i8),
+                _ => return Ok(wire),
+            };
+            let name = self.tensor_name(ix)?;
+            let wire = self.model.wire_node(format!("{}.quantize", name), quant, &[wire])?[0];
+            Ok(self.model.wire_node(
+                format!("{}.dequantize", name),
+                DequantizeLinearF32::new(scale, zero_point as i32),
+                &[wire],
+            )?[0])
+        } else {
+            Ok(wire)
+        }
+    }
+
+    /// Quantize a float wire with per-channel parameters. The result is a
+    /// float wire holding the rounded integer values, clamped to the range of
+    /// `dt` (U8 or I8).
+    fn quantize_per_channel(
+        &mut self,
+        name: &str,
+        wire: OutletId,
+        q: &Quantization,
+        dt: DatumType,
+    ) -> TractResult<OutletId> {
+        use tract_hir::tract_core::ops::math;
+        let (low, high) = if dt == DatumType::U8 { (0.0, 255.0) } else { (-128.0, 127.0) };
+        let recip = self.channel_values(wire, q, |c| q.scale[c].recip())?;
+        let zero_point = self.channel_values(wire, q, |c| q.zero_point(c) as f32)?;
+        let low = self.channel_values(wire, q, |_| low)?;
+        let high = self.channel_values(wire, q, |_| high)?;
+        let wire =
+            self.model.wire_node(format!("{}.quantize", name), math::mul::unary(recip), &[wire])?
+                [0];
+        let wire = self.model.wire_node(format!("{}.round", name), math::round(), &[wire])?[0];
+        let wire = self.model.wire_node(
+            format!("{}.zero_point", name),
+            math::add::unary(zero_point),
+            &[wire],
+        )?[0];
+        let wire =
+            self.model.wire_node(format!("{}.low", name), math::max::unary(low), &[wire])?[0];
+        Ok(self.model.wire_node(format!("{}.high", name), math::min::unary(high), &[wire])?[0])
+    }
+
+    /// Dequantize a wire with per-channel parameters, as a float wire.
+    fn dequantize_per_channel(
+        &mut self,
+        name: &str,
+        wire: OutletId,
+        q: &Quantization,
+    ) -> TractResult<OutletId> {
+        use tract_hir::tract_core::ops::{cast::cast, math};
+        let mut wire = wire;
+        if self.model.outlet_fact(wire)?.datum_type != f32::datum_type() {
+            wire =
+                self.model.wire_node(format!("{}.cast", name), cast(f32::datum_type()), &[wire])?
+                    [0];
+        }
+        let zero_point = self.channel_values(wire, q, |c| -q.zero_point(c) as f32)?;
+        let scale = self.channel_values(wire, q, |c| q.scale[c])?;
+        let wire = self.model.wire_node(
+            format!("{}.dequantize.zero_point", name),
+            math::add::unary(zero_point),
+            &[wire],
+        )?[0];
+        Ok(self.model.wire_node(
+            format!("{}.dequantize", name),
+            math::mul::unary(scale),
+            &[wire],
+        )?[0])
+    }
+
+    /// A constant with one value per channel of `q`, broadcastable to `wire`.
+    fn channel_values(
+        &self,
+        wire: OutletId,
+        q: &Quantization,
+        value: impl Fn(usize) -> f32,
+    ) -> TractResult<Arc<Tensor>> {
+        let shape = self.model.outlet_fact(wire)?.shape.to_tvec();
+        let channels = q.scale.len();
+        if q.quantized_dimension >= shape.len() || shape[q.quantized_dimension] != channels.to_dim()
+        {
+            bail!("Inconsistent per-channel quantization {:?} for shape {:?}", q, shape);
+        }
+        let mut broadcast = vec![1; shape.len()];
+        broadcast[q.quantized_dimension] = channels;
+        let values = (0..channels).map(value).collect::<Vec<f32>>();
+        Ok(tensor1(&values).into_shape(&broadcast)?.into_arc_tensor())
+    }
+}
+
+/// Dequantize a tensor, with per-tensor or per-axis parameters.
+pub fn dequantize(tensor: &Tensor, q: &Quantization) -> TractResult<Tensor> {
+    let values = tensor.cast_to::<f64>()?;
+    let values = values.as_slice::<f64>()?;
+    let shape = tensor.shape();
+    let (dim, stride) = if q.is_per_tensor() {
+        (1, 1)
+    } else {
+        if q.quantized_dimension >= shape.len() || shape[q.quantized_dimension] != q.scale.len() {
+            bail!("Inconsistent per-channel quantization {:?} for shape {:?}", q, shape);
+        }
+        (shape[q.quantized_dimension], shape[q.quantized_dimension + 1..].iter().product())
+    };
+    let result = values
+        .iter()
+        .enumerate()
+        .map(|(ix, &v)| {
+            let channel = (ix / stride) % dim;
+            ((v - q.zero_point(channel) as f64) * q.scale[channel] as f64) as f32
+        })
+        .collect::<Vec<f32>>();
+    Ok(tract_ndarray::ArrayD::from_shape_vec(shape, result)?.into_tensor())
+}
+
+impl Tflite {
+    pub fn parse(&self, proto: &TfliteProtoModel) -> TractResult<TypedModel> {
+        let model = proto.model()?;
+        let subgraphs = model.subgraphs()?;
+        let graph = subgraphs.get(0).ok_or_else(|| format_err!("Model has no subgraph"))?;
+        if subgraphs.len() > 1 {
+            warn!("Model has {} subgraphs, only the first one will be loaded", subgraphs.len());
+        }
+        let opcodes = model.operator_codes()?;
+        let mut builder = ModelBuilder {
+            model: TypedModel::default(),
+            tensors: graph.tensors()?,
+            buffers: model.buffers()?,
+            outlets: HashMap::default(),
+            op_name: String::new(),
+        };
+        for &input in &graph.inputs()? {
+            let tensor = builder.tensor(input)?.clone();
+            let name = builder.tensor_name(input)?;
+            let shape = tensor.shape()?.iter().map(|&d| d as usize).collect::<TVec<_>>();
+            let fact = TypedFact::dt_shape(tensor.datum_type()?, &*shape)?;
+            let mut wire = builder.model.add_source(&*name, fact)?;
+            if let Some(q) = tensor.quantization()? {
+                wire = if q.is_per_tensor() {
+                    builder.model.wire_node(
+                        format!("{}.dequantize", name),
+                        DequantizeLinearF32::new(q.scale[0], q.zero_point(0) as i32),
+                        &[wire],
+                    )?[0]
+                } else {
+                    builder.dequantize_per_channel(&name, wire, &q)?
+                };
+            }
+            builder.outlets.insert(input as usize, wire);
+        }
+        for (ix, op) in graph.operators()?.iter().enumerate() {
+            let opcode = opcodes
+                .get(op.opcode_index()? as usize)
+                .ok_or_else(|| format_err!("Invalid opcode index for operator #{}", ix))?;
+            let code = opcode.builtin_code()?;
+            let outputs = op.outputs()?;
+            builder.op_name = if let Some(&first) = outputs.get(0) {
+                builder.tensor_name(first)?
+            } else {
+                format!("op_{}", ix)
+            };
+            let op_builder = self.op_register.0.get(&code).ok_or_else(|| {
+                if let Ok(Some(custom)) = opcode.custom_code() {
+                    format_err!("Unsupported custom operator {} ({})", custom, builder.op_name)
+                } else {
+                    format_err!("Unsupported builtin operator {} ({})", code, builder.op_name)
+                }
+            })?;
+            let wires = (op_builder)(&mut builder, op)
+                .with_context(|| format!("Translating operator #{} ({})", ix, builder.op_name))?;
+            if wires.len() != outputs.len() {
+                bail!(
+                    "Operator #{} ({}) produced {} outputs, expected {}",
+                    ix,
+                    builder.op_name,
+                    wires.len(),
+                    outputs.len()
+                );
+            }
+            for (&tensor, &wire) in outputs.iter().zip(wires.iter()) {
+                let wire = builder.fake_quant(tensor, wire)?;
+                builder.outlets.insert(tensor as usize, wire);
+            }
+        }
+        let mut outputs = tvec!();
+        for &output in &graph.outputs()? {
+            let tensor = builder.tensor(output)?.clone();
+            let mut wire = builder.tensor_wire(output)?;
+            if let Some(q) = tensor.quantization()? {
+                let name = builder.tensor_name(output)?;
+                let dt = tensor.datum_type()?;
+                if !q.is_per_tensor() && (dt == DatumType::U8 || dt == DatumType::I8) {
+                    use tract_hir::tract_core::ops::cast::cast;
+                    let prefix = format!("{}.output", name);
+                    wire = builder.quantize_per_channel(&prefix, wire, &q, dt)?;
+                    wire = builder.model.wire_node(prefix, cast(dt), &[wire])?[0];
+                    outputs.push(wire);
+                    continue;
+                }
+                let quant = match dt {
+                    DatumType::U8 => {
+                        Some(quantize_linear_u8(q.scale[0].recip(), q.zero_point(0) as u8))
+                    }
+                    DatumType::I8 => {
+                        Some(quantize_linear_i8(q.scale[0].recip(), q.zero_point(0) as i8))
+                    }
+                    _ => None,
+                };
+                if let Some(quant) = quant {
+                    wire = builder.model.wire_node(format!("{}.output", name), quant, &[wire])?[0];
+                }
+            }
+            outputs.push(wire);
+        }
+        builder.model.set_output_outlets(&outputs)?;
+        Ok(builder.model)
+    }
+}
+
+impl Framework<TfliteProtoModel, TypedModel> for Tflite {
+    fn proto_model_for_path(&self, p: impl AsRef<path::Path>) -> TractResult<TfliteProtoModel> {
+        let data =
+            fs::read(p.as_ref()).with_context(|| format!("Could not open {:?}", p.as_ref()))?;
+        Ok(TfliteProtoModel(data))
+    }
+
+    fn proto_model_for_read(&self, r: &mut dyn std::io::Read) -> TractResult<TfliteProtoModel> {
+        let mut data = vec![];
+        r.read_to_end(&mut data)?;
+        Ok(TfliteProtoModel(data))
+    }
+
+    fn model_for_proto_model(&self, proto: &TfliteProtoModel) -> TractResult<TypedModel> {
+        self.parse(proto)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schema::builtin;
+    use flatbuffers::{FlatBufferBuilder, TableFinishedWIPOffset, WIPOffset};
+
+    type Offset = WIPOffset<TableFinishedWIPOffset>;
+
+    fn slot(s: u16) -> u16 {
+        4 + 2 * s
+    }
+
+    enum Opt {
+        I8(u16, i8),
+        F32(u16, f32),
+    }
+
+    struct TensorSpec {
+        shape: Vec<i32>,
+        dt: i8,
+        data: Vec<u8>,
+        quant: Option<(Vec<f32>, Vec<i64>, i32)>,
+    }
+
+    fn f32_tensor(shape: &[i32], data: &[f32]) -> TensorSpec {
+        let data = data.iter().flat_map(|x| x.to_le_bytes().to_vec()).collect();
+        TensorSpec { shape: shape.to_vec(), dt: 0, data, quant: None }
+    }
+
+    fn u8_tensor(shape: &[i32], data: &[u8], scale: f32, zero_point: i64) -> TensorSpec {
+        TensorSpec {
+            shape: shape.to_vec(),
+            dt: 3,
+            data: data.to_vec(),
+            quant: Some((vec![scale], vec![zero_point], 0)),
+        }
+    }
+
+    fn u8_per_channel(shape: &[i32], scale: &[f32], zero_point: &[i64], dim: i32) -> TensorSpec {
+        TensorSpec {
+            shape: shape.to_vec(),
+            dt: 3,
+            data: vec![],
+            quant: Some((scale.to_vec(), zero_point.to_vec(), dim)),
+        }
+    }
+
+    fn build(
+        tensors: &[TensorSpec],
+        ops: &[(i32, &[i32], &[i32], &[Opt])],
+        inputs: &[i32],
+        outputs: &[i32],
+    ) -> TfliteProtoModel {
+        let mut fbb = FlatBufferBuilder::new();
+        let mut buffers: Vec<Offset> = vec![];
+        let empty = fbb.start_table();
+        buffers.push(fbb.end_table(empty));
+        let mut tensor_tables: Vec<Offset> = vec![];
+        for (ix, t) in tensors.iter().enumerate() {
+            let buffer_ix = if t.data.len() > 0 {
+                let data = fbb.create_vector(&t.data);
+                let buffer = fbb.start_table();
+                fbb.push_slot_always(slot(0), data);
+                buffers.push(fbb.end_table(buffer));
+                buffers.len() as u32 - 1
+            } else {
+                0
+            };
+            let quant = t.quant.as_ref().map(|(scale, zero_point, dim)| {
+                let scale = fbb.create_vector(&scale[..]);
+                let zero_point = fbb.create_vector(&zero_point[..]);
+                let quant = fbb.start_table();
+                fbb.push_slot_always(slot(2), scale);
+                fbb.push_slot_always(slot(3), zero_point);
+                fbb.push_slot(slot(6), *dim, 0);
+                fbb.end_table(quant)
+            });
+            let shape = fbb.create_vector(&t.shape);
+            let name = fbb.create_string(&format!("t{}", ix));
+            let tensor = fbb.start_table();
+            fbb.push_slot_always(slot(0), shape);
+            fbb.push_slot(slot(1), t.dt, 0);
+            fbb.push_slot(slot(2), buffer_ix, 0);
+            fbb.push_slot_always(slot(3), name);
+            if let Some(quant) = quant {
+                fbb.push_slot_always(slot(4), quant);
+            }
+            tensor_tables.push(fbb.end_table(tensor));
+        }
+        let mut codes: Vec<i32> = ops.iter().map(|op| op.0).collect();
+        codes.sort();
+        codes.dedup();
+        let mut op_tables: Vec<Offset> = vec![];
+        for (code, op_inputs, op_outputs, options) in ops {
+            let op_inputs = fbb.create_vector(op_inputs);
+            let op_outputs = fbb.create_vector(op_outputs);
+            let opts = fbb.start_table();
+            for opt in options.iter() {
+                match opt {
+                    Opt::I8(s, v) => fbb.push_slot_always(slot(*s), *v),
+                    Opt::F32(s, v) => fbb.push_slot_always(slot(*s), *v),
+                }
+            }
+            let opts = fbb.end_table(opts);
+            let op = fbb.start_table();
+            fbb.push_slot_always(slot(0), codes.iter().position(|c| c == code).unwrap() as u32);
+            fbb.push_slot_always(slot(1), op_inputs);
+            fbb.push_slot_always(slot(2), op_outputs);
+            fbb.push_slot_always(slot(4), opts);
+            op_tables.push(fbb.end_table(op));
+        }
+        let tensors = fbb.create_vector(&tensor_tables);
+        let inputs = fbb.create_vector(inputs);
+        let outputs = fbb.create_vector(outputs);
+        let operators = fbb.create_vector(&op_tables);
+        let graph = fbb.start_table();
+        fbb.push_slot_always(slot(0), tensors);
+        fbb.push_slot_always(slot(1), inputs);
+        fbb.push_slot_always(slot(2), outputs);
+        fbb.push_slot_always(slot(3), operators);
+        let graph = fbb.end_table(graph);
+        let code_tables = codes
+            .iter()
+            .map(|&c| {
+                let code = fbb.start_table();
+                fbb.push_slot(slot(0), c.min(127) as i8, 0);
+                fbb.push_slot(slot(3), c, 0);
+                fbb.end_table(code)
+            })
+            .collect::<Vec<Offset>>();
+        let code_tables = fbb.create_vector(&code_tables);
+        let graphs = fbb.create_vector(&[graph]);
+        let buffers = fbb.create_vector(&buffers);
+        let model = fbb.start_table();
+        fbb.push_slot(slot(0), 3u32, 0);
+        fbb.push_slot_always(slot(1), code_tables);
+        fbb.push_slot_always(slot(2), graphs);
+        fbb.push_slot_always(slot(4), buffers);
+        let model = fbb.end_table(model);
+        fbb.finish(model, Some("TFL3"));
+        TfliteProtoModel(fbb.finished_data().to_vec())
+    }
+
+    #[test]
+    fn fully_connected_relu_softmax() -> TractResult<()> {
+        let proto = build(
+            &[
+                f32_tensor(&[1, 3], &[]),
+                f32_tensor(&[2, 3], &[1.0, 2.0, 3.0, -1.0, -2.0, -3.0]),
+                f32_tensor(&[2], &[0.5, 1.0]),
+                f32_tensor(&[1, 2], &[]),
+                f32_tensor(&[1, 2], &[]),
+            ],
+            &[
+                (builtin::FULLY_CONNECTED, &[0, 1, 2], &[3], &[Opt::I8(0, 1)]),
+                (builtin::SOFTMAX, &[3], &[4], &[Opt::F32(0, 2.0)]),
+            ],
+            &[0],
+            &[4],
+        );
+        let model = crate::tflite().model_for_proto_model(&proto)?;
+        let result = model.into_runnable()?.run(tvec!(tensor2(&[[1f32, 0.0, -1.0]])))?;
+        // fc = relu([-2 + 0.5, 2 + 1]) = [0, 3], softmax(2 * fc)
+        let e = 6f32.exp();
+        result[0].close_enough(&tensor2(&[[1.0 / (1.0 + e), e / (1.0 + e)]]), true)?;
+        Ok(())
+    }
+
+    #[test]
+    fn quantized_add_broadcast() -> TractResult<()> {
+        let proto = build(
+            &[
+                u8_tensor(&[1, 4], &[], 0.5, 128),
+                u8_tensor(&[4], &[132, 128, 120, 129], 0.5, 128),
+                u8_tensor(&[1, 4], &[], 0.5, 100),
+            ],
+            &[(builtin::ADD, &[0, 1], &[2], &[])],
+            &[0],
+            &[2],
+        );
+        let model = crate::tflite().model_for_proto_model(&proto)?;
+        let result = model.into_runnable()?.run(tvec!(tensor2(&[[130u8, 126, 140, 128]])))?;
+        assert_eq!(*result[0], tensor2(&[[106u8, 98, 104, 101]]));
+        Ok(())
+    }
+
+    #[test]
+    fn conv_and_depthwise_channels() -> TractResult<()> {
+        let proto = build(
+            &[
+                f32_tensor(&[1, 1, 1, 2], &[]),
+                f32_tensor(&[1, 1, 1, 2], &[3.0, 4.0]),
+                f32_tensor(&[1, 1, 1, 1], &[]),
+                f32_tensor(&[1, 1, 1, 4], &[1.0, 2.0, 3.0, 4.0]),
+                f32_tensor(&[1, 1, 1, 4], &[]),
+            ],
+            &[
+                (builtin::CONV_2D, &[0, 1, -1], &[2], &[Opt::I8(0, 1)]),
+                (builtin::DEPTHWISE_CONV_2D, &[0, 3, -1], &[4], &[Opt::I8(0, 1)]),
+            ],
+            &[0],
+            &[2, 4],
+        );
+        let model = crate::tflite().model_for_proto_model(&proto)?;
+        let result = model.into_runnable()?.run(tvec!(tensor4(&[[[[1f32, 2.0]]]])))?;
+        assert_eq!(*result[0], tensor4(&[[[[11f32]]]]));
+        assert_eq!(*result[1], tensor4(&[[[[1f32, 2.0, 6.0, 8.0]]]]));
+        Ok(())
+    }
+
+    #[test]
+    fn per_channel_fake_quant() -> TractResult<()> {
+        let proto = build(
+            &[
+                f32_tensor(&[1, 4], &[]),
+                f32_tensor(&[4], &[0.0; 4]),
+                u8_per_channel(&[1, 4], &[0.5, 1.0, 2.0, 0.25], &[10, 0, 0, 250], 1),
+                f32_tensor(&[1, 4], &[]),
+            ],
+            &[(builtin::ADD, &[0, 1], &[2], &[]), (builtin::ADD, &[2, 1], &[3], &[])],
+            &[0],
+            &[2, 3],
+        );
+        let model = crate::tflite().model_for_proto_model(&proto)?;
+        let result = model.into_runnable()?.run(tvec!(tensor2(&[[1.3f32, -2.6, 5.0, 80.0]])))?;
+        // rounded and clamped per channel: [3 + 10, max(-3, 0), 3, min(320 + 250, 255)]
+        assert_eq!(*result[0], tensor2(&[[13u8, 0, 3, 255]]));
+        assert_eq!(*result[1], tensor2(&[[1.5f32, 0.0, 6.0, 1.25]]));
+        Ok(())
+    }
+}
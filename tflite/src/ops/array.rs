@@ -0,0 +1,164 @@
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::array::{Pad, PadMode, TypedConcat};
+use tract_hir::tract_core::ops::change_axes::perm_to_ops;
+use tract_hir::tract_core::ops::math;
+use tract_hir::tract_core::ops::nn::{Reduce, Reducer};
+
+use super::{activation, option, option_vec};
+use crate::model::{ModelBuilder, TfliteOpRegister};
+use crate::schema::{builtin, Operator};
+
+pub fn register_all_ops(reg: &mut TfliteOpRegister) {
+    reg.insert(builtin::CONCATENATION, concatenation);
+    reg.insert(builtin::MEAN, mean);
+    reg.insert(builtin::PAD, pad);
+    reg.insert(builtin::PADV2, pad);
+    reg.insert(builtin::RESHAPE, reshape);
+    reg.insert(builtin::SQUEEZE, squeeze);
+    reg.insert(builtin::TRANSPOSE, transpose);
+}
+
+fn input_shape(builder: &ModelBuilder, input: OutletId) -> TractResult<TVec<usize>> {
+    builder.model.outlet_fact(input)?.shape.iter().map(|d| d.to_usize()).collect()
+}
+
+fn axis(axis: i64, rank: usize) -> TractResult<usize> {
+    let fixed = if axis < 0 { axis + rank as i64 } else { axis };
+    if fixed < 0 || fixed >= rank as i64 {
+        bail!("Invalid axis {} for rank {}", axis, rank)
+    }
+    Ok(fixed as usize)
+}
+
+fn concatenation(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let inputs =
+        (0..op.inputs()?.len()).map(|i| builder.input(op, i)).collect::<TractResult<TVec<_>>>()?;
+    let rank = builder.model.outlet_fact(inputs[0])?.rank();
+    let axis = axis(option(op, 0, 0i32)? as i64, rank)?;
+    let wire = builder.wire("", TypedConcat::concat_vars(axis, inputs.len()), &inputs)?[0];
+    Ok(tvec!(builder.activation(wire, activation(op, 1)?)?))
+}
+
+fn mean(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    let shape = input_shape(builder, input)?;
+    let mut axes = builder
+        .konst_input(op, 1)?
+        .cast_to::<i64>()?
+        .as_slice::<i64>()?
+        .iter()
+        .map(|&a| axis(a, shape.len()))
+        .collect::<TractResult<TVec<usize>>>()?;
+    axes.sort();
+    axes.dedup();
+    let cardinality = axes.iter().map(|&a| shape[a]).product::<usize>();
+    let mut wire = builder.wire("sum", Reduce::new(axes.clone(), Reducer::Sum), &[input])?[0];
+    let norm = tensor0((cardinality as f32).recip()).broadcast_into_rank(shape.len())?;
+    wire = builder.wire("norm", math::mul::unary(norm.into_arc_tensor()), &[wire])?[0];
+    if !option(op, 0, false)? {
+        for &axis in axes.iter().rev() {
+            wire = builder.wire(&format!("rm_axis_{}", axis), AxisOp::Rm(axis), &[wire])?[0];
+        }
+    }
+    Ok(tvec!(wire))
+}
+
+fn pad(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    let rank = builder.model.outlet_fact(input)?.rank();
+    let pads = builder.konst_input(op, 1)?;
+    if pads.shape() != &[rank, 2] {
+        bail!("Expected paddings of shape [{}, 2], got {:?}", rank, pads);
+    }
+    let pads = pads.cast_to::<i64>()?;
+    let pads = pads
+        .as_slice::<i64>()?
+        .chunks(2)
+        .map(|p| {
+            if p[0] < 0 || p[1] < 0 {
+                bail!("Negative paddings are not supported, got {:?}", p)
+            }
+            Ok((p[0] as usize, p[1] as usize))
+        })
+        .collect::<TractResult<Vec<_>>>()?;
+    let dt = builder.model.outlet_fact(input)?.datum_type;
+    let value = if let Some(value) = builder.opt_konst_input(op, 2)? {
+        value.cast_to_dt(dt)?.into_owned().into_shape(&[])?
+    } else {
+        Tensor::zero_dt(dt, &[])?
+    };
+    builder.wire("", Pad { pads, mode: PadMode::Constant(value.into_arc_tensor()) }, &[input])
+}
+
+fn reshape(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    let shape = input_shape(builder, input)?;
+    let target: Vec<i64> = if let Some(shape) = builder.opt_konst_input(op, 1)? {
+        shape.cast_to::<i64>()?.as_slice::<i64>()?.to_vec()
+    } else {
+        option_vec::<i32>(op, 0)?.into_iter().map(|d| d as i64).collect()
+    };
+    let volume = shape.iter().product::<usize>();
+    let known = target.iter().filter(|&&d| d >= 0).product::<i64>() as usize;
+    let target = target
+        .iter()
+        .map(|&d| {
+            if d >= 0 {
+                Ok(d as usize)
+            } else if known > 0 && volume % known == 0 {
+                Ok(volume / known)
+            } else {
+                bail!("Can not reshape {:?} to {:?}", shape, target)
+            }
+        })
+        .collect::<TractResult<TVec<usize>>>()?;
+    if target.iter().product::<usize>() != volume {
+        bail!("Can not reshape {:?} to {:?}", shape, target)
+    }
+    if target == shape {
+        return Ok(tvec!(input));
+    }
+    let op = AxisOp::Reshape(
+        0,
+        shape.iter().map(|d| d.to_dim()).collect(),
+        target.iter().map(|d| d.to_dim()).collect(),
+    );
+    builder.wire("", op, &[input])
+}
+
+fn squeeze(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let mut wire = builder.input(op, 0)?;
+    let shape = input_shape(builder, wire)?;
+    let mut axes = option_vec::<i32>(op, 0)?
+        .into_iter()
+        .map(|a| axis(a as i64, shape.len()))
+        .collect::<TractResult<Vec<usize>>>()?;
+    if axes.len() == 0 {
+        axes = (0..shape.len()).filter(|&a| shape[a] == 1).collect();
+    }
+    axes.sort();
+    axes.dedup();
+    for &axis in axes.iter().rev() {
+        if shape[axis] != 1 {
+            bail!("Can not squeeze axis {} of shape {:?}", axis, shape)
+        }
+        wire = builder.wire(&format!("rm_axis_{}", axis), AxisOp::Rm(axis), &[wire])?[0];
+    }
+    Ok(tvec!(wire))
+}
+
+fn transpose(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let mut wire = builder.input(op, 0)?;
+    let rank = builder.model.outlet_fact(wire)?.rank();
+    let perm = builder
+        .konst_input(op, 1)?
+        .cast_to::<i64>()?
+        .as_slice::<i64>()?
+        .iter()
+        .map(|&a| axis(a, rank))
+        .collect::<TractResult<TVec<usize>>>()?;
+    for (ix, axis_op) in perm_to_ops(&perm).into_iter().enumerate() {
+        wire = builder.wire(&format!("axis_op_{}", ix), axis_op, &[wire])?[0];
+    }
+    Ok(tvec!(wire))
+}
@@ -0,0 +1,49 @@
+use tract_hir::internal::*;
+use tract_hir::tract_core::ops::{math, nn};
+
+use super::activation;
+use crate::model::{ModelBuilder, TfliteOpRegister};
+use crate::schema::{builtin, Activation, Operator};
+
+pub fn register_all_ops(reg: &mut TfliteOpRegister) {
+    reg.insert(builtin::ADD, |b, op| binary(b, op, math::add::bin_typed(), true));
+    reg.insert(builtin::SUB, |b, op| binary(b, op, math::sub::bin_typed(), true));
+    reg.insert(builtin::MUL, |b, op| binary(b, op, math::mul::bin_typed(), true));
+    reg.insert(builtin::DIV, |b, op| binary(b, op, math::div::bin_typed(), true));
+    reg.insert(builtin::MAXIMUM, |b, op| binary(b, op, math::max::bin_typed(), false));
+    reg.insert(builtin::MINIMUM, |b, op| binary(b, op, math::min::bin_typed(), false));
+    reg.insert(builtin::EXP, |b, op| unary(b, op, math::exp()));
+    reg.insert(builtin::LOGISTIC, |b, op| unary(b, op, nn::sigmoid()));
+    reg.insert(builtin::TANH, |b, op| unary(b, op, math::tanh()));
+    reg.insert(builtin::RELU, |b, op| relu(b, op, Activation::Relu));
+    reg.insert(builtin::RELU6, |b, op| relu(b, op, Activation::Relu6));
+    reg.insert(builtin::RELU_N1_TO_1, |b, op| relu(b, op, Activation::ReluN1To1));
+}
+
+fn binary(
+    builder: &mut ModelBuilder,
+    op: &Operator,
+    mini_op: impl Into<Box<dyn TypedOp>>,
+    fused_activation: bool,
+) -> TractResult<TVec<OutletId>> {
+    let a = builder.input(op, 0)?;
+    let b = builder.input(op, 1)?;
+    let inputs = builder.multicast(&[a, b])?;
+    let wire = builder.wire("", mini_op, &inputs)?[0];
+    let act = if fused_activation { activation(op, 0)? } else { Activation::None };
+    Ok(tvec!(builder.activation(wire, act)?))
+}
+
+fn unary(
+    builder: &mut ModelBuilder,
+    op: &Operator,
+    mini_op: impl Into<Box<dyn TypedOp>>,
+) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    builder.wire("", mini_op, &[input])
+}
+
+fn relu(builder: &mut ModelBuilder, op: &Operator, act: Activation) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    Ok(tvec!(builder.activation(input, act)?))
+}
@@ -0,0 +1,42 @@
+use tract_hir::internal::*;
+
+use crate::flat::Scalar;
+use crate::model::{ModelBuilder, TfliteOpRegister};
+use crate::schema::{builtin, Activation, Operator};
+
+pub mod array;
+pub mod math;
+pub mod nn;
+
+pub fn register_all_ops(reg: &mut TfliteOpRegister) {
+    array::register_all_ops(reg);
+    math::register_all_ops(reg);
+    nn::register_all_ops(reg);
+    // quantized activations are computed in float, so these are no-ops
+    reg.insert(builtin::DEQUANTIZE, passthrough);
+    reg.insert(builtin::QUANTIZE, passthrough);
+}
+
+fn passthrough(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    Ok(tvec!(builder.input(op, 0)?))
+}
+
+/// Read a scalar from the operator builtin options, or its default value.
+pub fn option<T: Scalar>(op: &Operator, slot: usize, default: T) -> TractResult<T> {
+    match op.options()? {
+        Some(options) => options.scalar(slot, default),
+        None => Ok(default),
+    }
+}
+
+/// Read a vector of scalars from the operator builtin options.
+pub fn option_vec<T: Scalar>(op: &Operator, slot: usize) -> TractResult<Vec<T>> {
+    match op.options()? {
+        Some(options) => options.scalars(slot),
+        None => Ok(vec![]),
+    }
+}
+
+pub fn activation(op: &Operator, slot: usize) -> TractResult<Activation> {
+    Activation::from_code(option(op, slot, 0i8)?)
+}
@@ -0,0 +1,191 @@
+use tract_hir::internal::*;
+use tract_hir::ops::nn::LayerSoftmax;
+use tract_hir::tract_core::ops::cnn::{
+    ConvUnary, KernelFormat, MaxPool, PaddingSpec, PoolSpec, SumPool,
+};
+use tract_hir::tract_core::ops::math;
+use tract_hir::tract_core::ops::matmul::MatMulUnary;
+use tract_hir::tract_core::ops::nn::DataFormat;
+
+use super::{activation, option};
+use crate::model::{ModelBuilder, TfliteOpRegister};
+use crate::schema::{builtin, Operator};
+
+pub fn register_all_ops(reg: &mut TfliteOpRegister) {
+    reg.insert(builtin::AVERAGE_POOL_2D, average_pool_2d);
+    reg.insert(builtin::CONV_2D, conv_2d);
+    reg.insert(builtin::DEPTHWISE_CONV_2D, depthwise_conv_2d);
+    reg.insert(builtin::FULLY_CONNECTED, fully_connected);
+    reg.insert(builtin::MAX_POOL_2D, max_pool_2d);
+    reg.insert(builtin::SOFTMAX, softmax);
+}
+
+fn padding(op: &Operator) -> TractResult<PaddingSpec> {
+    Ok(match option(op, 0, 0i8)? {
+        0 => PaddingSpec::SameUpper,
+        1 => PaddingSpec::Valid,
+        p => bail!("Unsupported padding {}", p),
+    })
+}
+
+fn check_input_rank(builder: &ModelBuilder, input: OutletId, rank: usize) -> TractResult<()> {
+    let fact = builder.model.outlet_fact(input)?;
+    if fact.rank() != rank {
+        bail!("Expected input of rank {}, got {:?}", rank, fact)
+    }
+    Ok(())
+}
+
+fn bias(
+    builder: &ModelBuilder,
+    op: &Operator,
+    channels: usize,
+) -> TractResult<Option<Arc<Tensor>>> {
+    if let Some(bias) = builder.opt_konst_input(op, 2)? {
+        if bias.len() != channels {
+            bail!("Expected one bias value per output channel ({}), got {:?}", channels, bias);
+        }
+        Ok(Some(bias.cast_to::<f32>()?.into_owned().into_shape(&[channels])?.into_arc_tensor()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Conv2D: input is NHWC, filter OHWI, bias O.
+fn conv_2d(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    check_input_rank(builder, input, 4)?;
+    let kernel = builder.konst_input(op, 1)?;
+    if kernel.rank() != 4 {
+        bail!("Expected a filter of rank 4, got {:?}", kernel);
+    }
+    let output_channels = kernel.shape()[0];
+    let kernel = kernel.into_tensor().permute_axes(&[1, 2, 3, 0])?;
+    let pool_spec = PoolSpec::new(
+        DataFormat::NHWC,
+        kernel.shape()[0..2].into(),
+        padding(op)?,
+        Some(tvec!(option(op, 5, 1i32)? as usize, option(op, 4, 1i32)? as usize)),
+        Some(tvec!(option(op, 2, 1i32)? as usize, option(op, 1, 1i32)? as usize)),
+        Some(output_channels),
+    );
+    let bias = bias(builder, op, output_channels)?;
+    let conv =
+        ConvUnary::new(pool_spec, KernelFormat::HWIO, kernel.into_arc_tensor(), 1, bias, None);
+    let wire = builder.wire("", conv, &[input])?[0];
+    Ok(tvec!(builder.activation(wire, activation(op, 3)?)?))
+}
+
+/// DepthwiseConv2D: input is NHWC, filter 1HW(C*M), bias C*M.
+fn depthwise_conv_2d(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    check_input_rank(builder, input, 4)?;
+    let input_channels = builder.model.outlet_fact(input)?.shape[3].to_usize()?;
+    let kernel = builder.konst_input(op, 1)?;
+    if kernel.rank() != 4 || kernel.shape()[0] != 1 || kernel.shape()[3] % input_channels != 0 {
+        bail!("Expected a filter of shape [1, H, W, {}*M], got {:?}", input_channels, kernel);
+    }
+    let output_channels = kernel.shape()[3];
+    let multiplier = output_channels / input_channels;
+    let (h, w) = (kernel.shape()[1], kernel.shape()[2]);
+    let kernel = kernel.into_tensor().into_shape(&[h, w, input_channels, multiplier])?;
+    let pool_spec = PoolSpec::new(
+        DataFormat::NHWC,
+        tvec!(h, w),
+        padding(op)?,
+        Some(tvec!(option(op, 6, 1i32)? as usize, option(op, 5, 1i32)? as usize)),
+        Some(tvec!(option(op, 2, 1i32)? as usize, option(op, 1, 1i32)? as usize)),
+        Some(output_channels),
+    );
+    let bias = bias(builder, op, output_channels)?;
+    let conv = ConvUnary::new(
+        pool_spec,
+        KernelFormat::HWIO,
+        kernel.into_arc_tensor(),
+        input_channels,
+        bias,
+        None,
+    );
+    let wire = builder.wire("", conv, &[input])?[0];
+    Ok(tvec!(builder.activation(wire, activation(op, 4)?)?))
+}
+
+fn pool_spec(op: &Operator) -> TractResult<PoolSpec> {
+    Ok(PoolSpec::new(
+        DataFormat::NHWC,
+        tvec!(option(op, 4, 1i32)? as usize, option(op, 3, 1i32)? as usize),
+        padding(op)?,
+        None,
+        Some(tvec!(option(op, 2, 1i32)? as usize, option(op, 1, 1i32)? as usize)),
+        None,
+    ))
+}
+
+fn average_pool_2d(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    check_input_rank(builder, input, 4)?;
+    let pool = SumPool { pool_spec: pool_spec(op)?, count_include_pad: false, normalize: true };
+    let wire = builder.wire("", pool, &[input])?[0];
+    Ok(tvec!(builder.activation(wire, activation(op, 5)?)?))
+}
+
+fn max_pool_2d(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    check_input_rank(builder, input, 4)?;
+    let pool = MaxPool { pool_spec: pool_spec(op)?, with_index_outputs: None };
+    let wire = builder.wire("", pool, &[input])?[0];
+    Ok(tvec!(builder.activation(wire, activation(op, 5)?)?))
+}
+
+/// FullyConnected: weights are [O, K], input is flattened to [N, K].
+fn fully_connected(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let input = builder.input(op, 0)?;
+    let weights = builder.konst_input(op, 1)?;
+    if weights.rank() != 2 {
+        bail!("Expected weights of rank 2, got {:?}", weights);
+    }
+    if option(op, 1, 0i8)? != 0 {
+        bail!("Only default weights format is supported");
+    }
+    let (output_channels, k) = (weights.shape()[0], weights.shape()[1]);
+    let keep_num_dims = option(op, 2, false)?;
+    let input_shape = builder.model.outlet_fact(input)?.shape.to_tvec();
+    let input_dims = input_shape.iter().map(|d| d.to_usize()).collect::<TractResult<TVec<_>>>()?;
+    let volume = input_dims.iter().product::<usize>();
+    if volume % k != 0 {
+        bail!("Input shape {:?} is not compatible with weights {:?}", input_dims, weights);
+    }
+    let batch = volume / k;
+    let mut wire = input;
+    if input_dims.len() != 2 || input_dims[1] != k {
+        let reshape = AxisOp::Reshape(0, input_shape.clone(), tvec!(batch.to_dim(), k.to_dim()));
+        wire = builder.wire("reshape_input", reshape, &[wire])?[0];
+    }
+    let matmul = MatMulUnary::new(weights, false, true, true, None);
+    wire = builder.wire("", matmul, &[wire])?[0];
+    if let Some(bias) = bias(builder, op, output_channels)? {
+        let bias = bias.into_tensor().into_shape(&[1, output_channels])?;
+        wire = builder.wire("bias", math::add::unary(bias.into_arc_tensor()), &[wire])?[0];
+    }
+    if keep_num_dims && input_dims.len() != 2 {
+        let mut output_shape: TVec<TDim> = input_shape[..input_shape.len() - 1].into();
+        output_shape.push(output_channels.to_dim());
+        let reshape =
+            AxisOp::Reshape(0, tvec!(batch.to_dim(), output_channels.to_dim()), output_shape);
+        wire = builder.wire("reshape_output", reshape, &[wire])?[0];
+    }
+    Ok(tvec!(builder.activation(wire, activation(op, 0)?)?))
+}
+
+/// Softmax over the last axis, with a beta (inverse temperature) factor.
+fn softmax(builder: &mut ModelBuilder, op: &Operator) -> TractResult<TVec<OutletId>> {
+    let mut wire = builder.input(op, 0)?;
+    let rank = builder.model.outlet_fact(wire)?.rank();
+    let beta = option(op, 0, 1.0f32)?;
+    if beta != 1.0 {
+        let beta = tensor0(beta).broadcast_into_rank(rank)?.into_arc_tensor();
+        wire = builder.wire("beta", math::mul::unary(beta), &[wire])?[0];
+    }
+    let name = builder.op_name.clone();
+    LayerSoftmax::new(rank as isize - 1).wire(&name, &mut builder.model, &[wire])
+}
@@ -0,0 +1,241 @@
+//! Accessors for the subset of the TFLite schema (schema.fbs, version 3) used
+//! by the loader. Field slots follow the declaration order in the schema,
+//! unions using two slots (type and value).
+
+use crate::flat::Table;
+use tract_hir::internal::*;
+
+pub mod builtin {
+    pub const ADD: i32 = 0;
+    pub const AVERAGE_POOL_2D: i32 = 1;
+    pub const CONCATENATION: i32 = 2;
+    pub const CONV_2D: i32 = 3;
+    pub const DEPTHWISE_CONV_2D: i32 = 4;
+    pub const DEQUANTIZE: i32 = 6;
+    pub const FULLY_CONNECTED: i32 = 9;
+    pub const LOGISTIC: i32 = 14;
+    pub const MAX_POOL_2D: i32 = 17;
+    pub const MUL: i32 = 18;
+    pub const RELU: i32 = 19;
+    pub const RELU_N1_TO_1: i32 = 20;
+    pub const RELU6: i32 = 21;
+    pub const RESHAPE: i32 = 22;
+    pub const SOFTMAX: i32 = 25;
+    pub const TANH: i32 = 28;
+    pub const PAD: i32 = 34;
+    pub const TRANSPOSE: i32 = 39;
+    pub const MEAN: i32 = 40;
+    pub const SUB: i32 = 41;
+    pub const DIV: i32 = 42;
+    pub const SQUEEZE: i32 = 43;
+    pub const EXP: i32 = 47;
+    pub const MAXIMUM: i32 = 55;
+    pub const MINIMUM: i32 = 57;
+    pub const PADV2: i32 = 60;
+    pub const QUANTIZE: i32 = 114;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Model<'a>(Table<'a>);
+
+impl<'a> Model<'a> {
+    pub fn root(buf: &'a [u8]) -> TractResult<Model<'a>> {
+        Ok(Model(Table::root(buf)?))
+    }
+
+    pub fn version(&self) -> TractResult<u32> {
+        self.0.scalar(0, 0)
+    }
+
+    pub fn operator_codes(&self) -> TractResult<Vec<OperatorCode<'a>>> {
+        Ok(self.0.tables(1)?.into_iter().map(OperatorCode).collect())
+    }
+
+    pub fn subgraphs(&self) -> TractResult<Vec<SubGraph<'a>>> {
+        Ok(self.0.tables(2)?.into_iter().map(SubGraph).collect())
+    }
+
+    pub fn description(&self) -> TractResult<Option<&'a str>> {
+        self.0.string(3)
+    }
+
+    pub fn buffers(&self) -> TractResult<Vec<Buffer<'a>>> {
+        Ok(self.0.tables(4)?.into_iter().map(Buffer).collect())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct OperatorCode<'a>(Table<'a>);
+
+impl<'a> OperatorCode<'a> {
+    /// The builtin operator code. Schemas before 2.3 only have the (byte)
+    /// deprecated field, more recent ones only fill the int field beyond 127.
+    pub fn builtin_code(&self) -> TractResult<i32> {
+        let deprecated = self.0.scalar::<i8>(0, 0)? as i32;
+        let code = self.0.scalar::<i32>(3, 0)?;
+        Ok(code.max(deprecated))
+    }
+
+    pub fn custom_code(&self) -> TractResult<Option<&'a str>> {
+        self.0.string(1)
+    }
+
+    pub fn version(&self) -> TractResult<i32> {
+        self.0.scalar(2, 1)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SubGraph<'a>(Table<'a>);
+
+impl<'a> SubGraph<'a> {
+    pub fn tensors(&self) -> TractResult<Vec<TensorDef<'a>>> {
+        Ok(self.0.tables(0)?.into_iter().map(TensorDef).collect())
+    }
+
+    pub fn inputs(&self) -> TractResult<Vec<i32>> {
+        self.0.scalars(1)
+    }
+
+    pub fn outputs(&self) -> TractResult<Vec<i32>> {
+        self.0.scalars(2)
+    }
+
+    pub fn operators(&self) -> TractResult<Vec<Operator<'a>>> {
+        Ok(self.0.tables(3)?.into_iter().map(Operator).collect())
+    }
+
+    pub fn name(&self) -> TractResult<Option<&'a str>> {
+        self.0.string(4)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Buffer<'a>(Table<'a>);
+
+impl<'a> Buffer<'a> {
+    pub fn data(&self) -> TractResult<Option<&'a [u8]>> {
+        Ok(self.0.bytes(0)?.filter(|d| d.len() > 0))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TensorDef<'a>(Table<'a>);
+
+impl<'a> TensorDef<'a> {
+    pub fn shape(&self) -> TractResult<Vec<i32>> {
+        self.0.scalars(0)
+    }
+
+    pub fn datum_type(&self) -> TractResult<DatumType> {
+        let code = self.0.scalar::<i8>(1, 0)?;
+        Ok(match code {
+            0 => DatumType::F32,
+            1 => DatumType::F16,
+            2 => DatumType::I32,
+            3 => DatumType::U8,
+            4 => DatumType::I64,
+            5 => DatumType::String,
+            6 => DatumType::Bool,
+            7 => DatumType::I16,
+            9 => DatumType::I8,
+            10 => DatumType::F64,
+            12 => DatumType::U64,
+            15 => DatumType::U32,
+            16 => DatumType::U16,
+            _ => bail!("Unsupported TFLite tensor type {}", code),
+        })
+    }
+
+    pub fn buffer(&self) -> TractResult<u32> {
+        self.0.scalar(2, 0)
+    }
+
+    pub fn name(&self) -> TractResult<Option<&'a str>> {
+        self.0.string(3)
+    }
+
+    pub fn quantization(&self) -> TractResult<Option<Quantization>> {
+        if let Some(q) = self.0.table(4)? {
+            let scale = q.scalars::<f32>(2)?;
+            if scale.len() == 0 {
+                return Ok(None);
+            }
+            let zero_point = q.scalars::<i64>(3)?;
+            let quantized_dimension = q.scalar::<i32>(6, 0)? as usize;
+            Ok(Some(Quantization { scale, zero_point, quantized_dimension }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Affine quantization parameters: real = (quantized - zero_point) * scale.
+///
+/// With a single scale, quantization is per-tensor. Otherwise scale and
+/// zero_point have one value per slice along quantized_dimension.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quantization {
+    pub scale: Vec<f32>,
+    pub zero_point: Vec<i64>,
+    pub quantized_dimension: usize,
+}
+
+impl Quantization {
+    pub fn is_per_tensor(&self) -> bool {
+        self.scale.len() == 1
+    }
+
+    pub fn zero_point(&self, channel: usize) -> i64 {
+        if self.zero_point.len() > channel {
+            self.zero_point[channel]
+        } else {
+            self.zero_point.get(0).cloned().unwrap_or(0)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Operator<'a>(Table<'a>);
+
+impl<'a> Operator<'a> {
+    pub fn opcode_index(&self) -> TractResult<u32> {
+        self.0.scalar(0, 0)
+    }
+
+    pub fn inputs(&self) -> TractResult<Vec<i32>> {
+        self.0.scalars(1)
+    }
+
+    pub fn outputs(&self) -> TractResult<Vec<i32>> {
+        self.0.scalars(2)
+    }
+
+    /// The builtin options table. Its actual type depends on the operator.
+    pub fn options(&self) -> TractResult<Option<Table<'a>>> {
+        self.0.table(4)
+    }
+}
+
+/// Fused activation function (ActivationFunctionType enum).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Activation {
+    None,
+    Relu,
+    ReluN1To1,
+    Relu6,
+    Tanh,
+}
+
+impl Activation {
+    pub fn from_code(code: i8) -> TractResult<Activation> {
+        Ok(match code {
+            0 => Activation::None,
+            1 => Activation::Relu,
+            2 => Activation::ReluN1To1,
+            3 => Activation::Relu6,
+            4 => Activation::Tanh,
+            _ => bail!("Unsupported fused activation function {}", code),
+        })
+    }
+}